@@ -0,0 +1,144 @@
+//! Comparação de latência entre o pool de conexões compartilhado e a abertura
+//! do banco a cada chamada (comportamento anterior ao [`Database`]).
+//!
+//! O custo antigo é reproduzido por [`open_like_before`], que repete o que era
+//! feito a cada consulta (criar o diretório, abrir o arquivo, aplicar os
+//! PRAGMAs e conferir a versão do schema) antes da mesma operação do
+//! repositório.
+//!
+//! ```bash
+//! cargo bench --bench database
+//! ```
+
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use password_manager::{
+    app_context::AppContext,
+    infrastructure::database::Database,
+    models::credential::Credential,
+    repositories::credential_repository::{CredentialRepository, SqliteCredentialRepository},
+};
+use uuid::Uuid;
+
+/// Credenciais no cofre durante as listagens e em cada lote gravado.
+const CREDENTIALS: usize = 200;
+
+/// Repetições de cada cenário; o resultado é a mediana.
+const ROUNDS: usize = 30;
+
+fn main() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("bench.db");
+    let path = path.to_str().expect("caminho UTF-8");
+
+    let database = Database::open(path, 4)?;
+    let vault_id = create_vault(&database)?;
+    let pooled = SqliteCredentialRepository::new(database);
+    pooled.create_many(&batch(vault_id)?, 0)?;
+
+    println!("{CREDENTIALS} credenciais, mediana de {ROUNDS} execuções\n");
+    println!("{:<32} {:>12} {:>12}", "operação", "pool", "reabertura");
+
+    let list_pool = median(|| {
+        pooled.find_all_by_vault_id(vault_id)?;
+        Ok(())
+    })?;
+    let list_reopen = median(|| {
+        let _conn = open_like_before(path)?;
+        pooled.find_all_by_vault_id(vault_id)?;
+        Ok(())
+    })?;
+    report("list (1 consulta)", list_pool, list_reopen);
+
+    let create_reopen = median(|| {
+        for credential in batch(vault_id)? {
+            let _conn = open_like_before(path)?;
+            pooled.create(&credential, 0)?;
+        }
+        Ok(())
+    })?;
+    let create_pool = median(|| {
+        for credential in batch(vault_id)? {
+            pooled.create(&credential, 0)?;
+        }
+        Ok(())
+    })?;
+    report(
+        &format!("create ({CREDENTIALS} chamadas)"),
+        create_pool,
+        create_reopen,
+    );
+
+    // Antes do pool, as gravações em lote eram feitas registro a registro.
+    let create_many = median(|| Ok(pooled.create_many(&batch(vault_id)?, 0)?))?;
+    report(
+        &format!("create_many ({CREDENTIALS} registros)"),
+        create_many,
+        create_reopen,
+    );
+
+    Ok(())
+}
+
+/// Abertura por chamada, como fazia `get_database_connection` antes do pool.
+fn open_like_before(path: &str) -> Result<rusqlite::Connection> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let connection = rusqlite::Connection::open(path)?;
+    connection.pragma_update(None, "foreign_keys", "ON")?;
+    connection.pragma_update(None, "journal_mode", "WAL")?;
+    let _version: u32 = connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    Ok(connection)
+}
+
+/// Registra um usuário para obter um cofre válido (as credenciais exigem a
+/// chave estrangeira).
+fn create_vault(database: &Database) -> Result<Uuid> {
+    let ctx = AppContext::new(database.clone());
+    ctx.auth().register("bench", "senha-forte")?;
+    Ok(ctx.auth().login("bench", "senha-forte")?.vault_id)
+}
+
+/// Lote de credenciais com um cifrado de tamanho realista.
+fn batch(vault_id: Uuid) -> Result<Vec<Credential>> {
+    (0..CREDENTIALS)
+        .map(|i| {
+            Ok(Credential::new(
+                vault_id,
+                format!("Serviço {i}"),
+                Some(format!("usuario{i}@example.com")),
+                Some(format!("https://servico{i}.example.com")),
+                None,
+                Some(vec![0u8; 64]),
+            )?)
+        })
+        .collect()
+}
+
+/// Executa `f` [`ROUNDS`] vezes e retorna a duração mediana.
+fn median(mut f: impl FnMut() -> Result<()>) -> Result<Duration> {
+    let mut samples = Vec::with_capacity(ROUNDS);
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        f()?;
+        samples.push(start.elapsed());
+    }
+    samples.sort();
+    Ok(samples[ROUNDS / 2])
+}
+
+fn report(operation: &str, pool: Duration, reopen: Duration) {
+    println!(
+        "{:<32} {:>10.2}ms {:>10.2}ms  ({:.1}x)",
+        operation,
+        pool.as_secs_f64() * 1000.0,
+        reopen.as_secs_f64() * 1000.0,
+        reopen.as_secs_f64() / pool.as_secs_f64()
+    );
+}
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS login_attempt
+(
+    user_id        BLOB PRIMARY KEY,
+    failed_count   INTEGER NOT NULL DEFAULT 0,
+    last_failed_at TEXT,
+    locked_until   TEXT,
+    FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE
+);
@@ -0,0 +1,2 @@
+ALTER TABLE credential
+    ADD COLUMN totp_cipher BLOB;
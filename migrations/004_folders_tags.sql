@@ -0,0 +1,42 @@
+CREATE TABLE IF NOT EXISTS folder
+(
+    id         BLOB PRIMARY KEY,
+    vault_id   BLOB NOT NULL,
+    parent_id  BLOB,
+    name       TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (vault_id) REFERENCES vault (id) ON DELETE CASCADE,
+    FOREIGN KEY (parent_id) REFERENCES folder (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_folder_vault
+    ON folder (vault_id);
+
+ALTER TABLE credential
+    ADD COLUMN folder_id BLOB REFERENCES folder (id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_credential_folder
+    ON credential (folder_id);
+
+CREATE TABLE IF NOT EXISTS tag
+(
+    id         BLOB PRIMARY KEY,
+    vault_id   BLOB NOT NULL,
+    name       TEXT NOT NULL COLLATE NOCASE,
+    created_at TEXT NOT NULL,
+    UNIQUE (vault_id, name),
+    FOREIGN KEY (vault_id) REFERENCES vault (id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS credential_tag
+(
+    credential_id BLOB NOT NULL,
+    tag_id        BLOB NOT NULL,
+    PRIMARY KEY (credential_id, tag_id),
+    FOREIGN KEY (credential_id) REFERENCES credential (id) ON DELETE CASCADE,
+    FOREIGN KEY (tag_id) REFERENCES tag (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_credential_tag_tag
+    ON credential_tag (tag_id);
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS credential_history
+(
+    id              BLOB PRIMARY KEY,
+    credential_id   BLOB NOT NULL,
+    password_cipher BLOB,
+    notes_cipher    BLOB,
+    created_at      TEXT NOT NULL,
+    FOREIGN KEY (credential_id) REFERENCES credential (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_credential_history_credential
+    ON credential_history (credential_id, created_at);
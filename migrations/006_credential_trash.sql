@@ -0,0 +1,5 @@
+ALTER TABLE credential
+    ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_credential_deleted
+    ON credential (vault_id, deleted_at);
@@ -0,0 +1,8 @@
+ALTER TABLE vault
+    ADD COLUMN name TEXT NOT NULL DEFAULT 'Pessoal';
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_vault_user_name
+    ON vault (user_id, name COLLATE NOCASE);
+
+ALTER TABLE user
+    ADD COLUMN account_key_cipher BLOB;
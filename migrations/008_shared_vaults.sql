@@ -0,0 +1,21 @@
+ALTER TABLE user
+    ADD COLUMN public_key TEXT;
+
+ALTER TABLE user
+    ADD COLUMN private_key_cipher BLOB;
+
+CREATE TABLE IF NOT EXISTS vault_member
+(
+    vault_id         BLOB NOT NULL,
+    user_id          BLOB NOT NULL,
+    role             TEXT NOT NULL CHECK (role IN ('editor', 'viewer')),
+    vault_key_cipher BLOB NOT NULL,
+    created_at       TEXT NOT NULL,
+    updated_at       TEXT NOT NULL,
+    PRIMARY KEY (vault_id, user_id),
+    FOREIGN KEY (vault_id) REFERENCES vault (id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_vault_member_user
+    ON vault_member (user_id);
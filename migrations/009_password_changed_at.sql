@@ -0,0 +1,12 @@
+ALTER TABLE credential
+    ADD COLUMN password_changed_at TEXT;
+
+-- Credenciais existentes: última troca registrada no histórico ou, sem
+-- histórico, a criação (updated_at também muda ao mover, recifrar etc.).
+UPDATE credential
+SET password_changed_at = COALESCE(
+        (SELECT MAX(h.created_at)
+         FROM credential_history h
+         WHERE h.credential_id = credential.id
+           AND h.password_cipher IS NOT NULL),
+        created_at);
@@ -0,0 +1,4 @@
+-- Incrementado a cada troca da chave do cofre; sessões abertas com uma
+-- geração anterior deixam de gravar no cofre.
+ALTER TABLE vault
+    ADD COLUMN key_generation INTEGER NOT NULL DEFAULT 0;
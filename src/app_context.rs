@@ -0,0 +1,145 @@
+//! Contexto da aplicação: ponto único de montagem das dependências.
+//!
+//! O [`AppContext`] abre o banco uma única vez (pool de conexões e migrações),
+//! cria os repositórios sobre esse banco compartilhado e injeta-os nos serviços.
+//! As interfaces (CLI, Tauri) criam um contexto na inicialização e o reutilizam
+//! durante toda a execução.
+//!
+//! Para testes, [`AppContext::in_memory`] monta os mesmos serviços sobre
+//! repositórios em memória.
+
+use std::sync::Arc;
+
+use crate::{
+    error::Result,
+    infrastructure::{clipboard::Clipboard, database::Database, pwned_passwords::PwnedPasswords},
+    repositories::{
+        credential_history_repository::{
+            CredentialHistoryRepository, SqliteCredentialHistoryRepository,
+        },
+        credential_repository::{CredentialRepository, SqliteCredentialRepository},
+        folder_repository::{FolderRepository, SqliteFolderRepository},
+        in_memory_repository::InMemoryRepository,
+        login_attempt_repository::{LoginAttemptRepository, SqliteLoginAttemptRepository},
+        tag_repository::{SqliteTagRepository, TagRepository},
+        user_repository::{SqliteUserRepository, UserRepository},
+        vault_member_repository::{SqliteVaultMemberRepository, VaultMemberRepository},
+        vault_repository::{SqliteVaultRepository, VaultRepository},
+    },
+    services::{
+        auth_service::AuthService,
+        credential_service::{CredentialService, HistoryPolicy, TrashPolicy},
+        vault_service::VaultService,
+    },
+};
+
+/// Serviços da aplicação, já conectados aos repositórios.
+///
+/// Clonar o contexto é barato: todos os componentes compartilham os mesmos repositórios.
+#[derive(Clone)]
+pub struct AppContext {
+    auth: AuthService,
+    credentials: CredentialService,
+    vaults: VaultService,
+    clipboard: Option<Clipboard>,
+}
+
+impl AppContext {
+    /// Monta o contexto sobre um banco SQLite já aberto.
+    pub fn new(database: Database) -> Self {
+        Self::from_repositories(
+            Arc::new(SqliteUserRepository::new(database.clone())),
+            Arc::new(SqliteVaultRepository::new(database.clone())),
+            Arc::new(SqliteVaultMemberRepository::new(database.clone())),
+            Arc::new(SqliteLoginAttemptRepository::new(database.clone())),
+            Arc::new(SqliteCredentialRepository::new(database.clone())),
+            Arc::new(SqliteCredentialHistoryRepository::new(database.clone())),
+            Arc::new(SqliteFolderRepository::new(database.clone())),
+            Arc::new(SqliteTagRepository::new(database)),
+        )
+    }
+
+    /// Abre o banco configurado em `DATABASE_URL` e monta o contexto.
+    ///
+    /// ### Retorno
+    /// - `Ok(AppContext)` com as migrações aplicadas.
+    /// - `Err(Error)` quando o banco não puder ser aberto ou migrado.
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::new(Database::from_env()?))
+    }
+
+    /// Monta o contexto sobre repositórios em memória, vazios e sem persistência.
+    pub fn in_memory() -> Self {
+        let store = InMemoryRepository::new();
+        Self::from_repositories(
+            Arc::new(store.clone()),
+            Arc::new(store.clone()),
+            Arc::new(store.clone()),
+            Arc::new(store.clone()),
+            Arc::new(store.clone()),
+            Arc::new(store.clone()),
+            Arc::new(store.clone()),
+            Arc::new(store),
+        )
+    }
+
+    /// Monta o contexto sobre implementações arbitrárias dos repositórios.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_repositories(
+        users: Arc<dyn UserRepository>,
+        vaults: Arc<dyn VaultRepository>,
+        members: Arc<dyn VaultMemberRepository>,
+        login_attempts: Arc<dyn LoginAttemptRepository>,
+        credentials: Arc<dyn CredentialRepository>,
+        history: Arc<dyn CredentialHistoryRepository>,
+        folders: Arc<dyn FolderRepository>,
+        tags: Arc<dyn TagRepository>,
+    ) -> Self {
+        let credentials = CredentialService::new(
+            credentials,
+            history,
+            folders,
+            tags,
+            vaults.clone(),
+            members.clone(),
+            HistoryPolicy::from_env(),
+            TrashPolicy::from_env(),
+            PwnedPasswords::from_env(),
+        );
+        let auth = AuthService::new(
+            users.clone(),
+            vaults.clone(),
+            members.clone(),
+            login_attempts,
+            credentials.clone(),
+        );
+        let vaults = VaultService::new(users, vaults, members, credentials.clone());
+
+        Self {
+            auth,
+            credentials,
+            vaults,
+            clipboard: Clipboard::from_env(),
+        }
+    }
+
+    /// Serviço de autenticação e sessões.
+    pub fn auth(&self) -> &AuthService {
+        &self.auth
+    }
+
+    /// Serviço de credenciais.
+    pub fn credentials(&self) -> &CredentialService {
+        &self.credentials
+    }
+
+    /// Serviço de cofres do usuário.
+    pub fn vaults(&self) -> &VaultService {
+        &self.vaults
+    }
+
+    /// Área de transferência do sistema (`None` quando nenhuma está disponível).
+    pub fn clipboard(&self) -> Option<&Clipboard> {
+        self.clipboard.as_ref()
+    }
+}
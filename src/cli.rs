@@ -0,0 +1,1544 @@
+//! Subcomandos não interativos da linha de comando
+//!
+//! Cada subcomando executa uma única operação e encerra o processo, permitindo
+//! usar o gerenciador em scripts:
+//! - a senha mestre é lida de um descritor de arquivo (`--password-fd`), de uma
+//!   variável de ambiente (`PM_MASTER_PASSWORD` ou a indicada em `--password-env`)
+//!   ou, na falta de ambos, de um prompt no terminal;
+//! - `--json` substitui a saída legível por JSON na saída padrão (e os erros por
+//!   um objeto JSON na saída de erro);
+//! - o código de saída indica a categoria da falha (ver [`FailureKind`]).
+//!
+//! Sem subcomando, o binário abre o menu interativo.
+
+use std::{
+    collections::HashMap,
+    env,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use uuid::Uuid;
+use zeroize::Zeroizing;
+
+use password_manager::{
+    app_context::AppContext,
+    error::{Error, ErrorCode},
+    importers::{self, ImportFormat},
+    infrastructure::totp::TotpCode,
+    models::{credential::Credential, vault_member::VaultRole},
+    services::{
+        auth_service::Session,
+        credential_search::{MatchField, SearchOptions},
+        password_generator::{
+            DEFAULT_PASSPHRASE_WORDS, DEFAULT_PASSWORD_LENGTH, PassphrasePolicy, PasswordGenerator,
+            PasswordPolicy,
+        },
+        vault_service::TransferMode,
+    },
+};
+
+/// Variável de ambiente padrão com a senha mestre.
+const MASTER_PASSWORD_ENV: &str = "PM_MASTER_PASSWORD";
+
+/// Variável de ambiente padrão com a senha dos arquivos de exportação.
+const ARCHIVE_PASSPHRASE_ENV: &str = "PM_ARCHIVE_PASSPHRASE";
+
+/// Gerenciador de senhas local.
+///
+/// Sem subcomando, abre o menu interativo.
+#[derive(Debug, Parser)]
+#[command(name = "password_manager_cli", version)]
+pub struct Cli {
+    #[command(flatten)]
+    pub global: GlobalArgs,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Opções aceitas por todos os subcomandos.
+#[derive(Debug, Args)]
+pub struct GlobalArgs {
+    /// Emite a saída (e os erros) em JSON.
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Usuário dono do cofre.
+    #[arg(short, long, global = true, env = "PM_USER")]
+    user: Option<String>,
+
+    /// Lê a senha mestre da primeira linha do descritor de arquivo informado
+    /// (0 = entrada padrão).
+    #[arg(long, global = true, value_name = "FD")]
+    password_fd: Option<i32>,
+
+    /// Variável de ambiente com a senha mestre.
+    #[arg(long, global = true, value_name = "VAR", default_value = MASTER_PASSWORD_ENV)]
+    password_env: String,
+
+    /// Cofre aberto após o login (nome ou ID); padrão: o mais antigo da conta.
+    #[arg(long, global = true, env = "PM_VAULT", value_name = "COFRE")]
+    vault: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Cria um usuário e o respectivo cofre.
+    Register,
+
+    /// Verifica o usuário e a senha mestre.
+    Login,
+
+    /// Cria uma credencial.
+    Add {
+        /// Nome da credencial.
+        name: String,
+
+        #[command(flatten)]
+        fields: FieldArgs,
+
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+
+    /// Lista as credenciais do cofre.
+    List {
+        /// Somente as credenciais da pasta (caminho ou ID; `/` = fora de pastas).
+        #[arg(long, value_name = "PASTA", conflicts_with = "tag")]
+        folder: Option<String>,
+
+        /// Inclui as subpastas de `--folder`.
+        #[arg(short, long, requires = "folder")]
+        recursive: bool,
+
+        /// Somente as credenciais com a tag (nome ou ID).
+        #[arg(long, value_name = "TAG")]
+        tag: Option<String>,
+    },
+
+    /// Exibe uma credencial com senha, notas e código TOTP.
+    Get {
+        /// ID ou nome exato da credencial.
+        credential: String,
+
+        /// Exibe somente o valor de um campo.
+        #[arg(long, value_enum)]
+        field: Option<Field>,
+
+        /// Copia o campo (padrão: senha) para a área de transferência em vez de
+        /// exibi-lo e aguarda a limpeza automática.
+        #[arg(short, long)]
+        copy: bool,
+    },
+
+    /// Altera uma credencial; campos omitidos são mantidos.
+    Edit {
+        /// ID ou nome exato da credencial.
+        credential: String,
+
+        /// Novo nome.
+        #[arg(long)]
+        name: Option<String>,
+
+        #[command(flatten)]
+        fields: FieldArgs,
+
+        #[command(flatten)]
+        password: PasswordArgs,
+
+        /// Remove o TOTP configurado.
+        #[arg(long, conflicts_with = "totp")]
+        no_totp: bool,
+
+        /// Remove todas as tags.
+        #[arg(long, conflicts_with = "tags")]
+        no_tags: bool,
+    },
+
+    /// Move uma credencial para a lixeira.
+    Rm {
+        /// ID ou nome exato da credencial.
+        credential: String,
+
+        /// Exclui definitivamente, sem passar pela lixeira.
+        #[arg(long)]
+        purge: bool,
+    },
+
+    /// Pesquisa credenciais por nome, usuário ou URL.
+    Search {
+        /// Termo pesquisado.
+        query: String,
+
+        /// Inclui as notas cifradas na pesquisa.
+        #[arg(long)]
+        notes: bool,
+
+        /// Desativa a correspondência aproximada.
+        #[arg(long)]
+        exact: bool,
+    },
+
+    /// Gera uma senha aleatória ou frase secreta (não requer login).
+    Generate {
+        /// Comprimento da senha.
+        #[arg(short, long, default_value_t = DEFAULT_PASSWORD_LENGTH, conflicts_with = "passphrase")]
+        length: usize,
+
+        /// Não inclui símbolos.
+        #[arg(long, conflicts_with = "passphrase")]
+        no_symbols: bool,
+
+        /// Evita caracteres ambíguos (`I`, `l`, `1`, `|`, `O`, `0`, `o`).
+        #[arg(long, conflicts_with = "passphrase")]
+        exclude_ambiguous: bool,
+
+        /// Gera uma frase secreta em vez de uma senha de caracteres.
+        #[arg(long)]
+        passphrase: bool,
+
+        /// Quantidade de palavras da frase secreta.
+        #[arg(short, long, default_value_t = DEFAULT_PASSPHRASE_WORDS, requires = "passphrase")]
+        words: usize,
+    },
+
+    /// Exporta o cofre para um arquivo cifrado.
+    Export {
+        /// Arquivo de destino.
+        file: PathBuf,
+
+        #[command(flatten)]
+        passphrase: PassphraseArgs,
+    },
+
+    /// Importa um arquivo exportado pelo gerenciador ou por outro gerenciador.
+    Import {
+        /// Arquivo a importar.
+        file: PathBuf,
+
+        /// Origem do arquivo; sem esta opção, espera um arquivo exportado por este gerenciador.
+        #[arg(long, value_enum, value_name = "FORMATO")]
+        from: Option<ImportSource>,
+
+        /// Apenas simula a importação de outro gerenciador, sem gravar.
+        #[arg(long, requires = "from")]
+        dry_run: bool,
+
+        #[command(flatten)]
+        passphrase: PassphraseArgs,
+    },
+
+    /// Gerencia os cofres da conta e transfere credenciais entre eles.
+    Vault {
+        #[command(subcommand)]
+        command: VaultCommand,
+    },
+
+    /// Lista os usuários, renomeia ou exclui a conta.
+    Account {
+        #[command(subcommand)]
+        command: AccountCommand,
+    },
+}
+
+/// Operações de `account`.
+#[derive(Debug, Subcommand)]
+pub enum AccountCommand {
+    /// Lista os usuários cadastrados (exige login).
+    List,
+
+    /// Altera o nome de usuário da conta.
+    Rename {
+        /// Novo nome de usuário.
+        #[arg(value_name = "USUÁRIO")]
+        new_username: String,
+    },
+
+    /// Exclui definitivamente a conta, com todos os cofres e credenciais.
+    Delete,
+}
+
+/// Operações de `vault`.
+#[derive(Debug, Subcommand)]
+pub enum VaultCommand {
+    /// Lista os cofres da conta.
+    List,
+
+    /// Cria um cofre vazio.
+    Create {
+        /// Nome do cofre.
+        name: String,
+    },
+
+    /// Renomeia um cofre.
+    Rename {
+        /// ID ou nome do cofre.
+        // O ID `vault` já pertence à opção global `--vault`.
+        #[arg(id = "renamed_vault", value_name = "COFRE")]
+        vault: String,
+
+        /// Novo nome.
+        name: String,
+    },
+
+    /// Exclui definitivamente um cofre e todas as suas credenciais.
+    Rm {
+        /// ID ou nome do cofre; não pode ser o aberto (ver `--vault`).
+        #[arg(id = "removed_vault", value_name = "COFRE")]
+        vault: String,
+    },
+
+    /// Move uma credencial do cofre aberto para outro cofre.
+    Move {
+        /// ID ou nome exato da credencial.
+        credential: String,
+
+        /// ID ou nome do cofre de destino.
+        target: String,
+    },
+
+    /// Copia uma credencial do cofre aberto para outro cofre.
+    Copy {
+        /// ID ou nome exato da credencial.
+        credential: String,
+
+        /// ID ou nome do cofre de destino.
+        target: String,
+    },
+
+    /// Lista o dono e os membros de um cofre.
+    Members {
+        /// ID ou nome do cofre.
+        #[arg(id = "shared_vault", value_name = "COFRE")]
+        vault: String,
+    },
+
+    /// Compartilha um cofre próprio com outro usuário.
+    Invite {
+        /// ID ou nome do cofre.
+        #[arg(id = "shared_vault", value_name = "COFRE")]
+        vault: String,
+
+        /// Usuário convidado; precisa ter feito login ao menos uma vez.
+        #[arg(value_name = "USUÁRIO")]
+        member: String,
+
+        /// Papel do convidado.
+        #[arg(long, value_enum, default_value = "viewer")]
+        role: MemberRole,
+    },
+
+    /// Altera o papel de um membro do cofre.
+    Role {
+        /// ID ou nome do cofre.
+        #[arg(id = "shared_vault", value_name = "COFRE")]
+        vault: String,
+
+        /// Membro do cofre.
+        #[arg(value_name = "USUÁRIO")]
+        member: String,
+
+        /// Novo papel.
+        #[arg(value_enum)]
+        role: MemberRole,
+    },
+
+    /// Remove um membro do cofre e troca a chave do cofre.
+    Revoke {
+        /// ID ou nome do cofre.
+        #[arg(id = "shared_vault", value_name = "COFRE")]
+        vault: String,
+
+        /// Membro do cofre.
+        #[arg(value_name = "USUÁRIO")]
+        member: String,
+    },
+}
+
+/// Campos opcionais informados na criação e na alteração de credenciais.
+#[derive(Debug, Args)]
+pub struct FieldArgs {
+    /// Usuário da credencial.
+    #[arg(long)]
+    username: Option<String>,
+
+    /// URL de acesso.
+    #[arg(long)]
+    url: Option<String>,
+
+    /// Notas (armazenadas cifradas).
+    #[arg(long)]
+    notes: Option<String>,
+
+    /// TOTP: URI `otpauth://` ou segredo Base32.
+    #[arg(long)]
+    totp: Option<String>,
+
+    /// Pasta (caminho como `Trabalho/Servidores` ou ID; `/` = fora de pastas).
+    #[arg(long, value_name = "PASTA")]
+    folder: Option<String>,
+
+    /// Tag (repetível); na alteração substitui as tags atuais.
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+}
+
+/// Origem da senha de uma credencial.
+#[derive(Debug, Args)]
+pub struct PasswordArgs {
+    /// Senha da credencial. Fica visível na lista de processos; prefira `--password-stdin`.
+    #[arg(long, value_name = "SENHA", conflicts_with_all = ["password_stdin", "generate"])]
+    password: Option<String>,
+
+    /// Lê a senha da credencial da próxima linha da entrada padrão.
+    #[arg(long, conflicts_with = "generate")]
+    password_stdin: bool,
+
+    /// Gera uma senha aleatória para a credencial.
+    #[arg(long)]
+    generate: bool,
+
+    /// Comprimento da senha gerada.
+    #[arg(long, value_name = "N", requires = "generate")]
+    generate_length: Option<usize>,
+}
+
+/// Origem da senha dos arquivos de exportação.
+#[derive(Debug, Args)]
+pub struct PassphraseArgs {
+    /// Lê a senha do arquivo da primeira linha do descritor informado (0 = entrada padrão).
+    #[arg(long, value_name = "FD")]
+    passphrase_fd: Option<i32>,
+
+    /// Variável de ambiente com a senha do arquivo.
+    #[arg(long, value_name = "VAR", default_value = ARCHIVE_PASSPHRASE_ENV)]
+    passphrase_env: String,
+}
+
+/// Campo exibido isoladamente por `get --field`.
+#[derive(Debug, Clone, Copy, ValueEnum, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Field {
+    Password,
+    Username,
+    Url,
+    Notes,
+    /// Código TOTP vigente.
+    Totp,
+}
+
+/// Papel atribuído por `vault invite` e `vault role`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum MemberRole {
+    /// Lê e altera as credenciais do cofre.
+    Editor,
+    /// Apenas lê as credenciais do cofre.
+    Viewer,
+}
+
+impl From<MemberRole> for VaultRole {
+    fn from(role: MemberRole) -> Self {
+        match role {
+            MemberRole::Editor => Self::Editor,
+            MemberRole::Viewer => Self::Viewer,
+        }
+    }
+}
+
+/// Formato de origem aceito por `import --from`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ImportSource {
+    /// Detecta o formato pelo conteúdo.
+    Auto,
+    Bitwarden,
+    KeepassXml,
+    #[value(name = "1password-1pux")]
+    OnePassword1pux,
+    #[value(name = "1password-csv")]
+    OnePasswordCsv,
+    BrowserCsv,
+}
+
+impl ImportSource {
+    fn format(self) -> Option<ImportFormat> {
+        match self {
+            Self::Auto => None,
+            Self::Bitwarden => Some(ImportFormat::Bitwarden),
+            Self::KeepassXml => Some(ImportFormat::KeepassXml),
+            Self::OnePassword1pux => Some(ImportFormat::OnePassword1pux),
+            Self::OnePasswordCsv => Some(ImportFormat::OnePasswordCsv),
+            Self::BrowserCsv => Some(ImportFormat::BrowserCsv),
+        }
+    }
+}
+
+/// Categoria de falha de um subcomando, refletida no código de saída.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    /// Falha geral: banco de dados, arquivo, validação (código 1).
+    General,
+    /// Argumentos inválidos ou ausentes, como nos erros do próprio clap (código 2).
+    Usage,
+    /// Usuário ou senha mestre incorretos, conta temporariamente bloqueada ou
+    /// acesso negado, como alterar um cofre compartilhado como leitor (código 3).
+    Auth,
+    /// Cofre, credencial, pasta ou tag inexistente, ou nome ambíguo (código 4).
+    NotFound,
+    /// Segredo não informado e sem terminal para solicitá-lo (código 5).
+    MissingSecret,
+}
+
+impl FailureKind {
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Self::General => 1,
+            Self::Usage => 2,
+            Self::Auth => 3,
+            Self::NotFound => 4,
+            Self::MissingSecret => 5,
+        }
+    }
+}
+
+/// Erro de um subcomando com a respectiva categoria.
+#[derive(Debug)]
+pub struct CliError {
+    kind: FailureKind,
+    error: anyhow::Error,
+}
+
+impl CliError {
+    fn new(kind: FailureKind, error: anyhow::Error) -> Self {
+        Self { kind, error }
+    }
+}
+
+impl From<anyhow::Error> for CliError {
+    fn from(error: anyhow::Error) -> Self {
+        Self::new(FailureKind::General, error)
+    }
+}
+
+/// A categoria do erro da biblioteca define o código de saída.
+impl From<Error> for CliError {
+    fn from(error: Error) -> Self {
+        let kind = match error.code() {
+            ErrorCode::Unauthorized => FailureKind::Auth,
+            ErrorCode::NotFound => FailureKind::NotFound,
+            ErrorCode::Validation
+            | ErrorCode::Crypto
+            | ErrorCode::Storage
+            | ErrorCode::Conflict => FailureKind::General,
+        };
+        Self::new(kind, error.into())
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(error: io::Error) -> Self {
+        Self::new(FailureKind::General, error.into())
+    }
+}
+
+type CliResult<T = ()> = Result<T, CliError>;
+
+/// Executa um subcomando e converte o resultado no código de saída do processo.
+///
+/// Os erros são escritos na saída de erro, em texto ou em JSON conforme `--json`.
+/// No JSON, `code` traz a categoria dos erros da biblioteca (`null` nos erros
+/// de uso da própria CLI).
+pub fn execute(global: &GlobalArgs, command: Command) -> ExitCode {
+    match run(global, command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            let code = e.kind.exit_code();
+            if global.json {
+                let category = e.error.downcast_ref::<Error>().map(Error::code);
+                eprintln!(
+                    "{}",
+                    serde_json::json!({
+                        "error": format!("{:#}", e.error),
+                        "code": category,
+                        "exit_code": code
+                    })
+                );
+            } else {
+                eprintln!("Erro: {:#}", e.error);
+            }
+            ExitCode::from(code)
+        }
+    }
+}
+
+fn run(global: &GlobalArgs, command: Command) -> CliResult {
+    let json = global.json;
+
+    match command {
+        Command::Register => {
+            let ctx = AppContext::from_env()?;
+            let username = username(global)?;
+            let password = read_master_password(global, true)?;
+
+            ctx.auth().register(&username, &password)?;
+            print(json, &serde_json::json!({ "username": username }), |_| {
+                println!("Usuário '{username}' criado com sucesso.")
+            })
+        }
+
+        Command::Login => {
+            let ctx = AppContext::from_env()?;
+            let session = open_session(&ctx, global)?;
+            let vault = ctx.vaults().current(&session)?;
+
+            print(
+                json,
+                &serde_json::json!({
+                    "username": session.user.username(),
+                    "vault_id": vault.id(),
+                    "vault": vault.name(),
+                }),
+                |_| {
+                    println!(
+                        "Login realizado. Cofre '{}' ({}).",
+                        vault.name(),
+                        vault.id()
+                    )
+                },
+            )
+        }
+
+        Command::Add {
+            name,
+            fields,
+            password,
+        } => {
+            let ctx = AppContext::from_env()?;
+            let session = open_session(&ctx, global)?;
+            let credentials = ctx.credentials();
+
+            let folder = match fields.folder.as_deref() {
+                Some(value) => resolve_folder(&ctx, &session, value)?,
+                None => None,
+            };
+            let password = read_credential_password(&ctx, &password)?;
+
+            let cred = credentials.create(
+                &session,
+                &name,
+                fields.username,
+                fields.url,
+                fields.notes.map(String::into_bytes),
+                password.as_deref().map(String::as_str),
+            )?;
+            if let Some(totp) = fields.totp.as_deref() {
+                credentials.set_totp(&session, cred.id(), Some(totp))?;
+            }
+            if folder.is_some() {
+                credentials.move_to_folder(&session, cred.id(), folder)?;
+            }
+            if !fields.tags.is_empty() {
+                credentials.set_tags(&session, cred.id(), &fields.tags)?;
+            }
+
+            let view =
+                describe(&ctx, &session, vec![credentials.get(&session, cred.id())?])?.remove(0);
+            print(json, &view, |v| {
+                println!("Credencial criada: {} ({})", v.name, v.id)
+            })
+        }
+
+        Command::List {
+            folder,
+            recursive,
+            tag,
+        } => {
+            let ctx = AppContext::from_env()?;
+            let session = open_session(&ctx, global)?;
+            let credentials = ctx.credentials();
+
+            let list = if let Some(folder) = folder.as_deref() {
+                let folder_id = resolve_folder(&ctx, &session, folder)?;
+                credentials.list_by_folder(&session, folder_id, recursive)?
+            } else if let Some(tag) = tag.as_deref() {
+                credentials.list_by_tag(&session, resolve_tag(&ctx, &session, tag)?)?
+            } else {
+                credentials.list(&session)?
+            };
+
+            let views = describe(&ctx, &session, list)?;
+            print(json, &views, |views| {
+                for v in views {
+                    println!("→ {} ({})", v.name, v.id);
+                }
+            })
+        }
+
+        Command::Get {
+            credential,
+            field,
+            copy,
+        } => {
+            let ctx = AppContext::from_env()?;
+            let session = open_session(&ctx, global)?;
+            let cred = resolve_credential(&ctx, &session, &credential)?;
+
+            let field = if copy {
+                Some(field.unwrap_or(Field::Password))
+            } else {
+                field
+            };
+
+            match field {
+                Some(field) => {
+                    let value = field_value(&ctx, &session, &cred, field)?.ok_or_else(|| {
+                        CliError::new(
+                            FailureKind::NotFound,
+                            anyhow!(
+                                "A credencial '{}' não possui o campo solicitado",
+                                cred.name()
+                            ),
+                        )
+                    })?;
+                    if copy {
+                        return copy_to_clipboard(&ctx, json, field, &value);
+                    }
+                    print(
+                        json,
+                        &serde_json::json!({ "field": field, "value": &*value }),
+                        |_| println!("{}", *value),
+                    )
+                }
+                None => {
+                    let mut view = describe(&ctx, &session, vec![cred])?.remove(0);
+                    view.secrets = Some(reveal(&ctx, &session, view.id)?);
+                    print(json, &view, print_details)
+                }
+            }
+        }
+
+        Command::Edit {
+            credential,
+            name,
+            fields,
+            password,
+            no_totp,
+            no_tags,
+        } => {
+            let ctx = AppContext::from_env()?;
+            let session = open_session(&ctx, global)?;
+            let credentials = ctx.credentials();
+            let cred = resolve_credential(&ctx, &session, &credential)?;
+            let id = cred.id();
+
+            let folder = match fields.folder.as_deref() {
+                Some(value) => Some(resolve_folder(&ctx, &session, value)?),
+                None => None,
+            };
+            let password = read_credential_password(&ctx, &password)?;
+
+            credentials.update(
+                &session,
+                cred,
+                name,
+                fields.username,
+                fields.url,
+                fields.notes.map(String::into_bytes),
+                password.as_deref().map(String::as_str),
+            )?;
+            if fields.totp.is_some() || no_totp {
+                credentials.set_totp(&session, id, fields.totp.as_deref())?;
+            }
+            if let Some(folder) = folder {
+                credentials.move_to_folder(&session, id, folder)?;
+            }
+            if !fields.tags.is_empty() || no_tags {
+                credentials.set_tags(&session, id, &fields.tags)?;
+            }
+
+            let view = describe(&ctx, &session, vec![credentials.get(&session, id)?])?.remove(0);
+            print(json, &view, |v| {
+                println!("Credencial atualizada: {} ({})", v.name, v.id)
+            })
+        }
+
+        Command::Rm { credential, purge } => {
+            let ctx = AppContext::from_env()?;
+            let session = open_session(&ctx, global)?;
+            let cred = resolve_credential(&ctx, &session, &credential)?;
+
+            ctx.credentials().delete(&session, cred.id())?;
+            if purge {
+                ctx.credentials().purge(&session, cred.id())?;
+            }
+
+            print(
+                json,
+                &serde_json::json!({ "id": cred.id(), "name": cred.name(), "purged": purge }),
+                |_| {
+                    if purge {
+                        println!("Credencial '{}' excluída definitivamente.", cred.name());
+                    } else {
+                        println!("Credencial '{}' movida para a lixeira.", cred.name());
+                    }
+                },
+            )
+        }
+
+        Command::Search {
+            query,
+            notes,
+            exact,
+        } => {
+            let ctx = AppContext::from_env()?;
+            let session = open_session(&ctx, global)?;
+            let options = SearchOptions {
+                include_notes: notes,
+                fuzzy: !exact,
+            };
+
+            let hits = ctx.credentials().search_with(&session, &query, &options)?;
+            let scores: Vec<(MatchField, u32)> = hits.iter().map(|h| (h.field, h.score)).collect();
+            let views = describe(
+                &ctx,
+                &session,
+                hits.into_iter().map(|h| h.credential).collect(),
+            )?;
+            let results: Vec<SearchResult> = views
+                .into_iter()
+                .zip(scores)
+                .map(|(credential, (field, score))| SearchResult {
+                    credential,
+                    field,
+                    score,
+                })
+                .collect();
+
+            print(json, &results, |results| {
+                for r in results {
+                    println!(
+                        "→ {} ({}) [{}]",
+                        r.credential.name,
+                        r.credential.id,
+                        r.field.label()
+                    );
+                }
+            })
+        }
+
+        Command::Generate {
+            length,
+            no_symbols,
+            exclude_ambiguous,
+            passphrase,
+            words,
+        } => {
+            let generated = if passphrase {
+                PasswordGenerator::passphrase(&PassphrasePolicy {
+                    words,
+                    ..PassphrasePolicy::default()
+                })
+            } else {
+                PasswordGenerator::generate(&PasswordPolicy {
+                    length,
+                    symbols: !no_symbols,
+                    exclude_ambiguous,
+                    ..PasswordPolicy::default()
+                })
+            }
+            .map_err(|e| CliError::new(FailureKind::Usage, e.into()))?;
+
+            print(json, &generated, |g| println!("{}", g.value))
+        }
+
+        Command::Export { file, passphrase } => {
+            let ctx = AppContext::from_env()?;
+            let session = open_session(&ctx, global)?;
+            let secret = read_secret(
+                passphrase.passphrase_fd,
+                &passphrase.passphrase_env,
+                "Senha do arquivo",
+                true,
+            )?;
+
+            let data = ctx.credentials().export(&session, &secret)?;
+            std::fs::write(&file, data)?;
+
+            print(json, &serde_json::json!({ "file": file }), |_| {
+                println!("Cofre exportado para {}.", file.display())
+            })
+        }
+
+        Command::Import {
+            file,
+            from,
+            dry_run,
+            passphrase,
+        } => {
+            let ctx = AppContext::from_env()?;
+            let session = open_session(&ctx, global)?;
+            let data = std::fs::read(&file)?;
+
+            match from {
+                Some(source) => {
+                    let parsed = importers::parse(source.format(), &data)?;
+                    let report = ctx
+                        .credentials()
+                        .import_external(&session, &parsed, dry_run)?;
+                    print(json, &report, crate::exibir_relatorio_importacao)
+                }
+                None => {
+                    let secret = read_secret(
+                        passphrase.passphrase_fd,
+                        &passphrase.passphrase_env,
+                        "Senha do arquivo",
+                        false,
+                    )?;
+                    let summary = ctx.credentials().import(&session, &data, &secret)?;
+                    print(json, &summary, |s| {
+                        println!(
+                            "{} credencial(is) importada(s) (exportadas por '{}' em {}).",
+                            s.imported,
+                            s.exported_by,
+                            s.exported_at.format("%d/%m/%Y %H:%M")
+                        )
+                    })
+                }
+            }
+        }
+
+        Command::Vault { command } => {
+            let ctx = AppContext::from_env()?;
+            let session = open_session(&ctx, global)?;
+            run_vault(&ctx, session, json, command)
+        }
+
+        Command::Account { command } => run_account(&AppContext::from_env()?, global, command),
+    }
+}
+
+/// Executa as operações de `account`; a senha mestre confirma a renomeação e a exclusão.
+fn run_account(ctx: &AppContext, global: &GlobalArgs, command: AccountCommand) -> CliResult {
+    let json = global.json;
+
+    match command {
+        AccountCommand::List => {
+            let (session, _) = login(ctx, global)?;
+            let users = ctx.auth().list_users(&session)?;
+            print(json, &users, |users| {
+                for u in users {
+                    println!(
+                        "{}  {} (desde {})",
+                        u.id,
+                        u.username,
+                        u.created_at.format("%d/%m/%Y")
+                    );
+                }
+            })
+        }
+
+        AccountCommand::Rename { new_username } => {
+            let (mut session, password) = login(ctx, global)?;
+            ctx.auth()
+                .rename_user(&mut session, &password, &new_username)?;
+            let username = session.user.username();
+            print(
+                json,
+                &serde_json::json!({ "id": session.user.id(), "username": username }),
+                |_| println!("Usuário renomeado para '{username}'."),
+            )
+        }
+
+        AccountCommand::Delete => {
+            let (mut session, password) = login(ctx, global)?;
+            let username = session.user.username().to_string();
+            ctx.auth().delete_account(&mut session, &password)?;
+            print(
+                json,
+                &serde_json::json!({ "username": username, "deleted": true }),
+                |_| println!("Conta '{username}' excluída definitivamente."),
+            )
+        }
+    }
+}
+
+/// Executa as operações de `vault` na sessão já aberta.
+fn run_vault(
+    ctx: &AppContext,
+    mut session: Session,
+    json: bool,
+    command: VaultCommand,
+) -> CliResult {
+    let vaults = ctx.vaults();
+
+    match command {
+        VaultCommand::List => {
+            let list: Vec<VaultView> = vaults
+                .list(&session)?
+                .into_iter()
+                .map(|v| VaultView {
+                    id: v.vault.id(),
+                    name: v.vault.name().to_string(),
+                    credentials: v.credentials,
+                    current: v.current,
+                    role: v.role,
+                    owner: v.owner,
+                    members: v.members,
+                    created_at: v.vault.created_at(),
+                })
+                .collect();
+            print(json, &list, |list| {
+                for v in list {
+                    let sharing = match (&v.owner, v.members) {
+                        (Some(owner), _) => format!(" ({} de {owner})", v.role),
+                        (None, 0) => String::new(),
+                        (None, n) => format!(" (compartilhado com {n})"),
+                    };
+                    println!(
+                        "{} {}  {} [{} credencial(is)]{sharing}",
+                        if v.current { "*" } else { " " },
+                        v.id,
+                        v.name,
+                        v.credentials
+                    );
+                }
+            })
+        }
+
+        VaultCommand::Create { name } => {
+            let vault = vaults.create(&session, &name)?;
+            print(
+                json,
+                &serde_json::json!({ "id": vault.id(), "name": vault.name() }),
+                |_| println!("Cofre criado: {} ({})", vault.name(), vault.id()),
+            )
+        }
+
+        VaultCommand::Rename { vault, name } => {
+            let id = resolve_vault(ctx, &session, &vault)?;
+            let vault = vaults.rename(&session, id, &name)?;
+            print(
+                json,
+                &serde_json::json!({ "id": vault.id(), "name": vault.name() }),
+                |_| println!("Cofre renomeado: {} ({})", vault.name(), vault.id()),
+            )
+        }
+
+        VaultCommand::Rm { vault } => {
+            let id = resolve_vault(ctx, &session, &vault)?;
+            vaults.delete(&session, id)?;
+            print(
+                json,
+                &serde_json::json!({ "id": id, "deleted": true }),
+                |_| println!("Cofre '{vault}' excluído definitivamente."),
+            )
+        }
+
+        VaultCommand::Move { credential, target } => transfer(
+            ctx,
+            &session,
+            json,
+            &credential,
+            &target,
+            TransferMode::Move,
+        ),
+
+        VaultCommand::Copy { credential, target } => transfer(
+            ctx,
+            &session,
+            json,
+            &credential,
+            &target,
+            TransferMode::Copy,
+        ),
+
+        VaultCommand::Members { vault } => {
+            let id = resolve_vault(ctx, &session, &vault)?;
+            let members = vaults.members(&session, id)?;
+            print(json, &members, |members| {
+                for m in members {
+                    println!("{}  {} [{}]", m.user_id, m.username, m.role);
+                }
+            })
+        }
+
+        VaultCommand::Invite {
+            vault,
+            member,
+            role,
+        } => {
+            let id = resolve_vault(ctx, &session, &vault)?;
+            let member = vaults.invite(&session, id, &member, role.into())?;
+            print(json, &member, |m| {
+                println!("Cofre compartilhado com '{}' ({}).", m.username, m.role)
+            })
+        }
+
+        VaultCommand::Role {
+            vault,
+            member,
+            role,
+        } => {
+            let id = resolve_vault(ctx, &session, &vault)?;
+            let member = vaults.set_role(&session, id, &member, role.into())?;
+            print(json, &member, |m| {
+                println!("Papel de '{}' alterado para {}.", m.username, m.role)
+            })
+        }
+
+        VaultCommand::Revoke { vault, member } => {
+            let id = resolve_vault(ctx, &session, &vault)?;
+            vaults.revoke(&mut session, id, &member)?;
+            print(
+                json,
+                &serde_json::json!({ "vault_id": id, "member": member, "revoked": true }),
+                |_| println!("Acesso de '{member}' revogado; a chave do cofre foi trocada."),
+            )
+        }
+    }
+}
+
+/// Move ou copia uma credencial do cofre aberto para outro cofre.
+fn transfer(
+    ctx: &AppContext,
+    session: &Session,
+    json: bool,
+    credential: &str,
+    target: &str,
+    mode: TransferMode,
+) -> CliResult {
+    let cred = resolve_credential(ctx, session, credential)?;
+    let target = resolve_vault(ctx, session, target)?;
+
+    let transferred = ctx
+        .vaults()
+        .transfer(session, &[cred.id()], target, mode)?
+        .remove(0);
+    print(
+        json,
+        &serde_json::json!({
+            "id": transferred.id(),
+            "name": transferred.name(),
+            "vault_id": target,
+            "mode": mode,
+        }),
+        |_| match mode {
+            TransferMode::Move => println!("Credencial '{}' movida.", cred.name()),
+            TransferMode::Copy => println!(
+                "Credencial '{}' copiada ({}).",
+                cred.name(),
+                transferred.id()
+            ),
+        },
+    )
+}
+
+/// Cofre exibido por `vault list`, sem a chave cifrada.
+#[derive(Debug, Serialize)]
+struct VaultView {
+    id: Uuid,
+    name: String,
+    credentials: usize,
+    /// Indica o cofre aberto pelo comando (ver `--vault`).
+    current: bool,
+    role: VaultRole,
+    /// Dono do cofre, quando compartilhado por outro usuário.
+    owner: Option<String>,
+    /// Quantidade de membros, além do dono.
+    members: usize,
+    created_at: DateTime<Utc>,
+}
+
+/// Credencial exibida pelos subcomandos, sem os campos cifrados.
+#[derive(Debug, Serialize)]
+struct CredentialView {
+    id: Uuid,
+    name: String,
+    username: Option<String>,
+    url: Option<String>,
+    /// Caminho completo da pasta.
+    folder: Option<String>,
+    tags: Vec<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    /// Preenchido somente por `get`.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    secrets: Option<Secrets>,
+}
+
+/// Valores descriptografados de uma credencial.
+#[derive(Debug, Serialize)]
+struct Secrets {
+    password: Option<String>,
+    notes: Option<String>,
+    totp: Option<TotpCode>,
+}
+
+/// Resultado de `search`.
+#[derive(Debug, Serialize)]
+struct SearchResult {
+    credential: CredentialView,
+    field: MatchField,
+    score: u32,
+}
+
+/// Escreve o valor em JSON ou, sem `--json`, chama a função de exibição legível.
+fn print<T: Serialize>(json: bool, value: &T, text: impl FnOnce(&T)) -> CliResult {
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(value).map_err(anyhow::Error::from)?
+        );
+    } else {
+        text(value);
+    }
+    Ok(())
+}
+
+/// Exibe uma credencial com os valores descriptografados.
+fn print_details(view: &CredentialView) {
+    println!("Nome: {}", view.name);
+    println!("ID: {}", view.id);
+    if let Some(u) = &view.username {
+        println!("Usuário: {u}");
+    }
+    if let Some(url) = &view.url {
+        println!("URL: {url}");
+    }
+    if let Some(folder) = &view.folder {
+        println!("Pasta: {folder}");
+    }
+    if !view.tags.is_empty() {
+        println!("Tags: {}", view.tags.join(", "));
+    }
+
+    let Some(secrets) = &view.secrets else {
+        return;
+    };
+    if let Some(notes) = &secrets.notes {
+        println!("Notas: {notes}");
+    }
+    if let Some(password) = &secrets.password {
+        println!("Senha: {password}");
+    }
+    if let Some(totp) = &secrets.totp {
+        println!(
+            "Código TOTP: {} (expira em {}s)",
+            totp.code, totp.remaining_seconds
+        );
+    }
+}
+
+/// Monta a exibição das credenciais com o caminho da pasta e as tags.
+fn describe(
+    ctx: &AppContext,
+    session: &Session,
+    list: Vec<Credential>,
+) -> CliResult<Vec<CredentialView>> {
+    let folders: HashMap<Uuid, String> = ctx
+        .credentials()
+        .list_folders(session)?
+        .into_iter()
+        .map(|f| (f.folder.id(), f.path))
+        .collect();
+
+    list.into_iter()
+        .map(|c| {
+            let tags = ctx
+                .credentials()
+                .tags_of(session, c.id())?
+                .iter()
+                .map(|t| t.name().to_string())
+                .collect();
+
+            Ok(CredentialView {
+                id: c.id(),
+                name: c.name().to_string(),
+                username: c.username().map(str::to_string),
+                url: c.url().map(str::to_string),
+                folder: c.folder_id().and_then(|id| folders.get(&id).cloned()),
+                tags,
+                created_at: c.created_at(),
+                updated_at: c.updated_at(),
+                secrets: None,
+            })
+        })
+        .collect()
+}
+
+/// Descriptografa senha, notas e o código TOTP vigente de uma credencial.
+fn reveal(ctx: &AppContext, session: &Session, id: Uuid) -> CliResult<Secrets> {
+    let credentials = ctx.credentials();
+    Ok(Secrets {
+        password: credentials.reveal_password(session, id)?,
+        notes: credentials.reveal_notes(session, id)?,
+        totp: credentials.current_totp(session, id)?,
+    })
+}
+
+/// Copia o valor para a área de transferência e aguarda a limpeza agendada,
+/// que se perderia com o fim do processo.
+fn copy_to_clipboard(ctx: &AppContext, json: bool, field: Field, value: &str) -> CliResult {
+    let clipboard = ctx.clipboard().ok_or_else(|| {
+        anyhow!("Área de transferência indisponível (instale wl-clipboard, xclip ou xsel)")
+    })?;
+
+    let copy = clipboard.copy(value)?;
+    let clear_after = copy.clear_after().map(|d| d.as_secs());
+    print(
+        json,
+        &serde_json::json!({ "field": field, "copied": true, "clear_after_secs": clear_after }),
+        |_| match clear_after {
+            Some(secs) => eprintln!("Copiado. A área de transferência será limpa em {secs}s."),
+            None => eprintln!("Copiado."),
+        },
+    )?;
+    io::stdout().flush()?;
+
+    copy.wait();
+    Ok(())
+}
+
+/// Valor de um único campo para `get --field`.
+fn field_value(
+    ctx: &AppContext,
+    session: &Session,
+    cred: &Credential,
+    field: Field,
+) -> CliResult<Option<Zeroizing<String>>> {
+    let credentials = ctx.credentials();
+    let value = match field {
+        Field::Password => credentials.reveal_password(session, cred.id())?,
+        Field::Username => cred.username().map(str::to_string),
+        Field::Url => cred.url().map(str::to_string),
+        Field::Notes => credentials.reveal_notes(session, cred.id())?,
+        Field::Totp => credentials
+            .current_totp(session, cred.id())?
+            .map(|totp| totp.code),
+    };
+    Ok(value.map(Zeroizing::new))
+}
+
+/// Usuário informado em `--user` ou `PM_USER`.
+fn username(global: &GlobalArgs) -> CliResult<String> {
+    global.user.clone().ok_or_else(|| {
+        CliError::new(
+            FailureKind::Usage,
+            anyhow!("Informe o usuário com --user ou pela variável PM_USER"),
+        )
+    })
+}
+
+/// Autentica o usuário, devolvendo a sessão e a senha mestre lida.
+fn login(ctx: &AppContext, global: &GlobalArgs) -> CliResult<(Session, Zeroizing<String>)> {
+    let username = username(global)?;
+    let password = read_master_password(global, false)?;
+
+    let session = ctx.auth().login(&username, &password)?;
+    Ok((session, password))
+}
+
+/// Autentica o usuário e abre o cofre indicado em `--vault` (ou o padrão).
+fn open_session(ctx: &AppContext, global: &GlobalArgs) -> CliResult<Session> {
+    let (mut session, _) = login(ctx, global)?;
+
+    if let Some(value) = global.vault.as_deref() {
+        let id = resolve_vault(ctx, &session, value)?;
+        ctx.vaults().switch(&mut session, id)?;
+    }
+    Ok(session)
+}
+
+fn read_master_password(global: &GlobalArgs, confirm: bool) -> CliResult<Zeroizing<String>> {
+    read_secret(
+        global.password_fd,
+        &global.password_env,
+        "Senha mestre",
+        confirm,
+    )
+}
+
+/// Lê um segredo, na ordem: descritor de arquivo, variável de ambiente e prompt
+/// no terminal (com confirmação, quando solicitada).
+fn read_secret(
+    fd: Option<i32>,
+    env_var: &str,
+    label: &str,
+    confirm: bool,
+) -> CliResult<Zeroizing<String>> {
+    if let Some(fd) = fd {
+        return read_line_from_fd(fd).map_err(|e| {
+            CliError::new(
+                FailureKind::MissingSecret,
+                anyhow!("Falha ao ler {label} do descritor {fd}: {e}"),
+            )
+        });
+    }
+
+    if let Ok(value) = env::var(env_var)
+        && !value.is_empty()
+    {
+        return Ok(Zeroizing::new(value));
+    }
+
+    let missing = |e: io::Error| {
+        CliError::new(
+            FailureKind::MissingSecret,
+            anyhow!(
+                "{label} não informada: use o descritor de arquivo, a variável {env_var} ou um terminal ({e})"
+            ),
+        )
+    };
+
+    let value = Zeroizing::new(rpassword::prompt_password(format!("{label}: ")).map_err(missing)?);
+    if confirm {
+        let again =
+            Zeroizing::new(rpassword::prompt_password("Repita para confirmar: ").map_err(missing)?);
+        if *value != *again {
+            return Err(CliError::new(
+                FailureKind::Usage,
+                anyhow!("As senhas não coincidem"),
+            ));
+        }
+    }
+    Ok(value)
+}
+
+/// Primeira linha disponível no descritor de arquivo, sem a quebra de linha.
+fn read_line_from_fd(fd: i32) -> io::Result<Zeroizing<String>> {
+    let mut line = Zeroizing::new(String::new());
+
+    if fd == 0 {
+        io::stdin().lock().read_line(&mut line)?;
+    } else if cfg!(unix) {
+        let file = std::fs::File::open(format!("/dev/fd/{fd}"))?;
+        io::BufReader::new(file).read_line(&mut line)?;
+    } else {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "descritores de arquivo só são suportados em sistemas Unix",
+        ));
+    }
+
+    let len = line.trim_end_matches(['\r', '\n']).len();
+    line.truncate(len);
+    Ok(line)
+}
+
+/// Senha da credencial conforme `--password`, `--password-stdin` ou `--generate`.
+///
+/// Senhas informadas que constam na base local de senhas vazadas geram um aviso
+/// na saída de erro, sem interromper a operação.
+fn read_credential_password(
+    ctx: &AppContext,
+    args: &PasswordArgs,
+) -> CliResult<Option<Zeroizing<String>>> {
+    let password = if args.generate {
+        let policy = PasswordPolicy {
+            length: args.generate_length.unwrap_or(DEFAULT_PASSWORD_LENGTH),
+            ..PasswordPolicy::default()
+        };
+        let generated = PasswordGenerator::generate(&policy)
+            .map_err(|e| CliError::new(FailureKind::Usage, e.into()))?;
+        return Ok(Some(Zeroizing::new(generated.value)));
+    } else if args.password_stdin {
+        read_line_from_fd(0)?
+    } else if let Some(password) = &args.password {
+        Zeroizing::new(password.clone())
+    } else {
+        return Ok(None);
+    };
+
+    match ctx.credentials().breach_occurrences(&password) {
+        Ok(Some(count)) if count > 0 => {
+            eprintln!("Atenção: esta senha aparece {count} vez(es) em vazamentos conhecidos.")
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("Aviso: falha ao consultar a base de senhas vazadas: {e}"),
+    }
+    Ok(Some(password))
+}
+
+/// Localiza uma credencial pelo ID ou pelo nome exato (sem diferenciar maiúsculas).
+fn resolve_credential(ctx: &AppContext, session: &Session, value: &str) -> CliResult<Credential> {
+    let not_found = |e| CliError::new(FailureKind::NotFound, e);
+
+    if let Ok(id) = Uuid::parse_str(value) {
+        return Ok(ctx.credentials().get(session, id)?);
+    }
+
+    let mut matches: Vec<Credential> = ctx
+        .credentials()
+        .list(session)?
+        .into_iter()
+        .filter(|c| c.name().eq_ignore_ascii_case(value))
+        .collect();
+
+    match matches.len() {
+        0 => Err(not_found(anyhow!("Credencial não encontrada: '{value}'"))),
+        1 => Ok(matches.remove(0)),
+        _ => {
+            let ids: Vec<String> = matches.iter().map(|c| c.id().to_string()).collect();
+            Err(not_found(anyhow!(
+                "Há {} credenciais com o nome '{value}'; informe o ID ({})",
+                matches.len(),
+                ids.join(", ")
+            )))
+        }
+    }
+}
+
+/// Localiza uma pasta pelo ID ou pelo caminho completo; `/` representa a raiz.
+fn resolve_folder(ctx: &AppContext, session: &Session, value: &str) -> CliResult<Option<Uuid>> {
+    let path = value.trim_matches('/');
+    if path.is_empty() {
+        return Ok(None);
+    }
+
+    ctx.credentials()
+        .list_folders(session)?
+        .into_iter()
+        .find(|f| f.folder.id().to_string() == path || f.path.eq_ignore_ascii_case(path))
+        .map(|f| Some(f.folder.id()))
+        .ok_or_else(|| {
+            CliError::new(
+                FailureKind::NotFound,
+                anyhow!("Pasta não encontrada: '{value}'"),
+            )
+        })
+}
+
+/// Localiza um cofre da conta pelo ID ou pelo nome.
+fn resolve_vault(ctx: &AppContext, session: &Session, value: &str) -> CliResult<Uuid> {
+    ctx.vaults()
+        .list(session)?
+        .into_iter()
+        .find(|v| v.vault.id().to_string() == value || v.vault.name().eq_ignore_ascii_case(value))
+        .map(|v| v.vault.id())
+        .ok_or_else(|| {
+            CliError::new(
+                FailureKind::NotFound,
+                anyhow!("Cofre não encontrado: '{value}'"),
+            )
+        })
+}
+
+/// Localiza uma tag pelo ID ou pelo nome.
+fn resolve_tag(ctx: &AppContext, session: &Session, value: &str) -> CliResult<Uuid> {
+    ctx.credentials()
+        .list_tags(session)?
+        .into_iter()
+        .find(|t| t.tag.id().to_string() == value || t.tag.name().eq_ignore_ascii_case(value))
+        .map(|t| t.tag.id())
+        .ok_or_else(|| {
+            CliError::new(
+                FailureKind::NotFound,
+                anyhow!("Tag não encontrada: '{value}'"),
+            )
+        })
+}
@@ -0,0 +1,210 @@
+//! Erros da biblioteca.
+//!
+//! Todas as camadas (infraestrutura, modelos, repositórios e serviços) retornam
+//! [`Result`], cujo erro é um [`Error`] classificado por categoria. As
+//! interfaces decidem o que fazer a partir da categoria, e não do texto da
+//! mensagem: a CLI escolhe o código de saída e a aplicação desktop recebe o
+//! erro serializado como objeto:
+//!
+//! ```json
+//! { "code": "validation", "message": "O nome da credencial não pode ser vazio.", "field": "name" }
+//! ```
+//!
+//! As mensagens continuam em português e prontas para exibição; `field` só é
+//! enviado nos erros de validação.
+
+use serde::{Serialize, Serializer, ser::SerializeStruct};
+use std::fmt;
+
+/// Resultado padrão das operações da biblioteca.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Erro de domínio retornado pela biblioteca.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Registro inexistente ou fora do alcance da sessão (credencial, pasta,
+    /// tag, cofre, usuário ou membro).
+    #[error("{0}")]
+    NotFound(String),
+
+    /// Acesso negado: usuário ou senha incorretos, login temporariamente
+    /// bloqueado, sessão bloqueada ou expirada, ou papel sem permissão no cofre.
+    #[error("{0}")]
+    Unauthorized(String),
+
+    /// Dado de entrada inválido; `field` identifica o campo rejeitado.
+    #[error("{message}")]
+    Validation {
+        field: &'static str,
+        message: String,
+    },
+
+    /// Falha de cifragem ou decifragem: chave incorreta, conteúdo adulterado
+    /// ou formato cifrado desconhecido.
+    #[error("{0}")]
+    Crypto(String),
+
+    /// Falha de armazenamento ou de E/S: banco de dados (inclusive bloqueado
+    /// por outro processo), arquivos, configuração ou área de transferência.
+    #[error("{0}")]
+    Storage(String),
+
+    /// Operação incompatível com o estado atual, como um nome já em uso.
+    #[error("{0}")]
+    Conflict(String),
+}
+
+/// Categoria de um [`Error`], enviada às interfaces como `code`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    NotFound,
+    Unauthorized,
+    Validation,
+    Crypto,
+    Storage,
+    Conflict,
+}
+
+impl ErrorCode {
+    /// Nome serializado da categoria.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NotFound => "not_found",
+            Self::Unauthorized => "unauthorized",
+            Self::Validation => "validation",
+            Self::Crypto => "crypto",
+            Self::Storage => "storage",
+            Self::Conflict => "conflict",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Error {
+    /// Cria um [`Error::NotFound`].
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::NotFound(message.into())
+    }
+
+    /// Cria um [`Error::Unauthorized`].
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::Unauthorized(message.into())
+    }
+
+    /// Cria um [`Error::Validation`] para o campo informado.
+    pub fn validation(field: &'static str, message: impl Into<String>) -> Self {
+        Self::Validation {
+            field,
+            message: message.into(),
+        }
+    }
+
+    /// Cria um [`Error::Crypto`].
+    pub fn crypto(message: impl Into<String>) -> Self {
+        Self::Crypto(message.into())
+    }
+
+    /// Cria um [`Error::Storage`].
+    pub fn storage(message: impl Into<String>) -> Self {
+        Self::Storage(message.into())
+    }
+
+    /// Cria um [`Error::Conflict`].
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::Conflict(message.into())
+    }
+
+    /// Categoria do erro.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::NotFound(_) => ErrorCode::NotFound,
+            Self::Unauthorized(_) => ErrorCode::Unauthorized,
+            Self::Validation { .. } => ErrorCode::Validation,
+            Self::Crypto(_) => ErrorCode::Crypto,
+            Self::Storage(_) => ErrorCode::Storage,
+            Self::Conflict(_) => ErrorCode::Conflict,
+        }
+    }
+
+    /// Campo rejeitado, nos erros de validação.
+    pub fn field(&self) -> Option<&'static str> {
+        match self {
+            Self::Validation { field, .. } => Some(field),
+            _ => None,
+        }
+    }
+}
+
+/// Serializa como `{ "code", "message", "field"? }`.
+impl Serialize for Error {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let field = self.field();
+        let mut state =
+            serializer.serialize_struct("Error", if field.is_some() { 3 } else { 2 })?;
+        state.serialize_field("code", &self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        if let Some(field) = field {
+            state.serialize_field("field", field)?;
+        }
+        state.end()
+    }
+}
+
+/// Violações de unicidade e de chave estrangeira recebem a mesma categoria
+/// que os repositórios em memória atribuem aos casos equivalentes.
+impl From<rusqlite::Error> for Error {
+    fn from(err: rusqlite::Error) -> Self {
+        use rusqlite::ffi;
+
+        match &err {
+            rusqlite::Error::QueryReturnedNoRows => {
+                Self::NotFound("Registro não encontrado".into())
+            }
+            rusqlite::Error::SqliteFailure(e, _)
+                if e.extended_code == ffi::SQLITE_CONSTRAINT_UNIQUE
+                    || e.extended_code == ffi::SQLITE_CONSTRAINT_PRIMARYKEY =>
+            {
+                Self::Conflict(format!("Registro já cadastrado: {err}"))
+            }
+            rusqlite::Error::SqliteFailure(e, _)
+                if e.extended_code == ffi::SQLITE_CONSTRAINT_FOREIGNKEY =>
+            {
+                Self::NotFound(format!("Registro relacionado não encontrado: {err}"))
+            }
+            _ => Self::Storage(format!("Falha no banco de dados: {err}")),
+        }
+    }
+}
+
+impl From<r2d2::Error> for Error {
+    fn from(err: r2d2::Error) -> Self {
+        Self::Storage(format!(
+            "Nenhuma conexão com o banco de dados disponível: {err}"
+        ))
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Self::Storage(format!("Falha de leitura ou escrita: {err}"))
+    }
+}
+
+/// IDs e datas ilegíveis só surgem ao ler registros persistidos corrompidos.
+impl From<uuid::Error> for Error {
+    fn from(err: uuid::Error) -> Self {
+        Self::Storage(format!("Identificador armazenado inválido: {err}"))
+    }
+}
+
+impl From<chrono::ParseError> for Error {
+    fn from(err: chrono::ParseError) -> Self {
+        Self::Storage(format!("Data armazenada inválida: {err}"))
+    }
+}
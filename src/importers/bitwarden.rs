@@ -0,0 +1,119 @@
+//! Importador da exportação JSON (não criptografada) do Bitwarden.
+//!
+//! Estrutura relevante:
+//!
+//! ```json
+//! { "encrypted": false,
+//!   "items": [ { "type": 1, "name": "...", "notes": "...",
+//!                "login": { "username": "...", "password": "...", "totp": "...",
+//!                           "uris": [ { "uri": "..." } ] } } ] }
+//! ```
+
+use serde::Deserialize;
+
+use super::{ParsedImport, RawEntry};
+use crate::error::{Error, Result};
+
+/// Tipo de item "login" no Bitwarden.
+const TYPE_LOGIN: u8 = 1;
+
+/// Tipo de item "nota segura" no Bitwarden.
+const TYPE_SECURE_NOTE: u8 = 2;
+
+#[derive(Deserialize)]
+struct Export {
+    #[serde(default)]
+    encrypted: bool,
+    #[serde(default)]
+    items: Vec<Item>,
+}
+
+#[derive(Deserialize)]
+struct Item {
+    #[serde(rename = "type")]
+    kind: u8,
+    name: Option<String>,
+    notes: Option<String>,
+    login: Option<Login>,
+}
+
+#[derive(Deserialize)]
+struct Login {
+    username: Option<String>,
+    password: Option<String>,
+    totp: Option<String>,
+    #[serde(default)]
+    uris: Option<Vec<Uri>>,
+}
+
+#[derive(Deserialize)]
+struct Uri {
+    uri: Option<String>,
+}
+
+/// Interpreta o JSON do Bitwarden, acrescentando as entradas em `parsed`.
+///
+/// ### Retorno
+/// - `Err(Error)`: JSON malformado ou exportação protegida por senha/chave da conta.
+pub(crate) fn parse(data: &[u8], parsed: &mut ParsedImport) -> Result<()> {
+    let export: Export = serde_json::from_slice(data).map_err(|e| {
+        Error::validation("file", format!("Arquivo JSON do Bitwarden inválido: {e}"))
+    })?;
+
+    if export.encrypted {
+        return Err(Error::validation(
+            "file",
+            "Exportação criptografada do Bitwarden não é suportada; exporte no formato JSON sem criptografia",
+        ));
+    }
+
+    for (index, item) in export.items.into_iter().enumerate() {
+        let position = index + 1;
+
+        match item.kind {
+            TYPE_LOGIN => {
+                let login = item.login;
+                let (username, password, totp, url) = match login {
+                    Some(login) => (
+                        login.username,
+                        login.password,
+                        login.totp,
+                        login
+                            .uris
+                            .unwrap_or_default()
+                            .into_iter()
+                            .find_map(|u| u.uri.filter(|u| !u.trim().is_empty())),
+                    ),
+                    None => (None, None, None, None),
+                };
+
+                parsed.push(
+                    position,
+                    RawEntry {
+                        name: item.name,
+                        username,
+                        url,
+                        notes: item.notes,
+                        password,
+                        totp,
+                    },
+                );
+            }
+            TYPE_SECURE_NOTE => parsed.push(
+                position,
+                RawEntry {
+                    name: item.name,
+                    notes: item.notes,
+                    ..RawEntry::default()
+                },
+            ),
+            _ => parsed.skip(
+                position,
+                item.name.as_deref(),
+                "Tipo de item não suportado (cartão ou identidade)",
+            ),
+        }
+    }
+
+    Ok(())
+}
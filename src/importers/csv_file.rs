@@ -0,0 +1,101 @@
+//! Importador de arquivos CSV com cabeçalho.
+//!
+//! As colunas são identificadas pelo nome (sem diferenciar maiúsculas), o que
+//! cobre os formatos abaixo sem configuração adicional:
+//!
+//! | Origem        | Cabeçalho                                                         |
+//! | ------------- | ----------------------------------------------------------------- |
+//! | Chrome / Edge | `name,url,username,password,note`                                 |
+//! | Firefox       | `url,username,password,httpRealm,formActionOrigin,guid,...`       |
+//! | 1Password     | `Title,Url,Username,Password,OTPAuth,Favorite,Archived,Tags,Notes` |
+
+use super::{ParsedImport, RawEntry};
+use crate::error::{Error, Result};
+
+/// Campo de destino de uma coluna.
+#[derive(Clone, Copy)]
+enum Field {
+    Name,
+    Url,
+    Username,
+    Password,
+    Notes,
+    Totp,
+    Archived,
+}
+
+/// Associa o nome de uma coluna a um campo.
+fn field_for(header: &str) -> Option<Field> {
+    match header.trim().to_ascii_lowercase().as_str() {
+        "name" | "title" => Some(Field::Name),
+        "url" | "website" | "login_uri" | "uri" => Some(Field::Url),
+        "username" | "user name" | "login_username" | "login" => Some(Field::Username),
+        "password" | "login_password" => Some(Field::Password),
+        "note" | "notes" | "notesplain" | "extra" => Some(Field::Notes),
+        "otpauth" | "totp" | "login_totp" => Some(Field::Totp),
+        "archived" => Some(Field::Archived),
+        _ => None,
+    }
+}
+
+/// Interpreta um CSV com cabeçalho, acrescentando as entradas em `parsed`.
+///
+/// ### Retorno
+/// - `Err(Error)`: CSV malformado ou sem coluna de senha.
+pub(crate) fn parse(data: &[u8], parsed: &mut ParsedImport) -> Result<()> {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(data);
+
+    let columns: Vec<Option<Field>> = reader
+        .headers()
+        .map_err(|e| Error::validation("file", format!("Cabeçalho CSV inválido: {e}")))?
+        .iter()
+        .map(field_for)
+        .collect();
+
+    if !columns.iter().any(|c| matches!(c, Some(Field::Password))) {
+        return Err(Error::validation(
+            "file",
+            "Cabeçalho CSV não reconhecido: coluna de senha (\"password\") não encontrada",
+        ));
+    }
+
+    for (index, record) in reader.records().enumerate() {
+        let position = index + 1;
+        let record = record.map_err(|e| {
+            Error::validation("file", format!("Linha CSV {position} inválida: {e}"))
+        })?;
+
+        let mut raw = RawEntry::default();
+        let mut archived = false;
+
+        for (value, column) in record.iter().zip(&columns) {
+            let value = Some(value.to_string());
+            match column {
+                Some(Field::Name) => raw.name = value,
+                Some(Field::Url) => raw.url = value,
+                Some(Field::Username) => raw.username = value,
+                Some(Field::Password) => raw.password = value,
+                Some(Field::Notes) => raw.notes = value,
+                Some(Field::Totp) => raw.totp = value,
+                Some(Field::Archived) => {
+                    archived = value.is_some_and(|v| v.eq_ignore_ascii_case("true"))
+                }
+                None => {}
+            }
+        }
+
+        if archived {
+            parsed.skip(position, raw.name.as_deref(), "Item arquivado");
+        } else if raw
+            .url
+            .as_deref()
+            .is_some_and(|u| u.starts_with("chrome://") || u.starts_with("about:"))
+        {
+            parsed.skip(position, raw.url.as_deref(), "Entrada interna do navegador");
+        } else {
+            parsed.push(position, raw);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,221 @@
+//! Importador da exportação XML do KeePass 2 / KeePassXC.
+//!
+//! Estrutura relevante:
+//!
+//! ```xml
+//! <KeePassFile>
+//!   <Meta><RecycleBinUUID>...</RecycleBinUUID></Meta>
+//!   <Root>
+//!     <Group>
+//!       <UUID>...</UUID>
+//!       <Entry>
+//!         <String><Key>Title</Key><Value>...</Value></String>
+//!         <History><Entry>...</Entry></History>
+//!       </Entry>
+//!       <Group>...</Group>
+//!     </Group>
+//!   </Root>
+//! </KeePassFile>
+//! ```
+//!
+//! Versões anteriores das entradas (`History`) são ignoradas, assim como as
+//! entradas da lixeira.
+
+use quick_xml::escape::unescape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::reader::Reader;
+
+use super::{ParsedImport, RawEntry};
+use crate::error::{Error, Result};
+
+/// Grupo em processamento.
+struct Group {
+    /// O grupo é a lixeira ou está dentro dela.
+    in_recycle_bin: bool,
+}
+
+/// Entrada em processamento.
+#[derive(Default)]
+struct Entry {
+    raw: RawEntry,
+    /// Chave do `<String>` corrente, aguardando o `<Value>`.
+    key: Option<String>,
+}
+
+/// Interpreta o XML do KeePass, acrescentando as entradas em `parsed`.
+///
+/// ### Retorno
+/// - `Err(Error)`: XML malformado ou que não é uma exportação do KeePass.
+pub(crate) fn parse(data: &[u8], parsed: &mut ParsedImport) -> Result<()> {
+    let mut reader = Reader::from_reader(data);
+
+    let mut path: Vec<String> = Vec::new();
+    let mut groups: Vec<Group> = Vec::new();
+    let mut entry: Option<Entry> = None;
+    let mut recycle_bin: Option<String> = None;
+    let mut seen_root = false;
+    let mut position = 0;
+
+    loop {
+        let event = reader.read_event().map_err(|e| xml_error(&reader, e))?;
+
+        match event {
+            Event::Start(start) => {
+                let name = element_name(&start);
+                let in_history = path.iter().any(|p| p == "History");
+
+                match name.as_str() {
+                    "KeePassFile" => seen_root = true,
+                    "Group" => groups.push(Group {
+                        in_recycle_bin: groups.last().is_some_and(|g| g.in_recycle_bin),
+                    }),
+                    "Entry" if !in_history => entry = Some(Entry::default()),
+                    "RecycleBinUUID" | "UUID" | "Key" | "Value" => {
+                        let text = read_text(&mut reader, &start)?;
+                        handle_text(
+                            &name,
+                            text,
+                            &path,
+                            &mut groups,
+                            &mut entry,
+                            &mut recycle_bin,
+                        );
+                        continue;
+                    }
+                    _ => {}
+                }
+
+                path.push(name);
+            }
+            Event::Empty(empty) => {
+                // `<Value/>`: valor vazio.
+                let name = element_name(&empty);
+                if name == "Value" {
+                    handle_text(
+                        &name,
+                        String::new(),
+                        &path,
+                        &mut groups,
+                        &mut entry,
+                        &mut recycle_bin,
+                    );
+                }
+            }
+            Event::End(_) => {
+                let Some(name) = path.pop() else {
+                    return Err(Error::validation("file", "XML do KeePass malformado"));
+                };
+                let in_history = path.iter().any(|p| p == "History");
+
+                match name.as_str() {
+                    "Group" => {
+                        groups.pop();
+                    }
+                    "Entry" if !in_history => {
+                        if let Some(finished) = entry.take() {
+                            position += 1;
+                            let title = finished.raw.name.clone();
+
+                            if groups.last().is_some_and(|g| g.in_recycle_bin) {
+                                parsed.skip(position, title.as_deref(), "Entrada na lixeira");
+                            } else {
+                                parsed.push(position, finished.raw);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if !seen_root {
+        return Err(Error::validation(
+            "file",
+            "Arquivo não é uma exportação XML do KeePass",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Trata o conteúdo textual de um elemento de interesse.
+fn handle_text(
+    name: &str,
+    text: String,
+    path: &[String],
+    groups: &mut [Group],
+    entry: &mut Option<Entry>,
+    recycle_bin: &mut Option<String>,
+) {
+    let parent = path.last().map(String::as_str);
+    let in_history = path.iter().any(|p| p == "History");
+
+    match (name, parent) {
+        ("RecycleBinUUID", Some("Meta")) => {
+            *recycle_bin = Some(text).filter(|t| !t.is_empty());
+        }
+        ("UUID", Some("Group")) => {
+            if recycle_bin.as_deref() == Some(text.as_str())
+                && let Some(group) = groups.last_mut()
+            {
+                group.in_recycle_bin = true;
+            }
+        }
+        ("Key", Some("String")) if !in_history => {
+            if let Some(entry) = entry {
+                entry.key = Some(text);
+            }
+        }
+        ("Value", Some("String")) if !in_history => {
+            if let Some(entry) = entry {
+                let raw = &mut entry.raw;
+                let value = Some(text);
+
+                match entry.key.take().as_deref() {
+                    Some("Title") => raw.name = value,
+                    Some("UserName") => raw.username = value,
+                    Some("Password") => raw.password = value,
+                    Some("URL") => raw.url = value,
+                    Some("Notes") => raw.notes = value,
+                    // KeePassXC grava a URI completa; o KeePass 2 grava apenas o segredo.
+                    Some("otp") | Some("TimeOtp-Secret-Base32") if raw.totp.is_none() => {
+                        raw.totp = value
+                    }
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Nome local do elemento.
+fn element_name(start: &BytesStart) -> String {
+    String::from_utf8_lossy(start.local_name().as_ref()).into_owned()
+}
+
+/// Lê e decodifica o texto até o fechamento do elemento.
+fn read_text(reader: &mut Reader<&[u8]>, start: &BytesStart) -> Result<String> {
+    let raw = reader
+        .read_text(start.name())
+        .map_err(|e| xml_error(reader, e))?;
+
+    Ok(unescape(&raw)
+        .map_err(|e| {
+            Error::validation("file", format!("XML do KeePass com entidade inválida: {e}"))
+        })?
+        .into_owned())
+}
+
+fn xml_error(reader: &Reader<&[u8]>, err: impl std::fmt::Display) -> Error {
+    Error::validation(
+        "file",
+        format!(
+            "XML do KeePass inválido (posição {}): {err}",
+            reader.buffer_position()
+        ),
+    )
+}
@@ -0,0 +1,329 @@
+//! # Importers
+//!
+//! Este módulo converte arquivos exportados por **outros gerenciadores de senhas**
+//! em entradas prontas para serem gravadas no cofre.
+//!
+//! Os importadores apenas interpretam o arquivo: não acessam o banco nem a sessão.
+//! A gravação (com detecção de duplicatas e modo de simulação) fica a cargo de
+//! [`CredentialService::import_external`](crate::services::credential_service::CredentialService::import_external).
+//!
+//! ---
+//!
+//! ## Formatos suportados
+//!
+//! ### [`bitwarden`]
+//!
+//! Exportação JSON **não criptografada** do Bitwarden (itens de login e notas seguras).
+//!
+//! ---
+//!
+//! ### [`keepass`]
+//!
+//! Exportação XML do KeePass 2 / KeePassXC, percorrendo grupos aninhados e
+//! ignorando o histórico das entradas e a lixeira.
+//!
+//! ---
+//!
+//! ### [`onepassword`]
+//!
+//! Arquivos `.1pux` (ZIP com `export.data`) e exportações CSV do 1Password.
+//!
+//! ---
+//!
+//! ### [`csv_file`]
+//!
+//! Exportações CSV do Chrome/Edge e do Firefox, além do leitor CSV genérico
+//! (colunas identificadas pelo cabeçalho) compartilhado com o 1Password.
+//!
+//! ---
+//!
+//! ## Mapeamento
+//!
+//! Todo formato é convertido em [`ImportedEntry`] (nome, usuário, URL, notas,
+//! senha e TOTP). Entradas sem nome recebem o domínio da URL ou o usuário; nomes
+//! acima do limite do domínio são truncados. Itens que não representam credenciais
+//! (cartões, identidades, itens arquivados etc.) são descartados e relatados em
+//! [`ParsedImport::skipped`].
+
+pub mod bitwarden;
+pub mod csv_file;
+pub mod keepass;
+pub mod onepassword;
+
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use crate::error::{Error, Result};
+
+/// Tamanho máximo (em bytes) do nome de uma credencial.
+const MAX_NAME_LEN: usize = 64;
+
+/// Formato de arquivo de origem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    /// JSON não criptografado do Bitwarden.
+    Bitwarden,
+    /// XML do KeePass 2 / KeePassXC.
+    KeepassXml,
+    /// Arquivo `.1pux` do 1Password.
+    OnePassword1pux,
+    /// CSV do 1Password.
+    OnePasswordCsv,
+    /// CSV do Chrome/Edge ou do Firefox.
+    BrowserCsv,
+}
+
+impl ImportFormat {
+    /// Todos os formatos, na ordem exibida nas interfaces.
+    pub const ALL: [ImportFormat; 5] = [
+        Self::Bitwarden,
+        Self::KeepassXml,
+        Self::OnePassword1pux,
+        Self::OnePasswordCsv,
+        Self::BrowserCsv,
+    ];
+
+    /// Nome legível do formato.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Bitwarden => "Bitwarden (JSON)",
+            Self::KeepassXml => "KeePass 2 (XML)",
+            Self::OnePassword1pux => "1Password (1PUX)",
+            Self::OnePasswordCsv => "1Password (CSV)",
+            Self::BrowserCsv => "Chrome/Firefox (CSV)",
+        }
+    }
+
+    /// Identifica o formato a partir do conteúdo do arquivo.
+    ///
+    /// ### Retorno
+    /// - `Some(ImportFormat)`: formato reconhecido.
+    /// - `None`: conteúdo não corresponde a nenhum formato suportado.
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        if data.starts_with(b"PK\x03\x04") {
+            return Some(Self::OnePassword1pux);
+        }
+
+        let text = String::from_utf8_lossy(&data[..data.len().min(4096)]);
+        let text = text.trim_start_matches('\u{feff}').trim_start();
+
+        if text.starts_with('{') {
+            return text.contains("\"items\"").then_some(Self::Bitwarden);
+        }
+        if text.starts_with('<') {
+            return text.contains("<KeePassFile").then_some(Self::KeepassXml);
+        }
+
+        let header = text.lines().next()?.to_ascii_lowercase();
+        if header.contains("otpauth") || header.contains("title") {
+            Some(Self::OnePasswordCsv)
+        } else if header.contains("url") && header.contains("password") {
+            Some(Self::BrowserCsv)
+        } else {
+            None
+        }
+    }
+}
+
+/// Credencial lida de um arquivo externo, com campos sensíveis em texto plano.
+///
+/// Os campos sensíveis são apagados da memória quando a instância é descartada.
+pub struct ImportedEntry {
+    pub name: String,
+    pub username: Option<String>,
+    pub url: Option<String>,
+    pub notes: Option<String>,
+    pub password: Option<String>,
+    /// URI `otpauth://` ou segredo Base32, quando o gerenciador de origem o exporta.
+    pub totp: Option<String>,
+}
+
+impl Drop for ImportedEntry {
+    fn drop(&mut self) {
+        self.notes.zeroize();
+        self.password.zeroize();
+        self.totp.zeroize();
+    }
+}
+
+/// Problema encontrado em uma entrada durante a importação.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportIssue {
+    /// Nome (ou posição) da entrada no arquivo de origem.
+    pub entry: String,
+    /// Motivo legível.
+    pub reason: String,
+}
+
+/// Resultado da interpretação de um arquivo externo.
+pub struct ParsedImport {
+    pub format: ImportFormat,
+    /// Entradas que podem ser gravadas no cofre.
+    pub entries: Vec<ImportedEntry>,
+    /// Itens do arquivo descartados pelo importador.
+    pub skipped: Vec<ImportIssue>,
+}
+
+/// Relatório de uma importação (ou simulação) de arquivo externo.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReport {
+    pub format: ImportFormat,
+    /// Quando verdadeiro, nada foi gravado.
+    pub dry_run: bool,
+    /// Quantidade de itens encontrados no arquivo.
+    pub total: usize,
+    /// Credenciais gravadas (ou que seriam gravadas, na simulação).
+    pub imported: usize,
+    /// Entradas ignoradas por já existirem no cofre ou repetirem outra do arquivo.
+    pub duplicates: Vec<ImportIssue>,
+    /// Itens descartados (tipo não suportado, sem dados etc.).
+    pub skipped: Vec<ImportIssue>,
+    /// Entradas importadas com ressalvas (ex.: TOTP inválido descartado).
+    pub warnings: Vec<ImportIssue>,
+}
+
+/// Interpreta um arquivo exportado por outro gerenciador.
+///
+/// ### Parâmetros
+/// - `format`: Formato do arquivo; `None` tenta identificá-lo pelo conteúdo.
+/// - `data`: Conteúdo do arquivo.
+///
+/// ### Retorno
+/// - `Ok(ParsedImport)`: entradas válidas e itens descartados.
+/// - `Err(Error)`: formato não reconhecido ou arquivo malformado.
+pub fn parse(format: Option<ImportFormat>, data: &[u8]) -> Result<ParsedImport> {
+    let format = match format {
+        Some(format) => format,
+        None => ImportFormat::detect(data)
+            .ok_or_else(|| Error::validation("file", "Formato do arquivo não reconhecido"))?,
+    };
+
+    let mut parsed = ParsedImport {
+        format,
+        entries: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    match format {
+        ImportFormat::Bitwarden => bitwarden::parse(data, &mut parsed)?,
+        ImportFormat::KeepassXml => keepass::parse(data, &mut parsed)?,
+        ImportFormat::OnePassword1pux => onepassword::parse_1pux(data, &mut parsed)?,
+        ImportFormat::OnePasswordCsv => csv_file::parse(data, &mut parsed)?,
+        ImportFormat::BrowserCsv => csv_file::parse(data, &mut parsed)?,
+    }
+
+    Ok(parsed)
+}
+
+/// Campos brutos de um item, antes da normalização.
+#[derive(Default)]
+pub(crate) struct RawEntry {
+    pub name: Option<String>,
+    pub username: Option<String>,
+    pub url: Option<String>,
+    pub notes: Option<String>,
+    pub password: Option<String>,
+    pub totp: Option<String>,
+}
+
+impl ParsedImport {
+    /// Normaliza um item e o acrescenta às entradas (ou aos descartados).
+    ///
+    /// ### Aplicação
+    /// Campos em branco viram `None`; na ausência de nome usa-se o domínio da URL
+    /// ou o usuário; nomes longos são truncados. Itens sem nenhum dado útil são
+    /// descartados.
+    pub(crate) fn push(&mut self, position: usize, raw: RawEntry) {
+        let RawEntry {
+            name,
+            username,
+            url,
+            notes,
+            password,
+            totp,
+        } = raw;
+
+        let username = non_blank(username);
+        let url = non_blank(url);
+        let notes = non_blank(notes);
+        let password = non_blank(password);
+        let totp = non_blank(totp);
+
+        let name = non_blank(name)
+            .or_else(|| url.as_deref().and_then(host_of))
+            .or_else(|| username.clone());
+
+        let Some(name) = name else {
+            self.skip(position, None, "Entrada sem nome, usuário ou URL");
+            return;
+        };
+
+        if password.is_none() && notes.is_none() && totp.is_none() && url.is_none() {
+            self.skip(
+                position,
+                Some(&name),
+                "Entrada sem senha, notas, TOTP ou URL",
+            );
+            return;
+        }
+
+        self.entries.push(ImportedEntry {
+            name: truncate(name.trim(), MAX_NAME_LEN),
+            username,
+            url,
+            notes,
+            password,
+            totp,
+        });
+    }
+
+    /// Registra um item descartado.
+    pub(crate) fn skip(&mut self, position: usize, name: Option<&str>, reason: &str) {
+        self.skipped.push(ImportIssue {
+            entry: describe(position, name),
+            reason: reason.to_string(),
+        });
+    }
+}
+
+/// Identificação de um item para os relatórios: o nome ou a posição no arquivo.
+fn describe(position: usize, name: Option<&str>) -> String {
+    match name.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(name) => name.to_string(),
+        None => format!("item #{position}"),
+    }
+}
+
+/// Converte textos em branco em `None`.
+fn non_blank(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.trim().is_empty())
+}
+
+/// Extrai o domínio de uma URL (sem esquema, porta, caminho ou `www.`).
+fn host_of(url: &str) -> Option<String> {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let host = without_scheme
+        .split(['/', '?', '#'])
+        .next()?
+        .rsplit('@')
+        .next()?
+        .split(':')
+        .next()?
+        .trim_start_matches("www.");
+
+    (!host.is_empty()).then(|| host.to_string())
+}
+
+/// Trunca o texto em até `max` bytes, respeitando os limites de caractere.
+fn truncate(value: &str, max: usize) -> String {
+    if value.len() <= max {
+        return value.to_string();
+    }
+
+    let mut end = max;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    value[..end].trim_end().to_string()
+}
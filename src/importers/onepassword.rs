@@ -0,0 +1,130 @@
+//! Importador dos arquivos `.1pux` do 1Password.
+//!
+//! O arquivo é um ZIP cujo `export.data` contém um JSON com contas, cofres e itens:
+//!
+//! ```json
+//! { "accounts": [ { "vaults": [ { "items": [ {
+//!     "state": "active", "categoryUuid": "001",
+//!     "overview": { "title": "...", "url": "..." },
+//!     "details": { "loginFields": [ { "designation": "username", "value": "..." } ],
+//!                  "notesPlain": "...",
+//!                  "sections": [ { "fields": [ { "value": { "totp": "otpauth://..." } } ] } ] }
+//! } ] } ] } ] }
+//! ```
+//!
+//! A exportação CSV do 1Password é tratada pelo leitor genérico de [`super::csv_file`].
+
+use serde_json::Value;
+use std::io::{Cursor, Read};
+use zeroize::Zeroize;
+
+use super::{ParsedImport, RawEntry};
+use crate::error::{Error, Result};
+
+/// Nome do arquivo com os dados dentro do `.1pux`.
+const EXPORT_DATA: &str = "export.data";
+
+/// Categoria "Login".
+const CATEGORY_LOGIN: &str = "001";
+
+/// Categoria "Nota segura".
+const CATEGORY_SECURE_NOTE: &str = "003";
+
+/// Categoria "Senha".
+const CATEGORY_PASSWORD: &str = "005";
+
+/// Interpreta um arquivo `.1pux`, acrescentando as entradas em `parsed`.
+///
+/// ### Retorno
+/// - `Err(Error)`: arquivo que não é um ZIP, sem `export.data` ou com JSON inválido.
+pub(crate) fn parse_1pux(data: &[u8], parsed: &mut ParsedImport) -> Result<()> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))
+        .map_err(|e| Error::validation("file", format!("Arquivo 1PUX inválido: {e}")))?;
+
+    let mut json = Vec::new();
+    archive
+        .by_name(EXPORT_DATA)
+        .map_err(|_| Error::validation("file", format!("Arquivo 1PUX sem '{EXPORT_DATA}'")))?
+        .read_to_end(&mut json)
+        .map_err(|e| Error::validation("file", format!("Falha ao ler '{EXPORT_DATA}': {e}")))?;
+
+    let export: Result<Value> = serde_json::from_slice(&json)
+        .map_err(|e| Error::validation("file", format!("Conteúdo do arquivo 1PUX inválido: {e}")));
+    json.zeroize();
+    let export = export?;
+
+    let items = export["accounts"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|account| account["vaults"].as_array().into_iter().flatten())
+        .flat_map(|vault| vault["items"].as_array().into_iter().flatten());
+
+    for (index, item) in items.enumerate() {
+        let position = index + 1;
+        let overview = &item["overview"];
+        let details = &item["details"];
+        let title = text(&overview["title"]);
+
+        if item["state"].as_str() == Some("archived") {
+            parsed.skip(position, title.as_deref(), "Item arquivado");
+            continue;
+        }
+
+        let category = item["categoryUuid"].as_str().unwrap_or_default();
+        if ![CATEGORY_LOGIN, CATEGORY_SECURE_NOTE, CATEGORY_PASSWORD].contains(&category) {
+            parsed.skip(
+                position,
+                title.as_deref(),
+                "Categoria não suportada (apenas logins, senhas e notas seguras)",
+            );
+            continue;
+        }
+
+        let login_field = |designation: &str| {
+            details["loginFields"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .find(|f| f["designation"].as_str() == Some(designation))
+                .and_then(|f| text(&f["value"]))
+        };
+
+        let totp = details["sections"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .flat_map(|section| section["fields"].as_array().into_iter().flatten())
+            .find_map(|field| text(&field["value"]["totp"]));
+
+        let url = text(&overview["url"]).or_else(|| {
+            overview["urls"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .find_map(|u| text(&u["url"]))
+        });
+
+        parsed.push(
+            position,
+            RawEntry {
+                name: title,
+                username: login_field("username"),
+                url,
+                notes: text(&details["notesPlain"]),
+                password: login_field("password").or_else(|| text(&details["password"])),
+                totp,
+            },
+        );
+    }
+
+    Ok(())
+}
+
+/// Texto não vazio de um valor JSON.
+fn text(value: &Value) -> Option<String> {
+    value
+        .as_str()
+        .filter(|s| !s.trim().is_empty())
+        .map(str::to_string)
+}
@@ -0,0 +1,461 @@
+//! Área de transferência com limpeza automática
+//!
+//! Este componente provê funcionalidades de:
+//! - Cópia de senhas e códigos TOTP para a área de transferência do sistema;
+//! - Limpeza automática após um prazo configurável, somente se a área de
+//!   transferência ainda contiver o valor copiado;
+//! - Backends substituíveis ([`ClipboardBackend`]): Wayland (`wl-copy`/`wl-paste`),
+//!   X11 (`xclip` ou `xsel`), macOS (`pbcopy`/`pbpaste`), arquivo e memória.
+//!
+//! O valor copiado não é mantido pela limpeza agendada: apenas o seu hash
+//! SHA-256 é guardado para a comparação no momento da limpeza.
+//!
+//! ## Configuração
+//!
+//! ```env
+//! CLIPBOARD_BACKEND=auto   # auto, wayland, x11, macos ou file
+//! CLIPBOARD_FILE=          # arquivo usado pelo backend "file"
+//! CLIPBOARD_CLEAR_SECS=20  # zero desativa a limpeza
+//! ```
+
+use sha2::{Digest, Sha256};
+use std::{
+    env, fs,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+use tracing::{debug, info, warn};
+
+use crate::error::{Error, Result};
+
+/// Prazo padrão, em segundos, até a limpeza da área de transferência.
+pub const DEFAULT_CLIPBOARD_CLEAR_SECS: u64 = 20;
+
+/// Acesso de baixo nível a uma área de transferência.
+pub trait ClipboardBackend: Send + Sync {
+    /// Nome do backend, para mensagens e logs.
+    fn name(&self) -> &str;
+
+    /// Substitui o conteúdo da área de transferência.
+    fn set_text(&self, text: &str) -> Result<()>;
+
+    /// Conteúdo atual (`None` quando vazia ou sem texto).
+    fn get_text(&self) -> Result<Option<String>>;
+
+    /// Esvazia a área de transferência.
+    fn clear(&self) -> Result<()>;
+}
+
+/// Backend baseado em utilitários de linha de comando do sistema.
+///
+/// O texto é enviado pela entrada padrão do programa, nunca pelos argumentos.
+#[derive(Debug, Clone)]
+pub struct CommandBackend {
+    name: &'static str,
+    copy: &'static [&'static str],
+    paste: &'static [&'static str],
+    /// Comando de limpeza; sem ele, a limpeza copia um texto vazio.
+    clear: Option<&'static [&'static str]>,
+}
+
+impl CommandBackend {
+    /// Wayland, via `wl-clipboard`.
+    pub fn wayland() -> Self {
+        Self {
+            name: "wayland",
+            copy: &["wl-copy"],
+            paste: &["wl-paste", "--no-newline"],
+            clear: Some(&["wl-copy", "--clear"]),
+        }
+    }
+
+    /// X11, via `xclip`.
+    pub fn xclip() -> Self {
+        Self {
+            name: "x11 (xclip)",
+            copy: &["xclip", "-selection", "clipboard", "-in"],
+            paste: &["xclip", "-selection", "clipboard", "-out"],
+            clear: None,
+        }
+    }
+
+    /// X11, via `xsel`.
+    pub fn xsel() -> Self {
+        Self {
+            name: "x11 (xsel)",
+            copy: &["xsel", "--clipboard", "--input"],
+            paste: &["xsel", "--clipboard", "--output"],
+            clear: Some(&["xsel", "--clipboard", "--clear"]),
+        }
+    }
+
+    /// macOS, via `pbcopy`/`pbpaste`.
+    pub fn macos() -> Self {
+        Self {
+            name: "macos",
+            copy: &["pbcopy"],
+            paste: &["pbpaste"],
+            clear: None,
+        }
+    }
+
+    /// Indica se os programas usados pelo backend estão no `PATH`.
+    pub fn is_available(&self) -> bool {
+        find_program(self.copy[0]) && find_program(self.paste[0])
+    }
+
+    fn run(&self, args: &[&str], input: &str) -> Result<()> {
+        // A saída é descartada: utilitários como o xclip permanecem em segundo
+        // plano servindo o conteúdo e manteriam um pipe aberto indefinidamente.
+        let mut child = Command::new(args[0])
+            .args(&args[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| Error::storage(format!("Falha ao executar '{}': {e}", args[0])))?;
+
+        child
+            .stdin
+            .take()
+            .ok_or_else(|| Error::storage(format!("Entrada padrão de '{}' indisponível", args[0])))?
+            .write_all(input.as_bytes())?;
+
+        let status = child.wait()?;
+        if !status.success() {
+            return Err(Error::storage(format!(
+                "'{}' terminou com {status}",
+                args[0]
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl ClipboardBackend for CommandBackend {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn set_text(&self, text: &str) -> Result<()> {
+        self.run(self.copy, text)
+    }
+
+    fn get_text(&self) -> Result<Option<String>> {
+        let output = Command::new(self.paste[0])
+            .args(&self.paste[1..])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .map_err(|e| Error::storage(format!("Falha ao executar '{}': {e}", self.paste[0])))?;
+
+        // Área de transferência vazia ou com conteúdo não textual.
+        if !output.status.success() || output.stdout.is_empty() {
+            return Ok(None);
+        }
+        Ok(String::from_utf8(output.stdout).ok())
+    }
+
+    fn clear(&self) -> Result<()> {
+        match self.clear {
+            Some(args) => self.run(args, ""),
+            None => self.run(self.copy, ""),
+        }
+    }
+}
+
+/// Backend que grava o conteúdo em um arquivo, para ambientes sem interface
+/// gráfica e testes de integração.
+#[derive(Debug, Clone)]
+pub struct FileBackend {
+    path: PathBuf,
+}
+
+impl FileBackend {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl ClipboardBackend for FileBackend {
+    fn name(&self) -> &str {
+        "file"
+    }
+
+    fn set_text(&self, text: &str) -> Result<()> {
+        fs::write(&self.path, text)
+            .map_err(|e| Error::storage(format!("Falha ao gravar '{}': {e}", self.path.display())))
+    }
+
+    fn get_text(&self) -> Result<Option<String>> {
+        match fs::read_to_string(&self.path) {
+            Ok(text) if text.is_empty() => Ok(None),
+            Ok(text) => Ok(Some(text)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn clear(&self) -> Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Backend em memória, para testes.
+#[derive(Debug, Default)]
+pub struct MemoryBackend {
+    content: Mutex<Option<String>>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ClipboardBackend for MemoryBackend {
+    fn name(&self) -> &str {
+        "memory"
+    }
+
+    fn set_text(&self, text: &str) -> Result<()> {
+        *self.content.lock().unwrap() = Some(text.to_string());
+        Ok(())
+    }
+
+    fn get_text(&self) -> Result<Option<String>> {
+        Ok(self.content.lock().unwrap().clone())
+    }
+
+    fn clear(&self) -> Result<()> {
+        *self.content.lock().unwrap() = None;
+        Ok(())
+    }
+}
+
+/// Área de transferência com limpeza automática.
+///
+/// Clonar é barato: os clones compartilham o mesmo backend.
+#[derive(Clone)]
+pub struct Clipboard {
+    backend: Arc<dyn ClipboardBackend>,
+    clear_after: Option<Duration>,
+    /// Hash do último valor copiado cuja limpeza ainda não ocorreu.
+    pending: Arc<Mutex<Option<Vec<u8>>>>,
+}
+
+/// Resultado de uma cópia.
+pub struct ClipboardCopy {
+    clear_after: Option<Duration>,
+    clearer: Option<JoinHandle<()>>,
+}
+
+impl ClipboardCopy {
+    /// Prazo até a limpeza (`None` quando a limpeza está desativada).
+    pub fn clear_after(&self) -> Option<Duration> {
+        self.clear_after
+    }
+
+    /// Bloqueia até a limpeza agendada ser executada.
+    ///
+    /// ### Aplicação
+    /// Processos que encerram logo após copiar (como os subcomandos da CLI)
+    /// precisam aguardar, caso contrário a limpeza nunca aconteceria.
+    pub fn wait(self) {
+        if let Some(clearer) = self.clearer {
+            let _ = clearer.join();
+        }
+    }
+}
+
+impl Clipboard {
+    /// Cria a área de transferência sobre um backend.
+    ///
+    /// ### Parâmetros
+    /// - `backend`: Acesso à área de transferência.
+    /// - `clear_after`: Prazo até a limpeza (`None` desativa a limpeza).
+    pub fn new(backend: Arc<dyn ClipboardBackend>, clear_after: Option<Duration>) -> Self {
+        Self {
+            backend,
+            clear_after,
+            pending: Arc::default(),
+        }
+    }
+
+    /// Monta a área de transferência conforme as variáveis de ambiente.
+    ///
+    /// ### Retorno
+    /// - `Some(Clipboard)` com o backend configurado ou detectado.
+    /// - `None` quando nenhum backend está disponível (o motivo é registrado no log).
+    pub fn from_env() -> Option<Self> {
+        let backend = env::var("CLIPBOARD_BACKEND").unwrap_or_else(|_| "auto".to_string());
+
+        let backend: Arc<dyn ClipboardBackend> = match backend.trim().to_lowercase().as_str() {
+            "" | "auto" => detect()?,
+            "wayland" => Arc::new(CommandBackend::wayland()),
+            "x11" => {
+                let xclip = CommandBackend::xclip();
+                if xclip.is_available() {
+                    Arc::new(xclip)
+                } else {
+                    Arc::new(CommandBackend::xsel())
+                }
+            }
+            "macos" => Arc::new(CommandBackend::macos()),
+            "file" => match env::var("CLIPBOARD_FILE") {
+                Ok(path) if !path.trim().is_empty() => Arc::new(FileBackend::new(path.trim())),
+                _ => {
+                    warn!(
+                        "CLIPBOARD_BACKEND=file requer CLIPBOARD_FILE; área de transferência desativada"
+                    );
+                    return None;
+                }
+            },
+            other => {
+                warn!(
+                    "Valor inválido para CLIPBOARD_BACKEND='{other}'; área de transferência desativada"
+                );
+                return None;
+            }
+        };
+
+        let secs = match env::var("CLIPBOARD_CLEAR_SECS") {
+            Ok(value) => value.trim().parse::<u64>().unwrap_or_else(|_| {
+                warn!(
+                    "Valor inválido para CLIPBOARD_CLEAR_SECS='{value}', usando {DEFAULT_CLIPBOARD_CLEAR_SECS}s"
+                );
+                DEFAULT_CLIPBOARD_CLEAR_SECS
+            }),
+            Err(_) => DEFAULT_CLIPBOARD_CLEAR_SECS,
+        };
+
+        debug!("Área de transferência: backend '{}'", backend.name());
+        Some(Self::new(
+            backend,
+            (secs > 0).then(|| Duration::from_secs(secs)),
+        ))
+    }
+
+    /// Nome do backend em uso.
+    pub fn backend_name(&self) -> &str {
+        self.backend.name()
+    }
+
+    /// Prazo até a limpeza (`None` quando desativada).
+    pub fn clear_after(&self) -> Option<Duration> {
+        self.clear_after
+    }
+
+    /// Copia um valor e agenda a limpeza.
+    ///
+    /// ### Retorno
+    /// - `Ok(ClipboardCopy)` com o prazo da limpeza.
+    /// - `Err(Error)` quando o backend falhar.
+    ///
+    /// ### Aplicação
+    /// A limpeza ocorre em uma thread própria e só esvazia a área de
+    /// transferência se ela ainda contiver o valor copiado: um conteúdo copiado
+    /// depois pelo usuário (ou uma nova cópia) é preservado.
+    pub fn copy(&self, text: &str) -> Result<ClipboardCopy> {
+        self.backend.set_text(text)?;
+        info!(
+            "Valor copiado para a área de transferência (backend '{}')",
+            self.backend.name()
+        );
+
+        let digest = Sha256::digest(text.as_bytes()).to_vec();
+        *self.pending.lock().unwrap() = Some(digest.clone());
+
+        let clearer = self.clear_after.map(|delay| {
+            let clipboard = self.clone();
+
+            thread::spawn(move || {
+                thread::sleep(delay);
+                {
+                    let mut pending = clipboard.pending.lock().unwrap();
+                    if pending.as_deref() == Some(digest.as_slice()) {
+                        *pending = None;
+                    }
+                }
+                clipboard.clear_logging(&digest);
+            })
+        });
+
+        Ok(ClipboardCopy {
+            clear_after: self.clear_after,
+            clearer,
+        })
+    }
+
+    /// Limpa imediatamente o último valor copiado, sem aguardar o prazo.
+    ///
+    /// ### Aplicação
+    /// Chamado ao sair da aplicação ou encerrar a sessão, para que a limpeza
+    /// agendada não se perca com o fim do processo.
+    pub fn clear_pending(&self) {
+        let digest = self.pending.lock().unwrap().take();
+        if let Some(digest) = digest {
+            self.clear_logging(&digest);
+        }
+    }
+
+    fn clear_logging(&self, digest: &[u8]) {
+        match self.clear_if_holds(digest) {
+            Ok(true) => info!("Área de transferência limpa"),
+            Ok(false) => debug!("Área de transferência alterada; limpeza ignorada"),
+            Err(e) => warn!("Falha ao limpar a área de transferência: {e}"),
+        }
+    }
+
+    /// Esvazia a área de transferência se o conteúdo atual tiver o hash informado.
+    fn clear_if_holds(&self, digest: &[u8]) -> Result<bool> {
+        let holds = self
+            .backend
+            .get_text()?
+            .is_some_and(|current| Sha256::digest(current.as_bytes()).as_slice() == digest);
+
+        if holds {
+            self.backend.clear()?;
+        }
+        Ok(holds)
+    }
+}
+
+/// Detecta o backend pela sessão gráfica e pelos utilitários instalados.
+fn detect() -> Option<Arc<dyn ClipboardBackend>> {
+    let mut candidates = Vec::new();
+    if cfg!(target_os = "macos") {
+        candidates.push(CommandBackend::macos());
+    }
+    if env::var_os("WAYLAND_DISPLAY").is_some() {
+        candidates.push(CommandBackend::wayland());
+    }
+    if env::var_os("DISPLAY").is_some() {
+        candidates.extend([CommandBackend::xclip(), CommandBackend::xsel()]);
+    }
+
+    match candidates.into_iter().find(CommandBackend::is_available) {
+        Some(backend) => Some(Arc::new(backend)),
+        None => {
+            debug!(
+                "Nenhuma área de transferência disponível (wl-clipboard, xclip, xsel ou pbcopy)"
+            );
+            None
+        }
+    }
+}
+
+/// Indica se um executável com o nome informado existe em algum diretório do `PATH`.
+fn find_program(name: &str) -> bool {
+    env::var_os("PATH")
+        .is_some_and(|paths| env::split_paths(&paths).any(|dir| dir.join(name).is_file()))
+}
@@ -1,34 +1,89 @@
 //! Módulo de Criptografia / Segurança de Senhas
 //!
 //! Este componente provê funcionalidades de:
-//! - Derivação criptográfica de senhas (Argon2id);
+//! - Derivação criptográfica de senhas (Argon2id) com custo configurável;
 //! - Validação segura de credenciais;
-//! - Criptografia e descriptografia de dados sensíveis utilizando AGE com passphrase.
+//! - Proteção da chave do cofre utilizando AGE com passphrase;
+//! - Pares de chaves X25519 (AGE) para compartilhar chaves de cofres entre usuários;
+//! - Criptografia autenticada (XChaCha20-Poly1305) de campos sensíveis com a chave do cofre.
 //!
 //! Objetivo: oferecer mecanismos seguros para armazenamento e proteção
 //! de informações sigilosas no contexto da aplicação.
 
 // Dependências criptográficas e utilitárias
-use age::secrecy::SecretString;
-use age::{Decryptor, Encryptor};
-use anyhow::{Result, anyhow};
+use age::secrecy::{ExposeSecret, SecretString};
+use age::{Decryptor, Encryptor, x25519};
 use argon2::password_hash::{PasswordHash, SaltString};
-use argon2::{Argon2, PasswordHasher, PasswordVerifier};
-use rand_core::OsRng;
+use argon2::{Algorithm, Argon2, Params, PasswordHasher, PasswordVerifier, Version};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand_core::{OsRng, RngCore};
+use std::env;
 use std::io::{Read, Write};
 use tracing::{debug, info};
 
+use crate::error::{Error, Result};
+
+/// Tamanho, em bytes, da chave simétrica do cofre.
+pub const VAULT_KEY_LEN: usize = 32;
+
+/// Tamanho, em bytes, do nonce XChaCha20-Poly1305 gerado para cada registro.
+const RECORD_NONCE_LEN: usize = 24;
+
+/// Versão do formato dos campos cifrados com a chave do cofre.
+///
+/// Layout: `[versão: 1 byte][nonce: 24 bytes][ciphertext + tag]`.
+const RECORD_FORMAT_V1: u8 = 1;
+
+/// Cabeçalho presente em todo payload produzido pelo AGE.
+const AGE_HEADER: &[u8] = b"age-encryption.org/";
+
+/// Retorna os parâmetros de custo do Argon2id configurados para a aplicação.
+///
+/// Os valores são lidos das variáveis de ambiente abaixo, usando os padrões
+/// recomendados pelo crate `argon2` quando ausentes:
+/// ```env
+/// ARGON2_MEMORY_KIB=19456
+/// ARGON2_TIME_COST=2
+/// ARGON2_PARALLELISM=1
+/// ```
+///
+/// # Retorno
+/// - `Ok(Params)`: parâmetros válidos;
+/// - `Err(Error)`: valor não numérico ou fora dos limites do algoritmo.
+pub fn argon2_params() -> Result<Params> {
+    let memory = cost_from_env("ARGON2_MEMORY_KIB", Params::DEFAULT_M_COST)?;
+    let time = cost_from_env("ARGON2_TIME_COST", Params::DEFAULT_T_COST)?;
+    let parallelism = cost_from_env("ARGON2_PARALLELISM", Params::DEFAULT_P_COST)?;
+
+    Params::new(memory, time, parallelism, None)
+        .map_err(|err| Error::storage(format!("Parâmetros Argon2 inválidos: {}", err)))
+}
+
+/// Lê um parâmetro de custo numérico do ambiente.
+fn cost_from_env(var: &str, default: u32) -> Result<u32> {
+    match env::var(var) {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map_err(|err| Error::storage(format!("Valor inválido para {var}: '{value}' ({err})"))),
+        Err(_) => Ok(default),
+    }
+}
+
 /// Gera um hash criptográfico seguro para uma senha em texto puro.
 ///
-/// Este método utiliza o algoritmo **Argon2id** com salt aleatório,
-/// adequado para proteção contra ataques de força bruta e Rainbow Tables.
+/// Este método utiliza o algoritmo **Argon2id** com salt aleatório e os
+/// parâmetros de custo de [`argon2_params`], que ficam registrados no próprio
+/// hash (formato PHC), adequado para proteção contra ataques de força bruta e
+/// Rainbow Tables.
 ///
 /// # Parâmetros
 /// - `plain_password`: senha original em texto puro.
 ///
 /// # Retorno
 /// - `Ok(Vec<u8>)`: hash gerado no formato PHC (em bytes);
-/// - `Err(anyhow::Error)`: falha durante o processo criptográfico.
+/// - `Err(Error)`: falha durante o processo criptográfico.
 ///
 /// # Segurança
 /// O hash resultante **não permite a reversão da senha**.
@@ -36,11 +91,11 @@ pub fn hash_password(plain_password: &str) -> Result<Vec<u8>> {
     info!("Iniciando geração de hash criptográfico de senha");
 
     let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, argon2_params()?);
 
     let password_hash = argon2
         .hash_password(plain_password.as_bytes(), &salt)
-        .map_err(|err| anyhow!("Falha durante derivação criptográfica: {}", err))?;
+        .map_err(|err| Error::crypto(format!("Falha durante derivação criptográfica: {}", err)))?;
 
     debug!("Hash criptográfico de senha gerado com sucesso");
     Ok(password_hash.to_string().into_bytes())
@@ -55,22 +110,24 @@ pub fn hash_password(plain_password: &str) -> Result<Vec<u8>> {
 /// # Retorno
 /// - `Ok(true)` caso a senha seja válida;
 /// - `Ok(false)` caso seja inválida;
-/// - `Err(anyhow::Error)` em falha de validação ou formatação.
+/// - `Err(Error)` em falha de validação ou formatação.
 ///
 /// # Observação
 /// Utiliza Argon2id para verificação conforme práticas modernas de segurança.
+/// Os parâmetros de custo são obtidos do próprio hash armazenado, de modo que
+/// hashes gerados com configurações anteriores continuam válidos.
 pub fn verify_password(plain_password: &str, stored_password_hash: &[u8]) -> Result<bool> {
     info!("Validando credenciais fornecidas");
 
     let password_hash_str = std::str::from_utf8(stored_password_hash).map_err(|err| {
-        anyhow!(
+        Error::crypto(format!(
             "Falha ao interpretar hash criptográfico armazenado: {}",
             err
-        )
+        ))
     })?;
 
     let parsed_hash = PasswordHash::new(password_hash_str)
-        .map_err(|err| anyhow!("Hash criptográfico em formato inválido: {}", err))?;
+        .map_err(|err| Error::crypto(format!("Hash criptográfico em formato inválido: {}", err)))?;
 
     let argon2 = Argon2::default();
     let verification_result = argon2.verify_password(plain_password.as_bytes(), &parsed_hash);
@@ -87,6 +144,46 @@ pub fn verify_password(plain_password: &str, stored_password_hash: &[u8]) -> Res
     }
 }
 
+/// Indica se um hash armazenado foi gerado com parâmetros diferentes dos atuais.
+///
+/// # Parâmetros
+/// - `stored_password_hash`: hash PHC armazenado no banco.
+///
+/// # Retorno
+/// - `Ok(true)` quando o algoritmo, a versão ou algum custo (memória, tempo,
+///   paralelismo) divergir da configuração vigente;
+/// - `Ok(false)` quando o hash já estiver atualizado;
+/// - `Err(Error)` em hash malformado ou configuração inválida.
+///
+/// # Aplicação
+/// Utilizado após um login bem-sucedido para recalcular e persistir o hash
+/// de forma transparente, enquanto a senha em texto puro está disponível.
+pub fn password_hash_needs_rehash(stored_password_hash: &[u8]) -> Result<bool> {
+    let password_hash_str = std::str::from_utf8(stored_password_hash).map_err(|err| {
+        Error::crypto(format!(
+            "Falha ao interpretar hash criptográfico armazenado: {}",
+            err
+        ))
+    })?;
+
+    let parsed_hash = PasswordHash::new(password_hash_str)
+        .map_err(|err| Error::crypto(format!("Hash criptográfico em formato inválido: {}", err)))?;
+
+    if parsed_hash.algorithm != Algorithm::Argon2id.ident()
+        || parsed_hash.version != Some(Version::V0x13.into())
+    {
+        return Ok(true);
+    }
+
+    let stored = Params::try_from(&parsed_hash)
+        .map_err(|err| Error::crypto(format!("Parâmetros Argon2 inválidos no hash: {}", err)))?;
+    let current = argon2_params()?;
+
+    Ok(stored.m_cost() != current.m_cost()
+        || stored.t_cost() != current.t_cost()
+        || stored.p_cost() != current.p_cost())
+}
+
 /// Criptografa dados sigilosos utilizando o padrão **AGE** com passphrase.
 ///
 /// # Parâmetros
@@ -95,7 +192,7 @@ pub fn verify_password(plain_password: &str, stored_password_hash: &[u8]) -> Res
 ///
 /// # Retorno
 /// - `Ok(Vec<u8>)`: conteúdo cifrado pronto para persistência segura;
-/// - `Err(anyhow::Error)` em caso de falha.
+/// - `Err(Error)` em caso de falha.
 ///
 /// # Aplicação
 /// Ideal para proteção de campos sensíveis como senhas,
@@ -110,15 +207,15 @@ pub fn encrypt_with_passphrase(passphrase: &str, plaintext: &[u8]) -> Result<Vec
     {
         let mut writer = encryptor
             .wrap_output(&mut encrypted_bytes)
-            .map_err(|err| anyhow!("Falha ao iniciar processo AGE: {}", err))?;
+            .map_err(|err| Error::crypto(format!("Falha ao iniciar processo AGE: {}", err)))?;
 
-        writer
-            .write_all(plaintext)
-            .map_err(|err| anyhow!("Erro ao escrever dados criptografados: {}", err))?;
+        writer.write_all(plaintext).map_err(|err| {
+            Error::crypto(format!("Erro ao escrever dados criptografados: {}", err))
+        })?;
 
         writer
             .finish()
-            .map_err(|err| anyhow!("Falha ao finalizar operação AGE: {}", err))?;
+            .map_err(|err| Error::crypto(format!("Falha ao finalizar operação AGE: {}", err)))?;
     }
 
     debug!("Criptografia concluída com sucesso");
@@ -133,7 +230,7 @@ pub fn encrypt_with_passphrase(passphrase: &str, plaintext: &[u8]) -> Result<Vec
 ///
 /// # Retorno
 /// - `Ok(Vec<u8>)`: dados originais em texto puro;
-/// - `Err(anyhow::Error)` em falhas ou passphrase incorreta.
+/// - `Err(Error)` em falhas ou passphrase incorreta.
 ///
 /// # Observação
 /// Retorna erro caso o payload não tenha sido cifrado com passphrase AGE.
@@ -141,23 +238,227 @@ pub fn decrypt_with_passphrase(passphrase: &str, ciphertext: &[u8]) -> Result<Ve
     info!("Executando descriptografia AGE com passphrase");
 
     let decryptor = Decryptor::new(ciphertext)
-        .map_err(|err| anyhow!("Falha ao inicializar mecanismo AGE: {}", err))?;
+        .map_err(|err| Error::crypto(format!("Falha ao inicializar mecanismo AGE: {}", err)))?;
 
     let passphrase_decryptor = match decryptor {
         Decryptor::Passphrase(d) => d,
-        _ => return Err(anyhow!("Payload não foi protegido com AGE/passphrase")),
+        _ => {
+            return Err(Error::crypto(
+                "Payload não foi protegido com AGE/passphrase",
+            ));
+        }
     };
 
     let secret = SecretString::new(passphrase.to_owned());
     let mut reader = passphrase_decryptor
         .decrypt(&secret, None)
-        .map_err(|err| anyhow!("Falha na autenticação de chave AGE: {}", err))?;
+        .map_err(|err| Error::crypto(format!("Falha na autenticação de chave AGE: {}", err)))?;
 
     let mut decrypted_bytes = Vec::new();
-    reader
-        .read_to_end(&mut decrypted_bytes)
-        .map_err(|err| anyhow!("Erro ao extrair conteúdo descriptografado: {}", err))?;
+    reader.read_to_end(&mut decrypted_bytes).map_err(|err| {
+        Error::crypto(format!(
+            "Erro ao extrair conteúdo descriptografado: {}",
+            err
+        ))
+    })?;
 
     debug!("Descriptografia concluída com sucesso");
     Ok(decrypted_bytes)
 }
+
+/// Gera um par de chaves X25519 para o compartilhamento de cofres.
+///
+/// # Retorno
+/// - `(String, Vec<u8>)`: chave pública (`age1...`) e chave privada
+///   (`AGE-SECRET-KEY-1...`, em UTF-8).
+///
+/// # Segurança
+/// A chave privada nunca é persistida em texto puro: o chamador deve cifrá-la
+/// (com a chave da conta) e aplicar `zeroize` após o uso.
+pub fn generate_key_pair() -> (String, Vec<u8>) {
+    let identity = x25519::Identity::generate();
+    let public_key = identity.to_public().to_string();
+    let private_key = identity.to_string().expose_secret().as_bytes().to_vec();
+    (public_key, private_key)
+}
+
+/// Criptografa dados para o titular de uma chave pública X25519 (AGE).
+///
+/// # Parâmetros
+/// - `public_key`: chave pública do destinatário (`age1...`);
+/// - `plaintext`: dados a serem criptografados.
+///
+/// # Retorno
+/// - `Ok(Vec<u8>)`: payload AGE que somente a chave privada correspondente abre;
+/// - `Err(Error)`: chave pública inválida ou falha na cifragem.
+///
+/// # Aplicação
+/// Embrulha a chave de um cofre compartilhado para cada membro, sem exigir
+/// a participação do destinatário.
+pub fn encrypt_for_recipient(public_key: &str, plaintext: &[u8]) -> Result<Vec<u8>> {
+    debug!("Executando criptografia AGE para destinatário X25519");
+
+    let recipient: x25519::Recipient = public_key
+        .parse()
+        .map_err(|err| Error::crypto(format!("Chave pública inválida: {}", err)))?;
+    let encryptor = Encryptor::with_recipients(vec![Box::new(recipient)])
+        .ok_or_else(|| Error::crypto("Nenhum destinatário informado"))?;
+
+    let mut encrypted_bytes: Vec<u8> = Vec::new();
+    {
+        let mut writer = encryptor
+            .wrap_output(&mut encrypted_bytes)
+            .map_err(|err| Error::crypto(format!("Falha ao iniciar processo AGE: {}", err)))?;
+
+        writer.write_all(plaintext).map_err(|err| {
+            Error::crypto(format!("Erro ao escrever dados criptografados: {}", err))
+        })?;
+
+        writer
+            .finish()
+            .map_err(|err| Error::crypto(format!("Falha ao finalizar operação AGE: {}", err)))?;
+    }
+
+    Ok(encrypted_bytes)
+}
+
+/// Descriptografa dados cifrados com [`encrypt_for_recipient`].
+///
+/// # Parâmetros
+/// - `private_key`: chave privada X25519 (`AGE-SECRET-KEY-1...`, em UTF-8);
+/// - `ciphertext`: payload AGE destinado à chave pública correspondente.
+///
+/// # Retorno
+/// - `Ok(Vec<u8>)`: dados originais em texto puro;
+/// - `Err(Error)`: chave inválida, payload de outro destinatário ou adulterado.
+pub fn decrypt_with_identity(private_key: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+    debug!("Executando descriptografia AGE com identidade X25519");
+
+    let identity: x25519::Identity = std::str::from_utf8(private_key)
+        .map_err(|_| Error::crypto("Chave privada inválida"))?
+        .parse()
+        .map_err(|err| Error::crypto(format!("Chave privada inválida: {}", err)))?;
+
+    let decryptor = Decryptor::new(ciphertext)
+        .map_err(|err| Error::crypto(format!("Falha ao inicializar mecanismo AGE: {}", err)))?;
+
+    let recipients_decryptor = match decryptor {
+        Decryptor::Recipients(d) => d,
+        _ => {
+            return Err(Error::crypto(
+                "Payload não foi protegido com chave pública AGE",
+            ));
+        }
+    };
+
+    let mut reader = recipients_decryptor
+        .decrypt(std::iter::once(&identity as &dyn age::Identity))
+        .map_err(|err| Error::crypto(format!("Falha na autenticação de chave AGE: {}", err)))?;
+
+    let mut decrypted_bytes = Vec::new();
+    reader.read_to_end(&mut decrypted_bytes).map_err(|err| {
+        Error::crypto(format!(
+            "Erro ao extrair conteúdo descriptografado: {}",
+            err
+        ))
+    })?;
+
+    Ok(decrypted_bytes)
+}
+
+/// Gera uma nova chave aleatória para um cofre.
+///
+/// # Retorno
+/// - `[u8; VAULT_KEY_LEN]`: chave gerada a partir do gerador seguro do sistema operacional.
+///
+/// # Segurança
+/// O chamador é responsável por aplicar `zeroize` à chave após o uso.
+pub fn generate_vault_key() -> [u8; VAULT_KEY_LEN] {
+    let mut key = [0u8; VAULT_KEY_LEN];
+    OsRng.fill_bytes(&mut key);
+    key
+}
+
+/// Criptografa um campo sensível com a chave do cofre (XChaCha20-Poly1305).
+///
+/// # Parâmetros
+/// - `vault_key`: chave simétrica do cofre (32 bytes);
+/// - `plaintext`: dados a serem criptografados.
+///
+/// # Retorno
+/// - `Ok(Vec<u8>)`: versão do formato, nonce aleatório e conteúdo cifrado autenticado;
+/// - `Err(Error)`: chave com tamanho inválido ou falha na cifragem.
+///
+/// # Segurança
+/// Um nonce de 192 bits é gerado a cada chamada, tornando seguro cifrar
+/// qualquer quantidade de registros com a mesma chave.
+pub fn encrypt_with_key(vault_key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+    debug!("Executando criptografia autenticada com a chave do cofre");
+
+    let cipher = build_record_cipher(vault_key)?;
+
+    let mut nonce = [0u8; RECORD_NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+
+    let sealed = cipher
+        .encrypt(XNonce::from_slice(&nonce), plaintext)
+        .map_err(|_| Error::crypto("Falha ao criptografar dados com a chave do cofre"))?;
+
+    let mut output = Vec::with_capacity(1 + RECORD_NONCE_LEN + sealed.len());
+    output.push(RECORD_FORMAT_V1);
+    output.extend_from_slice(&nonce);
+    output.extend_from_slice(&sealed);
+
+    Ok(output)
+}
+
+/// Descriptografa um campo protegido com a chave do cofre.
+///
+/// # Parâmetros
+/// - `vault_key`: chave simétrica do cofre (32 bytes);
+/// - `ciphertext`: bytes produzidos por [`encrypt_with_key`].
+///
+/// # Retorno
+/// - `Ok(Vec<u8>)`: dados originais em texto puro;
+/// - `Err(Error)`: formato desconhecido, chave incorreta ou conteúdo adulterado.
+pub fn decrypt_with_key(vault_key: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+    debug!("Executando descriptografia autenticada com a chave do cofre");
+
+    let cipher = build_record_cipher(vault_key)?;
+
+    let (version, rest) = ciphertext
+        .split_first()
+        .ok_or_else(|| Error::crypto("Conteúdo cifrado vazio"))?;
+
+    if *version != RECORD_FORMAT_V1 {
+        return Err(Error::crypto("Formato de conteúdo cifrado não suportado"));
+    }
+
+    if rest.len() < RECORD_NONCE_LEN {
+        return Err(Error::crypto("Conteúdo cifrado truncado"));
+    }
+
+    let (nonce, sealed) = rest.split_at(RECORD_NONCE_LEN);
+
+    cipher
+        .decrypt(XNonce::from_slice(nonce), sealed)
+        .map_err(|_| Error::crypto("Falha na autenticação do conteúdo cifrado"))
+}
+
+/// Indica se um payload foi produzido pelo formato legado (AGE com passphrase).
+///
+/// # Aplicação
+/// Utilizado pela migração que recifra campos antigos com a chave do cofre.
+pub fn is_passphrase_cipher(ciphertext: &[u8]) -> bool {
+    ciphertext.starts_with(AGE_HEADER)
+}
+
+/// Constrói a instância AEAD validando o tamanho da chave do cofre.
+fn build_record_cipher(vault_key: &[u8]) -> Result<XChaCha20Poly1305> {
+    if vault_key.len() != VAULT_KEY_LEN {
+        return Err(Error::crypto("Chave do cofre com tamanho inválido"));
+    }
+
+    XChaCha20Poly1305::new_from_slice(vault_key)
+        .map_err(|_| Error::crypto("Chave do cofre com tamanho inválido"))
+}
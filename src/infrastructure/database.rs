@@ -1,47 +1,190 @@
 //! Módulo de acesso ao banco de dados SQLite
 //!
-//! Responsável por inicializar conexão, aplicar migrações e configurar
+//! Responsável por inicializar o pool de conexões, aplicar migrações e configurar
 //! parâmetros fundamentais de segurança e integridade transacional.
+//!
+//! ## Pool de conexões
+//!
+//! O banco é aberto uma única vez por processo em um [`Database`], que mantém um
+//! pool `r2d2` de conexões já configuradas. O [`Database`] é criado pela
+//! aplicação (ver [`crate::app_context::AppContext`]) e compartilhado com os
+//! repositórios; cada operação apenas empresta uma conexão do pool, evitando o
+//! custo de abrir o arquivo, aplicar PRAGMAs e conferir o schema a cada consulta.
+//!
+//! ## Migrações
+//!
+//! As migrações são os arquivos numerados de `migrations/`, embutidos no binário
+//! e listados em [`MIGRATIONS`]. A versão do schema aplicada fica registrada em
+//! `PRAGMA user_version`; na abertura do [`Database`], apenas as migrações com
+//! número maior que essa versão são executadas, cada uma em sua própria transação.
+//!
+//! Para alterar o schema, crie um novo arquivo `NNN_descricao.sql` e acrescente-o
+//! ao final de [`MIGRATIONS`]. Arquivos já publicados nunca devem ser editados.
 
-use anyhow::{Context, Result};
-use once_cell::sync::Lazy;
-use rusqlite::Connection;
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::{Connection, TransactionBehavior, functions::FunctionFlags};
 use std::{env, fs, path::Path};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
+
+use crate::error::{Error, Result};
+use crate::services::credential_search::fold;
 
-/// Conteúdo SQL inicial da migração do banco.
-static DATABASE_MIGRATIONS: Lazy<&'static str> =
-    Lazy::new(|| include_str!("../../migrations/001_init.sql"));
+/// Quantidade padrão de conexões mantidas no pool.
+const DEFAULT_POOL_SIZE: u32 = 4;
 
-/// Retorna uma conexão SQLite pronta para uso, aplicando migrações
-/// automaticamente na primeira execução.
+/// Conexão emprestada do pool; devolvida automaticamente ao sair de escopo.
+pub type PooledConnection = r2d2::PooledConnection<SqliteConnectionManager>;
+
+/// Migração de schema embutida no binário.
+struct Migration {
+    /// Número da migração; corresponde ao `user_version` após sua aplicação.
+    version: u32,
+    /// Nome do arquivo de origem, para fins de log.
+    name: &'static str,
+    /// Script SQL executado.
+    sql: &'static str,
+}
+
+/// Migrações conhecidas por este binário, em ordem estritamente crescente de versão.
+static MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "001_init.sql",
+        sql: include_str!("../../migrations/001_init.sql"),
+    },
+    Migration {
+        version: 2,
+        name: "002_login_attempts.sql",
+        sql: include_str!("../../migrations/002_login_attempts.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "003_credential_totp.sql",
+        sql: include_str!("../../migrations/003_credential_totp.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "004_folders_tags.sql",
+        sql: include_str!("../../migrations/004_folders_tags.sql"),
+    },
+    Migration {
+        version: 5,
+        name: "005_credential_history.sql",
+        sql: include_str!("../../migrations/005_credential_history.sql"),
+    },
+    Migration {
+        version: 6,
+        name: "006_credential_trash.sql",
+        sql: include_str!("../../migrations/006_credential_trash.sql"),
+    },
+    Migration {
+        version: 7,
+        name: "007_multiple_vaults.sql",
+        sql: include_str!("../../migrations/007_multiple_vaults.sql"),
+    },
+    Migration {
+        version: 8,
+        name: "008_shared_vaults.sql",
+        sql: include_str!("../../migrations/008_shared_vaults.sql"),
+    },
+    Migration {
+        version: 9,
+        name: "009_password_changed_at.sql",
+        sql: include_str!("../../migrations/009_password_changed_at.sql"),
+    },
+    Migration {
+        version: 10,
+        name: "010_vault_key_generation.sql",
+        sql: include_str!("../../migrations/010_vault_key_generation.sql"),
+    },
+];
+
+/// Banco de dados da aplicação: um pool de conexões SQLite com o schema já migrado.
 ///
-/// O caminho do banco é lido da variável:
-/// ```env
-/// DATABASE_URL="data/vault.db"
-/// ```
-pub fn get_database_connection() -> Result<Connection> {
-    info!("Obtendo nova conexão com o banco de dados");
-
-    let db_url =
-        env::var("DATABASE_URL").context("Variável de ambiente DATABASE_URL não foi definida")?;
-
-    // Cria pasta caso não exista
-    if let Some(parent) = Path::new(&db_url).parent() {
-        fs::create_dir_all(parent).with_context(|| {
-            format!(
-                "Falha ao criar diretórios necessários para armazenamento do banco: {:?}",
-                parent
-            )
+/// Clonar um `Database` é barato e compartilha o mesmo pool.
+#[derive(Clone)]
+pub struct Database {
+    pool: Pool<SqliteConnectionManager>,
+}
+
+impl Database {
+    /// Abre o banco cujo caminho é lido da variável:
+    /// ```env
+    /// DATABASE_URL="data/vault.db"
+    /// ```
+    ///
+    /// O tamanho do pool pode ser ajustado com `DATABASE_POOL_SIZE` (padrão: 4).
+    pub fn from_env() -> Result<Self> {
+        let db_url = env::var("DATABASE_URL")
+            .map_err(|_| Error::storage("Variável de ambiente DATABASE_URL não foi definida"))?;
+
+        Self::open(&db_url, pool_size_from_env())
+    }
+
+    /// Abre o banco no caminho informado, cria o pool e aplica as migrações pendentes.
+    ///
+    /// ### Parâmetros
+    /// - `db_url`: Caminho do arquivo SQLite; diretórios ausentes são criados.
+    /// - `pool_size`: Quantidade máxima de conexões simultâneas.
+    ///
+    /// ### Retorno
+    /// - `Ok(Database)` com o schema na versão mais recente.
+    /// - `Err(Error)` quando o arquivo não puder ser aberto ou uma migração falhar.
+    pub fn open(db_url: &str, pool_size: u32) -> Result<Self> {
+        info!("Abrindo banco de dados em {} (pool={})", db_url, pool_size);
+
+        // Cria pasta caso não exista
+        if let Some(parent) = Path::new(db_url).parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                Error::storage(format!(
+                    "Falha ao criar diretórios necessários para armazenamento do banco: {:?}: {e}",
+                    parent
+                ))
+            })?;
+        }
+
+        let manager = SqliteConnectionManager::file(db_url).with_init(configure_connection);
+
+        let pool = Pool::builder()
+            .max_size(pool_size.max(1))
+            .build(manager)
+            .map_err(|e| Error::storage(format!("Falha ao abrir banco em {}: {e}", db_url)))?;
+
+        let mut connection = pool.get().map_err(|e| {
+            Error::storage(format!("Falha ao obter conexão do pool para migração: {e}"))
         })?;
+        apply_migrations(&mut connection)?;
+
+        Ok(Self { pool })
     }
 
-    let is_first_creation = !Path::new(&db_url).exists();
+    /// Empresta uma conexão do pool.
+    ///
+    /// ### Retorno
+    /// - `Ok(PooledConnection)`: conexão configurada, devolvida ao pool no `drop`.
+    /// - `Err(Error)`: quando nenhuma conexão ficar disponível dentro do tempo limite.
+    pub fn connection(&self) -> Result<PooledConnection> {
+        debug!("Obtendo conexão do pool");
+        self.pool.get().map_err(|e| {
+            Error::storage(format!("Falha ao obter conexão com o banco de dados: {e}"))
+        })
+    }
+}
 
-    debug!("Abrindo banco SQLite em {}", db_url);
-    let connection =
-        Connection::open(&db_url).with_context(|| format!("Falha ao abrir banco em {}", db_url))?;
+/// Lê o tamanho do pool da variável `DATABASE_POOL_SIZE`.
+fn pool_size_from_env() -> u32 {
+    match env::var("DATABASE_POOL_SIZE") {
+        Ok(value) => value.trim().parse::<u32>().unwrap_or_else(|_| {
+            warn!("Valor inválido para DATABASE_POOL_SIZE='{value}', usando {DEFAULT_POOL_SIZE}");
+            DEFAULT_POOL_SIZE
+        }),
+        Err(_) => DEFAULT_POOL_SIZE,
+    }
+}
 
+/// Aplica os PRAGMAs recomendados a cada conexão criada pelo pool.
+fn configure_connection(connection: &mut Connection) -> rusqlite::Result<()> {
     // Configuração de parâmetros recomendados para o SQLite:
     //
     // - PRAGMA foreign_keys = ON
@@ -53,33 +196,83 @@ pub fn get_database_connection() -> Result<Connection> {
     //   de dados e melhor desempenho em cenários com múltiplas leituras/gravações.
 
     debug!("Ativando PRAGMA foreign_keys = ON");
-    connection
-        .pragma_update(None, "foreign_keys", &"ON")
-        .context("Falha ao ativar PRAGMA foreign_keys")?;
+    connection.pragma_update(None, "foreign_keys", "ON")?;
 
     debug!("Ativando PRAGMA journal_mode = WAL");
+    connection.pragma_update(None, "journal_mode", "WAL")?;
+
+    // A função `fold` remove acentos e converte para minúsculas, permitindo
+    // pesquisar por trecho no próprio SQL com a mesma normalização da pesquisa
+    // em memória (o `LIKE` do SQLite só ignora maiúsculas ASCII).
+    debug!("Registrando função SQL fold");
+    connection.create_scalar_function(
+        "fold",
+        1,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        |ctx| Ok(ctx.get::<Option<String>>(0)?.map(|text| fold(&text))),
+    )?;
+
+    Ok(())
+}
+
+/// Lê a versão de schema registrada no banco.
+fn schema_version(connection: &Connection) -> Result<u32> {
     connection
-        .pragma_update(None, "journal_mode", &"WAL")
-        .context("Falha ao ativar PRAGMA journal_mode")?;
-
-    // Executa migrações apenas na primeira criação do banco
-    if is_first_creation {
-        info!("Banco criado pela primeira vez, executando migrações...");
-        apply_migrations(&connection)?;
-        info!("Migrações concluídas com sucesso.");
+        .pragma_query_value(None, "user_version", |row| row.get(0))
+        .map_err(|e| Error::storage(format!("Falha ao ler PRAGMA user_version: {e}")))
+}
+
+/// Aplica, em ordem, as migrações ainda não registradas no banco.
+///
+/// Cada migração roda em uma transação `IMMEDIATE` que também atualiza o
+/// `user_version`; uma falha desfaz apenas a migração corrente. A versão é
+/// conferida novamente dentro da transação para que dois processos abrindo o
+/// mesmo banco não apliquem a mesma migração duas vezes.
+///
+/// Recusa bancos com versão superior à mais recente conhecida pelo binário,
+/// evitando que uma versão antiga da aplicação opere sobre um schema que não entende.
+fn apply_migrations(connection: &mut Connection) -> Result<()> {
+    let latest = MIGRATIONS.last().map_or(0, |m| m.version);
+    let current = schema_version(connection)?;
+
+    if current > latest {
+        return Err(Error::storage(format!(
+            "O banco de dados está na versão {current}, mais recente que a suportada por esta aplicação ({latest})"
+        )));
     }
 
-    Ok(connection)
-}
+    if current == latest {
+        debug!("Schema atualizado (versão {current})");
+        return Ok(());
+    }
 
-/// Executa o script SQL de migrações iniciais.
-fn apply_migrations(connection: &Connection) -> Result<()> {
-    info!("Executando migrações do banco de dados");
+    info!("Atualizando schema do banco da versão {current} para {latest}");
 
-    debug!("Rodando script SQL de migração inicial");
-    connection
-        .execute_batch(*DATABASE_MIGRATIONS)
-        .context("Erro ao executar migrações SQL")?;
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        let tx = connection
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(|e| Error::storage(format!("Falha ao iniciar transação de migração: {e}")))?;
+
+        if schema_version(&tx)? >= migration.version {
+            debug!("Migração {} já aplicada por outro processo", migration.name);
+            continue;
+        }
+
+        debug!("Aplicando migração {}", migration.name);
+        tx.execute_batch(migration.sql).map_err(|e| {
+            Error::storage(format!("Erro ao executar migração {}: {e}", migration.name))
+        })?;
+        tx.pragma_update(None, "user_version", migration.version)
+            .map_err(|e| Error::storage(format!("Falha ao atualizar PRAGMA user_version: {e}")))?;
+        tx.commit().map_err(|e| {
+            Error::storage(format!(
+                "Falha ao confirmar migração {}: {e}",
+                migration.name
+            ))
+        })?;
+
+        info!("Migração {} aplicada", migration.name);
+    }
 
     Ok(())
 }
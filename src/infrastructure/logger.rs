@@ -7,12 +7,12 @@ ou desenvolvimento, com saída em arquivo e/ou terminal.
 
 Variáveis de ambiente suportadas:
 - LOG_LEVEL: Define o nível mínimo de log (error, warn, info, debug, trace).
-             Valor padrão: "info".
+  Valor padrão: "info".
 - LOG_FILE_ONLY: Quando "true", envia logs somente para o arquivo.
-                 Quando "false", envia logs para arquivo e console (stdout).
-                 Valor padrão: "false".
+  Quando "false", envia logs para arquivo e console (stdout).
+  Valor padrão: "false".
 - LOG_FILE_PATH: Define o caminho completo do arquivo de log.
-                 Valor padrão: "logs/password_manager.log".
+  Valor padrão: "logs/password_manager.log".
 */
 
 use std::{env, fs};
@@ -21,6 +21,22 @@ use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberI
 
 /// Inicializa o registrador de logs da aplicação.
 pub fn init_logger() {
+    // Obtém configuração se logs devem ir somente para arquivo.
+    let log_file_only = env::var("LOG_FILE_ONLY")
+        .map(|v| v.to_lowercase() == "true")
+        .unwrap_or(false);
+
+    install(!log_file_only);
+}
+
+/// Inicializa o logging somente em arquivo, independentemente de `LOG_FILE_ONLY`.
+///
+/// Usado pelos subcomandos da CLI, cuja saída padrão é consumida por scripts.
+pub fn init_file_logger() {
+    install(false);
+}
+
+fn install(console: bool) {
     // Obtém o caminho do arquivo de log ou usa padrão.
     let log_file_path =
         env::var("LOG_FILE_PATH").unwrap_or_else(|_| "logs/password_manager.log".to_string());
@@ -40,11 +56,6 @@ pub fn init_logger() {
         _ => Level::INFO,
     };
 
-    // Obtém configuração se logs devem ir somente para arquivo.
-    let log_file_only = env::var("LOG_FILE_ONLY")
-        .map(|v| v.to_lowercase() == "true")
-        .unwrap_or(false);
-
     // Define filtro de nível.
     let filter_layer = EnvFilter::default().add_directive(level.into());
 
@@ -69,9 +80,9 @@ pub fn init_logger() {
         .with(filter_layer)
         .with(file_layer);
 
-    if log_file_only {
-        registry.init();
-    } else {
+    if console {
         registry.with(console_layer).init();
+    } else {
+        registry.init();
     }
 }
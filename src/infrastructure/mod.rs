@@ -28,8 +28,8 @@
 //!
 //! Gerencia o acesso ao banco de dados:
 //!
-//! - Criação e reaproveitamento de conexões
-//! - Inicialização do schema
+//! - Pool de conexões compartilhado ([`database::Database`])
+//! - Aplicação das migrações do schema
 //! - Encapsulamento da camada de persistência
 //!
 //! ---
@@ -47,6 +47,34 @@
 //!
 //! ---
 //!
+//! ### [`totp`]
+//!
+//! Senhas de uso único baseadas em tempo (RFC 6238):
+//!
+//! - Interpretação de URIs `otpauth://totp/...` e de segredos Base32
+//! - Cálculo do código vigente com SHA-1, SHA-256 ou SHA-512
+//!
+//! ---
+//!
+//! ### [`pwned_passwords`]
+//!
+//! Consulta offline a uma base Pwned Passwords baixada localmente:
+//!
+//! - Arquivo único ordenado ou diretório de arquivos de faixa
+//! - Hashes SHA-1 ou NTLM, identificados automaticamente
+//! - Busca binária direto no disco, sem acesso à rede
+//!
+//! ---
+//!
+//! ### [`clipboard`]
+//!
+//! Área de transferência do sistema com limpeza automática:
+//!
+//! - Backends Wayland, X11 e macOS por utilitários do sistema, além de arquivo e memória
+//! - Limpeza após um prazo, somente se o valor copiado ainda estiver lá
+//!
+//! ---
+//!
 //! ### [`logger`]
 //!
 //! Inicializa e configura o sistema de logging:
@@ -78,6 +106,9 @@
 //! diretamente a segurança e estabilidade do sistema.
 
 
+pub mod clipboard;
 pub mod crypto;
 pub mod database;
 pub mod logger;
+pub mod pwned_passwords;
+pub mod totp;
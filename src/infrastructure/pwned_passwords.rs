@@ -0,0 +1,244 @@
+//! Consulta offline à base Pwned Passwords (Have I Been Pwned)
+//!
+//! Este componente provê funcionalidades de:
+//! - Abertura de uma base baixada localmente, em arquivo único ou em arquivos de faixa;
+//! - Identificação automática do hash usado pela base (SHA-1 ou NTLM);
+//! - Busca binária do hash de uma senha, sem carregar a base em memória.
+//!
+//! Nenhuma informação é enviada pela rede: a senha é convertida em hash apenas
+//! em memória e comparada com o conteúdo do disco.
+//!
+//! ## Formatos aceitos
+//!
+//! - **Arquivo único**: linhas `HASH:OCORRÊNCIAS` ordenadas pelo hash, como as
+//!   geradas pelo `haveibeenpwned-downloader` sem a opção de faixas.
+//! - **Diretório de faixas**: um arquivo `XXXXX.txt` por prefixo de cinco dígitos
+//!   hexadecimais, com linhas `SUFIXO:OCORRÊNCIAS` ordenadas (formato da API de faixas).
+
+use md4::Md4;
+use serde::Serialize;
+use sha1::{Digest, Sha1};
+use std::{
+    cmp::Ordering,
+    env,
+    fs::File,
+    io::{BufRead, BufReader, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+use tracing::{debug, info, warn};
+
+use crate::error::{Error, Result};
+
+/// Quantidade de dígitos hexadecimais do prefixo que nomeia os arquivos de faixa.
+const RANGE_PREFIX_LENGTH: usize = 5;
+
+/// Algoritmo de hash usado pela base.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PwnedHash {
+    Sha1,
+    /// MD4 da senha em UTF-16LE, como no Windows.
+    Ntlm,
+}
+
+impl PwnedHash {
+    /// Quantidade de dígitos hexadecimais do hash completo.
+    fn hex_length(&self) -> usize {
+        match self {
+            Self::Sha1 => 40,
+            Self::Ntlm => 32,
+        }
+    }
+
+    /// Identifica o algoritmo pelo tamanho do hash de uma linha da base.
+    fn detect(hex_length: usize) -> Result<Self> {
+        [Self::Sha1, Self::Ntlm]
+            .into_iter()
+            .find(|kind| kind.hex_length() == hex_length)
+            .ok_or_else(|| Error::storage("Formato da base Pwned Passwords não reconhecido"))
+    }
+
+    /// Hash da senha em hexadecimal maiúsculo, como na base.
+    pub fn hash(&self, password: &str) -> String {
+        let digest = match self {
+            Self::Sha1 => Sha1::digest(password.as_bytes()).to_vec(),
+            Self::Ntlm => {
+                let utf16: Vec<u8> = password.encode_utf16().flat_map(u16::to_le_bytes).collect();
+                Md4::digest(&utf16).to_vec()
+            }
+        };
+
+        digest.iter().map(|b| format!("{b:02X}")).collect()
+    }
+}
+
+/// Organização da base no disco.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    /// Arquivo único com os hashes completos.
+    File,
+    /// Diretório com um arquivo por prefixo.
+    Ranges,
+}
+
+/// Base Pwned Passwords aberta para consulta.
+#[derive(Debug, Clone)]
+pub struct PwnedPasswords {
+    path: PathBuf,
+    layout: Layout,
+    hash: PwnedHash,
+}
+
+impl PwnedPasswords {
+    /// Abre uma base local, identificando o formato e o algoritmo de hash.
+    ///
+    /// ### Parâmetros
+    /// - `path`: Arquivo único ordenado ou diretório com os arquivos de faixa.
+    ///
+    /// ### Retorno
+    /// - `Ok(PwnedPasswords)` pronta para consulta.
+    /// - `Err(Error)` quando o caminho não existe, está vazio ou tem formato desconhecido.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        let (layout, sample) = if path.is_dir() {
+            (
+                Layout::Ranges,
+                path.join(format!("{}.txt", "0".repeat(RANGE_PREFIX_LENGTH))),
+            )
+        } else {
+            (Layout::File, path.clone())
+        };
+
+        let mut first = String::new();
+        BufReader::new(File::open(&sample).map_err(|e| {
+            Error::storage(format!(
+                "Falha ao abrir a base Pwned Passwords '{}': {e}",
+                sample.display()
+            ))
+        })?)
+        .read_line(&mut first)?;
+
+        let prefix = if layout == Layout::Ranges {
+            RANGE_PREFIX_LENGTH
+        } else {
+            0
+        };
+        let hash = PwnedHash::detect(hash_part(&first).len() + prefix)?;
+
+        info!(
+            "Base Pwned Passwords aberta: '{}' ({:?}, {:?})",
+            path.display(),
+            layout,
+            hash
+        );
+        Ok(Self { path, layout, hash })
+    }
+
+    /// Abre a base indicada na variável de ambiente `PWNED_PASSWORDS_PATH`.
+    ///
+    /// ### Retorno
+    /// - `Some(PwnedPasswords)` quando a variável aponta para uma base válida.
+    /// - `None` quando a variável está ausente ou a base não pôde ser aberta
+    ///   (o motivo é registrado no log).
+    pub fn from_env() -> Option<Self> {
+        let path = env::var("PWNED_PASSWORDS_PATH").ok()?;
+        if path.trim().is_empty() {
+            return None;
+        }
+
+        Self::open(path.trim())
+            .inspect_err(|e| warn!("Verificação de senhas vazadas desativada: {e}"))
+            .ok()
+    }
+
+    /// Algoritmo de hash da base.
+    pub fn hash(&self) -> PwnedHash {
+        self.hash
+    }
+
+    /// Quantas vezes a senha aparece em vazamentos conhecidos.
+    ///
+    /// ### Retorno
+    /// - `Ok(0)` quando a senha não consta na base.
+    /// - `Ok(n)` com a quantidade de ocorrências registradas.
+    /// - `Err(Error)` quando a base não pode ser lida.
+    pub fn occurrences(&self, password: &str) -> Result<u64> {
+        let hash = self.hash.hash(password);
+
+        let (file, key) = match self.layout {
+            Layout::File => (self.path.clone(), hash.as_str()),
+            Layout::Ranges => {
+                let (prefix, suffix) = hash.split_at(RANGE_PREFIX_LENGTH);
+                let file = self.path.join(format!("{prefix}.txt"));
+                if !file.exists() {
+                    debug!("Arquivo de faixa ausente: '{}'", file.display());
+                    return Ok(0);
+                }
+                (file, suffix)
+            }
+        };
+
+        search(&file, key)
+    }
+}
+
+/// Busca binária de um hash em um arquivo de linhas ordenadas `HASH:OCORRÊNCIAS`.
+///
+/// O intervalo `[lo, hi)` contém os inícios de linha ainda candidatos; a cada
+/// passo é lida a primeira linha que começa a partir do meio do intervalo.
+fn search(path: &Path, key: &str) -> Result<u64> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut lo = 0u64;
+    let mut hi = reader.get_ref().metadata()?.len();
+    let mut line = String::new();
+
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+
+        let start = if mid == 0 {
+            reader.seek(SeekFrom::Start(0))?;
+            0
+        } else {
+            reader.seek(SeekFrom::Start(mid - 1))?;
+            let mut skipped = Vec::new();
+            mid - 1 + reader.read_until(b'\n', &mut skipped)? as u64
+        };
+
+        line.clear();
+        let read = reader.read_line(&mut line)? as u64;
+        if read == 0 || start >= hi {
+            hi = mid;
+            continue;
+        }
+
+        let candidate = hash_part(&line);
+        match compare_hex(candidate, key) {
+            Ordering::Equal => {
+                let count = line
+                    .trim_end()
+                    .split_once(':')
+                    .and_then(|(_, count)| count.trim().parse().ok())
+                    .unwrap_or(1);
+                return Ok(count);
+            }
+            Ordering::Less => lo = start + read,
+            Ordering::Greater => hi = mid,
+        }
+    }
+
+    Ok(0)
+}
+
+/// Hash de uma linha da base, sem a contagem e a quebra de linha.
+fn hash_part(line: &str) -> &str {
+    let line = line.trim_end();
+    line.split_once(':').map_or(line, |(hash, _)| hash).trim()
+}
+
+/// Compara dois hashes hexadecimais sem diferenciar maiúsculas.
+fn compare_hex(a: &str, b: &str) -> Ordering {
+    a.bytes()
+        .map(|c| c.to_ascii_uppercase())
+        .cmp(b.bytes().map(|c| c.to_ascii_uppercase()))
+}
@@ -0,0 +1,299 @@
+//! Módulo de Senhas de Uso Único Baseadas em Tempo (TOTP, RFC 6238)
+//!
+//! Este componente provê funcionalidades de:
+//! - Interpretação de URIs `otpauth://totp/...` (segredo, dígitos, período e algoritmo);
+//! - Aceitação de segredos Base32 avulsos, com os parâmetros padrão (6 dígitos, 30 s, SHA-1);
+//! - Cálculo do código vigente e do tempo restante até a próxima troca.
+//!
+//! O segredo nunca é persistido por este módulo: a camada de serviços cifra a URI
+//! normalizada com a chave do cofre antes de gravá-la.
+
+use hmac::{Hmac, Mac};
+use percent_encoding::percent_decode_str;
+use serde::Serialize;
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+use zeroize::Zeroize;
+
+use crate::error::{Error, Result};
+
+/// Quantidade padrão de dígitos do código.
+pub const DEFAULT_DIGITS: u32 = 6;
+
+/// Período padrão, em segundos, de validade de cada código.
+pub const DEFAULT_PERIOD: u64 = 30;
+
+/// Prefixo das URIs aceitas.
+const OTPAUTH_PREFIX: &str = "otpauth://";
+
+/// Algoritmo HMAC utilizado no cálculo do código.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum TotpAlgorithm {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl TotpAlgorithm {
+    /// Nome do algoritmo no formato usado pelas URIs `otpauth`.
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sha1 => "SHA1",
+            Self::Sha256 => "SHA256",
+            Self::Sha512 => "SHA512",
+        }
+    }
+
+    fn parse(value: &str) -> Result<Self> {
+        match value.to_ascii_uppercase().replace('-', "").as_str() {
+            "SHA1" => Ok(Self::Sha1),
+            "SHA256" => Ok(Self::Sha256),
+            "SHA512" => Ok(Self::Sha512),
+            _ => Err(Error::validation(
+                "totp",
+                format!("Algoritmo TOTP não suportado: '{value}'"),
+            )),
+        }
+    }
+}
+
+/// Configuração TOTP de uma credencial.
+///
+/// O segredo é apagado da memória quando a instância é descartada.
+pub struct Totp {
+    secret: Vec<u8>,
+    digits: u32,
+    period: u64,
+    algorithm: TotpAlgorithm,
+    label: Option<String>,
+    issuer: Option<String>,
+}
+
+/// Código TOTP vigente.
+#[derive(Debug, Clone, Serialize)]
+pub struct TotpCode {
+    /// Código com a quantidade de dígitos configurada (zeros à esquerda preservados).
+    pub code: String,
+    /// Segundos até o código expirar.
+    pub remaining_seconds: u64,
+    /// Período de validade de cada código, em segundos.
+    pub period: u64,
+}
+
+impl Totp {
+    /// Interpreta uma URI `otpauth://totp/...` ou um segredo Base32 avulso.
+    ///
+    /// ### Parâmetros
+    /// - `input`: URI completa (ex.: `otpauth://totp/GitHub:alice?secret=JBSW...&digits=6`)
+    ///   ou apenas o segredo Base32 (espaços e letras minúsculas são aceitos).
+    ///
+    /// ### Retorno
+    /// - `Ok(Totp)`: configuração válida.
+    /// - `Err(Error)`: URI de outro tipo (ex.: `hotp`), segredo ausente ou inválido,
+    ///   ou parâmetros fora dos limites (6 a 8 dígitos, período positivo).
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+
+        if !input
+            .get(..OTPAUTH_PREFIX.len())
+            .is_some_and(|p| p.eq_ignore_ascii_case(OTPAUTH_PREFIX))
+        {
+            return Self::from_secret(input, None, None);
+        }
+
+        let rest = &input[OTPAUTH_PREFIX.len()..];
+        let (kind, rest) = rest
+            .split_once('/')
+            .ok_or_else(|| Error::validation("totp", "URI otpauth inválida"))?;
+        if !kind.eq_ignore_ascii_case("totp") {
+            return Err(Error::validation(
+                "totp",
+                "Apenas URIs do tipo TOTP são suportadas",
+            ));
+        }
+
+        let (label, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let label = decode(label)?;
+
+        let mut secret = None;
+        let mut issuer = None;
+        let mut digits = DEFAULT_DIGITS;
+        let mut period = DEFAULT_PERIOD;
+        let mut algorithm = TotpAlgorithm::Sha1;
+
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let value = decode(value)?;
+
+            match key.to_ascii_lowercase().as_str() {
+                "secret" => secret = Some(value),
+                "issuer" => issuer = Some(value),
+                "digits" => {
+                    digits = value.parse().map_err(|_| {
+                        Error::validation("totp", "Quantidade de dígitos TOTP inválida")
+                    })?
+                }
+                "period" => {
+                    period = value
+                        .parse()
+                        .map_err(|_| Error::validation("totp", "Período TOTP inválido"))?
+                }
+                "algorithm" => algorithm = TotpAlgorithm::parse(&value)?,
+                _ => {}
+            }
+        }
+
+        let secret = secret
+            .ok_or_else(|| Error::validation("totp", "URI otpauth sem o parâmetro 'secret'"))?;
+        let label = Some(label).filter(|l| !l.is_empty());
+
+        let mut totp = Self::from_secret(&secret, label, issuer.filter(|i| !i.is_empty()))?;
+        totp.digits = digits;
+        totp.period = period;
+        totp.algorithm = algorithm;
+        totp.validate()?;
+        Ok(totp)
+    }
+
+    /// Cria a configuração a partir de um segredo Base32 com os parâmetros padrão.
+    fn from_secret(secret: &str, label: Option<String>, issuer: Option<String>) -> Result<Self> {
+        let normalized: String = secret
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-' && *c != '=')
+            .collect::<String>()
+            .to_ascii_uppercase();
+
+        let secret = base32::decode(base32::Alphabet::Rfc4648 { padding: false }, &normalized)
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| {
+                Error::validation("totp", "Segredo TOTP inválido: esperado texto em Base32")
+            })?;
+
+        Ok(Self {
+            secret,
+            digits: DEFAULT_DIGITS,
+            period: DEFAULT_PERIOD,
+            algorithm: TotpAlgorithm::Sha1,
+            label,
+            issuer,
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !(6..=8).contains(&self.digits) {
+            return Err(Error::validation(
+                "totp",
+                "O código TOTP deve ter entre 6 e 8 dígitos",
+            ));
+        }
+        if self.period == 0 {
+            return Err(Error::validation(
+                "totp",
+                "O período TOTP deve ser maior que zero",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Quantidade de dígitos do código.
+    pub fn digits(&self) -> u32 {
+        self.digits
+    }
+
+    /// Período de validade de cada código, em segundos.
+    pub fn period(&self) -> u64 {
+        self.period
+    }
+
+    /// Algoritmo HMAC configurado.
+    pub fn algorithm(&self) -> TotpAlgorithm {
+        self.algorithm
+    }
+
+    /// Serializa a configuração como URI `otpauth://totp/...` normalizada.
+    ///
+    /// ### Aplicação
+    /// É o formato cifrado e persistido na credencial, preservando todos os parâmetros.
+    pub fn to_uri(&self) -> String {
+        let secret = base32::encode(base32::Alphabet::Rfc4648 { padding: false }, &self.secret);
+        let label = self.label.as_deref().map(encode).unwrap_or_default();
+
+        let mut uri = format!(
+            "otpauth://totp/{label}?secret={secret}&digits={}&period={}&algorithm={}",
+            self.digits,
+            self.period,
+            self.algorithm.as_str()
+        );
+        if let Some(issuer) = &self.issuer {
+            uri.push_str("&issuer=");
+            uri.push_str(&encode(issuer));
+        }
+        uri
+    }
+
+    /// Calcula o código válido no instante informado.
+    ///
+    /// ### Parâmetros
+    /// - `unix_time`: Segundos desde 1970-01-01 UTC.
+    pub fn code_at(&self, unix_time: u64) -> TotpCode {
+        let counter = unix_time / self.period;
+        let mut digest = self.hmac(&counter.to_be_bytes());
+
+        // Truncamento dinâmico (RFC 4226, seção 5.3).
+        let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([
+            digest[offset] & 0x7f,
+            digest[offset + 1],
+            digest[offset + 2],
+            digest[offset + 3],
+        ]);
+        digest.zeroize();
+
+        let value = binary % 10u32.pow(self.digits);
+
+        TotpCode {
+            code: format!("{value:0width$}", width = self.digits as usize),
+            remaining_seconds: self.period - unix_time % self.period,
+            period: self.period,
+        }
+    }
+
+    fn hmac(&self, message: &[u8]) -> Vec<u8> {
+        macro_rules! compute {
+            ($hash:ty) => {{
+                let mut mac = Hmac::<$hash>::new_from_slice(&self.secret)
+                    .expect("HMAC aceita chaves de qualquer tamanho");
+                mac.update(message);
+                mac.finalize().into_bytes().to_vec()
+            }};
+        }
+
+        match self.algorithm {
+            TotpAlgorithm::Sha1 => compute!(Sha1),
+            TotpAlgorithm::Sha256 => compute!(Sha256),
+            TotpAlgorithm::Sha512 => compute!(Sha512),
+        }
+    }
+}
+
+impl Drop for Totp {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+/// Decodifica um componente percent-encoded da URI.
+fn decode(value: &str) -> Result<String> {
+    Ok(percent_decode_str(&value.replace('+', " "))
+        .decode_utf8()
+        .map_err(|_| Error::validation("totp", "URI otpauth com caracteres inválidos"))?
+        .into_owned())
+}
+
+/// Codifica um componente de texto livre (rótulo ou emissor) para a URI.
+fn encode(value: &str) -> String {
+    percent_encoding::utf8_percent_encode(value, percent_encoding::NON_ALPHANUMERIC)
+        .to_string()
+        // ':' separa emissor e conta no rótulo e é mantido legível.
+        .replace("%3A", ":")
+}
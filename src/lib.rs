@@ -49,6 +49,34 @@
 //!
 //! ## Módulos
 //!
+//! ### [`app_context`]
+//!
+//! Ponto de montagem da aplicação:
+//!
+//! - Abre o banco uma única vez (pool de conexões e migrações)
+//! - Cria repositórios e serviços sobre o banco compartilhado
+//!
+//! As interfaces obtêm os serviços a partir de um [`app_context::AppContext`].
+//!
+//! ---
+//!
+//! ### [`error`]
+//!
+//! Tipo de erro único da biblioteca, [`error::Error`], classificado por
+//! categoria (não encontrado, não autorizado, validação, criptografia,
+//! armazenamento e conflito). As interfaces decidem pela categoria, e não
+//! pelo texto da mensagem.
+//!
+//! ---
+//!
+//! ### [`importers`]
+//!
+//! Leitura de arquivos exportados por outros gerenciadores de senhas
+//! (Bitwarden, KeePass, 1Password e navegadores), convertidos em entradas
+//! prontas para o [`services::credential_service::CredentialService`].
+//!
+//! ---
+//!
 //! ### [`infrastructure`]
 //!
 //! Camada responsável por detalhes técnicos:
@@ -103,8 +131,8 @@
 //! ## Fluxo típico de uso
 //!
 //! ```text
-//! Usuário → AuthService → Session
-//!        → CredentialService (com Session válida)
+//! AppContext → AuthService → Session
+//!            → CredentialService (com Session válida)
 //! ```
 //!
 //! O acesso às credenciais exige sempre uma sessão autenticada,
@@ -137,6 +165,9 @@
 //!
 
 
+pub mod app_context;
+pub mod error;
+pub mod importers;
 pub mod infrastructure;
 pub mod models;
 pub mod repositories;
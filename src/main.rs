@@ -1,17 +1,32 @@
-mod infrastructure;
-mod models;
-mod repositories;
-mod services;
-
+use clap::Parser;
 use std::{
     env,
     io::{self, Write},
+    process::ExitCode,
 };
 use tracing::{info, warn};
 use uuid::Uuid;
+use zeroize::Zeroizing;
+
+use password_manager::{
+    app_context::AppContext,
+    error::{Error, Result},
+    importers::{self, ImportFormat, ImportReport},
+    infrastructure::logger::{init_file_logger, init_logger},
+    models::vault_member::VaultRole,
+    services::{
+        auth_service::{Session, SessionState},
+        credential_search::SearchOptions,
+        password_generator::{
+            DEFAULT_PASSPHRASE_WORDS, DEFAULT_PASSWORD_LENGTH, GeneratedPassword, PassphrasePolicy,
+            PasswordGenerator, PasswordPolicy,
+        },
+        security_report::{DEFAULT_MAX_PASSWORD_AGE_DAYS, ReportOptions, SecurityReport},
+        vault_service::TransferMode,
+    },
+};
 
-use infrastructure::{database::get_database_connection, logger::init_logger};
-use services::{auth_service::AuthService, credential_service::CredentialService};
+mod cli;
 
 /// Função auxiliar para entrada de dados via CLI.
 fn input(prompt: &str) -> String {
@@ -22,18 +37,39 @@ fn input(prompt: &str) -> String {
     s.trim().to_string()
 }
 
-fn main() -> anyhow::Result<()> {
+fn main() -> ExitCode {
     let app_env = env::var("APP_ENV").unwrap_or_else(|_| "development".to_string());
     let env_file = format!(".env.{}", app_env);
+    let env_loaded = dotenvy::from_filename(&env_file).is_ok();
 
-    match dotenvy::from_filename(&env_file) {
-        Ok(_) => println!("Usando ambiente: {app_env} ({env_file})"),
-        Err(_) => println!("Aviso: {env_file} não encontrado, seguindo sem .env específico"),
+    // O .env precisa estar carregado antes da leitura dos argumentos (PM_USER).
+    let cli::Cli { global, command } = cli::Cli::parse();
+
+    if let Some(command) = command {
+        init_file_logger();
+        return cli::execute(&global, command);
+    }
+
+    if env_loaded {
+        println!("Usando ambiente: {app_env} ({env_file})");
+    } else {
+        println!("Aviso: {env_file} não encontrado, seguindo sem .env específico");
     }
 
     init_logger();
 
-    let _ = get_database_connection()?;
+    match menu_inicial(&app_env) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Erro: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Menu interativo inicial, usado quando nenhum subcomando é informado.
+fn menu_inicial(app_env: &str) -> Result<()> {
+    let ctx = AppContext::from_env()?;
     info!("Password Manager iniciado no modo '{app_env}'");
 
     loop {
@@ -46,7 +82,7 @@ fn main() -> anyhow::Result<()> {
             "1" => {
                 let user = input("Novo usuário: ");
                 let pass = input("Senha: ");
-                match AuthService::register(&user, &pass) {
+                match ctx.auth().register(&user, &pass) {
                     Ok(_) => info!("Usuário criado com sucesso."),
                     Err(e) => warn!("Falha ao criar usuário: {e}"),
                 }
@@ -54,12 +90,15 @@ fn main() -> anyhow::Result<()> {
             "2" => {
                 let user = input("Usuário: ");
                 let pass = input("Senha: ");
-                match AuthService::login(&user, &pass) {
-                    Ok(session) => menu_credenciais(session)?,
+                match ctx.auth().login(&user, &pass) {
+                    Ok(session) => menu_credenciais(&ctx, session)?,
                     Err(e) => warn!("Falha no login: {e}"),
                 }
             }
             "0" => {
+                if let Some(clipboard) = ctx.clipboard() {
+                    clipboard.clear_pending();
+                }
                 println!("Saindo...");
                 return Ok(());
             }
@@ -69,7 +108,7 @@ fn main() -> anyhow::Result<()> {
 }
 
 /// Submenu de operações relacionadas às credenciais do cofre do usuário logado.
-fn menu_credenciais(session: services::auth_service::Session) -> anyhow::Result<()> {
+fn menu_credenciais(ctx: &AppContext, mut session: Session) -> Result<()> {
     loop {
         println!("\n=== Menu de Credenciais ===");
         println!("[1] Criar credencial");
@@ -78,17 +117,38 @@ fn menu_credenciais(session: services::auth_service::Session) -> anyhow::Result<
         println!("[4] Mostrar credencial completa");
         println!("[5] Atualizar credencial");
         println!("[6] Remover credencial");
+        println!("[7] Alterar senha mestre");
+        println!("[8] Bloquear cofre");
+        println!("[9] Exportar cofre");
+        println!("[10] Importar cofre");
+        println!("[11] Importar de outro gerenciador");
+        println!("[12] Pastas e tags");
+        println!("[13] Histórico de senhas");
+        println!("[14] Lixeira");
+        println!("[15] Relatório de segurança");
+        println!("[16] Cofres");
+        println!("[17] Renomear usuário");
+        println!("[18] Excluir conta");
+        println!("[19] Listar usuários");
         println!("[0] Logout");
 
-        match input("Escolha: ").as_str() {
+        let choice = input("Escolha: ");
+
+        if choice != "0" && !garantir_sessao_ativa(ctx, &mut session) {
+            println!("Logout realizado.");
+            return Ok(());
+        }
+
+        match choice.as_str() {
             "1" => {
                 let name = input("Nome da credencial: ");
                 let user = input("Username (opcional): ");
                 let url = input("URL (opcional): ");
                 let notes = input("Notas (opcional): ");
-                let pwd = input("Senha (opcional): ");
+                let pwd = ler_senha(ctx, "Senha (opcional, \"*\" = gerar): ");
+                let totp = input("TOTP (opcional, URI otpauth:// ou segredo Base32): ");
 
-                match CredentialService::create(
+                match ctx.credentials().create(
                     &session,
                     &name,
                     if user.is_empty() { None } else { Some(user) },
@@ -100,13 +160,21 @@ fn menu_credenciais(session: services::auth_service::Session) -> anyhow::Result<
                     },
                     if pwd.is_empty() { None } else { Some(&pwd) },
                 ) {
-                    Ok(_) => println!("Credencial criada!"),
+                    Ok(cred) => {
+                        println!("Credencial criada!");
+                        if !totp.is_empty()
+                            && let Err(e) =
+                                ctx.credentials().set_totp(&session, cred.id(), Some(&totp))
+                        {
+                            println!("TOTP não configurado: {e}");
+                        }
+                    }
                     Err(e) => warn!("Erro ao criar credencial: {e}"),
                 }
             }
 
             "2" => {
-                let list = CredentialService::list(&session)?;
+                let list = ctx.credentials().list(&session)?;
                 println!("\nCredenciais:");
                 for c in list {
                     println!("→ {} ({})", c.name(), c.id());
@@ -114,11 +182,17 @@ fn menu_credenciais(session: services::auth_service::Session) -> anyhow::Result<
             }
 
             "3" => {
-                let q = input("Buscar por nome: ");
-                let results = CredentialService::search(&session, &q)?;
+                let q = input("Buscar (nome, usuário ou URL): ");
+                let options = SearchOptions {
+                    include_notes: input("Incluir notas? (s/N): ").eq_ignore_ascii_case("s"),
+                    fuzzy: true,
+                };
+
+                let results = ctx.credentials().search_with(&session, &q, &options)?;
                 println!("\nResultados:");
-                for c in results {
-                    println!("→ {} ({})", c.name(), c.id());
+                for hit in results {
+                    let c = &hit.credential;
+                    println!("→ {} ({}) [{}]", c.name(), c.id(), hit.field.label());
                 }
             }
 
@@ -129,7 +203,7 @@ fn menu_credenciais(session: services::auth_service::Session) -> anyhow::Result<
                     continue;
                 };
 
-                match CredentialService::get(&session, uuid) {
+                match ctx.credentials().get(&session, uuid) {
                     Ok(cred) => {
                         println!("\n--- Credencial ---");
                         println!("Nome: {}", cred.name());
@@ -140,17 +214,77 @@ fn menu_credenciais(session: services::auth_service::Session) -> anyhow::Result<
                         if let Some(url) = cred.url() {
                             println!("URL: {url}");
                         }
+                        if let Some(folder_id) = cred.folder_id()
+                            && let Some(f) = ctx
+                                .credentials()
+                                .list_folders(&session)?
+                                .into_iter()
+                                .find(|f| f.folder.id() == folder_id)
+                        {
+                            println!("Pasta: {}", f.path);
+                        }
+                        let tags = ctx.credentials().tags_of(&session, uuid)?;
+                        if !tags.is_empty() {
+                            let names: Vec<&str> = tags.iter().map(|t| t.name()).collect();
+                            println!("Tags: {}", names.join(", "));
+                        }
 
-                        match CredentialService::reveal_notes(&session, uuid) {
+                        match ctx.credentials().reveal_notes(&session, uuid) {
                             Ok(Some(n)) => println!("Notas: {n}"),
                             Ok(None) => println!("Sem notas armazenadas."),
                             Err(e) => println!("Erro ao descriptografar notas: {e}"),
                         }
 
-                        match CredentialService::reveal_password(&session, uuid) {
-                            Ok(Some(p)) => println!("Senha: {p}"),
-                            Ok(None) => println!("Sem senha armazenada."),
-                            Err(e) => println!("Erro ao descriptografar senha: {e}"),
+                        let password = match ctx.credentials().reveal_password(&session, uuid) {
+                            Ok(Some(p)) => {
+                                println!("Senha: ******** (oculta)");
+                                Some(Zeroizing::new(p))
+                            }
+                            Ok(None) => {
+                                println!("Sem senha armazenada.");
+                                None
+                            }
+                            Err(e) => {
+                                println!("Erro ao descriptografar senha: {e}");
+                                None
+                            }
+                        };
+
+                        let totp = match ctx.credentials().current_totp(&session, uuid) {
+                            Ok(Some(totp)) => {
+                                println!(
+                                    "Código TOTP: {} (expira em {}s)",
+                                    totp.code, totp.remaining_seconds
+                                );
+                                Some(totp.code)
+                            }
+                            Ok(None) => None,
+                            Err(e) => {
+                                println!("Erro ao calcular código TOTP: {e}");
+                                None
+                            }
+                        };
+
+                        if password.is_none() && totp.is_none() {
+                            continue;
+                        }
+                        match input("[c] copiar senha / [t] copiar código TOTP / [m] mostrar senha / [Enter] voltar: ").as_str() {
+                            "c" | "C" => {
+                                if let Some(p) = &password {
+                                    copiar(ctx, p);
+                                }
+                            }
+                            "t" | "T" => {
+                                if let Some(code) = &totp {
+                                    copiar(ctx, code);
+                                }
+                            }
+                            "m" | "M" => {
+                                if let Some(p) = &password {
+                                    println!("Senha: {}", p.as_str());
+                                }
+                            }
+                            _ => {}
                         }
                     }
                     Err(e) => println!("Erro: {e}"),
@@ -164,7 +298,7 @@ fn menu_credenciais(session: services::auth_service::Session) -> anyhow::Result<
                     continue;
                 };
 
-                match CredentialService::get(&session, uuid) {
+                match ctx.credentials().get(&session, uuid) {
                     Ok(cred) => {
                         println!("\n--- Atualizar Credencial ---");
 
@@ -172,9 +306,10 @@ fn menu_credenciais(session: services::auth_service::Session) -> anyhow::Result<
                         let newuser = input("Novo username (vazio = manter): ");
                         let newurl = input("Nova URL (vazio = manter): ");
                         let newnotes = input("Novas notas (vazio = manter): ");
-                        let newpwd = input("Nova senha (vazio = manter): ");
+                        let newpwd = ler_senha(ctx, "Nova senha (vazio = manter, \"*\" = gerar): ");
+                        let newtotp = input("Novo TOTP (vazio = manter, \"-\" = remover): ");
 
-                        match CredentialService::update(
+                        match ctx.credentials().update(
                             &session,
                             cred,
                             if newname.is_empty() {
@@ -204,7 +339,19 @@ fn menu_credenciais(session: services::auth_service::Session) -> anyhow::Result<
                             },
                         ) {
                             Ok(_) => println!("Credencial atualizada."),
-                            Err(e) => println!("Erro ao atualizar: {e}"),
+                            Err(e) => {
+                                println!("Erro ao atualizar: {e}");
+                                continue;
+                            }
+                        }
+
+                        if !newtotp.is_empty() {
+                            let value = Some(newtotp.as_str()).filter(|v| *v != "-");
+                            match ctx.credentials().set_totp(&session, uuid, value) {
+                                Ok(_) if value.is_some() => println!("TOTP atualizado."),
+                                Ok(_) => println!("TOTP removido."),
+                                Err(e) => println!("TOTP não atualizado: {e}"),
+                            }
                         }
                     }
                     Err(_) => println!("Credencial não encontrada."),
@@ -218,13 +365,216 @@ fn menu_credenciais(session: services::auth_service::Session) -> anyhow::Result<
                     continue;
                 };
 
-                match CredentialService::delete(&session, uuid) {
-                    Ok(_) => println!("Operação concluída."),
+                let cred = match ctx.credentials().get(&session, uuid) {
+                    Ok(cred) => cred,
+                    Err(_) => {
+                        println!("Credencial não encontrada.");
+                        continue;
+                    }
+                };
+                if !input(&format!("Mover '{}' para a lixeira? (s/N): ", cred.name()))
+                    .eq_ignore_ascii_case("s")
+                {
+                    println!("Remoção cancelada.");
+                    continue;
+                }
+
+                match ctx.credentials().delete(&session, uuid) {
+                    Ok(_) => println!("Credencial movida para a lixeira."),
                     Err(e) => println!("Falha na operação: {e}"),
                 }
             }
 
+            "7" => {
+                let old = input("Senha atual: ");
+                let new = input("Nova senha: ");
+                let confirm = input("Confirme a nova senha: ");
+
+                if new != confirm {
+                    println!("As senhas não coincidem.");
+                    continue;
+                }
+
+                match ctx.auth().change_password(&session, &old, &new) {
+                    Ok(_) => println!("Senha mestre alterada."),
+                    Err(e) => println!("Falha ao alterar senha: {e}"),
+                }
+            }
+
+            "8" => {
+                session.lock();
+                println!("Cofre bloqueado.");
+            }
+
+            "9" => {
+                let path = input("Arquivo de destino: ");
+                let passphrase = input("Senha do arquivo: ");
+                let confirm = input("Confirme a senha do arquivo: ");
+
+                if passphrase != confirm {
+                    println!("As senhas não coincidem.");
+                    continue;
+                }
+
+                match ctx
+                    .credentials()
+                    .export(&session, &passphrase)
+                    .and_then(|data| Ok(std::fs::write(&path, data)?))
+                {
+                    Ok(_) => println!("Cofre exportado para {path}."),
+                    Err(e) => println!("Falha na exportação: {e}"),
+                }
+            }
+
+            "10" => {
+                let path = input("Arquivo a importar: ");
+                let data = match std::fs::read(&path) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        println!("Falha ao ler o arquivo: {e}");
+                        continue;
+                    }
+                };
+                let passphrase = input("Senha do arquivo: ");
+
+                match ctx.credentials().import(&session, &data, &passphrase) {
+                    Ok(summary) => println!(
+                        "{} credencial(is) importada(s) (exportadas por '{}' em {}).",
+                        summary.imported,
+                        summary.exported_by,
+                        summary.exported_at.format("%d/%m/%Y %H:%M")
+                    ),
+                    Err(e) => println!("Falha na importação: {e}"),
+                }
+            }
+
+            "11" => importar_externo(ctx, &session),
+
+            "12" => menu_organizacao(ctx, &mut session),
+
+            "13" => {
+                let id = input("ID da credencial: ");
+                let Ok(uuid) = Uuid::parse_str(&id) else {
+                    println!("UUID inválido.");
+                    continue;
+                };
+
+                let history = match ctx.credentials().password_history(&session, uuid) {
+                    Ok(history) => history,
+                    Err(e) => {
+                        println!("Erro: {e}");
+                        continue;
+                    }
+                };
+                if history.is_empty() {
+                    println!("Nenhuma versão anterior registrada.");
+                    continue;
+                }
+
+                println!("\nVersões anteriores (mais recente primeiro):");
+                for (i, version) in history.iter().enumerate() {
+                    println!(
+                        "[{}] {} → senha: {}{}",
+                        i + 1,
+                        version.replaced_at.format("%d/%m/%Y %H:%M"),
+                        version.password.as_deref().unwrap_or("(inalterada)"),
+                        if version.notes.is_some() {
+                            " (com notas)"
+                        } else {
+                            ""
+                        }
+                    );
+                }
+
+                let Some(version) = input("Restaurar versão (vazio = não): ")
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| i.checked_sub(1))
+                    .and_then(|i| history.get(i))
+                else {
+                    continue;
+                };
+
+                match ctx
+                    .credentials()
+                    .restore_version(&session, uuid, version.id)
+                {
+                    Ok(_) => println!("Versão restaurada."),
+                    Err(e) => println!("Falha ao restaurar: {e}"),
+                }
+            }
+
+            "14" => menu_lixeira(ctx, &mut session),
+
+            "15" => {
+                let max_age_days = input(&format!(
+                    "Considerar antigas as senhas com mais de quantos dias? [{DEFAULT_MAX_PASSWORD_AGE_DAYS}]: "
+                ))
+                .parse()
+                .unwrap_or(DEFAULT_MAX_PASSWORD_AGE_DAYS);
+                let options = ReportOptions {
+                    max_age_days,
+                    ..ReportOptions::default()
+                };
+
+                match ctx.credentials().security_report(&session, &options) {
+                    Ok(report) => exibir_relatorio_seguranca(&report),
+                    Err(e) => println!("Falha ao gerar o relatório: {e}"),
+                }
+            }
+
+            "16" => menu_cofres(ctx, &mut session),
+
+            "17" => {
+                let new_username = input("Novo nome de usuário: ");
+                let password = input("Senha mestre: ");
+
+                match ctx
+                    .auth()
+                    .rename_user(&mut session, &password, &new_username)
+                {
+                    Ok(_) => println!("Usuário renomeado para '{}'.", session.user.username()),
+                    Err(e) => println!("Falha ao renomear usuário: {e}"),
+                }
+            }
+
+            "18" => {
+                println!(
+                    "A conta, todos os seus cofres e credenciais serão excluídos; membros de \
+                     cofres compartilhados perderão o acesso."
+                );
+                if input("Digite o nome de usuário para confirmar: ") != session.user.username() {
+                    println!("Exclusão cancelada.");
+                    continue;
+                }
+                let password = input("Senha mestre: ");
+
+                match ctx.auth().delete_account(&mut session, &password) {
+                    Ok(_) => {
+                        if let Some(clipboard) = ctx.clipboard() {
+                            clipboard.clear_pending();
+                        }
+                        println!("Conta excluída.");
+                        return Ok(());
+                    }
+                    Err(e) => println!("Falha ao excluir conta: {e}"),
+                }
+            }
+
+            "19" => match ctx.auth().list_users(&session) {
+                Ok(users) => {
+                    println!("\nUsuários:");
+                    for u in users {
+                        println!("→ {} (desde {})", u.username, u.created_at.format("%d/%m/%Y"));
+                    }
+                }
+                Err(e) => println!("Falha ao listar usuários: {e}"),
+            },
+
             "0" => {
+                if let Some(clipboard) = ctx.clipboard() {
+                    clipboard.clear_pending();
+                }
                 println!("Logout realizado.");
                 return Ok(());
             }
@@ -233,3 +583,744 @@ fn menu_credenciais(session: services::auth_service::Session) -> anyhow::Result<
         }
     }
 }
+
+/// Submenu de organização das credenciais em pastas e tags.
+fn menu_organizacao(ctx: &AppContext, session: &mut Session) {
+    loop {
+        println!("\n=== Pastas e Tags ===");
+        println!("[1] Listar pastas");
+        println!("[2] Criar pasta");
+        println!("[3] Renomear pasta");
+        println!("[4] Mover pasta");
+        println!("[5] Remover pasta");
+        println!("[6] Mover credencial para pasta");
+        println!("[7] Navegar por pasta");
+        println!("[8] Listar tags");
+        println!("[9] Definir tags de uma credencial");
+        println!("[10] Renomear tag");
+        println!("[11] Remover tag");
+        println!("[12] Navegar por tag");
+        println!("[0] Voltar");
+
+        let choice = input("Escolha: ");
+
+        if choice == "0" || !garantir_sessao_ativa(ctx, session) {
+            return;
+        }
+
+        let result = match choice.as_str() {
+            "1" => ctx.credentials().list_folders(session).map(|folders| {
+                println!("\nPastas:");
+                for f in folders {
+                    println!(
+                        "{}→ {} ({}) [{} credencial(is)]",
+                        "  ".repeat(f.depth),
+                        f.folder.name(),
+                        f.folder.id(),
+                        f.credentials
+                    );
+                }
+            }),
+
+            "2" => {
+                let name = input("Nome da pasta: ");
+                ler_pasta(ctx, session, "Pasta superior (caminho ou ID, vazio = raiz): ").and_then(
+                    |parent| {
+                        let folder = ctx.credentials().create_folder(session, &name, parent)?;
+                        println!("Pasta criada ({}).", folder.id());
+                        Ok(())
+                    },
+                )
+            }
+
+            "3" => ler_pasta(ctx, session, "Pasta (caminho ou ID): ").and_then(|folder| {
+                let folder = folder.ok_or_else(|| Error::validation("folder", "Informe a pasta."))?;
+                let name = input("Novo nome: ");
+                ctx.credentials().rename_folder(session, folder, &name)?;
+                println!("Pasta renomeada.");
+                Ok(())
+            }),
+
+            "4" => ler_pasta(ctx, session, "Pasta (caminho ou ID): ").and_then(|folder| {
+                let folder = folder.ok_or_else(|| Error::validation("folder", "Informe a pasta."))?;
+                let parent =
+                    ler_pasta(ctx, session, "Novo destino (caminho ou ID, vazio = raiz): ")?;
+                ctx.credentials().move_folder(session, folder, parent)?;
+                println!("Pasta movida.");
+                Ok(())
+            }),
+
+            "5" => ler_pasta(ctx, session, "Pasta (caminho ou ID): ").and_then(|folder| {
+                let folder = folder.ok_or_else(|| Error::validation("folder", "Informe a pasta."))?;
+                ctx.credentials().delete_folder(session, folder)?;
+                println!("Pasta removida; o conteúdo foi para a pasta superior.");
+                Ok(())
+            }),
+
+            "6" => ler_uuid("ID da credencial: ").and_then(|cred| {
+                let folder = ler_pasta(ctx, session, "Pasta (caminho ou ID, vazio = raiz): ")?;
+                ctx.credentials().move_to_folder(session, cred, folder)?;
+                println!("Credencial movida.");
+                Ok(())
+            }),
+
+            "7" => ler_pasta(ctx, session, "Pasta (caminho ou ID, vazio = raiz): ").and_then(
+                |folder| {
+                    let recursive = input("Incluir subpastas? (s/N): ").eq_ignore_ascii_case("s");
+                    let list = ctx
+                        .credentials()
+                        .list_by_folder(session, folder, recursive)?;
+                    println!("\nCredenciais:");
+                    for c in list {
+                        println!("→ {} ({})", c.name(), c.id());
+                    }
+                    Ok(())
+                },
+            ),
+
+            "8" => ctx.credentials().list_tags(session).map(|tags| {
+                println!("\nTags:");
+                for t in tags {
+                    println!(
+                        "→ {} ({}) [{} credencial(is)]",
+                        t.tag.name(),
+                        t.tag.id(),
+                        t.credentials
+                    );
+                }
+            }),
+
+            "9" => ler_uuid("ID da credencial: ").and_then(|cred| {
+                let current: Vec<String> = ctx
+                    .credentials()
+                    .tags_of(session, cred)?
+                    .iter()
+                    .map(|t| t.name().to_string())
+                    .collect();
+                println!("Tags atuais: {}", current.join(", "));
+
+                let names: Vec<String> = input("Novas tags (separadas por vírgula): ")
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|n| !n.is_empty())
+                    .map(String::from)
+                    .collect();
+                ctx.credentials().set_tags(session, cred, &names)?;
+                println!("Tags atualizadas.");
+                Ok(())
+            }),
+
+            "10" => ler_tag(ctx, session).and_then(|tag| {
+                let name = input("Novo nome: ");
+                ctx.credentials().rename_tag(session, tag, &name)?;
+                println!("Tag renomeada.");
+                Ok(())
+            }),
+
+            "11" => ler_tag(ctx, session).and_then(|tag| {
+                ctx.credentials().delete_tag(session, tag)?;
+                println!("Tag removida.");
+                Ok(())
+            }),
+
+            "12" => ler_tag(ctx, session).and_then(|tag| {
+                let list = ctx.credentials().list_by_tag(session, tag)?;
+                println!("\nCredenciais:");
+                for c in list {
+                    println!("→ {} ({})", c.name(), c.id());
+                }
+                Ok(())
+            }),
+
+            _ => {
+                println!("Opção inválida.");
+                Ok(())
+            }
+        };
+
+        if let Err(e) = result {
+            println!("Falha na operação: {e}");
+        }
+    }
+}
+
+/// Submenu da lixeira: credenciais removidas podem ser restauradas ou excluídas
+/// definitivamente.
+fn menu_lixeira(ctx: &AppContext, session: &mut Session) {
+    loop {
+        println!("\n=== Lixeira ===");
+        println!("[1] Listar credenciais removidas");
+        println!("[2] Restaurar credencial");
+        println!("[3] Excluir definitivamente");
+        println!("[4] Esvaziar lixeira");
+        println!("[0] Voltar");
+
+        let choice = input("Escolha: ");
+
+        if choice == "0" || !garantir_sessao_ativa(ctx, session) {
+            return;
+        }
+
+        let result = match choice.as_str() {
+            "1" => ctx.credentials().list_trash(session).map(|list| {
+                if list.is_empty() {
+                    println!("A lixeira está vazia.");
+                }
+                for c in list {
+                    let removed = c
+                        .deleted_at()
+                        .map(|at| at.format("%d/%m/%Y %H:%M").to_string())
+                        .unwrap_or_default();
+                    println!("→ {} ({}) removida em {}", c.name(), c.id(), removed);
+                }
+            }),
+
+            "2" => ler_uuid("ID da credencial: ").and_then(|cred| {
+                let cred = ctx.credentials().restore(session, cred)?;
+                println!("Credencial '{}' restaurada.", cred.name());
+                Ok(())
+            }),
+
+            "3" => ler_uuid("ID da credencial: ").and_then(|cred| {
+                if !input("Excluir definitivamente? Esta ação não pode ser desfeita. (s/N): ")
+                    .eq_ignore_ascii_case("s")
+                {
+                    println!("Exclusão cancelada.");
+                    return Ok(());
+                }
+                ctx.credentials().purge(session, cred)?;
+                println!("Credencial excluída definitivamente.");
+                Ok(())
+            }),
+
+            "4" => {
+                if input("Esvaziar a lixeira? Esta ação não pode ser desfeita. (s/N): ")
+                    .eq_ignore_ascii_case("s")
+                {
+                    ctx.credentials().empty_trash(session).map(|purged| {
+                        println!("{purged} credencial(is) excluída(s) definitivamente.");
+                    })
+                } else {
+                    println!("Operação cancelada.");
+                    Ok(())
+                }
+            }
+
+            _ => {
+                println!("Opção inválida.");
+                Ok(())
+            }
+        };
+
+        if let Err(e) = result {
+            println!("Falha na operação: {e}");
+        }
+    }
+}
+
+/// Submenu dos cofres do usuário: criação, troca do cofre aberto e
+/// transferência de credenciais entre cofres.
+fn menu_cofres(ctx: &AppContext, session: &mut Session) {
+    loop {
+        println!("\n=== Cofres ===");
+        println!("[1] Listar cofres");
+        println!("[2] Criar cofre");
+        println!("[3] Renomear cofre");
+        println!("[4] Alternar cofre");
+        println!("[5] Excluir cofre");
+        println!("[6] Mover credencial para outro cofre");
+        println!("[7] Copiar credencial para outro cofre");
+        println!("[8] Listar membros de um cofre");
+        println!("[9] Compartilhar cofre com outro usuário");
+        println!("[10] Alterar papel de um membro");
+        println!("[11] Revogar acesso de um membro");
+        println!("[0] Voltar");
+
+        let choice = input("Escolha: ");
+
+        if choice == "0" || !garantir_sessao_ativa(ctx, session) {
+            return;
+        }
+
+        let result = match choice.as_str() {
+            "1" => ctx.vaults().list(session).map(|vaults| {
+                println!("\nCofres (* = aberto):");
+                for v in vaults {
+                    let compartilhamento = match (&v.owner, v.members) {
+                        (Some(owner), _) => format!(" — {} de {owner}", nome_papel(v.role)),
+                        (None, 0) => String::new(),
+                        (None, n) => format!(" — compartilhado com {n} membro(s)"),
+                    };
+                    println!(
+                        "{} {} ({}) [{} credencial(is)]{}",
+                        if v.current { "*" } else { "→" },
+                        v.vault.name(),
+                        v.vault.id(),
+                        v.credentials,
+                        compartilhamento
+                    );
+                }
+            }),
+
+            "2" => {
+                let name = input("Nome do cofre: ");
+                ctx.vaults().create(session, &name).map(|vault| {
+                    println!("Cofre '{}' criado ({}).", vault.name(), vault.id());
+                })
+            }
+
+            "3" => ler_cofre(ctx, session, "Cofre (nome ou ID): ").and_then(|vault| {
+                let name = input("Novo nome: ");
+                ctx.vaults().rename(session, vault, &name)?;
+                println!("Cofre renomeado.");
+                Ok(())
+            }),
+
+            "4" => ler_cofre(ctx, session, "Cofre (nome ou ID): ").and_then(|vault| {
+                let vault = ctx.vaults().switch(session, vault)?;
+                println!("Cofre '{}' aberto.", vault.name());
+                Ok(())
+            }),
+
+            "5" => ler_cofre(ctx, session, "Cofre (nome ou ID): ").and_then(|vault| {
+                if !input(
+                    "Excluir o cofre e todas as suas credenciais? Esta ação não pode ser desfeita. (s/N): ",
+                )
+                .eq_ignore_ascii_case("s")
+                {
+                    println!("Exclusão cancelada.");
+                    return Ok(());
+                }
+                ctx.vaults().delete(session, vault)?;
+                println!("Cofre excluído.");
+                Ok(())
+            }),
+
+            "6" | "7" => ler_uuid("ID da credencial: ").and_then(|cred| {
+                let target = ler_cofre(ctx, session, "Cofre de destino (nome ou ID): ")?;
+                let mode = if choice == "6" {
+                    TransferMode::Move
+                } else {
+                    TransferMode::Copy
+                };
+                ctx.vaults().transfer(session, &[cred], target, mode)?;
+                println!(
+                    "Credencial {}.",
+                    if mode == TransferMode::Move {
+                        "movida"
+                    } else {
+                        "copiada"
+                    }
+                );
+                Ok(())
+            }),
+
+            "8" => ler_cofre(ctx, session, "Cofre (nome ou ID): ").and_then(|vault| {
+                for m in ctx.vaults().members(session, vault)? {
+                    println!("→ {} [{}]", m.username, nome_papel(m.role));
+                }
+                Ok(())
+            }),
+
+            "9" => ler_cofre(ctx, session, "Cofre (nome ou ID): ").and_then(|vault| {
+                let username = input("Usuário convidado: ");
+                let role = ler_papel()?;
+                let member = ctx.vaults().invite(session, vault, &username, role)?;
+                println!(
+                    "Cofre compartilhado com '{}' como {}.",
+                    member.username,
+                    nome_papel(member.role)
+                );
+                Ok(())
+            }),
+
+            "10" => ler_cofre(ctx, session, "Cofre (nome ou ID): ").and_then(|vault| {
+                let username = input("Membro: ");
+                let role = ler_papel()?;
+                ctx.vaults().set_role(session, vault, &username, role)?;
+                println!("Papel alterado.");
+                Ok(())
+            }),
+
+            "11" => ler_cofre(ctx, session, "Cofre (nome ou ID): ").and_then(|vault| {
+                let username = input("Membro: ");
+                println!("Trocando a chave do cofre e recifrando as credenciais...");
+                ctx.vaults().revoke(session, vault, &username)?;
+                println!("Acesso de '{username}' revogado.");
+                Ok(())
+            }),
+
+            _ => {
+                println!("Opção inválida.");
+                Ok(())
+            }
+        };
+
+        if let Err(e) = result {
+            println!("Falha na operação: {e}");
+        }
+    }
+}
+
+/// Exibe as seções do relatório de segurança do cofre.
+fn exibir_relatorio_seguranca(report: &SecurityReport) {
+    println!(
+        "\n=== Relatório de segurança ({} credencial(is)) ===",
+        report.total
+    );
+    if report.is_clean() {
+        println!("Nenhum problema encontrado.");
+        return;
+    }
+
+    println!("\nSenhas fracas: {}", report.weak.len());
+    for w in &report.weak {
+        let motivos: Vec<&str> = w.strength.warnings.iter().map(|w| w.label()).collect();
+        println!(
+            "→ {} ({}) pontuação {}/4, ~{:.0} bits{}",
+            w.credential.name(),
+            w.credential.id(),
+            w.strength.score,
+            w.strength.entropy_bits,
+            if motivos.is_empty() {
+                String::new()
+            } else {
+                format!(": {}", motivos.join(", "))
+            }
+        );
+    }
+
+    if report.breach_checked {
+        println!("\nSenhas vazadas: {}", report.breached.len());
+        for b in &report.breached {
+            println!(
+                "→ {} ({}) encontrada {} vez(es) em vazamentos",
+                b.credential.name(),
+                b.credential.id(),
+                b.occurrences
+            );
+        }
+    }
+
+    println!("\nSenhas repetidas: {} grupo(s)", report.reused.len());
+    for group in &report.reused {
+        let nomes: Vec<&str> = group.credentials.iter().map(|c| c.name()).collect();
+        println!("→ {}", nomes.join(", "));
+    }
+
+    println!(
+        "\nSenhas sem troca há {} dias ou mais: {}",
+        report.options.max_age_days,
+        report.stale.len()
+    );
+    for s in &report.stale {
+        println!(
+            "→ {} ({}) há {} dia(s)",
+            s.credential.name(),
+            s.credential.id(),
+            s.age_days
+        );
+    }
+
+    println!("\nCredenciais incompletas: {}", report.incomplete.len());
+    for i in &report.incomplete {
+        let faltando: Vec<&str> = [(i.missing_username, "usuário"), (i.missing_url, "URL")]
+            .into_iter()
+            .filter(|(missing, _)| *missing)
+            .map(|(_, field)| field)
+            .collect();
+        println!(
+            "→ {} ({}) sem {}",
+            i.credential.name(),
+            i.credential.id(),
+            faltando.join(" e ")
+        );
+    }
+}
+
+/// Lê um UUID digitado pelo usuário.
+fn ler_uuid(prompt: &str) -> Result<Uuid> {
+    Uuid::parse_str(&input(prompt)).map_err(|_| Error::validation("id", "UUID inválido."))
+}
+
+/// Lê uma pasta pelo ID ou pelo caminho completo (ex.: `Trabalho/Servidores`).
+///
+/// Entrada vazia representa a raiz do cofre (`None`).
+fn ler_pasta(ctx: &AppContext, session: &Session, prompt: &str) -> Result<Option<Uuid>> {
+    let value = input(prompt);
+    if value.is_empty() {
+        return Ok(None);
+    }
+    if let Ok(id) = Uuid::parse_str(&value) {
+        return Ok(Some(id));
+    }
+
+    ctx.credentials()
+        .list_folders(session)?
+        .into_iter()
+        .find(|f| f.path.eq_ignore_ascii_case(value.trim_matches('/')))
+        .map(|f| Some(f.folder.id()))
+        .ok_or_else(|| Error::not_found(format!("Pasta não encontrada: '{value}'")))
+}
+
+/// Lê uma tag pelo ID ou pelo nome.
+fn ler_tag(ctx: &AppContext, session: &Session) -> Result<Uuid> {
+    let value = input("Tag (nome ou ID): ");
+    if let Ok(id) = Uuid::parse_str(&value) {
+        return Ok(id);
+    }
+
+    ctx.credentials()
+        .list_tags(session)?
+        .into_iter()
+        .find(|t| t.tag.name().eq_ignore_ascii_case(&value))
+        .map(|t| t.tag.id())
+        .ok_or_else(|| Error::not_found(format!("Tag não encontrada: '{value}'")))
+}
+
+/// Lê um cofre do usuário pelo ID ou pelo nome.
+fn ler_cofre(ctx: &AppContext, session: &Session, prompt: &str) -> Result<Uuid> {
+    let value = input(prompt);
+    if let Ok(id) = Uuid::parse_str(&value) {
+        return Ok(id);
+    }
+
+    ctx.vaults()
+        .list(session)?
+        .into_iter()
+        .find(|v| v.vault.name().eq_ignore_ascii_case(&value))
+        .map(|v| v.vault.id())
+        .ok_or_else(|| Error::not_found(format!("Cofre não encontrado: '{value}'")))
+}
+
+/// Lê o papel de um membro de cofre compartilhado.
+fn ler_papel() -> Result<VaultRole> {
+    match input("Papel ([e]ditor ou [l]eitor): ").to_lowercase().as_str() {
+        "e" | "editor" => Ok(VaultRole::Editor),
+        "l" | "leitor" => Ok(VaultRole::Viewer),
+        other => Err(Error::validation("role", format!("Papel inválido: '{other}'"))),
+    }
+}
+
+/// Nome do papel exibido no menu.
+fn nome_papel(role: VaultRole) -> &'static str {
+    match role {
+        VaultRole::Owner => "dono",
+        VaultRole::Editor => "editor",
+        VaultRole::Viewer => "leitor",
+    }
+}
+
+/// Importação de arquivos de outros gerenciadores: identifica o formato, exibe a
+/// simulação e grava somente após a confirmação do usuário.
+fn importar_externo(ctx: &AppContext, session: &Session) {
+    let path = input("Arquivo a importar: ");
+    let data = match std::fs::read(&path) {
+        Ok(data) => data,
+        Err(e) => {
+            println!("Falha ao ler o arquivo: {e}");
+            return;
+        }
+    };
+
+    println!("[0] Detectar automaticamente");
+    for (i, format) in ImportFormat::ALL.iter().enumerate() {
+        println!("[{}] {}", i + 1, format.label());
+    }
+    let format = input("Formato (padrão 0): ")
+        .parse::<usize>()
+        .ok()
+        .and_then(|i| i.checked_sub(1))
+        .and_then(|i| ImportFormat::ALL.get(i).copied());
+
+    let parsed = match importers::parse(format, &data) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            println!("Falha ao interpretar o arquivo: {e}");
+            return;
+        }
+    };
+
+    match ctx.credentials().import_external(session, &parsed, true) {
+        Ok(report) => exibir_relatorio_importacao(&report),
+        Err(e) => {
+            println!("Falha na simulação: {e}");
+            return;
+        }
+    }
+
+    if !input("Confirmar importação? (s/N): ").eq_ignore_ascii_case("s") {
+        println!("Importação cancelada.");
+        return;
+    }
+
+    match ctx.credentials().import_external(session, &parsed, false) {
+        Ok(report) => println!("{} credencial(is) importada(s).", report.imported),
+        Err(e) => println!("Falha na importação: {e}"),
+    }
+}
+
+/// Exibe o relatório de uma importação de arquivo externo.
+fn exibir_relatorio_importacao(report: &ImportReport) {
+    println!("\n--- {} ---", report.format.label());
+    println!("Itens no arquivo: {}", report.total);
+    println!(
+        "{}: {}",
+        if report.dry_run {
+            "Seriam importados"
+        } else {
+            "Importados"
+        },
+        report.imported
+    );
+
+    for (title, issues) in [
+        ("Duplicados", &report.duplicates),
+        ("Ignorados", &report.skipped),
+        ("Avisos", &report.warnings),
+    ] {
+        if issues.is_empty() {
+            continue;
+        }
+        println!("{title}: {}", issues.len());
+        for issue in issues {
+            println!("  - {}: {}", issue.entry, issue.reason);
+        }
+    }
+}
+
+/// Verifica o estado da sessão antes de cada operação, solicitando a senha mestre
+/// quando o cofre estiver bloqueado (por inatividade ou manualmente).
+///
+/// Retorna `false` quando a sessão expirou ou o usuário desistiu do desbloqueio,
+/// situações em que é necessário voltar ao menu inicial.
+fn garantir_sessao_ativa(ctx: &AppContext, session: &mut Session) -> bool {
+    loop {
+        match session.state() {
+            SessionState::Active => return true,
+            SessionState::Expired => {
+                session.lock();
+                println!("Sessão expirada. Faça login novamente.");
+                return false;
+            }
+            SessionState::Locked => {
+                session.lock();
+                println!("\nCofre bloqueado.");
+
+                let pass = input("Senha mestre (vazio = logout): ");
+                if pass.is_empty() {
+                    return false;
+                }
+
+                match ctx.auth().unlock(session, &pass) {
+                    Ok(_) => println!("Cofre desbloqueado."),
+                    Err(e) => println!("Falha ao desbloquear: {e}"),
+                }
+            }
+        }
+    }
+}
+
+/// Copia um valor para a área de transferência, informando quando ela será limpa.
+fn copiar(ctx: &AppContext, value: &str) {
+    let Some(clipboard) = ctx.clipboard() else {
+        println!("Área de transferência indisponível (instale wl-clipboard, xclip ou xsel).");
+        return;
+    };
+
+    match clipboard.copy(value) {
+        Ok(copy) => match copy.clear_after() {
+            Some(delay) => println!(
+                "Copiado. A área de transferência será limpa em {}s.",
+                delay.as_secs()
+            ),
+            None => println!("Copiado."),
+        },
+        Err(e) => println!("Falha ao copiar: {e}"),
+    }
+}
+
+/// Lê uma senha do usuário; a entrada `*` abre o gerador de senhas.
+///
+/// Senhas digitadas que constam na base local de senhas vazadas geram um aviso
+/// e só são aceitas após confirmação.
+///
+/// Retorna a senha digitada ou gerada (vazia quando o usuário não informar nada).
+fn ler_senha(ctx: &AppContext, prompt: &str) -> String {
+    loop {
+        let value = input(prompt);
+        if value != "*" {
+            match ctx.credentials().breach_occurrences(&value) {
+                Ok(Some(count)) if count > 0 => {
+                    println!(
+                        "Atenção: esta senha aparece {count} vez(es) em vazamentos conhecidos."
+                    );
+                    if !input("Usar mesmo assim? (s/N): ").eq_ignore_ascii_case("s") {
+                        continue;
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Falha ao consultar a base de senhas vazadas: {e}"),
+            }
+            return value;
+        }
+
+        if let Some(generated) = gerar_senha() {
+            return generated;
+        }
+    }
+}
+
+/// Gerador interativo de senhas e frases secretas.
+///
+/// Retorna `None` quando o usuário cancela sem aceitar nenhuma sugestão.
+fn gerar_senha() -> Option<String> {
+    println!("\n--- Gerador de senhas ---");
+    println!("[1] Senha aleatória");
+    println!("[2] Frase secreta");
+
+    let generate: Box<dyn Fn() -> Result<GeneratedPassword>> =
+        if input("Tipo (padrão 1): ") == "2" {
+            let policy = PassphrasePolicy {
+                words: input(&format!(
+                    "Quantidade de palavras (padrão {DEFAULT_PASSPHRASE_WORDS}): "
+                ))
+                .parse()
+                .unwrap_or(DEFAULT_PASSPHRASE_WORDS),
+                ..PassphrasePolicy::default()
+            };
+            Box::new(move || PasswordGenerator::passphrase(&policy))
+        } else {
+            let policy = PasswordPolicy {
+                length: input(&format!("Comprimento (padrão {DEFAULT_PASSWORD_LENGTH}): "))
+                    .parse()
+                    .unwrap_or(DEFAULT_PASSWORD_LENGTH),
+                symbols: !input("Incluir símbolos? (S/n): ").eq_ignore_ascii_case("n"),
+                exclude_ambiguous: input("Evitar caracteres ambíguos? (s/N): ")
+                    .eq_ignore_ascii_case("s"),
+                ..PasswordPolicy::default()
+            };
+            Box::new(move || PasswordGenerator::generate(&policy))
+        };
+
+    loop {
+        let generated = match generate() {
+            Ok(g) => g,
+            Err(e) => {
+                println!("Não foi possível gerar a senha: {e}");
+                return None;
+            }
+        };
+
+        println!(
+            "\nSugestão: {} (~{:.0} bits de entropia)",
+            generated.value, generated.entropy_bits
+        );
+
+        match input("[Enter] usar / [r] gerar outra / [c] cancelar: ").as_str() {
+            "" => return Some(generated.value),
+            "r" | "R" => continue,
+            _ => return None,
+        }
+    }
+}
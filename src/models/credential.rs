@@ -1,12 +1,13 @@
-use anyhow::{Result, anyhow};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::error::{Error, Result};
+
 /// Entidade de domínio que representa uma credencial armazenada em um cofre.
 ///
-/// Campos como nome de usuário, URL, notas e senha são opcionais.
-/// Tanto a senha quanto as notas, quando presentes, são sempre armazenadas de forma cifrada
+/// Campos como nome de usuário, URL, notas, senha e segredo TOTP são opcionais.
+/// A senha, as notas e o segredo TOTP, quando presentes, são sempre armazenados de forma cifrada
 /// para evitar exposição de informações sensíveis mesmo em caso de acesso indevido ao banco de dados.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Credential {
@@ -17,8 +18,12 @@ pub struct Credential {
     url: Option<String>,
     notes: Option<Vec<u8>>,
     password_cipher: Option<Vec<u8>>,
+    totp_cipher: Option<Vec<u8>>,
+    folder_id: Option<Uuid>,
+    deleted_at: Option<DateTime<Utc>>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    password_changed_at: DateTime<Utc>,
 }
 
 impl Credential {
@@ -34,7 +39,7 @@ impl Credential {
     ///
     /// ### Retorno
     /// - `Ok(Credential)` quando válida.
-    /// - `Err(anyhow)` quando `vault_id` inválido ou `name` em branco.
+    /// - `Err(Error)` quando `vault_id` inválido ou `name` em branco.
     pub fn new(
         vault_id: Uuid,
         name: String,
@@ -56,8 +61,12 @@ impl Credential {
             url,
             notes,
             password_cipher,
+            totp_cipher: None,
+            folder_id: None,
+            deleted_at: None,
             created_at: now,
             updated_at: now,
+            password_changed_at: now,
         })
     }
 
@@ -98,6 +107,26 @@ impl Credential {
         self.password_cipher.as_deref()
     }
 
+    /// Retorna a configuração TOTP cifrada (URI `otpauth`), quando existir.
+    pub fn totp_cipher(&self) -> Option<&[u8]> {
+        self.totp_cipher.as_deref()
+    }
+
+    /// Retorna o ID da pasta da credencial, quando existir.
+    pub fn folder_id(&self) -> Option<Uuid> {
+        self.folder_id
+    }
+
+    /// Instante em que a credencial foi movida para a lixeira, quando estiver lá.
+    pub fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at
+    }
+
+    /// Indica se a credencial está na lixeira.
+    pub fn is_trashed(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
     /// Timestamp de criação.
     pub fn created_at(&self) -> DateTime<Utc> {
         self.created_at
@@ -108,6 +137,14 @@ impl Credential {
         self.updated_at
     }
 
+    /// Timestamp da última troca de senha (ou da criação da credencial).
+    ///
+    /// Diferente de [`Self::updated_at`], não muda ao mover, renomear ou recifrar
+    /// a credencial.
+    pub fn password_changed_at(&self) -> DateTime<Utc> {
+        self.password_changed_at
+    }
+
     // Setters
 
     /// Atualiza o nome da credencial.
@@ -136,10 +173,67 @@ impl Credential {
         self.touch();
     }
 
-    /// Atualiza a senha cifrada.
+    /// Troca a senha cifrada, registrando o momento da troca.
+    ///
+    /// Para apenas recifrar a mesma senha, use [`Self::set_ciphers`].
     pub fn set_password_cipher(&mut self, cipher: Option<Vec<u8>>) {
         self.password_cipher = cipher;
         self.touch();
+        self.password_changed_at = self.updated_at;
+    }
+
+    /// Atualiza a configuração TOTP cifrada.
+    pub fn set_totp_cipher(&mut self, cipher: Option<Vec<u8>>) {
+        self.totp_cipher = cipher;
+        self.touch();
+    }
+
+    /// Move a credencial para uma pasta (ou a retira de qualquer pasta).
+    pub fn set_folder_id(&mut self, folder_id: Option<Uuid>) {
+        self.folder_id = folder_id;
+        self.touch();
+    }
+
+    /// Transfere a credencial para outro cofre.
+    ///
+    /// Os campos cifrados devem ser recifrados com a chave do cofre de destino
+    /// pelo serviço antes da gravação.
+    pub(crate) fn set_vault_id(&mut self, vault_id: Uuid) {
+        self.vault_id = vault_id;
+        self.touch();
+    }
+
+    /// Substitui os campos cifrados após a rotação da chave do cofre.
+    ///
+    /// Não altera o timestamp de modificação: o conteúdo da credencial continua o mesmo.
+    pub(crate) fn set_ciphers(
+        &mut self,
+        password_cipher: Option<Vec<u8>>,
+        notes: Option<Vec<u8>>,
+        totp_cipher: Option<Vec<u8>>,
+    ) {
+        self.password_cipher = password_cipher;
+        self.notes = notes;
+        self.totp_cipher = totp_cipher;
+    }
+
+    /// Move a credencial para a lixeira (`Some`) ou a restaura (`None`).
+    ///
+    /// Não altera o timestamp de modificação: o conteúdo da credencial continua o mesmo.
+    pub fn set_deleted_at(&mut self, deleted_at: Option<DateTime<Utc>>) {
+        self.deleted_at = deleted_at;
+    }
+
+    /// Restaura as datas de criação e de modificação de uma credencial importada
+    /// de uma exportação do próprio gerenciador.
+    pub(crate) fn set_timestamps(&mut self, created_at: DateTime<Utc>, updated_at: DateTime<Utc>) {
+        self.created_at = created_at;
+        self.updated_at = updated_at;
+    }
+
+    /// Define o momento da última troca de senha, preservado em cópias e importações.
+    pub(crate) fn set_password_changed_at(&mut self, at: DateTime<Utc>) {
+        self.password_changed_at = at;
     }
 
     /// Atualiza o timestamp de modificação.
@@ -148,6 +242,7 @@ impl Credential {
     }
 
     /// Reidrata uma credencial já persistida.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn from_persisted(
         id: Uuid,
         vault_id: Uuid,
@@ -156,8 +251,12 @@ impl Credential {
         url: Option<String>,
         notes: Option<Vec<u8>>,
         password_cipher: Option<Vec<u8>>,
+        totp_cipher: Option<Vec<u8>>,
+        folder_id: Option<Uuid>,
+        deleted_at: Option<DateTime<Utc>>,
         created_at: DateTime<Utc>,
         updated_at: DateTime<Utc>,
+        password_changed_at: DateTime<Utc>,
     ) -> Self {
         Self {
             id,
@@ -167,8 +266,12 @@ impl Credential {
             url,
             notes,
             password_cipher,
+            totp_cipher,
+            folder_id,
+            deleted_at,
             created_at,
             updated_at,
+            password_changed_at,
         }
     }
 }
@@ -180,7 +283,10 @@ impl Credential {
 /// Valida se o UUID não é nulo.
 fn validate_uuid(id: Uuid) -> Result<()> {
     if id.is_nil() {
-        return Err(anyhow!("O ID do cofre não pode ser nulo."));
+        return Err(Error::validation(
+            "vault_id",
+            "O ID do cofre não pode ser nulo.",
+        ));
     }
     Ok(())
 }
@@ -188,11 +294,15 @@ fn validate_uuid(id: Uuid) -> Result<()> {
 /// Valida nome da credencial.
 fn validate_name(name: &str) -> Result<()> {
     if name.trim().is_empty() {
-        return Err(anyhow!("O nome da credencial não pode ser vazio."));
+        return Err(Error::validation(
+            "name",
+            "O nome da credencial não pode ser vazio.",
+        ));
     }
     if name.len() > 64 {
-        return Err(anyhow!(
-            "O nome da credencial excede o limite de 64 caracteres."
+        return Err(Error::validation(
+            "name",
+            "O nome da credencial excede o limite de 64 caracteres.",
         ));
     }
     Ok(())
@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+
+/// Entidade de domínio que representa uma versão anterior de uma credencial.
+///
+/// Guarda os valores substituídos por uma atualização (a senha e, quando
+/// configurado, as notas), cifrados com a chave do cofre exatamente como
+/// estavam na credencial. Um campo ausente indica que ele não foi alterado
+/// naquela atualização.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CredentialHistoryEntry {
+    id: Uuid,
+    credential_id: Uuid,
+    password_cipher: Option<Vec<u8>>,
+    notes_cipher: Option<Vec<u8>>,
+    created_at: DateTime<Utc>,
+}
+
+impl CredentialHistoryEntry {
+    /// Cria uma nova versão com os valores substituídos.
+    ///
+    /// ### Parâmetros
+    /// - `credential_id`: Credencial alterada.
+    /// - `password_cipher`: Senha anterior cifrada (quando a senha mudou).
+    /// - `notes_cipher`: Notas anteriores cifradas (quando as notas mudaram).
+    ///
+    /// ### Retorno
+    /// - `Ok(CredentialHistoryEntry)` quando válida.
+    /// - `Err(Error)` quando `credential_id` é nulo ou nenhum valor foi informado.
+    pub fn new(
+        credential_id: Uuid,
+        password_cipher: Option<Vec<u8>>,
+        notes_cipher: Option<Vec<u8>>,
+    ) -> Result<Self> {
+        if credential_id.is_nil() {
+            return Err(Error::validation(
+                "credential_id",
+                "O ID da credencial não pode ser nulo.",
+            ));
+        }
+        if password_cipher.is_none() && notes_cipher.is_none() {
+            return Err(Error::validation(
+                "password",
+                "A versão do histórico não possui valores.",
+            ));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            credential_id,
+            password_cipher,
+            notes_cipher,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Retorna o ID único da versão.
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Retorna o ID da credencial à qual a versão pertence.
+    pub fn credential_id(&self) -> Uuid {
+        self.credential_id
+    }
+
+    /// Retorna a senha anterior cifrada, quando existir.
+    pub fn password_cipher(&self) -> Option<&[u8]> {
+        self.password_cipher.as_deref()
+    }
+
+    /// Retorna as notas anteriores cifradas, quando existirem.
+    pub fn notes_cipher(&self) -> Option<&[u8]> {
+        self.notes_cipher.as_deref()
+    }
+
+    /// Instante em que os valores foram substituídos.
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    /// Substitui os valores cifrados, preservando quais campos estão presentes.
+    ///
+    /// ### Aplicação
+    /// Usado quando a credencial é transferida para outro cofre, ou quando a
+    /// chave do cofre é trocada, e o histórico precisa ser recifrado.
+    pub(crate) fn set_ciphers(
+        &mut self,
+        password_cipher: Option<Vec<u8>>,
+        notes_cipher: Option<Vec<u8>>,
+    ) {
+        self.password_cipher = password_cipher;
+        self.notes_cipher = notes_cipher;
+    }
+
+    /// Reidrata uma versão já persistida.
+    pub(crate) fn from_persisted(
+        id: Uuid,
+        credential_id: Uuid,
+        password_cipher: Option<Vec<u8>>,
+        notes_cipher: Option<Vec<u8>>,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            credential_id,
+            password_cipher,
+            notes_cipher,
+            created_at,
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+
+/// Tamanho máximo (em bytes) do nome de uma pasta.
+const MAX_NAME_LEN: usize = 64;
+
+/// Separador dos caminhos de pastas exibidos nas interfaces (ex.: `Trabalho/Servidores`).
+pub const PATH_SEPARATOR: char = '/';
+
+/// Entidade de domínio que representa uma pasta de credenciais.
+///
+/// As pastas formam uma hierarquia dentro do cofre: uma pasta sem `parent_id`
+/// fica na raiz. Cada credencial pertence a no máximo uma pasta.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Folder {
+    id: Uuid,
+    vault_id: Uuid,
+    parent_id: Option<Uuid>,
+    name: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl Folder {
+    /// Cria uma nova pasta validando o nome.
+    ///
+    /// ### Parâmetros
+    /// - `vault_id`: Identificador do cofre ao qual a pasta pertence.
+    /// - `parent_id`: Pasta superior (`None` para a raiz).
+    /// - `name`: Nome da pasta.
+    ///
+    /// ### Retorno
+    /// - `Ok(Folder)` quando válida.
+    /// - `Err(Error)` quando `vault_id` é nulo ou o nome é inválido.
+    pub fn new(vault_id: Uuid, parent_id: Option<Uuid>, name: String) -> Result<Self> {
+        if vault_id.is_nil() {
+            return Err(Error::validation(
+                "vault_id",
+                "O ID do cofre não pode ser nulo.",
+            ));
+        }
+        let name = validate_name(name)?;
+
+        let now = Utc::now();
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            vault_id,
+            parent_id,
+            name,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Retorna o ID único da pasta.
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Retorna o ID do cofre ao qual a pasta pertence.
+    pub fn vault_id(&self) -> Uuid {
+        self.vault_id
+    }
+
+    /// Retorna o ID da pasta superior, quando existir.
+    pub fn parent_id(&self) -> Option<Uuid> {
+        self.parent_id
+    }
+
+    /// Retorna o nome da pasta.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Timestamp de criação.
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    /// Timestamp da última atualização.
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    /// Renomeia a pasta.
+    pub fn set_name(&mut self, name: String) -> Result<()> {
+        self.name = validate_name(name)?;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Move a pasta para dentro de outra (ou para a raiz).
+    pub fn set_parent_id(&mut self, parent_id: Option<Uuid>) {
+        self.parent_id = parent_id;
+        self.updated_at = Utc::now();
+    }
+
+    /// Reidrata uma pasta já persistida.
+    pub(crate) fn from_persisted(
+        id: Uuid,
+        vault_id: Uuid,
+        parent_id: Option<Uuid>,
+        name: String,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            vault_id,
+            parent_id,
+            name,
+            created_at,
+            updated_at,
+        }
+    }
+}
+
+/// Valida o nome da pasta, retornando-o sem espaços nas extremidades.
+fn validate_name(name: String) -> Result<String> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(Error::validation(
+            "name",
+            "O nome da pasta não pode ser vazio.",
+        ));
+    }
+    if name.len() > MAX_NAME_LEN {
+        return Err(Error::validation(
+            "name",
+            format!("O nome da pasta excede o limite de {MAX_NAME_LEN} caracteres."),
+        ));
+    }
+    if name.contains(PATH_SEPARATOR) {
+        return Err(Error::validation(
+            "name",
+            format!("O nome da pasta não pode conter '{PATH_SEPARATOR}'."),
+        ));
+    }
+    Ok(name.to_string())
+}
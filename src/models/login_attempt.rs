@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+
+/// Entidade de domínio que acompanha as tentativas de login malsucedidas de um usuário.
+///
+/// Mantém o contador de falhas consecutivas e, quando aplicável, o instante até o qual
+/// novas tentativas de autenticação devem ser recusadas.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LoginAttempt {
+    user_id: Uuid,
+    failed_count: u32,
+    last_failed_at: Option<DateTime<Utc>>,
+    locked_until: Option<DateTime<Utc>>,
+}
+
+impl LoginAttempt {
+    /// Cria um registro zerado de tentativas para o usuário.
+    ///
+    /// ### Parâmetros
+    /// - `user_id`: Identificador do usuário monitorado.
+    ///
+    /// ### Retorno
+    /// - `Ok(LoginAttempt)`: registro sem falhas.
+    /// - `Err(Error)`: quando o `user_id` é nulo.
+    pub fn new(user_id: Uuid) -> Result<Self> {
+        if user_id.is_nil() {
+            return Err(Error::validation(
+                "user_id",
+                "O ID do usuário não pode ser nulo.",
+            ));
+        }
+
+        Ok(Self {
+            user_id,
+            failed_count: 0,
+            last_failed_at: None,
+            locked_until: None,
+        })
+    }
+
+    /// Retorna o ID do usuário monitorado.
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    /// Retorna a quantidade de falhas consecutivas.
+    pub fn failed_count(&self) -> u32 {
+        self.failed_count
+    }
+
+    /// Retorna o instante da última falha, quando existir.
+    pub fn last_failed_at(&self) -> Option<DateTime<Utc>> {
+        self.last_failed_at
+    }
+
+    /// Retorna o instante até o qual o login está bloqueado, quando existir.
+    pub fn locked_until(&self) -> Option<DateTime<Utc>> {
+        self.locked_until
+    }
+
+    /// Indica se o login está bloqueado no instante informado.
+    pub fn is_locked_at(&self, now: DateTime<Utc>) -> bool {
+        self.locked_until.is_some_and(|until| until > now)
+    }
+
+    /// Registra uma nova falha de autenticação.
+    ///
+    /// ### Parâmetros
+    /// - `now`: Instante da falha.
+    /// - `locked_until`: Fim do bloqueio decidido pela camada de serviço (opcional).
+    pub fn record_failure(&mut self, now: DateTime<Utc>, locked_until: Option<DateTime<Utc>>) {
+        self.failed_count = self.failed_count.saturating_add(1);
+        self.last_failed_at = Some(now);
+        self.locked_until = locked_until;
+    }
+
+    /// Reidrata um registro de tentativas já persistido.
+    pub(crate) fn from_persisted(
+        user_id: Uuid,
+        failed_count: u32,
+        last_failed_at: Option<DateTime<Utc>>,
+        locked_until: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            user_id,
+            failed_count,
+            last_failed_at,
+            locked_until,
+        }
+    }
+}
@@ -38,21 +38,67 @@
 //! Representa o cofre de credenciais de um usuário:
 //!
 //! - Isolamento lógico de credenciais
-//! - Associação direta a um único usuário
+//! - Associação direta a um único usuário, o dono
 //! - Contêiner lógico para dados sensíveis
 //!
 //! ---
 //!
+//! ### [`vault_member`]
+//!
+//! Representa o acesso de outro usuário a um cofre compartilhado:
+//!
+//! - Papel do membro (editor ou leitor)
+//! - Chave do cofre embrulhada com a chave pública do membro
+//!
+//! ---
+//!
 //! ### [`credential`]
 //!
 //! Representa uma credencial armazenada no cofre:
 //!
 //! - Nome descritivo
 //! - Dados opcionais (username, URL, notas, senha)
+//! - Pasta opcional dentro do cofre
 //! - Identidade única para referência e persistência
 //!
 //! ---
 //!
+//! ### [`credential_history`]
+//!
+//! Representa uma versão anterior de uma credencial:
+//!
+//! - Senha e, opcionalmente, notas substituídas, ainda cifradas
+//! - Instante em que os valores foram substituídos
+//!
+//! ---
+//!
+//! ### [`folder`]
+//!
+//! Representa uma pasta de credenciais:
+//!
+//! - Hierarquia dentro do cofre (pasta superior opcional)
+//! - Nome único entre as pastas irmãs
+//!
+//! ---
+//!
+//! ### [`tag`]
+//!
+//! Representa uma tag livre do cofre:
+//!
+//! - Nome único no cofre, sem diferenciar maiúsculas
+//! - Associação muitos-para-muitos com credenciais
+//!
+//! ---
+//!
+//! ### [`login_attempt`]
+//!
+//! Representa as tentativas de login malsucedidas de um usuário:
+//!
+//! - Contador de falhas consecutivas
+//! - Instante do bloqueio temporário, quando aplicável
+//!
+//! ---
+//!
 //! ## Princípios de design
 //!
 //! - **Modelo rico, porém passivo**: entidades carregam dados e invariantes,
@@ -81,4 +127,9 @@
 
 pub mod user;
 pub mod vault;
-pub mod credential;
\ No newline at end of file
+pub mod vault_member;
+pub mod credential;
+pub mod credential_history;
+pub mod folder;
+pub mod login_attempt;
+pub mod tag;
\ No newline at end of file
@@ -0,0 +1,116 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+
+/// Tamanho máximo (em bytes) do nome de uma tag.
+const MAX_NAME_LEN: usize = 32;
+
+/// Entidade de domínio que representa uma tag livre do cofre.
+///
+/// Uma credencial pode ter várias tags e uma tag pode marcar várias credenciais.
+/// Os nomes são únicos dentro do cofre, sem diferenciar maiúsculas.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Tag {
+    id: Uuid,
+    vault_id: Uuid,
+    name: String,
+    created_at: DateTime<Utc>,
+}
+
+impl Tag {
+    /// Cria uma nova tag validando o nome.
+    ///
+    /// ### Parâmetros
+    /// - `vault_id`: Identificador do cofre ao qual a tag pertence.
+    /// - `name`: Nome da tag (ex.: "trabalho").
+    ///
+    /// ### Retorno
+    /// - `Ok(Tag)` quando válida.
+    /// - `Err(Error)` quando `vault_id` é nulo ou o nome é inválido.
+    pub fn new(vault_id: Uuid, name: String) -> Result<Self> {
+        if vault_id.is_nil() {
+            return Err(Error::validation(
+                "vault_id",
+                "O ID do cofre não pode ser nulo.",
+            ));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            vault_id,
+            name: validate_name(name)?,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Retorna o ID único da tag.
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Retorna o ID do cofre ao qual a tag pertence.
+    pub fn vault_id(&self) -> Uuid {
+        self.vault_id
+    }
+
+    /// Retorna o nome da tag.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Timestamp de criação.
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    /// Renomeia a tag.
+    pub fn set_name(&mut self, name: String) -> Result<()> {
+        self.name = validate_name(name)?;
+        Ok(())
+    }
+
+    /// Reidrata uma tag já persistida.
+    pub(crate) fn from_persisted(
+        id: Uuid,
+        vault_id: Uuid,
+        name: String,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            vault_id,
+            name,
+            created_at,
+        }
+    }
+}
+
+/// Valida o nome da tag, retornando-o sem espaços nas extremidades.
+///
+/// Vírgulas não são aceitas, pois as interfaces recebem listas de tags
+/// separadas por vírgula.
+fn validate_name(name: String) -> Result<String> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(Error::validation(
+            "name",
+            "O nome da tag não pode ser vazio.",
+        ));
+    }
+    if name.len() > MAX_NAME_LEN {
+        return Err(Error::validation(
+            "name",
+            format!("O nome da tag excede o limite de {MAX_NAME_LEN} caracteres."),
+        ));
+    }
+    if name.contains(',') {
+        return Err(Error::validation(
+            "name",
+            "O nome da tag não pode conter vírgulas.",
+        ));
+    }
+    Ok(name.to_string())
+}
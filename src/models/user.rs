@@ -1,18 +1,30 @@
-use anyhow::{Result, anyhow};
 use chrono::{DateTime, Utc};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::error::{Error, Result};
+
 /// Entidade de domínio que representa um usuário cadastrado no sistema.
 ///
 /// Esta entidade é utilizada pela camada de domínio e serviços de autenticação.
 /// Armazena somente dados essenciais, incluindo o hash da senha (nunca a senha original).
+///
+/// A chave da conta (`account_key_cipher`) é embrulhada com a senha mestre e
+/// protege as chaves de todos os cofres do usuário; contas criadas antes dos
+/// múltiplos cofres não a possuem até o primeiro login após a atualização.
+///
+/// O par de chaves X25519 (`public_key` e `private_key_cipher`, esta cifrada
+/// com a chave da conta) permite que outros usuários compartilhem cofres com
+/// este; contas anteriores aos cofres compartilhados o recebem no login.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct User {
     id: Uuid,
     username: String,
     password_hash: Vec<u8>,
+    account_key_cipher: Option<Vec<u8>>,
+    public_key: Option<String>,
+    private_key_cipher: Option<Vec<u8>>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
 }
@@ -23,17 +35,23 @@ impl User {
     /// ### Parâmetros
     /// - `username`: Nome de identificação pública do usuário.
     /// - `password_hash`: Hash criptográfico resultante do processo de derivação de senha.
+    /// - `account_key_cipher`: Chave da conta embrulhada com a senha mestre.
     ///
     /// ### Retorno
     /// - `Ok(User)`: Instância válida pronta para persistência.
-    /// - `Err(anyhow)`: Caso as regras de domínio sejam violadas.
+    /// - `Err(Error)`: Caso as regras de domínio sejam violadas.
     ///
     /// ### Aplicação
     /// Utilizado no fluxo de **cadastro** ou **criação interna automática** de contas.
     /// Normalmente consumido por `UserService` e posteriormente persistido pelo `UserRepository`.
-    pub fn new(username: String, password_hash: Vec<u8>) -> Result<Self> {
+    pub fn new(
+        username: String,
+        password_hash: Vec<u8>,
+        account_key_cipher: Vec<u8>,
+    ) -> Result<Self> {
         validate_username(&username)?;
         validate_password_hash(&password_hash)?;
+        validate_account_key(&account_key_cipher)?;
 
         let now = Utc::now();
 
@@ -41,6 +59,9 @@ impl User {
             id: Uuid::new_v4(),
             username,
             password_hash,
+            account_key_cipher: Some(account_key_cipher),
+            public_key: None,
+            private_key_cipher: None,
             created_at: now,
             updated_at: now,
         })
@@ -79,6 +100,35 @@ impl User {
         &self.password_hash
     }
 
+    /// Obtém a chave da conta embrulhada com a senha mestre.
+    ///
+    /// ### Retorno
+    /// - `Some(&[u8])` com o cipher AGE da chave.
+    /// - `None` para contas legadas ainda não migradas.
+    ///
+    /// ### Aplicação
+    /// Desembrulhada no login e no desbloqueio para abrir as chaves dos cofres.
+    pub fn account_key_cipher(&self) -> Option<&[u8]> {
+        self.account_key_cipher.as_deref()
+    }
+
+    /// Obtém a chave pública X25519 (`age1...`) usada para compartilhar cofres
+    /// com o usuário.
+    ///
+    /// ### Retorno
+    /// - `None` para contas que ainda não receberam o par de chaves.
+    pub fn public_key(&self) -> Option<&str> {
+        self.public_key.as_deref()
+    }
+
+    /// Obtém a chave privada X25519 cifrada com a chave da conta.
+    ///
+    /// ### Aplicação
+    /// Aberta na sessão para desembrulhar as chaves de cofres compartilhados.
+    pub fn private_key_cipher(&self) -> Option<&[u8]> {
+        self.private_key_cipher.as_deref()
+    }
+
     /// Obtém a data de criação do registro.
     ///
     /// ### Retorno
@@ -101,12 +151,101 @@ impl User {
         self.updated_at
     }
 
+    /// Altera o nome de usuário.
+    ///
+    /// ### Parâmetros
+    /// - `username`: Novo nome, sujeito às mesmas regras do cadastro.
+    ///
+    /// ### Retorno
+    /// - `Ok(())`: quando o nome é válido.
+    /// - `Err(Error)`: quando viola as regras de nome de usuário.
+    ///
+    /// ### Aplicação
+    /// Utilizado na renomeação da conta; a unicidade é verificada pelo serviço.
+    pub fn set_username(&mut self, username: String) -> Result<()> {
+        validate_username(&username)?;
+        self.username = username;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Substitui o hash de senha do usuário.
+    ///
+    /// ### Parâmetros
+    /// - `password_hash`: Novo hash criptográfico no formato PHC.
+    ///
+    /// ### Retorno
+    /// - `Ok(())`: quando o hash é válido.
+    /// - `Err(Error)`: quando o hash está vazio.
+    ///
+    /// ### Aplicação
+    /// Utilizado no fluxo de troca da senha mestre.
+    pub fn set_password_hash(&mut self, password_hash: Vec<u8>) -> Result<()> {
+        validate_password_hash(&password_hash)?;
+        self.password_hash = password_hash;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Substitui a chave da conta embrulhada.
+    ///
+    /// ### Parâmetros
+    /// - `account_key_cipher`: Chave da conta embrulhada com a senha mestre vigente.
+    ///
+    /// ### Retorno
+    /// - `Ok(())`: quando a chave possui conteúdo.
+    /// - `Err(Error)`: quando está vazia.
+    ///
+    /// ### Aplicação
+    /// Utilizado na troca da senha mestre e na migração de contas legadas.
+    pub fn set_account_key_cipher(&mut self, account_key_cipher: Vec<u8>) -> Result<()> {
+        validate_account_key(&account_key_cipher)?;
+        self.account_key_cipher = Some(account_key_cipher);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Define o par de chaves X25519 do usuário.
+    ///
+    /// ### Parâmetros
+    /// - `public_key`: Chave pública (`age1...`).
+    /// - `private_key_cipher`: Chave privada cifrada com a chave da conta.
+    ///
+    /// ### Retorno
+    /// - `Ok(())`: quando a chave pública tem o formato AGE e a privada possui conteúdo.
+    /// - `Err(Error)`: caso contrário.
+    ///
+    /// ### Aplicação
+    /// Utilizado no cadastro e na primeira sessão de contas anteriores aos
+    /// cofres compartilhados.
+    pub fn set_key_pair(&mut self, public_key: String, private_key_cipher: Vec<u8>) -> Result<()> {
+        if !public_key.starts_with("age1") {
+            return Err(Error::validation(
+                "public_key",
+                "A chave pública do usuário é inválida.",
+            ));
+        }
+        if private_key_cipher.is_empty() {
+            return Err(Error::validation(
+                "private_key_cipher",
+                "A chave privada criptografada do usuário não pode ser vazia.",
+            ));
+        }
+
+        self.public_key = Some(public_key);
+        self.private_key_cipher = Some(private_key_cipher);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
     /// Reidrata um `User` a partir de dados já persistidos (sem validação de domínio).
     ///
     /// ### Parâmetros
     /// - `id`: Identificador único previamente armazenado.
     /// - `username`: Nome de usuário conforme registro existente.
     /// - `password_hash`: Hash criptográfico já persistido.
+    /// - `account_key_cipher`: Chave da conta embrulhada (ausente em contas legadas).
+    /// - `public_key`, `private_key_cipher`: Par de chaves X25519 (ausente em contas legadas).
     /// - `created_at`: Timestamp original de criação.
     /// - `updated_at`: Timestamp da última atualização.
     ///
@@ -116,10 +255,14 @@ impl User {
     /// ### Aplicação
     /// Usado por repositórios ao reconstruir a entidade a partir da base
     /// de dados, evitando aplicar novamente validações de criação.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn from_persisted(
         id: Uuid,
         username: String,
         password_hash: Vec<u8>,
+        account_key_cipher: Option<Vec<u8>>,
+        public_key: Option<String>,
+        private_key_cipher: Option<Vec<u8>>,
         created_at: DateTime<Utc>,
         updated_at: DateTime<Utc>,
     ) -> Self {
@@ -127,21 +270,13 @@ impl User {
             id,
             username,
             password_hash,
+            account_key_cipher,
+            public_key,
+            private_key_cipher,
             created_at,
             updated_at,
         }
     }
-
-    pub fn from_session(username: String) -> Self {
-        let now = Utc::now();
-        Self {
-            id: Uuid::new_v4(),
-            username,
-            password_hash: Vec::new(),
-            created_at: now,
-            updated_at: now,
-        }
-    }
 }
 
 /// Valida regras comerciais de nome de usuário.
@@ -151,23 +286,30 @@ impl User {
 ///
 /// ### Retorno
 /// - `Ok(())`: quando válido.
-/// - `Err(anyhow)`: quando viola requisitos mínimos.
+/// - `Err(Error)`: quando viola requisitos mínimos.
 ///
 /// ### Aplicação
-/// Aplicado no fluxo de criação, garantindo consistência do domínio.
+/// Aplicado na criação e na renomeação, garantindo consistência do domínio.
 fn validate_username(username: &str) -> Result<()> {
     if username.trim().is_empty() {
-        return Err(anyhow!("O nome de usuário não pode ser vazio."));
+        return Err(Error::validation(
+            "username",
+            "O nome de usuário não pode ser vazio.",
+        ));
     }
     if username.len() < 3 || username.len() > 32 {
-        return Err(anyhow!(
-            "O nome de usuário deve ter entre 3 e 32 caracteres."
+        return Err(Error::validation(
+            "username",
+            "O nome de usuário deve ter entre 3 e 32 caracteres.",
         ));
     }
 
     let re = Regex::new(r"^[a-zA-Z0-9_.-]+$").unwrap();
     if !re.is_match(username) {
-        return Err(anyhow!("O nome de usuário contém caracteres inválidos."));
+        return Err(Error::validation(
+            "username",
+            "O nome de usuário contém caracteres inválidos.",
+        ));
     }
 
     Ok(())
@@ -180,14 +322,32 @@ fn validate_username(username: &str) -> Result<()> {
 ///
 /// ### Retorno
 /// - `Ok(())`: quando os dados estão íntegros.
-/// - `Err(anyhow)`: quando o valor está vazio.
+/// - `Err(Error)`: quando o valor está vazio.
 ///
 /// ### Aplicação
 /// Utilizado diretamente antes da criação do usuário
 /// para evitar estados inválidos de segurança.
 fn validate_password_hash(password_hash: &[u8]) -> Result<()> {
     if password_hash.is_empty() {
-        return Err(anyhow!("O hash de senha não pode ser vazio."));
+        return Err(Error::validation(
+            "password_hash",
+            "O hash de senha não pode ser vazio.",
+        ));
+    }
+    Ok(())
+}
+
+/// Valida a integridade da chave da conta embrulhada.
+///
+/// ### Retorno
+/// - `Ok(())`: quando possui conteúdo.
+/// - `Err(Error)`: quando está vazia.
+fn validate_account_key(account_key_cipher: &[u8]) -> Result<()> {
+    if account_key_cipher.is_empty() {
+        return Err(Error::validation(
+            "account_key_cipher",
+            "A chave criptografada da conta não pode ser vazia.",
+        ));
     }
     Ok(())
 }
@@ -1,18 +1,30 @@
-use anyhow::{Result, anyhow};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::error::{Error, Result};
+
+/// Nome do cofre criado automaticamente no cadastro do usuário.
+pub const DEFAULT_VAULT_NAME: &str = "Pessoal";
+
+/// Tamanho máximo (em bytes) do nome de um cofre.
+const MAX_NAME_LEN: usize = 64;
+
 /// Entidade de domínio que representa um cofre criptográfico.
 ///
 /// O cofre armazena dados sigilosos pertencentes a um usuário e é protegido
 /// por uma chave criptografada (vault_key_cipher). Esta chave somente deve
 /// ser descriptografada no momento de uso dentro da camada de serviço.
+///
+/// Um usuário pode possuir vários cofres, identificados por nomes únicos
+/// (sem diferenciar maiúsculas) dentro da conta.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Vault {
     id: Uuid,
     user_id: Uuid,
+    name: String,
     vault_key_cipher: Vec<u8>,
+    key_generation: u32,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
 }
@@ -22,17 +34,21 @@ impl Vault {
     ///
     /// ### Parâmetros
     /// - `user_id`: Identificador do usuário proprietário do cofre.
+    /// - `name`: Nome de exibição (ex.: "Pessoal", "Trabalho"); espaços nas
+    ///   extremidades são descartados.
     /// - `vault_key_cipher`: Chave do cofre criptografada (não deve ser exposta).
     ///
     /// ### Retorno
     /// - `Ok(Vault)`: Instância pronta para persistência.
-    /// - `Err(anyhow)`: Quando o `user_id` é inválido ou a chave está vazia.
+    /// - `Err(Error)`: Quando o `user_id` é inválido, o nome é vazio ou longo
+    ///   demais, ou a chave está vazia.
     ///
     /// ### Aplicação
-    /// Utilizado no fluxo de criação inicial do cofre após cadastro do usuário.
-    /// Normalmente consumido por `VaultService` e persistido por `VaultRepository`.
-    pub fn new(user_id: Uuid, vault_key_cipher: Vec<u8>) -> Result<Self> {
+    /// Utilizado no cadastro do usuário (cofre inicial) e na criação de novos
+    /// cofres por `VaultService`; persistido por `VaultRepository`.
+    pub fn new(user_id: Uuid, name: String, vault_key_cipher: Vec<u8>) -> Result<Self> {
         validate_uuid(user_id)?;
+        let name = validate_name(name)?;
         validate_vault_key(&vault_key_cipher)?;
 
         let now = Utc::now();
@@ -40,7 +56,9 @@ impl Vault {
         Ok(Self {
             id: Uuid::new_v4(),
             user_id,
+            name,
             vault_key_cipher,
+            key_generation: 0,
             created_at: now,
             updated_at: now,
         })
@@ -56,11 +74,21 @@ impl Vault {
         self.user_id
     }
 
+    /// Retorna o nome de exibição do cofre.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// Retorna o cipher contendo a chave criptografada do cofre.
     pub fn vault_key_cipher(&self) -> &[u8] {
         &self.vault_key_cipher
     }
 
+    /// Retorna a geração da chave do cofre, incrementada a cada troca da chave.
+    pub fn key_generation(&self) -> u32 {
+        self.key_generation
+    }
+
     /// Retorna a data de criação do registro.
     pub fn created_at(&self) -> DateTime<Utc> {
         self.created_at
@@ -71,12 +99,59 @@ impl Vault {
         self.updated_at
     }
 
+    /// Renomeia o cofre.
+    pub fn set_name(&mut self, name: String) -> Result<()> {
+        self.name = validate_name(name)?;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Substitui a chave criptografada do cofre.
+    ///
+    /// ### Parâmetros
+    /// - `vault_key_cipher`: Mesma chave do cofre, embrulhada com um novo segredo.
+    ///
+    /// ### Retorno
+    /// - `Ok(())`: quando a chave possui conteúdo.
+    /// - `Err(Error)`: quando está vazia.
+    ///
+    /// ### Aplicação
+    /// Utilizado quando a chave passa a ser protegida pela chave da conta, sem
+    /// necessidade de recifrar as credenciais.
+    pub fn set_vault_key_cipher(&mut self, vault_key_cipher: Vec<u8>) -> Result<()> {
+        validate_vault_key(&vault_key_cipher)?;
+        self.vault_key_cipher = vault_key_cipher;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Substitui a chave do cofre por uma nova, avançando a geração.
+    ///
+    /// ### Parâmetros
+    /// - `vault_key_cipher`: Nova chave do cofre, embrulhada com a chave da conta do dono.
+    ///
+    /// ### Retorno
+    /// - `Ok(())`: quando a chave possui conteúdo.
+    /// - `Err(Error)`: quando está vazia.
+    ///
+    /// ### Aplicação
+    /// Utilizado ao revogar um membro. Sessões que abriram o cofre com a
+    /// geração anterior ainda guardam a chave antiga e passam a ter as
+    /// gravações recusadas até abrirem o cofre novamente.
+    pub fn rotate_key(&mut self, vault_key_cipher: Vec<u8>) -> Result<()> {
+        self.set_vault_key_cipher(vault_key_cipher)?;
+        self.key_generation += 1;
+        Ok(())
+    }
+
     /// Reidrata um cofre já persistido sem validações de domínio.
     ///
     /// ### Parâmetros
     /// - `id`: Identificador único do cofre.
     /// - `user_id`: Identificador do usuário associado.
+    /// - `name`: Nome de exibição armazenado.
     /// - `vault_key_cipher`: Chave criptografada armazenada.
+    /// - `key_generation`: Geração da chave armazenada.
     /// - `created_at`: Timestamp de criação persistido.
     /// - `updated_at`: Timestamp da última atualização persistido.
     ///
@@ -88,14 +163,18 @@ impl Vault {
     pub(crate) fn from_persisted(
         id: Uuid,
         user_id: Uuid,
+        name: String,
         vault_key_cipher: Vec<u8>,
+        key_generation: u32,
         created_at: DateTime<Utc>,
         updated_at: DateTime<Utc>,
     ) -> Self {
         Self {
             id,
             user_id,
+            name,
             vault_key_cipher,
+            key_generation,
             created_at,
             updated_at,
         }
@@ -109,14 +188,36 @@ impl Vault {
 ///
 /// ### Retorno
 /// - `Ok(())`: Quando válido.
-/// - `Err(anyhow)`: Quando inválido.
+/// - `Err(Error)`: Quando inválido.
 fn validate_uuid(id: Uuid) -> Result<()> {
     if id.is_nil() {
-        return Err(anyhow!("O ID do usuário não pode ser nulo."));
+        return Err(Error::validation(
+            "user_id",
+            "O ID do usuário não pode ser nulo.",
+        ));
     }
     Ok(())
 }
 
+/// Valida o nome do cofre, retornando-o sem espaços nas extremidades.
+fn validate_name(name: String) -> Result<String> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(Error::validation(
+            "name",
+            "O nome do cofre não pode ser vazio.",
+        ));
+    }
+    if name.len() > MAX_NAME_LEN {
+        return Err(Error::validation(
+            "name",
+            format!("O nome do cofre excede o limite de {MAX_NAME_LEN} caracteres."),
+        ));
+    }
+    Ok(name.to_string())
+}
+
 /// Valida a integridade da chave criptografada do cofre.
 ///
 /// ### Parâmetros
@@ -124,11 +225,12 @@ fn validate_uuid(id: Uuid) -> Result<()> {
 ///
 /// ### Retorno
 /// - `Ok(())`: Quando possui conteúdo.
-/// - `Err(anyhow)`: Quando está vazia.
+/// - `Err(Error)`: Quando está vazia.
 fn validate_vault_key(vault_key_cipher: &[u8]) -> Result<()> {
     if vault_key_cipher.is_empty() {
-        return Err(anyhow!(
-            "A chave criptografada do cofre não pode ser vazia."
+        return Err(Error::validation(
+            "vault_key_cipher",
+            "A chave criptografada do cofre não pode ser vazia.",
         ));
     }
     Ok(())
@@ -0,0 +1,199 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+
+/// Papel de um usuário em um cofre.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VaultRole {
+    /// Criador do cofre: lê, altera e administra membros, nome e exclusão.
+    Owner,
+    /// Lê e altera as credenciais, pastas e tags do cofre.
+    Editor,
+    /// Apenas lê as credenciais do cofre.
+    Viewer,
+}
+
+impl VaultRole {
+    /// Nome persistido na coluna `vault_member.role`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Owner => "owner",
+            Self::Editor => "editor",
+            Self::Viewer => "viewer",
+        }
+    }
+
+    /// Indica se o papel permite alterar o conteúdo do cofre.
+    pub fn can_edit(self) -> bool {
+        self != Self::Viewer
+    }
+}
+
+impl fmt::Display for VaultRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for VaultRole {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "owner" => Ok(Self::Owner),
+            "editor" => Ok(Self::Editor),
+            "viewer" => Ok(Self::Viewer),
+            other => Err(Error::validation(
+                "role",
+                format!("Papel de cofre desconhecido: '{other}'"),
+            )),
+        }
+    }
+}
+
+/// Entidade de domínio que concede a um usuário acesso ao cofre de outro.
+///
+/// A chave do cofre é embrulhada com a chave pública X25519 do membro
+/// (`vault_key_cipher`), de modo que somente ele a abre com a sua chave privada.
+/// O dono do cofre não possui registro de membro: a sua cópia da chave fica no
+/// próprio cofre, embrulhada com a chave da conta.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VaultMember {
+    vault_id: Uuid,
+    user_id: Uuid,
+    role: VaultRole,
+    #[serde(skip_serializing)]
+    vault_key_cipher: Vec<u8>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl VaultMember {
+    /// Cria o vínculo de um membro com o cofre.
+    ///
+    /// ### Parâmetros
+    /// - `vault_id`: Cofre compartilhado.
+    /// - `user_id`: Usuário convidado.
+    /// - `role`: [`VaultRole::Editor`] ou [`VaultRole::Viewer`].
+    /// - `vault_key_cipher`: Chave do cofre embrulhada para o membro.
+    ///
+    /// ### Retorno
+    /// - `Ok(VaultMember)` quando válido.
+    /// - `Err(Error)` para IDs nulos, papel de dono ou chave vazia.
+    pub fn new(
+        vault_id: Uuid,
+        user_id: Uuid,
+        role: VaultRole,
+        vault_key_cipher: Vec<u8>,
+    ) -> Result<Self> {
+        if vault_id.is_nil() || user_id.is_nil() {
+            return Err(Error::validation(
+                "user_id",
+                "Os IDs do cofre e do membro não podem ser nulos.",
+            ));
+        }
+        validate_role(role)?;
+        validate_vault_key(&vault_key_cipher)?;
+
+        let now = Utc::now();
+        Ok(Self {
+            vault_id,
+            user_id,
+            role,
+            vault_key_cipher,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Retorna o ID do cofre compartilhado.
+    pub fn vault_id(&self) -> Uuid {
+        self.vault_id
+    }
+
+    /// Retorna o ID do usuário membro.
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    /// Retorna o papel do membro.
+    pub fn role(&self) -> VaultRole {
+        self.role
+    }
+
+    /// Retorna a chave do cofre embrulhada para o membro.
+    pub fn vault_key_cipher(&self) -> &[u8] {
+        &self.vault_key_cipher
+    }
+
+    /// Timestamp de entrada no cofre.
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    /// Timestamp da última alteração de papel ou chave.
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    /// Altera o papel do membro.
+    pub fn set_role(&mut self, role: VaultRole) -> Result<()> {
+        validate_role(role)?;
+        self.role = role;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Substitui a chave embrulhada, após a rotação da chave do cofre.
+    pub fn set_vault_key_cipher(&mut self, vault_key_cipher: Vec<u8>) -> Result<()> {
+        validate_vault_key(&vault_key_cipher)?;
+        self.vault_key_cipher = vault_key_cipher;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Reidrata um membro já persistido.
+    pub(crate) fn from_persisted(
+        vault_id: Uuid,
+        user_id: Uuid,
+        role: VaultRole,
+        vault_key_cipher: Vec<u8>,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            vault_id,
+            user_id,
+            role,
+            vault_key_cipher,
+            created_at,
+            updated_at,
+        }
+    }
+}
+
+/// O cofre tem um único dono, o seu criador; membros são editores ou leitores.
+fn validate_role(role: VaultRole) -> Result<()> {
+    if role == VaultRole::Owner {
+        return Err(Error::validation(
+            "role",
+            "O cofre tem um único dono; convide membros como editor ou leitor.",
+        ));
+    }
+    Ok(())
+}
+
+/// Valida a integridade da chave embrulhada.
+fn validate_vault_key(vault_key_cipher: &[u8]) -> Result<()> {
+    if vault_key_cipher.is_empty() {
+        return Err(Error::validation(
+            "vault_key_cipher",
+            "A chave criptografada do cofre não pode ser vazia.",
+        ));
+    }
+    Ok(())
+}
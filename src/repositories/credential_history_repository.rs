@@ -0,0 +1,220 @@
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension};
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::infrastructure::database::Database;
+use crate::models::credential_history::CredentialHistoryEntry;
+use crate::repositories::vault_repository::SqliteVaultRepository;
+
+/// Repositório responsável pela persistência do histórico de senhas das credenciais.
+pub trait CredentialHistoryRepository: Send + Sync {
+    /// Registra uma versão anterior e descarta as que excederem a retenção.
+    ///
+    /// ### Parâmetros
+    /// - `entry`: Versão com os valores substituídos.
+    /// - `retention`: Quantidade máxima de versões mantidas para a credencial.
+    /// - `key_generation`: Geração da chave do cofre usada para cifrar a versão.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` em caso de sucesso.
+    /// - `Err(Error)` em falha de gravação (ex.: credencial inexistente) ou, como
+    ///   conflito, quando o cofre da credencial não está mais em `key_generation`;
+    ///   nesses casos nada é alterado.
+    fn record(
+        &self,
+        entry: &CredentialHistoryEntry,
+        retention: usize,
+        key_generation: u32,
+    ) -> Result<()>;
+
+    /// Busca uma versão pelo ID.
+    fn find_by_id(&self, id: Uuid) -> Result<Option<CredentialHistoryEntry>>;
+
+    /// Lista as versões de uma credencial, da mais recente para a mais antiga.
+    fn find_by_credential_id(&self, credential_id: Uuid) -> Result<Vec<CredentialHistoryEntry>>;
+}
+
+/// Implementação de [`CredentialHistoryRepository`] sobre o banco SQLite.
+///
+/// Mantém uma referência ao [`Database`] compartilhado da aplicação e empresta
+/// uma conexão do pool a cada operação.
+#[derive(Clone)]
+pub struct SqliteCredentialHistoryRepository {
+    db: Database,
+}
+
+impl SqliteCredentialHistoryRepository {
+    /// Cria o repositório sobre o banco compartilhado da aplicação.
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// Regrava os campos cifrados de uma versão utilizando a conexão (ou
+    /// transação) informada.
+    ///
+    /// ### Aplicação
+    /// Usado dentro das transações que transferem uma credencial para outro
+    /// cofre e que trocam a chave de um cofre.
+    pub(crate) fn write_ciphers(conn: &Connection, entry: &CredentialHistoryEntry) -> Result<()> {
+        let rows = conn.execute(
+            "UPDATE credential_history SET password_cipher = ?1, notes_cipher = ?2 WHERE id = ?3",
+            (
+                entry.password_cipher(),
+                entry.notes_cipher(),
+                entry.id().as_bytes(),
+            ),
+        )?;
+
+        if rows != 1 {
+            return Err(Error::not_found("Versão do histórico não encontrada"));
+        }
+        Ok(())
+    }
+
+    /// Lê as versões de todas as credenciais de um cofre na conexão (ou
+    /// transação) informada.
+    ///
+    /// ### Aplicação
+    /// Usado dentro da transação que troca a chave de um cofre.
+    pub(crate) fn read_vault(
+        conn: &Connection,
+        vault_id: Uuid,
+    ) -> Result<Vec<CredentialHistoryEntry>> {
+        let mut stmt = conn.prepare(
+            "SELECT h.id, h.credential_id, h.password_cipher, h.notes_cipher, h.created_at
+             FROM credential_history h
+             JOIN credential c ON c.id = h.credential_id
+             WHERE c.vault_id = ?1",
+        )?;
+
+        let mut rows = stmt.query([vault_id.as_bytes()])?;
+        let mut entries = Vec::new();
+        while let Some(row) = rows.next()? {
+            entries.push(Self::row_to_model(row)?);
+        }
+        Ok(entries)
+    }
+
+    /// Converte uma linha SQL em objeto de domínio.
+    fn row_to_model(row: &rusqlite::Row) -> Result<CredentialHistoryEntry> {
+        Ok(CredentialHistoryEntry::from_persisted(
+            Uuid::from_slice(&row.get::<_, Vec<u8>>(0)?)?,
+            Uuid::from_slice(&row.get::<_, Vec<u8>>(1)?)?,
+            row.get(2)?,
+            row.get(3)?,
+            DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)?.with_timezone(&Utc),
+        ))
+    }
+}
+
+impl CredentialHistoryRepository for SqliteCredentialHistoryRepository {
+    fn record(
+        &self,
+        entry: &CredentialHistoryEntry,
+        retention: usize,
+        key_generation: u32,
+    ) -> Result<()> {
+        info!(
+            "Registrando versão anterior da credencial id='{}'",
+            entry.credential_id()
+        );
+
+        let mut conn = self.db.connection()?;
+        let tx = conn.transaction()?;
+
+        let inserted = tx.execute(
+            "INSERT INTO credential_history
+                (id, credential_id, password_cipher, notes_cipher, created_at)
+             SELECT ?1, ?2, ?3, ?4, ?5
+             WHERE EXISTS (SELECT 1 FROM credential c
+                           JOIN vault v ON v.id = c.vault_id
+                           WHERE c.id = ?2 AND v.key_generation = ?6)",
+            (
+                entry.id().as_bytes(),
+                entry.credential_id().as_bytes(),
+                entry.password_cipher(),
+                entry.notes_cipher(),
+                entry.created_at().to_rfc3339(),
+                key_generation,
+            ),
+        )?;
+
+        if inserted != 1 {
+            let vault_id: Option<Vec<u8>> = tx
+                .query_row(
+                    "SELECT vault_id FROM credential WHERE id = ?1",
+                    [entry.credential_id().as_bytes()],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if let Some(vault_id) = vault_id {
+                SqliteVaultRepository::check_key_generation(
+                    &tx,
+                    Uuid::from_slice(&vault_id)?,
+                    key_generation,
+                )?;
+            }
+            return Err(Error::not_found("Credencial não encontrada"));
+        }
+
+        let removed = tx.execute(
+            "DELETE FROM credential_history
+             WHERE credential_id = ?1
+               AND id NOT IN (SELECT id FROM credential_history
+                              WHERE credential_id = ?1
+                              ORDER BY created_at DESC, rowid DESC
+                              LIMIT ?2)",
+            (
+                entry.credential_id().as_bytes(),
+                i64::try_from(retention).unwrap_or(i64::MAX),
+            ),
+        )?;
+        tx.commit()?;
+
+        if removed > 0 {
+            debug!(
+                "{} versão(ões) antiga(s) descartada(s) da credencial id='{}'",
+                removed,
+                entry.credential_id()
+            );
+        }
+        Ok(())
+    }
+
+    fn find_by_id(&self, id: Uuid) -> Result<Option<CredentialHistoryEntry>> {
+        debug!("Consultando versão do histórico id='{}'", id);
+
+        let conn = self.db.connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, credential_id, password_cipher, notes_cipher, created_at
+             FROM credential_history WHERE id = ?1",
+        )?;
+
+        let mut rows = stmt.query([id.as_bytes()])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(Self::row_to_model(row)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn find_by_credential_id(&self, credential_id: Uuid) -> Result<Vec<CredentialHistoryEntry>> {
+        debug!("Listando histórico da credencial id='{}'", credential_id);
+
+        let conn = self.db.connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, credential_id, password_cipher, notes_cipher, created_at
+             FROM credential_history WHERE credential_id = ?1
+             ORDER BY created_at DESC, rowid DESC",
+        )?;
+
+        let mut rows = stmt.query([credential_id.as_bytes()])?;
+        let mut entries = Vec::new();
+        while let Some(row) = rows.next()? {
+            entries.push(Self::row_to_model(row)?);
+        }
+
+        Ok(entries)
+    }
+}
@@ -1,28 +1,409 @@
-use anyhow::Result;
 use chrono::{DateTime, Utc};
+use rusqlite::Connection;
 use std::time::Instant;
 use tracing::{debug, error, info, trace, warn};
 use uuid::Uuid;
 
-use crate::infrastructure::database::get_database_connection;
-use crate::models::credential::Credential;
+use crate::error::{Error, Result};
+use crate::infrastructure::database::Database;
+use crate::models::{credential::Credential, credential_history::CredentialHistoryEntry};
+use crate::repositories::credential_history_repository::SqliteCredentialHistoryRepository;
+use crate::repositories::vault_repository::SqliteVaultRepository;
+use crate::services::credential_search::fold;
 
 /// Repositório responsável por operações de armazenamento e consulta de credenciais.
-pub struct CredentialRepository;
-
-impl CredentialRepository {
+///
+/// As gravações que regravam os campos cifrados recebem a `key_generation` da
+/// chave do cofre usada para cifrá-los e só são aplicadas se o cofre ainda
+/// estiver nessa geração; do contrário retornam um conflito e nada é alterado.
+/// A verificação ocorre no próprio comando de gravação, de modo que uma troca
+/// de chave concorrente não deixa registros cifrados com a chave revogada.
+pub trait CredentialRepository: Send + Sync {
     /// Insere uma nova credencial no banco.
     ///
     /// ### Parâmetros
     /// - `credential`: Referência para a credencial já validada pelo domínio.
+    /// - `key_generation`: Geração da chave do cofre usada para cifrá-la.
     ///
     /// ### Retorno
     /// - `Ok(())` quando persistida com sucesso.
-    /// - `Err(anyhow)` quando ocorre falha de gravação.
+    /// - `Err(Error)` quando ocorre falha de gravação ou a chave do cofre foi trocada.
     ///
     /// ### Aplicação
     /// Usado ao cadastrar uma credencial vinculada a um cofre existente.
-    pub fn create(credential: &Credential) -> Result<()> {
+    fn create(&self, credential: &Credential, key_generation: u32) -> Result<()>;
+
+    /// Insere várias credenciais em uma única transação.
+    ///
+    /// ### Parâmetros
+    /// - `credentials`: Credenciais já validadas pelo domínio.
+    /// - `key_generation`: Geração da chave do cofre usada para cifrá-las.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando todas forem persistidas.
+    /// - `Err(Error)` quando alguma inserção falhar; nesse caso nenhuma é aplicada.
+    ///
+    /// ### Aplicação
+    /// Usado na importação de cofres, para que um arquivo seja importado por inteiro ou não seja importado.
+    fn create_many(&self, credentials: &[Credential], key_generation: u32) -> Result<()>;
+
+    /// Atualiza os dados de uma credencial existente.
+    ///
+    /// ### Parâmetros
+    /// - `credential`: Referência da credencial com dados já atualizados.
+    /// - `key_generation`: Geração da chave do cofre usada nos campos cifrados.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` mesmo quando nenhuma linha for afetada.
+    /// - `Err(Error)` quando ocorre falha de atualização ou a chave do cofre foi trocada.
+    ///
+    /// ### Aplicação
+    /// Usado quando o usuário altera dados como nome, url, notas ou senha.
+    fn update(&self, credential: &Credential, key_generation: u32) -> Result<()>;
+
+    /// Atualiza várias credenciais em uma única transação.
+    ///
+    /// ### Parâmetros
+    /// - `credentials`: Credenciais com dados já atualizados.
+    /// - `key_generation`: Geração da chave do cofre usada nos campos cifrados.
+    ///
+    /// ### Retorno
+    /// - `Ok(usize)` com a quantidade de linhas afetadas.
+    /// - `Err(Error)` quando alguma atualização falhar; nesse caso nenhuma é aplicada.
+    ///
+    /// ### Aplicação
+    /// Usado em operações em lote (ex.: recifragem de credenciais legadas), evitando
+    /// um commit por registro.
+    fn update_many(&self, credentials: &[Credential], key_generation: u32) -> Result<usize>;
+
+    /// Grava uma credencial transferida para outro cofre junto com o seu histórico.
+    ///
+    /// ### Parâmetros
+    /// - `credential`: Credencial com o novo `vault_id` e os campos já recifrados.
+    /// - `history`: Versões anteriores recifradas com a chave do cofre de destino.
+    /// - `key_generation`: Geração da chave do cofre de destino.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando a credencial e todas as versões forem atualizadas.
+    /// - `Err(Error)` em qualquer falha; nesse caso nenhuma alteração é aplicada.
+    ///
+    /// ### Aplicação
+    /// Usado ao mover credenciais entre cofres. Os vínculos com tags do cofre de
+    /// origem são removidos na mesma transação.
+    fn move_to_vault(
+        &self,
+        credential: &Credential,
+        history: &[CredentialHistoryEntry],
+        key_generation: u32,
+    ) -> Result<()>;
+
+    /// Remove uma credencial pelo ID.
+    ///
+    /// ### Parâmetros
+    /// - `id`: ID da credencial a ser removida.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` mesmo que não exista.
+    /// - `Err(Error)` quando falha a operação de remoção.
+    ///
+    /// ### Aplicação
+    /// Usado quando o usuário exclui permanentemente uma credencial do cofre.
+    fn delete(&self, id: Uuid) -> Result<()>;
+
+    /// Busca uma credencial pelo ID.
+    fn find_by_id(&self, id: Uuid) -> Result<Option<Credential>>;
+
+    /// Lista todas as credenciais pertencentes a um cofre, exceto as que estão na lixeira.
+    fn find_all_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<Credential>>;
+
+    /// Pesquisa credenciais cujo nome, usuário ou URL contém o termo informado.
+    ///
+    /// O termo é tratado literalmente (`%` e `_` não são curingas), a comparação
+    /// ignora maiúsculas e acentos e o resultado é ordenado pelo nome.
+    /// Credenciais na lixeira não são retornadas.
+    fn search(&self, vault_id: Uuid, query: &str) -> Result<Vec<Credential>>;
+
+    /// Lista as credenciais de um cofre que estão na lixeira, das removidas mais
+    /// recentemente às mais antigas.
+    fn find_trashed_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<Credential>>;
+
+    /// Exclui permanentemente as credenciais da lixeira removidas antes do instante informado.
+    ///
+    /// ### Parâmetros
+    /// - `vault_id`: Cofre cuja lixeira será esvaziada.
+    /// - `before`: Limite; credenciais movidas para a lixeira antes dele são
+    ///   excluídas (`None` exclui todas).
+    ///
+    /// ### Retorno
+    /// - `Ok(usize)` com a quantidade de credenciais excluídas.
+    /// - `Err(Error)` quando falha a operação de remoção.
+    fn purge_trashed(&self, vault_id: Uuid, before: Option<DateTime<Utc>>) -> Result<usize>;
+}
+
+/// Implementação de [`CredentialRepository`] sobre o banco SQLite.
+///
+/// Mantém uma referência ao [`Database`] compartilhado da aplicação e empresta
+/// uma conexão do pool a cada operação.
+#[derive(Clone)]
+pub struct SqliteCredentialRepository {
+    db: Database,
+}
+
+impl SqliteCredentialRepository {
+    /// Cria o repositório sobre o banco compartilhado da aplicação.
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// Executa o INSERT de uma credencial na conexão (ou transação) informada,
+    /// condicionado à geração da chave do cofre.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando a credencial for inserida.
+    /// - `Err(Error)` em falha de gravação ou, como conflito, quando o cofre
+    ///   não está mais em `key_generation`.
+    fn write_insert(conn: &Connection, credential: &Credential, key_generation: u32) -> Result<()> {
+        let rows = conn.execute(
+            "INSERT INTO credential
+                (id, vault_id, name, username, url, notes, password_cipher, totp_cipher,
+                 created_at, updated_at, folder_id, deleted_at, password_changed_at)
+             SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13
+             WHERE EXISTS (SELECT 1 FROM vault WHERE id = ?2 AND key_generation = ?14)",
+            (
+                credential.id().as_bytes(),
+                credential.vault_id().as_bytes(),
+                credential.name(),
+                credential.username(),
+                credential.url(),
+                credential.notes(),
+                credential.password_cipher(),
+                credential.totp_cipher(),
+                credential.created_at().to_rfc3339(),
+                credential.updated_at().to_rfc3339(),
+                credential.folder_id().map(|id| id.into_bytes()),
+                credential.deleted_at().map(|at| at.to_rfc3339()),
+                credential.password_changed_at().to_rfc3339(),
+                key_generation,
+            ),
+        )?;
+
+        if rows != 1 {
+            SqliteVaultRepository::check_key_generation(
+                conn,
+                credential.vault_id(),
+                key_generation,
+            )?;
+            return Err(Error::not_found("Cofre da credencial não encontrado"));
+        }
+        Ok(())
+    }
+
+    /// Executa o UPDATE de uma credencial na conexão (ou transação) informada,
+    /// condicionado à geração da chave do cofre.
+    ///
+    /// ### Retorno
+    /// - `Ok(usize)` com a quantidade de linhas afetadas (zero quando a
+    ///   credencial não existe).
+    /// - `Err(Error)` em falha de gravação ou, como conflito, quando o cofre
+    ///   não está mais em `key_generation`.
+    fn write_update(
+        conn: &Connection,
+        credential: &Credential,
+        key_generation: u32,
+    ) -> Result<usize> {
+        let rows = conn.execute(
+            "UPDATE credential
+                SET name = ?1, username = ?2, url = ?3, notes = ?4, password_cipher = ?5,
+                    totp_cipher = ?6, folder_id = ?7, deleted_at = ?8, updated_at = ?9,
+                    vault_id = ?10, password_changed_at = ?11
+             WHERE id = ?12
+               AND EXISTS (SELECT 1 FROM vault WHERE id = ?10 AND key_generation = ?13)",
+            (
+                credential.name(),
+                credential.username(),
+                credential.url(),
+                credential.notes(),
+                credential.password_cipher(),
+                credential.totp_cipher(),
+                credential.folder_id().map(|id| id.into_bytes()),
+                credential.deleted_at().map(|at| at.to_rfc3339()),
+                Utc::now().to_rfc3339(),
+                credential.vault_id().as_bytes(),
+                credential.password_changed_at().to_rfc3339(),
+                credential.id().as_bytes(),
+                key_generation,
+            ),
+        )?;
+
+        if rows == 0 {
+            SqliteVaultRepository::check_key_generation(
+                conn,
+                credential.vault_id(),
+                key_generation,
+            )?;
+        }
+        Ok(rows)
+    }
+
+    /// Regrava somente os campos cifrados de uma credencial, preservando o
+    /// timestamp de modificação, na conexão (ou transação) informada.
+    ///
+    /// ### Aplicação
+    /// Usado por [`super::vault_member_repository::SqliteVaultMemberRepository`]
+    /// dentro da transação que troca a chave de um cofre, depois de
+    /// [`Self::read_vault`].
+    pub(crate) fn write_ciphers(conn: &Connection, credential: &Credential) -> Result<()> {
+        let rows = conn.execute(
+            "UPDATE credential SET notes = ?1, password_cipher = ?2, totp_cipher = ?3
+             WHERE id = ?4",
+            (
+                credential.notes(),
+                credential.password_cipher(),
+                credential.totp_cipher(),
+                credential.id().as_bytes(),
+            ),
+        )?;
+
+        if rows != 1 {
+            return Err(Error::not_found("Credencial não encontrada"));
+        }
+        Ok(())
+    }
+
+    /// Lê todas as credenciais de um cofre, inclusive as da lixeira, na
+    /// conexão (ou transação) informada.
+    ///
+    /// ### Aplicação
+    /// Usado dentro da transação que troca a chave de um cofre, para que os
+    /// registros recifrados sejam exatamente os gravados até ali.
+    pub(crate) fn read_vault(conn: &Connection, vault_id: Uuid) -> Result<Vec<Credential>> {
+        let mut stmt = conn.prepare(
+            "SELECT id, vault_id, name, username, url, notes, password_cipher, totp_cipher,
+                    created_at, updated_at, folder_id, deleted_at, password_changed_at
+             FROM credential WHERE vault_id = ?1",
+        )?;
+
+        let rows = stmt.query_map([vault_id.as_bytes()], Self::row_to_model)?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    /// Converte uma linha SQL em objeto de domínio.
+    ///
+    /// ### Aplicação
+    /// Uso interno do repositório durante consultas.
+    fn row_to_model(row: &rusqlite::Row) -> rusqlite::Result<Credential> {
+        trace!("Convertendo linha SQL em Credential ...");
+
+        let id = Uuid::from_slice(&row.get::<_, Vec<u8>>(0)?).map_err(|e| {
+            error!("Falha ao converter UUID(id) a partir do banco: {}", e);
+            rusqlite::Error::FromSqlConversionFailure(16, rusqlite::types::Type::Blob, Box::new(e))
+        })?;
+
+        let vault_id = Uuid::from_slice(&row.get::<_, Vec<u8>>(1)?).map_err(|e| {
+            error!("Falha ao converter UUID(vault_id) a partir do banco: {}", e);
+            rusqlite::Error::FromSqlConversionFailure(16, rusqlite::types::Type::Blob, Box::new(e))
+        })?;
+
+        let created_at = DateTime::parse_from_rfc3339(&row.get::<_, String>(8)?)
+            .map_err(|e| {
+                error!(
+                    "Falha ao converter created_at (RFC3339) a partir do banco: {}",
+                    e
+                );
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })?
+            .with_timezone(&Utc);
+
+        let updated_at = DateTime::parse_from_rfc3339(&row.get::<_, String>(9)?)
+            .map_err(|e| {
+                error!(
+                    "Falha ao converter updated_at (RFC3339) a partir do banco: {}",
+                    e
+                );
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })?
+            .with_timezone(&Utc);
+
+        let folder_id = row
+            .get::<_, Option<Vec<u8>>>(10)?
+            .map(|bytes| Uuid::from_slice(&bytes))
+            .transpose()
+            .map_err(|e| {
+                error!(
+                    "Falha ao converter UUID(folder_id) a partir do banco: {}",
+                    e
+                );
+                rusqlite::Error::FromSqlConversionFailure(
+                    16,
+                    rusqlite::types::Type::Blob,
+                    Box::new(e),
+                )
+            })?;
+
+        let deleted_at = row
+            .get::<_, Option<String>>(11)?
+            .map(|value| DateTime::parse_from_rfc3339(&value))
+            .transpose()
+            .map_err(|e| {
+                error!(
+                    "Falha ao converter deleted_at (RFC3339) a partir do banco: {}",
+                    e
+                );
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })?
+            .map(|at| at.with_timezone(&Utc));
+
+        let password_changed_at = DateTime::parse_from_rfc3339(&row.get::<_, String>(12)?)
+            .map_err(|e| {
+                error!(
+                    "Falha ao converter password_changed_at (RFC3339) a partir do banco: {}",
+                    e
+                );
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })?
+            .with_timezone(&Utc);
+
+        debug!(
+            "Linha convertida em Credential {{ id='{}', vault_id='{}' }}",
+            id, vault_id
+        );
+
+        Ok(Credential::from_persisted(
+            id,
+            vault_id,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+            row.get(6)?,
+            row.get(7)?,
+            folder_id,
+            deleted_at,
+            created_at,
+            updated_at,
+            password_changed_at,
+        ))
+    }
+}
+
+impl CredentialRepository for SqliteCredentialRepository {
+    fn create(&self, credential: &Credential, key_generation: u32) -> Result<()> {
         let start = Instant::now();
         info!(
             "Iniciando criação da credencial: id='{}', vault_id='{}', name='{}'",
@@ -31,7 +412,7 @@ impl CredentialRepository {
             credential.name()
         );
 
-        let conn = match get_database_connection() {
+        let conn = match self.db.connection() {
             Ok(c) => c,
             Err(err) => {
                 error!("Falha ao abrir conexão com banco na criação: {}", err);
@@ -41,29 +422,13 @@ impl CredentialRepository {
 
         trace!("Executando INSERT na tabela 'credential' ...");
 
-        let result = conn.execute(
-            "INSERT INTO credential
-                (id, vault_id, name, username, url, notes, password_cipher, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-            (
-                credential.id().as_bytes(),
-                credential.vault_id().as_bytes(),
-                credential.name(),
-                credential.username(),
-                credential.url(),
-                credential.notes(),
-                credential.password_cipher(),
-                credential.created_at().to_rfc3339(),
-                credential.updated_at().to_rfc3339(),
-            ),
-        );
+        let result = Self::write_insert(&conn, credential, key_generation);
 
         match result {
-            Ok(rows) => {
+            Ok(()) => {
                 info!(
-                    "Credencial criada com sucesso id='{}' | linhas inseridas={} | tempo={}ms",
+                    "Credencial criada com sucesso id='{}' | tempo={}ms",
                     credential.id(),
-                    rows,
                     start.elapsed().as_millis()
                 );
                 debug!(
@@ -81,23 +446,32 @@ impl CredentialRepository {
                     err,
                     start.elapsed().as_millis()
                 );
-                Err(err.into())
+                Err(err)
             }
         }
     }
 
-    /// Atualiza os dados de uma credencial existente.
-    ///
-    /// ### Parâmetros
-    /// - `credential`: Referência da credencial com dados já atualizados.
-    ///
-    /// ### Retorno
-    /// - `Ok(())` mesmo quando nenhuma linha for afetada.
-    /// - `Err(anyhow)` quando ocorre falha de atualização.
-    ///
-    /// ### Aplicação
-    /// Usado quando o usuário altera dados como nome, url, notas ou senha.
-    pub fn update(credential: &Credential) -> Result<()> {
+    fn create_many(&self, credentials: &[Credential], key_generation: u32) -> Result<()> {
+        let start = Instant::now();
+        info!("Inserindo {} credencial(is) em lote", credentials.len());
+
+        let mut conn = self.db.connection()?;
+        let tx = conn.transaction()?;
+
+        for credential in credentials {
+            Self::write_insert(&tx, credential, key_generation)?;
+        }
+        tx.commit()?;
+
+        info!(
+            "Lote de credenciais inserido | linhas inseridas={} | tempo={}ms",
+            credentials.len(),
+            start.elapsed().as_millis()
+        );
+        Ok(())
+    }
+
+    fn update(&self, credential: &Credential, key_generation: u32) -> Result<()> {
         let start = Instant::now();
         info!(
             "Atualizando credencial id='{}' name='{}'",
@@ -105,7 +479,7 @@ impl CredentialRepository {
             credential.name()
         );
 
-        let conn = match get_database_connection() {
+        let conn = match self.db.connection() {
             Ok(c) => c,
             Err(err) => {
                 error!("Falha ao abrir conexão com banco na atualização: {}", err);
@@ -113,24 +487,9 @@ impl CredentialRepository {
             }
         };
 
-        let now = Utc::now();
-
         trace!("Executando UPDATE na tabela 'credential' ...");
 
-        let result = conn.execute(
-            "UPDATE credential
-                SET name = ?1, username = ?2, url = ?3, notes = ?4, password_cipher = ?5, updated_at = ?6
-             WHERE id = ?7",
-            (
-                credential.name(),
-                credential.username(),
-                credential.url(),
-                credential.notes(),
-                credential.password_cipher(),
-                now.to_rfc3339(),
-                credential.id().as_bytes(),
-            ),
-        );
+        let result = Self::write_update(&conn, credential, key_generation);
 
         match result {
             Ok(rows) => {
@@ -157,27 +516,74 @@ impl CredentialRepository {
                     err,
                     start.elapsed().as_millis()
                 );
-                Err(err.into())
+                Err(err)
             }
         }
     }
 
-    /// Remove uma credencial pelo ID.
-    ///
-    /// ### Parâmetros
-    /// - `id`: ID da credencial a ser removida.
-    ///
-    /// ### Retorno
-    /// - `Ok(())` mesmo que não exista.
-    /// - `Err(anyhow)` quando falha a operação de remoção.
-    ///
-    /// ### Aplicação
-    /// Usado quando o usuário exclui permanentemente uma credencial do cofre.
-    pub fn delete(id: Uuid) -> Result<()> {
+    fn update_many(&self, credentials: &[Credential], key_generation: u32) -> Result<usize> {
+        let start = Instant::now();
+        info!("Atualizando {} credencial(is) em lote", credentials.len());
+
+        let mut conn = self.db.connection()?;
+        let tx = conn.transaction()?;
+
+        let mut rows = 0;
+        for credential in credentials {
+            rows += Self::write_update(&tx, credential, key_generation)?;
+        }
+        tx.commit()?;
+
+        info!(
+            "Lote de credenciais atualizado | linhas afetadas={} | tempo={}ms",
+            rows,
+            start.elapsed().as_millis()
+        );
+        Ok(rows)
+    }
+
+    fn move_to_vault(
+        &self,
+        credential: &Credential,
+        history: &[CredentialHistoryEntry],
+        key_generation: u32,
+    ) -> Result<()> {
+        let start = Instant::now();
+        info!(
+            "Transferindo credencial id='{}' para vault_id='{}'",
+            credential.id(),
+            credential.vault_id()
+        );
+
+        let mut conn = self.db.connection()?;
+        let tx = conn.transaction()?;
+
+        if Self::write_update(&tx, credential, key_generation)? != 1 {
+            return Err(Error::not_found("Credencial não encontrada"));
+        }
+        tx.execute(
+            "DELETE FROM credential_tag WHERE credential_id = ?1",
+            [credential.id().as_bytes()],
+        )?;
+        for entry in history {
+            SqliteCredentialHistoryRepository::write_ciphers(&tx, entry)?;
+        }
+        tx.commit()?;
+
+        info!(
+            "Credencial transferida id='{}' | versões={} | tempo={}ms",
+            credential.id(),
+            history.len(),
+            start.elapsed().as_millis()
+        );
+        Ok(())
+    }
+
+    fn delete(&self, id: Uuid) -> Result<()> {
         let start = Instant::now();
         info!("Removendo credencial id='{}'", id);
 
-        let conn = match get_database_connection() {
+        let conn = match self.db.connection() {
             Ok(c) => c,
             Err(err) => {
                 error!("Falha ao abrir conexão com banco na remoção: {}", err);
@@ -219,13 +625,13 @@ impl CredentialRepository {
         }
     }
 
-    /// Busca uma credencial pelo ID.
-    pub fn find_by_id(id: Uuid) -> Result<Option<Credential>> {
+    fn find_by_id(&self, id: Uuid) -> Result<Option<Credential>> {
         debug!("Consultando credencial por id='{}'", id);
 
-        let conn = get_database_connection()?;
+        let conn = self.db.connection()?;
         let mut stmt = conn.prepare(
-            "SELECT id, vault_id, name, username, url, notes, password_cipher, created_at, updated_at
+            "SELECT id, vault_id, name, username, url, notes, password_cipher, totp_cipher,
+                    created_at, updated_at, folder_id, deleted_at, password_changed_at
              FROM credential WHERE id = ?1",
         )?;
 
@@ -239,17 +645,17 @@ impl CredentialRepository {
         Ok(None)
     }
 
-    /// Lista todas as credenciais pertencentes a um cofre.
-    pub fn find_all_by_vault_id(vault_id: Uuid) -> Result<Vec<Credential>> {
+    fn find_all_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<Credential>> {
         debug!("Listando credenciais para vault_id='{}'", vault_id);
 
-        let conn = get_database_connection()?;
+        let conn = self.db.connection()?;
         let mut stmt = conn.prepare(
-            "SELECT id, vault_id, name, username, url, notes, password_cipher, created_at, updated_at
-             FROM credential WHERE vault_id = ?1",
+            "SELECT id, vault_id, name, username, url, notes, password_cipher, totp_cipher,
+                    created_at, updated_at, folder_id, deleted_at, password_changed_at
+             FROM credential WHERE vault_id = ?1 AND deleted_at IS NULL",
         )?;
 
-        let rows = stmt.query_map([vault_id.as_bytes()], |row| Self::row_to_model(row))?;
+        let rows = stmt.query_map([vault_id.as_bytes()], Self::row_to_model)?;
         let list: rusqlite::Result<Vec<_>> = rows.collect();
 
         info!(
@@ -260,26 +666,31 @@ impl CredentialRepository {
         Ok(list?)
     }
 
-    /// Pesquisa credenciais pelo nome.
-    pub fn search(vault_id: Uuid, query: &str) -> Result<Vec<Credential>> {
+    fn search(&self, vault_id: Uuid, query: &str) -> Result<Vec<Credential>> {
         debug!(
             "Pesquisando credenciais: vault_id='{}', termo='{}'",
             vault_id, query
         );
 
-        let conn = get_database_connection()?;
-        let pattern = format!("%{}%", query);
+        let conn = self.db.connection()?;
+        let pattern = format!("%{}%", escape_like(&fold(query)));
 
         let mut stmt = conn.prepare(
-            "SELECT id, vault_id, name, username, url, notes, password_cipher, created_at, updated_at
+            "SELECT id, vault_id, name, username, url, notes, password_cipher, totp_cipher,
+                    created_at, updated_at, folder_id, deleted_at, password_changed_at
              FROM credential
-             WHERE vault_id = ?1 AND name LIKE ?2
+             WHERE vault_id = ?1
+               AND deleted_at IS NULL
+               AND (fold(name) LIKE ?2 ESCAPE '\\'
+                    OR fold(username) LIKE ?2 ESCAPE '\\'
+                    OR fold(url) LIKE ?2 ESCAPE '\\')
              ORDER BY name ASC",
         )?;
 
-        let rows = stmt.query_map(rusqlite::params![vault_id.as_bytes(), pattern], |row| {
-            Self::row_to_model(row)
-        })?;
+        let rows = stmt.query_map(
+            rusqlite::params![vault_id.as_bytes(), pattern],
+            Self::row_to_model,
+        )?;
 
         let list: rusqlite::Result<Vec<_>> = rows.collect();
 
@@ -292,66 +703,60 @@ impl CredentialRepository {
         Ok(list?)
     }
 
-    /// Converte uma linha SQL em objeto de domínio.
-    ///
-    /// ### Aplicação
-    /// Uso interno do repositório durante consultas.
-    fn row_to_model(row: &rusqlite::Row) -> rusqlite::Result<Credential> {
-        trace!("Convertendo linha SQL em Credential ...");
+    fn find_trashed_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<Credential>> {
+        debug!("Listando lixeira para vault_id='{}'", vault_id);
 
-        let id = Uuid::from_slice(&row.get::<_, Vec<u8>>(0)?).map_err(|e| {
-            error!("Falha ao converter UUID(id) a partir do banco: {}", e);
-            rusqlite::Error::FromSqlConversionFailure(16, rusqlite::types::Type::Blob, Box::new(e))
-        })?;
+        let conn = self.db.connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, vault_id, name, username, url, notes, password_cipher, totp_cipher,
+                    created_at, updated_at, folder_id, deleted_at, password_changed_at
+             FROM credential
+             WHERE vault_id = ?1 AND deleted_at IS NOT NULL
+             ORDER BY deleted_at DESC",
+        )?;
 
-        let vault_id = Uuid::from_slice(&row.get::<_, Vec<u8>>(1)?).map_err(|e| {
-            error!("Falha ao converter UUID(vault_id) a partir do banco: {}", e);
-            rusqlite::Error::FromSqlConversionFailure(16, rusqlite::types::Type::Blob, Box::new(e))
-        })?;
+        let rows = stmt.query_map([vault_id.as_bytes()], Self::row_to_model)?;
+        let list: rusqlite::Result<Vec<_>> = rows.collect();
 
-        let created_at = DateTime::parse_from_rfc3339(&row.get::<_, String>(7)?)
-            .map_err(|e| {
-                error!(
-                    "Falha ao converter created_at (RFC3339) a partir do banco: {}",
-                    e
-                );
-                rusqlite::Error::FromSqlConversionFailure(
-                    0,
-                    rusqlite::types::Type::Text,
-                    Box::new(e),
-                )
-            })?
-            .with_timezone(&Utc);
+        Ok(list?)
+    }
 
-        let updated_at = DateTime::parse_from_rfc3339(&row.get::<_, String>(8)?)
-            .map_err(|e| {
-                error!(
-                    "Falha ao converter updated_at (RFC3339) a partir do banco: {}",
-                    e
-                );
-                rusqlite::Error::FromSqlConversionFailure(
-                    0,
-                    rusqlite::types::Type::Text,
-                    Box::new(e),
-                )
-            })?
-            .with_timezone(&Utc);
+    fn purge_trashed(&self, vault_id: Uuid, before: Option<DateTime<Utc>>) -> Result<usize> {
+        let start = Instant::now();
+        let before = before.map(|at| at.to_rfc3339());
+        info!(
+            "Esvaziando lixeira vault_id='{}' (removidas antes de {})",
+            vault_id,
+            before.as_deref().unwrap_or("agora")
+        );
 
-        debug!(
-            "Linha convertida em Credential {{ id='{}', vault_id='{}' }}",
-            id, vault_id
+        let conn = self.db.connection()?;
+        let rows = conn.execute(
+            "DELETE FROM credential
+             WHERE vault_id = ?1
+               AND deleted_at IS NOT NULL
+               AND (?2 IS NULL OR deleted_at < ?2)",
+            (vault_id.as_bytes(), before),
+        )?;
+
+        info!(
+            "Lixeira esvaziada | linhas removidas={} | tempo={}ms",
+            rows,
+            start.elapsed().as_millis()
         );
+        Ok(rows)
+    }
+}
 
-        Ok(Credential::from_persisted(
-            id,
-            vault_id,
-            row.get(2)?,
-            row.get(3)?,
-            row.get(4)?,
-            row.get(5)?,
-            row.get(6)?,
-            created_at,
-            updated_at,
-        ))
+/// Escapa os curingas do `LIKE` (`%`, `_`) e o próprio caractere de escape, para
+/// que o termo seja comparado literalmente (usar com `ESCAPE '\'`).
+fn escape_like(query: &str) -> String {
+    let mut escaped = String::with_capacity(query.len());
+    for c in query.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
     }
+    escaped
 }
@@ -0,0 +1,187 @@
+use chrono::{DateTime, Utc};
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::infrastructure::database::Database;
+use crate::models::folder::Folder;
+
+/// Repositório responsável pela persistência das pastas de credenciais.
+pub trait FolderRepository: Send + Sync {
+    /// Insere uma nova pasta.
+    ///
+    /// ### Parâmetros
+    /// - `folder`: Pasta já validada pelo domínio.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` em caso de sucesso.
+    /// - `Err(Error)` em falha de gravação (ex.: cofre ou pasta superior inexistente).
+    fn create(&self, folder: &Folder) -> Result<()>;
+
+    /// Atualiza o nome e a pasta superior de uma pasta existente.
+    ///
+    /// ### Parâmetros
+    /// - `folder`: Pasta com os dados já atualizados.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` mesmo quando nenhuma linha for afetada.
+    /// - `Err(Error)` em falha de gravação.
+    fn update(&self, folder: &Folder) -> Result<()>;
+
+    /// Remove uma pasta, levando seu conteúdo para a pasta superior.
+    ///
+    /// ### Parâmetros
+    /// - `folder`: Pasta a ser removida.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` mesmo que não exista.
+    /// - `Err(Error)` em falha de gravação; nesse caso nada é alterado.
+    ///
+    /// ### Aplicação
+    /// Subpastas e credenciais da pasta passam para a pasta superior (ou para a
+    /// raiz) na mesma transação da remoção: nenhuma credencial é apagada.
+    fn delete(&self, folder: &Folder) -> Result<()>;
+
+    /// Busca uma pasta pelo ID.
+    fn find_by_id(&self, id: Uuid) -> Result<Option<Folder>>;
+
+    /// Lista todas as pastas de um cofre, ordenadas pelo nome.
+    fn find_all_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<Folder>>;
+}
+
+/// Implementação de [`FolderRepository`] sobre o banco SQLite.
+///
+/// Mantém uma referência ao [`Database`] compartilhado da aplicação e empresta
+/// uma conexão do pool a cada operação.
+#[derive(Clone)]
+pub struct SqliteFolderRepository {
+    db: Database,
+}
+
+impl SqliteFolderRepository {
+    /// Cria o repositório sobre o banco compartilhado da aplicação.
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// Converte uma linha SQL em objeto de domínio.
+    fn row_to_model(row: &rusqlite::Row) -> Result<Folder> {
+        Ok(Folder::from_persisted(
+            Uuid::from_slice(&row.get::<_, Vec<u8>>(0)?)?,
+            Uuid::from_slice(&row.get::<_, Vec<u8>>(1)?)?,
+            row.get::<_, Option<Vec<u8>>>(2)?
+                .map(|bytes| Uuid::from_slice(&bytes))
+                .transpose()?,
+            row.get(3)?,
+            parse_timestamp(&row.get::<_, String>(4)?)?,
+            parse_timestamp(&row.get::<_, String>(5)?)?,
+        ))
+    }
+}
+
+impl FolderRepository for SqliteFolderRepository {
+    fn create(&self, folder: &Folder) -> Result<()> {
+        info!(
+            "Criando pasta id='{}' vault_id='{}' name='{}'",
+            folder.id(),
+            folder.vault_id(),
+            folder.name()
+        );
+
+        let conn = self.db.connection()?;
+        conn.execute(
+            "INSERT INTO folder (id, vault_id, parent_id, name, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            (
+                folder.id().as_bytes(),
+                folder.vault_id().as_bytes(),
+                folder.parent_id().map(|id| id.into_bytes()),
+                folder.name(),
+                folder.created_at().to_rfc3339(),
+                folder.updated_at().to_rfc3339(),
+            ),
+        )?;
+
+        Ok(())
+    }
+
+    fn update(&self, folder: &Folder) -> Result<()> {
+        info!("Atualizando pasta id='{}'", folder.id());
+
+        let conn = self.db.connection()?;
+        conn.execute(
+            "UPDATE folder SET parent_id = ?1, name = ?2, updated_at = ?3 WHERE id = ?4",
+            (
+                folder.parent_id().map(|id| id.into_bytes()),
+                folder.name(),
+                folder.updated_at().to_rfc3339(),
+                folder.id().as_bytes(),
+            ),
+        )?;
+
+        Ok(())
+    }
+
+    fn delete(&self, folder: &Folder) -> Result<()> {
+        info!("Removendo pasta id='{}'", folder.id());
+
+        let parent = folder.parent_id().map(|id| id.into_bytes());
+        let id = folder.id().into_bytes();
+
+        let mut conn = self.db.connection()?;
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "UPDATE folder SET parent_id = ?1 WHERE parent_id = ?2",
+            (parent, id),
+        )?;
+        tx.execute(
+            "UPDATE credential SET folder_id = ?1 WHERE folder_id = ?2",
+            (parent, id),
+        )?;
+        tx.execute("DELETE FROM folder WHERE id = ?1", [id])?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    fn find_by_id(&self, id: Uuid) -> Result<Option<Folder>> {
+        debug!("Consultando pasta por id='{}'", id);
+
+        let conn = self.db.connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, vault_id, parent_id, name, created_at, updated_at
+             FROM folder WHERE id = ?1",
+        )?;
+
+        let mut rows = stmt.query([id.as_bytes()])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(Self::row_to_model(row)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn find_all_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<Folder>> {
+        debug!("Listando pastas para vault_id='{}'", vault_id);
+
+        let conn = self.db.connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, vault_id, parent_id, name, created_at, updated_at
+             FROM folder WHERE vault_id = ?1
+             ORDER BY name ASC",
+        )?;
+
+        let mut rows = stmt.query([vault_id.as_bytes()])?;
+        let mut folders = Vec::new();
+        while let Some(row) = rows.next()? {
+            folders.push(Self::row_to_model(row)?);
+        }
+
+        Ok(folders)
+    }
+}
+
+/// Converte um timestamp RFC3339 vindo do banco.
+fn parse_timestamp(value: &str) -> Result<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(value)?.with_timezone(&Utc))
+}
@@ -0,0 +1,935 @@
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::models::{
+    credential::Credential, credential_history::CredentialHistoryEntry, folder::Folder,
+    login_attempt::LoginAttempt, tag::Tag, user::User, vault::Vault, vault_member::VaultMember,
+};
+use crate::repositories::{
+    credential_history_repository::CredentialHistoryRepository,
+    credential_repository::CredentialRepository,
+    folder_repository::FolderRepository,
+    login_attempt_repository::LoginAttemptRepository,
+    tag_repository::TagRepository,
+    user_repository::UserRepository,
+    vault_member_repository::{ResealFn, VaultMemberRepository},
+    vault_repository::{STALE_KEY_MESSAGE, VaultRepository},
+};
+use crate::services::credential_search::fold;
+
+/// Tabelas mantidas em memória, na ordem de inserção.
+#[derive(Default)]
+struct Tables {
+    users: Vec<User>,
+    vaults: Vec<Vault>,
+    vault_members: Vec<VaultMember>,
+    credentials: Vec<Credential>,
+    /// Histórico de senhas, em ordem de inserção.
+    history: Vec<CredentialHistoryEntry>,
+    login_attempts: Vec<LoginAttempt>,
+    folders: Vec<Folder>,
+    tags: Vec<Tag>,
+    /// Vínculos `(credencial, tag)`.
+    credential_tags: Vec<(Uuid, Uuid)>,
+}
+
+impl Tables {
+    /// Remove um cofre e, em cascata, o seu conteúdo e os seus membros.
+    fn delete_vault(&mut self, id: Uuid) {
+        let removed: Vec<Uuid> = self
+            .credentials
+            .iter()
+            .filter(|c| c.vault_id() == id)
+            .map(|c| c.id())
+            .collect();
+        let tags: Vec<Uuid> = self
+            .tags
+            .iter()
+            .filter(|t| t.vault_id() == id)
+            .map(|t| t.id())
+            .collect();
+
+        self.credentials.retain(|c| c.vault_id() != id);
+        self.history
+            .retain(|h| !removed.contains(&h.credential_id()));
+        self.credential_tags
+            .retain(|(credential, tag)| !removed.contains(credential) && !tags.contains(tag));
+        self.tags.retain(|t| t.vault_id() != id);
+        self.folders.retain(|f| f.vault_id() != id);
+        self.vault_members.retain(|m| m.vault_id() != id);
+        self.vaults.retain(|v| v.id() != id);
+    }
+
+    /// Confere se a pasta de uma credencial existe (chave estrangeira `folder_id`).
+    fn check_folder(&self, credential: &Credential) -> Result<()> {
+        match credential.folder_id() {
+            Some(id) if !self.folders.iter().any(|f| f.id() == id) => {
+                Err(Error::not_found("Pasta da credencial não encontrada"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Confere se o cofre ainda está na geração de chave informada (condição
+    /// `key_generation = ?` das gravações SQLite). Cofres inexistentes passam,
+    /// para que a falha seja a da própria gravação.
+    fn check_key_generation(&self, vault_id: Uuid, key_generation: u32) -> Result<()> {
+        match self.vaults.iter().find(|v| v.id() == vault_id) {
+            Some(vault) if vault.key_generation() != key_generation => {
+                Err(Error::conflict(STALE_KEY_MESSAGE))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Confere se o nome do cofre é único entre os cofres do usuário (índice
+    /// `idx_vault_user_name`).
+    fn check_vault_name(&self, vault: &Vault) -> Result<()> {
+        if self.vaults.iter().any(|v| {
+            v.id() != vault.id()
+                && v.user_id() == vault.user_id()
+                && v.name().eq_ignore_ascii_case(vault.name())
+        }) {
+            return Err(Error::conflict(format!(
+                "Cofre já cadastrado: '{}'",
+                vault.name()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Implementação em memória de todos os repositórios da aplicação.
+///
+/// Reproduz as restrições relevantes do schema SQLite (nomes de usuário únicos,
+/// cofres vinculados a usuários existentes e com nomes únicos por usuário,
+/// membros únicos por cofre, credenciais vinculadas a cofres e pastas existentes, tags únicas por cofre),
+/// permitindo exercitar os serviços sem tocar no sistema de arquivos.
+///
+/// Clonar a instância compartilha as mesmas tabelas, de modo que um único valor
+/// pode ser entregue a todos os serviços.
+#[derive(Clone, Default)]
+pub struct InMemoryRepository {
+    tables: Arc<Mutex<Tables>>,
+}
+
+impl InMemoryRepository {
+    /// Cria um repositório vazio.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn tables(&self) -> MutexGuard<'_, Tables> {
+        self.tables
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl UserRepository for InMemoryRepository {
+    fn create(&self, user: &User) -> Result<()> {
+        debug!("[memória] Inserindo usuário username='{}'", user.username());
+
+        let mut tables = self.tables();
+        if tables
+            .users
+            .iter()
+            .any(|u| u.id() == user.id() || u.username() == user.username())
+        {
+            return Err(Error::conflict(format!(
+                "Usuário já cadastrado: '{}'",
+                user.username()
+            )));
+        }
+
+        tables.users.push(user.clone());
+        Ok(())
+    }
+
+    fn update_password_hash(&self, user: &User) -> Result<()> {
+        let mut tables = self.tables();
+        let stored = tables
+            .users
+            .iter_mut()
+            .find(|u| u.id() == user.id())
+            .ok_or_else(|| Error::not_found("Usuário não encontrado"))?;
+
+        *stored = user.clone();
+        Ok(())
+    }
+
+    fn update_password(&self, user: &User, vaults: &[Vault]) -> Result<()> {
+        let mut tables = self.tables();
+
+        let user_index = tables
+            .users
+            .iter()
+            .position(|u| u.id() == user.id())
+            .ok_or_else(|| Error::not_found("Usuário não encontrado"))?;
+        let vault_indexes = vaults
+            .iter()
+            .map(|vault| {
+                tables.check_key_generation(vault.id(), vault.key_generation())?;
+                tables
+                    .vaults
+                    .iter()
+                    .position(|v| v.id() == vault.id())
+                    .ok_or_else(|| Error::not_found("Vault não encontrado"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        tables.users[user_index] = user.clone();
+        for (index, vault) in vault_indexes.into_iter().zip(vaults) {
+            tables.vaults[index] = vault.clone();
+        }
+        Ok(())
+    }
+
+    fn update_key_pair(&self, user: &User) -> Result<()> {
+        self.update_password_hash(user)
+    }
+
+    fn update_username(&self, user: &User) -> Result<()> {
+        let mut tables = self.tables();
+        if tables
+            .users
+            .iter()
+            .any(|u| u.id() != user.id() && u.username() == user.username())
+        {
+            return Err(Error::conflict(format!(
+                "Usuário já cadastrado: '{}'",
+                user.username()
+            )));
+        }
+
+        let stored = tables
+            .users
+            .iter_mut()
+            .find(|u| u.id() == user.id())
+            .ok_or_else(|| Error::not_found("Usuário não encontrado"))?;
+        *stored = user.clone();
+        Ok(())
+    }
+
+    fn delete(&self, id: Uuid) -> Result<()> {
+        let mut tables = self.tables();
+        if !tables.users.iter().any(|u| u.id() == id) {
+            return Err(Error::not_found("Usuário não encontrado"));
+        }
+
+        let vaults: Vec<Uuid> = tables
+            .vaults
+            .iter()
+            .filter(|v| v.user_id() == id)
+            .map(|v| v.id())
+            .collect();
+        for vault in vaults {
+            tables.delete_vault(vault);
+        }
+        tables.vault_members.retain(|m| m.user_id() != id);
+        tables.login_attempts.retain(|a| a.user_id() != id);
+        tables.users.retain(|u| u.id() != id);
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<User>> {
+        let mut users = self.tables().users.clone();
+        users.sort_by(|a, b| a.username().cmp(b.username()));
+        Ok(users)
+    }
+
+    fn find_by_username(&self, username: &str) -> Result<Option<User>> {
+        Ok(self
+            .tables()
+            .users
+            .iter()
+            .find(|u| u.username() == username)
+            .cloned())
+    }
+
+    fn find_by_id(&self, id: Uuid) -> Result<Option<User>> {
+        Ok(self.tables().users.iter().find(|u| u.id() == id).cloned())
+    }
+}
+
+impl VaultRepository for InMemoryRepository {
+    fn create(&self, vault: &Vault) -> Result<()> {
+        debug!("[memória] Inserindo cofre vault_id='{}'", vault.id());
+
+        let mut tables = self.tables();
+        if !tables.users.iter().any(|u| u.id() == vault.user_id()) {
+            return Err(Error::not_found("Usuário do cofre não encontrado"));
+        }
+        if tables.vaults.iter().any(|v| v.id() == vault.id()) {
+            return Err(Error::conflict("Cofre já cadastrado"));
+        }
+        tables.check_vault_name(vault)?;
+
+        tables.vaults.push(vault.clone());
+        Ok(())
+    }
+
+    fn update(&self, vault: &Vault) -> Result<()> {
+        let mut tables = self.tables();
+        tables.check_vault_name(vault)?;
+
+        let stored = tables
+            .vaults
+            .iter_mut()
+            .find(|v| v.id() == vault.id())
+            .ok_or_else(|| Error::not_found("Vault não encontrado"))?;
+        stored.set_name(vault.name().to_string())
+    }
+
+    fn delete(&self, id: Uuid) -> Result<()> {
+        self.tables().delete_vault(id);
+        Ok(())
+    }
+
+    fn find_by_id(&self, id: Uuid) -> Result<Option<Vault>> {
+        Ok(self.tables().vaults.iter().find(|v| v.id() == id).cloned())
+    }
+
+    fn find_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Vault>> {
+        Ok(self
+            .tables()
+            .vaults
+            .iter()
+            .filter(|v| v.user_id() == user_id)
+            .cloned()
+            .collect())
+    }
+}
+
+impl VaultMemberRepository for InMemoryRepository {
+    fn create(&self, member: &VaultMember, key_generation: u32) -> Result<()> {
+        debug!(
+            "[memória] Inserindo membro user_id='{}' no cofre vault_id='{}'",
+            member.user_id(),
+            member.vault_id()
+        );
+
+        let mut tables = self.tables();
+        if !tables.vaults.iter().any(|v| v.id() == member.vault_id()) {
+            return Err(Error::not_found("Cofre do membro não encontrado"));
+        }
+        tables.check_key_generation(member.vault_id(), key_generation)?;
+        if !tables.users.iter().any(|u| u.id() == member.user_id()) {
+            return Err(Error::not_found("Usuário do membro não encontrado"));
+        }
+        if tables
+            .vault_members
+            .iter()
+            .any(|m| m.vault_id() == member.vault_id() && m.user_id() == member.user_id())
+        {
+            return Err(Error::conflict("Membro já cadastrado no cofre"));
+        }
+
+        tables.vault_members.push(member.clone());
+        Ok(())
+    }
+
+    fn update(&self, member: &VaultMember) -> Result<()> {
+        let mut tables = self.tables();
+        let stored = tables
+            .vault_members
+            .iter_mut()
+            .find(|m| m.vault_id() == member.vault_id() && m.user_id() == member.user_id())
+            .ok_or_else(|| Error::not_found("Membro do cofre não encontrado"))?;
+
+        stored.set_role(member.role())
+    }
+
+    fn find(&self, vault_id: Uuid, user_id: Uuid) -> Result<Option<VaultMember>> {
+        Ok(self
+            .tables()
+            .vault_members
+            .iter()
+            .find(|m| m.vault_id() == vault_id && m.user_id() == user_id)
+            .cloned())
+    }
+
+    fn find_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<VaultMember>> {
+        Ok(self
+            .tables()
+            .vault_members
+            .iter()
+            .filter(|m| m.vault_id() == vault_id)
+            .cloned()
+            .collect())
+    }
+
+    fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<VaultMember>> {
+        Ok(self
+            .tables()
+            .vault_members
+            .iter()
+            .filter(|m| m.user_id() == user_id)
+            .cloned()
+            .collect())
+    }
+
+    fn revoke(
+        &self,
+        vault: &Vault,
+        previous_generation: u32,
+        user_id: Uuid,
+        members: &[VaultMember],
+        reseal: &ResealFn<'_>,
+    ) -> Result<()> {
+        let mut tables = self.tables();
+
+        // Localiza e recifra todos os registros antes de alterar qualquer um, como a transação SQLite.
+        let vault_index = tables
+            .vaults
+            .iter()
+            .position(|v| v.id() == vault.id())
+            .ok_or_else(|| Error::not_found("Vault não encontrado"))?;
+        tables.check_key_generation(vault.id(), previous_generation)?;
+        let removed = tables
+            .vault_members
+            .iter()
+            .position(|m| m.vault_id() == vault.id() && m.user_id() == user_id)
+            .ok_or_else(|| Error::not_found("Membro do cofre não encontrado"))?;
+        let remaining = tables
+            .vault_members
+            .iter()
+            .filter(|m| m.vault_id() == vault.id())
+            .count()
+            - 1;
+        if remaining != members.len() {
+            return Err(Error::conflict(
+                "Os membros do cofre mudaram; tente novamente",
+            ));
+        }
+        let member_indexes = members
+            .iter()
+            .map(|member| {
+                tables
+                    .vault_members
+                    .iter()
+                    .position(|m| {
+                        m.vault_id() == member.vault_id() && m.user_id() == member.user_id()
+                    })
+                    .ok_or_else(|| Error::not_found("Membro do cofre não encontrado"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut credentials: Vec<Credential> = tables
+            .credentials
+            .iter()
+            .filter(|c| c.vault_id() == vault.id())
+            .cloned()
+            .collect();
+        let mut history: Vec<CredentialHistoryEntry> = tables
+            .history
+            .iter()
+            .filter(|h| credentials.iter().any(|c| c.id() == h.credential_id()))
+            .cloned()
+            .collect();
+        reseal(&mut credentials, &mut history)?;
+
+        tables.vaults[vault_index] = vault.clone();
+        for (index, member) in member_indexes.into_iter().zip(members) {
+            tables.vault_members[index] = member.clone();
+        }
+        for credential in credentials {
+            if let Some(stored) = tables
+                .credentials
+                .iter_mut()
+                .find(|c| c.id() == credential.id())
+            {
+                *stored = credential;
+            }
+        }
+        for entry in history {
+            if let Some(stored) = tables.history.iter_mut().find(|h| h.id() == entry.id()) {
+                *stored = entry;
+            }
+        }
+        tables.vault_members.remove(removed);
+        Ok(())
+    }
+}
+
+impl CredentialRepository for InMemoryRepository {
+    fn create(&self, credential: &Credential, key_generation: u32) -> Result<()> {
+        debug!("[memória] Inserindo credencial id='{}'", credential.id());
+
+        let mut tables = self.tables();
+        if !tables
+            .vaults
+            .iter()
+            .any(|v| v.id() == credential.vault_id())
+        {
+            return Err(Error::not_found("Cofre da credencial não encontrado"));
+        }
+        tables.check_key_generation(credential.vault_id(), key_generation)?;
+        if tables.credentials.iter().any(|c| c.id() == credential.id()) {
+            return Err(Error::conflict("Credencial já cadastrada"));
+        }
+        tables.check_folder(credential)?;
+
+        tables.credentials.push(credential.clone());
+        Ok(())
+    }
+
+    fn create_many(&self, credentials: &[Credential], key_generation: u32) -> Result<()> {
+        let mut tables = self.tables();
+
+        for (i, credential) in credentials.iter().enumerate() {
+            if !tables
+                .vaults
+                .iter()
+                .any(|v| v.id() == credential.vault_id())
+            {
+                return Err(Error::not_found("Cofre da credencial não encontrado"));
+            }
+            tables.check_key_generation(credential.vault_id(), key_generation)?;
+            if tables.credentials.iter().any(|c| c.id() == credential.id())
+                || credentials[..i].iter().any(|c| c.id() == credential.id())
+            {
+                return Err(Error::conflict("Credencial já cadastrada"));
+            }
+            tables.check_folder(credential)?;
+        }
+
+        tables.credentials.extend(credentials.iter().cloned());
+        Ok(())
+    }
+
+    fn update(&self, credential: &Credential, key_generation: u32) -> Result<()> {
+        self.update_many(std::slice::from_ref(credential), key_generation)?;
+        Ok(())
+    }
+
+    fn update_many(&self, credentials: &[Credential], key_generation: u32) -> Result<usize> {
+        let mut tables = self.tables();
+        let mut rows = 0;
+
+        for credential in credentials {
+            tables.check_folder(credential)?;
+            tables.check_key_generation(credential.vault_id(), key_generation)?;
+        }
+
+        for credential in credentials {
+            if let Some(stored) = tables
+                .credentials
+                .iter_mut()
+                .find(|c| c.id() == credential.id())
+            {
+                *stored = credential.clone();
+                rows += 1;
+            }
+        }
+
+        Ok(rows)
+    }
+
+    fn move_to_vault(
+        &self,
+        credential: &Credential,
+        history: &[CredentialHistoryEntry],
+        key_generation: u32,
+    ) -> Result<()> {
+        let mut tables = self.tables();
+        if !tables
+            .vaults
+            .iter()
+            .any(|v| v.id() == credential.vault_id())
+        {
+            return Err(Error::not_found("Cofre da credencial não encontrado"));
+        }
+        tables.check_key_generation(credential.vault_id(), key_generation)?;
+        tables.check_folder(credential)?;
+
+        let index = tables
+            .credentials
+            .iter()
+            .position(|c| c.id() == credential.id())
+            .ok_or_else(|| Error::not_found("Credencial não encontrada"))?;
+        let entries = history
+            .iter()
+            .map(|entry| {
+                tables
+                    .history
+                    .iter()
+                    .position(|h| h.id() == entry.id())
+                    .ok_or_else(|| Error::not_found("Versão do histórico não encontrada"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        tables.credentials[index] = credential.clone();
+        for (index, entry) in entries.into_iter().zip(history) {
+            tables.history[index] = entry.clone();
+        }
+        tables
+            .credential_tags
+            .retain(|&(linked, _)| linked != credential.id());
+        Ok(())
+    }
+
+    fn delete(&self, id: Uuid) -> Result<()> {
+        let mut tables = self.tables();
+        tables.credentials.retain(|c| c.id() != id);
+        tables.history.retain(|h| h.credential_id() != id);
+        tables
+            .credential_tags
+            .retain(|&(credential, _)| credential != id);
+        Ok(())
+    }
+
+    fn find_by_id(&self, id: Uuid) -> Result<Option<Credential>> {
+        Ok(self
+            .tables()
+            .credentials
+            .iter()
+            .find(|c| c.id() == id)
+            .cloned())
+    }
+
+    fn find_all_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<Credential>> {
+        Ok(self
+            .tables()
+            .credentials
+            .iter()
+            .filter(|c| c.vault_id() == vault_id && !c.is_trashed())
+            .cloned()
+            .collect())
+    }
+
+    fn search(&self, vault_id: Uuid, query: &str) -> Result<Vec<Credential>> {
+        let query = fold(query);
+
+        let mut found: Vec<Credential> = self
+            .tables()
+            .credentials
+            .iter()
+            .filter(|c| {
+                c.vault_id() == vault_id
+                    && !c.is_trashed()
+                    && [Some(c.name()), c.username(), c.url()]
+                        .into_iter()
+                        .flatten()
+                        .any(|field| fold(field).contains(&query))
+            })
+            .cloned()
+            .collect();
+
+        found.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(found)
+    }
+
+    fn find_trashed_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<Credential>> {
+        let mut trashed: Vec<Credential> = self
+            .tables()
+            .credentials
+            .iter()
+            .filter(|c| c.vault_id() == vault_id && c.is_trashed())
+            .cloned()
+            .collect();
+
+        trashed.sort_by_key(|c| std::cmp::Reverse(c.deleted_at()));
+        Ok(trashed)
+    }
+
+    fn purge_trashed(&self, vault_id: Uuid, before: Option<DateTime<Utc>>) -> Result<usize> {
+        let mut tables = self.tables();
+        let expired: Vec<Uuid> = tables
+            .credentials
+            .iter()
+            .filter(|c| {
+                c.vault_id() == vault_id
+                    && c.deleted_at()
+                        .is_some_and(|at| before.is_none_or(|before| at < before))
+            })
+            .map(|c| c.id())
+            .collect();
+
+        tables.credentials.retain(|c| !expired.contains(&c.id()));
+        tables
+            .history
+            .retain(|h| !expired.contains(&h.credential_id()));
+        tables
+            .credential_tags
+            .retain(|(credential, _)| !expired.contains(credential));
+
+        debug!(
+            "{} credenciais removidas da lixeira em memória",
+            expired.len()
+        );
+        Ok(expired.len())
+    }
+}
+
+impl LoginAttemptRepository for InMemoryRepository {
+    fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<LoginAttempt>> {
+        Ok(self
+            .tables()
+            .login_attempts
+            .iter()
+            .find(|a| a.user_id() == user_id)
+            .cloned())
+    }
+
+    fn save(&self, attempt: &LoginAttempt) -> Result<()> {
+        let mut tables = self.tables();
+
+        match tables
+            .login_attempts
+            .iter_mut()
+            .find(|a| a.user_id() == attempt.user_id())
+        {
+            Some(stored) => *stored = attempt.clone(),
+            None => tables.login_attempts.push(attempt.clone()),
+        }
+
+        Ok(())
+    }
+
+    fn delete(&self, user_id: Uuid) -> Result<()> {
+        self.tables()
+            .login_attempts
+            .retain(|a| a.user_id() != user_id);
+        Ok(())
+    }
+}
+
+impl FolderRepository for InMemoryRepository {
+    fn create(&self, folder: &Folder) -> Result<()> {
+        debug!("[memória] Inserindo pasta id='{}'", folder.id());
+
+        let mut tables = self.tables();
+        if !tables.vaults.iter().any(|v| v.id() == folder.vault_id()) {
+            return Err(Error::not_found("Cofre da pasta não encontrado"));
+        }
+        if let Some(parent) = folder.parent_id()
+            && !tables.folders.iter().any(|f| f.id() == parent)
+        {
+            return Err(Error::not_found("Pasta superior não encontrada"));
+        }
+
+        tables.folders.push(folder.clone());
+        Ok(())
+    }
+
+    fn update(&self, folder: &Folder) -> Result<()> {
+        let mut tables = self.tables();
+        if let Some(stored) = tables.folders.iter_mut().find(|f| f.id() == folder.id()) {
+            *stored = folder.clone();
+        }
+        Ok(())
+    }
+
+    fn delete(&self, folder: &Folder) -> Result<()> {
+        let mut tables = self.tables();
+
+        for child in tables
+            .folders
+            .iter_mut()
+            .filter(|f| f.parent_id() == Some(folder.id()))
+        {
+            child.set_parent_id(folder.parent_id());
+        }
+        for credential in tables
+            .credentials
+            .iter_mut()
+            .filter(|c| c.folder_id() == Some(folder.id()))
+        {
+            credential.set_folder_id(folder.parent_id());
+        }
+        tables.folders.retain(|f| f.id() != folder.id());
+
+        Ok(())
+    }
+
+    fn find_by_id(&self, id: Uuid) -> Result<Option<Folder>> {
+        Ok(self.tables().folders.iter().find(|f| f.id() == id).cloned())
+    }
+
+    fn find_all_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<Folder>> {
+        let mut folders: Vec<Folder> = self
+            .tables()
+            .folders
+            .iter()
+            .filter(|f| f.vault_id() == vault_id)
+            .cloned()
+            .collect();
+
+        folders.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(folders)
+    }
+}
+
+impl TagRepository for InMemoryRepository {
+    fn create(&self, tag: &Tag) -> Result<()> {
+        debug!("[memória] Inserindo tag id='{}'", tag.id());
+
+        let mut tables = self.tables();
+        if !tables.vaults.iter().any(|v| v.id() == tag.vault_id()) {
+            return Err(Error::not_found("Cofre da tag não encontrado"));
+        }
+        if tables
+            .tags
+            .iter()
+            .any(|t| t.vault_id() == tag.vault_id() && t.name().eq_ignore_ascii_case(tag.name()))
+        {
+            return Err(Error::conflict(format!(
+                "Tag já cadastrada: '{}'",
+                tag.name()
+            )));
+        }
+
+        tables.tags.push(tag.clone());
+        Ok(())
+    }
+
+    fn update(&self, tag: &Tag) -> Result<()> {
+        let mut tables = self.tables();
+        if tables.tags.iter().any(|t| {
+            t.id() != tag.id()
+                && t.vault_id() == tag.vault_id()
+                && t.name().eq_ignore_ascii_case(tag.name())
+        }) {
+            return Err(Error::conflict(format!(
+                "Tag já cadastrada: '{}'",
+                tag.name()
+            )));
+        }
+
+        if let Some(stored) = tables.tags.iter_mut().find(|t| t.id() == tag.id()) {
+            *stored = tag.clone();
+        }
+        Ok(())
+    }
+
+    fn delete(&self, id: Uuid) -> Result<()> {
+        let mut tables = self.tables();
+        tables.tags.retain(|t| t.id() != id);
+        tables.credential_tags.retain(|&(_, tag)| tag != id);
+        Ok(())
+    }
+
+    fn find_all_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<Tag>> {
+        let mut tags: Vec<Tag> = self
+            .tables()
+            .tags
+            .iter()
+            .filter(|t| t.vault_id() == vault_id)
+            .cloned()
+            .collect();
+
+        tags.sort_by_key(|t| t.name().to_ascii_lowercase());
+        Ok(tags)
+    }
+
+    fn find_by_credential_id(&self, credential_id: Uuid) -> Result<Vec<Tag>> {
+        let tables = self.tables();
+        let mut tags: Vec<Tag> = tables
+            .tags
+            .iter()
+            .filter(|t| tables.credential_tags.contains(&(credential_id, t.id())))
+            .cloned()
+            .collect();
+
+        tags.sort_by_key(|t| t.name().to_ascii_lowercase());
+        Ok(tags)
+    }
+
+    fn find_links_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<(Uuid, Uuid)>> {
+        let tables = self.tables();
+        Ok(tables
+            .credential_tags
+            .iter()
+            .filter(|&&(_, tag)| {
+                tables
+                    .tags
+                    .iter()
+                    .any(|t| t.id() == tag && t.vault_id() == vault_id)
+            })
+            .copied()
+            .collect())
+    }
+
+    fn replace_links(&self, credential_id: Uuid, tag_ids: &[Uuid]) -> Result<()> {
+        let mut tables = self.tables();
+        if !tables.credentials.iter().any(|c| c.id() == credential_id) {
+            return Err(Error::not_found("Credencial não encontrada"));
+        }
+        if let Some(missing) = tag_ids
+            .iter()
+            .find(|&&id| !tables.tags.iter().any(|t| t.id() == id))
+        {
+            return Err(Error::not_found(format!("Tag não encontrada: '{missing}'")));
+        }
+
+        tables
+            .credential_tags
+            .retain(|&(credential, _)| credential != credential_id);
+        for &tag_id in tag_ids {
+            if !tables.credential_tags.contains(&(credential_id, tag_id)) {
+                tables.credential_tags.push((credential_id, tag_id));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CredentialHistoryRepository for InMemoryRepository {
+    fn record(
+        &self,
+        entry: &CredentialHistoryEntry,
+        retention: usize,
+        key_generation: u32,
+    ) -> Result<()> {
+        debug!(
+            "[memória] Registrando versão da credencial id='{}'",
+            entry.credential_id()
+        );
+
+        let mut tables = self.tables();
+        let vault_id = tables
+            .credentials
+            .iter()
+            .find(|c| c.id() == entry.credential_id())
+            .map(|c| c.vault_id())
+            .ok_or_else(|| Error::not_found("Credencial do histórico não encontrada"))?;
+        tables.check_key_generation(vault_id, key_generation)?;
+
+        tables.history.push(entry.clone());
+
+        let versions = tables
+            .history
+            .iter()
+            .filter(|h| h.credential_id() == entry.credential_id())
+            .count();
+        let mut excess = versions.saturating_sub(retention);
+        tables.history.retain(|h| {
+            if excess > 0 && h.credential_id() == entry.credential_id() {
+                excess -= 1;
+                return false;
+            }
+            true
+        });
+
+        Ok(())
+    }
+
+    fn find_by_id(&self, id: Uuid) -> Result<Option<CredentialHistoryEntry>> {
+        Ok(self.tables().history.iter().find(|h| h.id() == id).cloned())
+    }
+
+    fn find_by_credential_id(&self, credential_id: Uuid) -> Result<Vec<CredentialHistoryEntry>> {
+        Ok(self
+            .tables()
+            .history
+            .iter()
+            .rev()
+            .filter(|h| h.credential_id() == credential_id)
+            .cloned()
+            .collect())
+    }
+}
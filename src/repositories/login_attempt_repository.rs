@@ -0,0 +1,129 @@
+use chrono::{DateTime, Utc};
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::infrastructure::database::Database;
+use crate::models::login_attempt::LoginAttempt;
+
+/// Repositório responsável pela persistência das tentativas de login malsucedidas.
+pub trait LoginAttemptRepository: Send + Sync {
+    /// Busca o registro de tentativas de um usuário.
+    ///
+    /// ### Parâmetros
+    /// - `user_id`: Identificador do usuário.
+    ///
+    /// ### Retorno
+    /// - `Ok(Some(LoginAttempt))` quando existirem falhas registradas.
+    /// - `Ok(None)` quando o usuário não possuir falhas pendentes.
+    /// - `Err(Error)` em falha de consulta ou desserialização.
+    fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<LoginAttempt>>;
+
+    /// Insere ou atualiza o registro de tentativas de um usuário.
+    ///
+    /// ### Parâmetros
+    /// - `attempt`: Estado atualizado das tentativas.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` em caso de sucesso.
+    /// - `Err(Error)` em falha de gravação.
+    fn save(&self, attempt: &LoginAttempt) -> Result<()>;
+
+    /// Remove o registro de tentativas de um usuário (após login bem-sucedido).
+    ///
+    /// ### Parâmetros
+    /// - `user_id`: Identificador do usuário.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` mesmo que não exista registro.
+    /// - `Err(Error)` em falha de remoção.
+    fn delete(&self, user_id: Uuid) -> Result<()>;
+}
+
+/// Implementação de [`LoginAttemptRepository`] sobre o banco SQLite.
+///
+/// Mantém uma referência ao [`Database`] compartilhado da aplicação e empresta
+/// uma conexão do pool a cada operação.
+#[derive(Clone)]
+pub struct SqliteLoginAttemptRepository {
+    db: Database,
+}
+
+impl SqliteLoginAttemptRepository {
+    /// Cria o repositório sobre o banco compartilhado da aplicação.
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+impl LoginAttemptRepository for SqliteLoginAttemptRepository {
+    fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<LoginAttempt>> {
+        debug!("Consultando tentativas de login para user_id='{}'", user_id);
+
+        let conn = self.db.connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT user_id, failed_count, last_failed_at, locked_until
+             FROM login_attempt WHERE user_id = ?1",
+        )?;
+
+        let mut rows = stmt.query([user_id.as_bytes()])?;
+
+        if let Some(row) = rows.next()? {
+            let attempt = LoginAttempt::from_persisted(
+                Uuid::from_slice(&row.get::<_, Vec<u8>>(0)?)?,
+                row.get(1)?,
+                parse_optional_timestamp(row.get(2)?)?,
+                parse_optional_timestamp(row.get(3)?)?,
+            );
+            return Ok(Some(attempt));
+        }
+
+        Ok(None)
+    }
+
+    fn save(&self, attempt: &LoginAttempt) -> Result<()> {
+        info!(
+            "Registrando tentativas de login user_id='{}' falhas={}",
+            attempt.user_id(),
+            attempt.failed_count()
+        );
+
+        let conn = self.db.connection()?;
+        conn.execute(
+            "INSERT INTO login_attempt (user_id, failed_count, last_failed_at, locked_until)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (user_id) DO UPDATE SET
+                failed_count = excluded.failed_count,
+                last_failed_at = excluded.last_failed_at,
+                locked_until = excluded.locked_until",
+            (
+                attempt.user_id().as_bytes(),
+                attempt.failed_count(),
+                attempt.last_failed_at().map(|t| t.to_rfc3339()),
+                attempt.locked_until().map(|t| t.to_rfc3339()),
+            ),
+        )?;
+
+        Ok(())
+    }
+
+    fn delete(&self, user_id: Uuid) -> Result<()> {
+        debug!("Zerando tentativas de login para user_id='{}'", user_id);
+
+        let conn = self.db.connection()?;
+        conn.execute(
+            "DELETE FROM login_attempt WHERE user_id = ?1",
+            [user_id.as_bytes()],
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Converte um timestamp RFC3339 opcional vindo do banco.
+fn parse_optional_timestamp(value: Option<String>) -> Result<Option<DateTime<Utc>>> {
+    Ok(match value {
+        Some(s) => Some(DateTime::parse_from_rfc3339(&s)?.with_timezone(&Utc)),
+        None => None,
+    })
+}
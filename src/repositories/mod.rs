@@ -21,6 +21,16 @@
 //!
 //! ---
 //!
+//! ## Contratos e implementações
+//!
+//! Cada repositório é definido por um *trait* (ex.: [`user_repository::UserRepository`]),
+//! do qual os serviços dependem. Há duas implementações:
+//!
+//! - `Sqlite*Repository`: persistência real, sobre o pool de [`crate::infrastructure::database::Database`]
+//! - [`in_memory_repository::InMemoryRepository`]: tabelas em memória, para testes
+//!
+//! ---
+//!
 //! ## Submódulos
 //!
 //! ### [`user_repository`]
@@ -43,6 +53,15 @@
 //!
 //! ---
 //!
+//! ### [`vault_member_repository`]
+//!
+//! Gerencia a persistência dos membros de cofres compartilhados:
+//!
+//! - Papel e chave do cofre embrulhada para cada membro
+//! - Revogação com troca da chave do cofre em uma única transação
+//!
+//! ---
+//!
 //! ### [`credential_repository`]
 //!
 //! Gerencia a persistência das credenciais:
@@ -53,6 +72,51 @@
 //!
 //! ---
 //!
+//! ### [`credential_history_repository`]
+//!
+//! Gerencia a persistência do histórico de senhas das credenciais:
+//!
+//! - Versões anteriores cifradas, da mais recente para a mais antiga
+//! - Descarte das versões além do limite de retenção
+//!
+//! ---
+//!
+//! ### [`folder_repository`]
+//!
+//! Gerencia a persistência das pastas de credenciais:
+//!
+//! - Hierarquia de pastas por cofre
+//! - Remoção que devolve o conteúdo à pasta superior
+//!
+//! ---
+//!
+//! ### [`tag_repository`]
+//!
+//! Gerencia a persistência das tags:
+//!
+//! - Tags únicas por cofre
+//! - Vínculos muitos-para-muitos com credenciais
+//!
+//! ---
+//!
+//! ### [`login_attempt_repository`]
+//!
+//! Gerencia a persistência das tentativas de login:
+//!
+//! - Contadores de falhas por usuário
+//! - Bloqueios temporários contra força bruta
+//!
+//! ---
+//!
+//! ### [`in_memory_repository`]
+//!
+//! Implementação em memória de todos os repositórios:
+//!
+//! - Reproduz as restrições do schema (unicidade e vínculos entre tabelas)
+//! - Permite testar os serviços sem sistema de arquivos
+//!
+//! ---
+//!
 //! ## Relação com outras camadas
 //!
 //! - Depende de [`crate::infrastructure`] para acesso ao banco de dados
@@ -76,6 +140,12 @@
 //! Alterações na estrutura dos repositórios podem impactar a integridade
 //! dos dados e devem ser realizadas com atenção.
 
+pub mod credential_history_repository;
 pub mod credential_repository;
+pub mod folder_repository;
+pub mod in_memory_repository;
+pub mod login_attempt_repository;
+pub mod tag_repository;
 pub mod user_repository;
+pub mod vault_member_repository;
 pub mod vault_repository;
@@ -0,0 +1,211 @@
+use chrono::{DateTime, Utc};
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::infrastructure::database::Database;
+use crate::models::tag::Tag;
+
+/// Repositório responsável pela persistência das tags e de seus vínculos com
+/// credenciais.
+pub trait TagRepository: Send + Sync {
+    /// Insere uma nova tag.
+    ///
+    /// ### Parâmetros
+    /// - `tag`: Tag já validada pelo domínio.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` em caso de sucesso.
+    /// - `Err(Error)` em falha de gravação (ex.: nome já usado no cofre).
+    fn create(&self, tag: &Tag) -> Result<()>;
+
+    /// Atualiza o nome de uma tag existente.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` mesmo quando nenhuma linha for afetada.
+    /// - `Err(Error)` em falha de gravação (ex.: nome já usado no cofre).
+    fn update(&self, tag: &Tag) -> Result<()>;
+
+    /// Remove uma tag e todos os seus vínculos com credenciais.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` mesmo que não exista.
+    /// - `Err(Error)` em falha de remoção.
+    fn delete(&self, id: Uuid) -> Result<()>;
+
+    /// Lista todas as tags de um cofre, ordenadas pelo nome.
+    fn find_all_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<Tag>>;
+
+    /// Lista as tags de uma credencial, ordenadas pelo nome.
+    fn find_by_credential_id(&self, credential_id: Uuid) -> Result<Vec<Tag>>;
+
+    /// Lista os vínculos `(credencial, tag)` de todas as credenciais de um cofre.
+    ///
+    /// ### Aplicação
+    /// Usado para filtrar credenciais por tag e contar o uso de cada tag com uma
+    /// única consulta.
+    fn find_links_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<(Uuid, Uuid)>>;
+
+    /// Substitui as tags de uma credencial.
+    ///
+    /// ### Parâmetros
+    /// - `credential_id`: Credencial marcada.
+    /// - `tag_ids`: Conjunto completo de tags da credencial (vazio remove todas).
+    ///
+    /// ### Retorno
+    /// - `Ok(())` em caso de sucesso.
+    /// - `Err(Error)` em falha de gravação; nesse caso nada é alterado.
+    fn replace_links(&self, credential_id: Uuid, tag_ids: &[Uuid]) -> Result<()>;
+}
+
+/// Implementação de [`TagRepository`] sobre o banco SQLite.
+///
+/// Mantém uma referência ao [`Database`] compartilhado da aplicação e empresta
+/// uma conexão do pool a cada operação.
+#[derive(Clone)]
+pub struct SqliteTagRepository {
+    db: Database,
+}
+
+impl SqliteTagRepository {
+    /// Cria o repositório sobre o banco compartilhado da aplicação.
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// Executa uma consulta de tags com um único parâmetro de ID.
+    fn query_tags(&self, sql: &str, id: Uuid) -> Result<Vec<Tag>> {
+        let conn = self.db.connection()?;
+        let mut stmt = conn.prepare(sql)?;
+
+        let mut rows = stmt.query([id.as_bytes()])?;
+        let mut tags = Vec::new();
+        while let Some(row) = rows.next()? {
+            tags.push(Tag::from_persisted(
+                Uuid::from_slice(&row.get::<_, Vec<u8>>(0)?)?,
+                Uuid::from_slice(&row.get::<_, Vec<u8>>(1)?)?,
+                row.get(2)?,
+                DateTime::parse_from_rfc3339(&row.get::<_, String>(3)?)?.with_timezone(&Utc),
+            ));
+        }
+
+        Ok(tags)
+    }
+}
+
+impl TagRepository for SqliteTagRepository {
+    fn create(&self, tag: &Tag) -> Result<()> {
+        info!(
+            "Criando tag id='{}' vault_id='{}' name='{}'",
+            tag.id(),
+            tag.vault_id(),
+            tag.name()
+        );
+
+        let conn = self.db.connection()?;
+        conn.execute(
+            "INSERT INTO tag (id, vault_id, name, created_at) VALUES (?1, ?2, ?3, ?4)",
+            (
+                tag.id().as_bytes(),
+                tag.vault_id().as_bytes(),
+                tag.name(),
+                tag.created_at().to_rfc3339(),
+            ),
+        )?;
+
+        Ok(())
+    }
+
+    fn update(&self, tag: &Tag) -> Result<()> {
+        info!("Renomeando tag id='{}' para '{}'", tag.id(), tag.name());
+
+        let conn = self.db.connection()?;
+        conn.execute(
+            "UPDATE tag SET name = ?1 WHERE id = ?2",
+            (tag.name(), tag.id().as_bytes()),
+        )?;
+
+        Ok(())
+    }
+
+    fn delete(&self, id: Uuid) -> Result<()> {
+        info!("Removendo tag id='{}'", id);
+
+        let conn = self.db.connection()?;
+        conn.execute("DELETE FROM tag WHERE id = ?1", [id.as_bytes()])?;
+
+        Ok(())
+    }
+
+    fn find_all_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<Tag>> {
+        debug!("Listando tags para vault_id='{}'", vault_id);
+
+        self.query_tags(
+            "SELECT id, vault_id, name, created_at
+             FROM tag WHERE vault_id = ?1
+             ORDER BY name ASC",
+            vault_id,
+        )
+    }
+
+    fn find_by_credential_id(&self, credential_id: Uuid) -> Result<Vec<Tag>> {
+        debug!("Listando tags da credencial id='{}'", credential_id);
+
+        self.query_tags(
+            "SELECT t.id, t.vault_id, t.name, t.created_at
+             FROM tag t
+             JOIN credential_tag ct ON ct.tag_id = t.id
+             WHERE ct.credential_id = ?1
+             ORDER BY t.name ASC",
+            credential_id,
+        )
+    }
+
+    fn find_links_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<(Uuid, Uuid)>> {
+        debug!("Listando vínculos de tags para vault_id='{}'", vault_id);
+
+        let conn = self.db.connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT ct.credential_id, ct.tag_id
+             FROM credential_tag ct
+             JOIN tag t ON t.id = ct.tag_id
+             WHERE t.vault_id = ?1",
+        )?;
+
+        let mut rows = stmt.query([vault_id.as_bytes()])?;
+        let mut links = Vec::new();
+        while let Some(row) = rows.next()? {
+            links.push((
+                Uuid::from_slice(&row.get::<_, Vec<u8>>(0)?)?,
+                Uuid::from_slice(&row.get::<_, Vec<u8>>(1)?)?,
+            ));
+        }
+
+        Ok(links)
+    }
+
+    fn replace_links(&self, credential_id: Uuid, tag_ids: &[Uuid]) -> Result<()> {
+        info!(
+            "Definindo {} tag(s) para a credencial id='{}'",
+            tag_ids.len(),
+            credential_id
+        );
+
+        let mut conn = self.db.connection()?;
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "DELETE FROM credential_tag WHERE credential_id = ?1",
+            [credential_id.as_bytes()],
+        )?;
+        for tag_id in tag_ids {
+            tx.execute(
+                "INSERT OR IGNORE INTO credential_tag (credential_id, tag_id) VALUES (?1, ?2)",
+                (credential_id.as_bytes(), tag_id.as_bytes()),
+            )?;
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+}
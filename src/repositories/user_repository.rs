@@ -1,15 +1,15 @@
-use crate::infrastructure::database::get_database_connection;
-use crate::models::user::User;
-use anyhow::{Result, anyhow};
+use crate::error::{Error, Result};
+use crate::infrastructure::database::Database;
+use crate::models::{user::User, vault::Vault};
+use crate::repositories::vault_repository::SqliteVaultRepository;
 use chrono::{DateTime, Utc};
+use rusqlite::Connection;
 use std::time::Instant;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 /// Repositório responsável por operações de persistência e consulta de usuários.
-pub struct UserRepository;
-
-impl UserRepository {
+pub trait UserRepository: Send + Sync {
     /// Insere um usuário no banco de dados.
     ///
     /// ### Parâmetros
@@ -17,8 +17,170 @@ impl UserRepository {
     ///
     /// ### Retorno
     /// - `Ok(())` em sucesso.
-    /// - `Err(anyhow)` em falha de gravação.
-    pub fn create(user: &User) -> Result<()> {
+    /// - `Err(Error)` em falha de gravação.
+    fn create(&self, user: &User) -> Result<()>;
+
+    /// Atualiza o hash de senha de um usuário.
+    ///
+    /// ### Parâmetros
+    /// - `user`: Usuário com o novo hash já aplicado.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando exatamente um registro for atualizado.
+    /// - `Err(Error)` em falha de gravação ou usuário inexistente.
+    fn update_password_hash(&self, user: &User) -> Result<()>;
+
+    /// Persiste uma troca de segredos da conta: o hash do usuário, a chave da
+    /// conta e, quando informadas, as chaves de cofres reembrulhadas.
+    ///
+    /// ### Parâmetros
+    /// - `user`: Usuário com o novo hash e a chave da conta já aplicados.
+    /// - `vaults`: Cofres cujas chaves foram reembrulhadas (pode ser vazio).
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando todos os registros forem atualizados.
+    /// - `Err(Error)` em qualquer falha; nesse caso nenhuma alteração é aplicada.
+    ///
+    /// ### Aplicação
+    /// Usado na troca da senha mestre e na migração de contas legadas para a
+    /// chave da conta. As escritas ocorrem na mesma transação, de modo que o
+    /// hash e as chaves nunca fiquem protegidos por senhas diferentes.
+    fn update_password(&self, user: &User, vaults: &[Vault]) -> Result<()>;
+
+    /// Grava o par de chaves X25519 do usuário.
+    ///
+    /// ### Parâmetros
+    /// - `user`: Usuário com a chave pública e a privada cifrada já aplicadas.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando exatamente um registro for atualizado.
+    /// - `Err(Error)` em falha de gravação ou usuário inexistente.
+    fn update_key_pair(&self, user: &User) -> Result<()>;
+
+    /// Grava o novo nome de um usuário.
+    ///
+    /// ### Parâmetros
+    /// - `user`: Usuário com o novo nome já aplicado.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando exatamente um registro for atualizado.
+    /// - `Err(Error)` quando o nome já estiver em uso, o usuário não existir
+    ///   ou em falha de gravação.
+    fn update_username(&self, user: &User) -> Result<()>;
+
+    /// Exclui um usuário com todos os seus dados.
+    ///
+    /// ### Parâmetros
+    /// - `id`: Identificador do usuário.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando o usuário for removido.
+    /// - `Err(Error)` quando o usuário não existir ou em falha de remoção.
+    ///
+    /// ### Aplicação
+    /// Cofres, credenciais, histórico, pastas, tags, tentativas de login e
+    /// vínculos de membro são removidos em cascata (`ON DELETE CASCADE`),
+    /// inclusive os vínculos de outros usuários com os cofres excluídos.
+    fn delete(&self, id: Uuid) -> Result<()>;
+
+    /// Lista todos os usuários cadastrados, ordenados pelo nome.
+    ///
+    /// ### Retorno
+    /// - `Ok(Vec<User>)`: usuários (vazio quando não houver).
+    /// - `Err(Error)` em falha de consulta ou desserialização.
+    fn list(&self) -> Result<Vec<User>>;
+
+    /// Busca um usuário pelo seu nome de login.
+    ///
+    /// ### Parâmetros
+    /// - `username`: Nome de usuário desejado.
+    ///
+    /// ### Retorno
+    /// - `Ok(Some(User))` se encontrado.
+    /// - `Ok(None)` quando não existe.
+    /// - `Err(Error)` quando ocorre erro de consulta ou parsing de dados.
+    fn find_by_username(&self, username: &str) -> Result<Option<User>>;
+
+    /// Busca um usuário pelo ID.
+    ///
+    /// ### Retorno
+    /// - `Ok(Some(User))` se encontrado.
+    /// - `Ok(None)` quando não existe.
+    /// - `Err(Error)` quando ocorre erro de consulta ou parsing de dados.
+    fn find_by_id(&self, id: Uuid) -> Result<Option<User>>;
+}
+
+/// Colunas lidas pelas consultas de usuário, na ordem esperada por `row_to_model`.
+const USER_COLUMNS: &str = "id, username, password_hash, account_key_cipher, public_key,
+                            private_key_cipher, created_at, updated_at";
+
+/// Implementação de [`UserRepository`] sobre o banco SQLite.
+///
+/// Mantém uma referência ao [`Database`] compartilhado da aplicação e empresta
+/// uma conexão do pool a cada operação.
+#[derive(Clone)]
+pub struct SqliteUserRepository {
+    db: Database,
+}
+
+impl SqliteUserRepository {
+    /// Cria o repositório sobre o banco compartilhado da aplicação.
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// Converte uma linha SQL (colunas de [`USER_COLUMNS`]) em objeto de domínio.
+    fn row_to_model(row: &rusqlite::Row) -> Result<User> {
+        let id = Uuid::from_slice(&row.get::<_, Vec<u8>>(0)?)
+            .map_err(|_| Error::storage("UUID inválido no banco de dados"))?;
+
+        Ok(User::from_persisted(
+            id,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+            DateTime::parse_from_rfc3339(&row.get::<_, String>(6)?)?.with_timezone(&Utc),
+            DateTime::parse_from_rfc3339(&row.get::<_, String>(7)?)?.with_timezone(&Utc),
+        ))
+    }
+
+    /// Grava o hash de senha e a chave da conta utilizando a conexão (ou
+    /// transação) informada.
+    fn write_password_hash(conn: &Connection, user: &User) -> Result<()> {
+        info!(
+            "Atualizando hash de senha do usuário. username='{}' id='{}'",
+            user.username(),
+            user.id()
+        );
+
+        let rows = conn.execute(
+            "UPDATE user SET password_hash = ?1, account_key_cipher = ?2, updated_at = ?3
+             WHERE id = ?4",
+            (
+                user.password_hash(),
+                user.account_key_cipher(),
+                user.updated_at().to_rfc3339(),
+                user.id().as_bytes(),
+            ),
+        )?;
+
+        if rows != 1 {
+            error!(
+                "Atualização de senha não encontrou o usuário id='{}'",
+                user.id()
+            );
+            return Err(Error::not_found("Usuário não encontrado"));
+        }
+
+        debug!("Hash de senha atualizado para id='{}'", user.id());
+        Ok(())
+    }
+}
+
+impl UserRepository for SqliteUserRepository {
+    fn create(&self, user: &User) -> Result<()> {
         let start = Instant::now();
         info!(
             "Iniciando persistência do usuário. username='{}' id='{}'",
@@ -26,7 +188,7 @@ impl UserRepository {
             user.id()
         );
 
-        let conn = match get_database_connection() {
+        let conn = match self.db.connection() {
             Ok(c) => c,
             Err(err) => {
                 error!(
@@ -39,12 +201,17 @@ impl UserRepository {
         };
 
         let result = conn.execute(
-            "INSERT INTO user (id, username, password_hash, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
+            "INSERT INTO user
+                (id, username, password_hash, account_key_cipher, public_key,
+                 private_key_cipher, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             (
                 user.id().as_bytes(),
                 user.username(),
                 user.password_hash(),
+                user.account_key_cipher(),
+                user.public_key(),
+                user.private_key_cipher(),
                 user.created_at().to_rfc3339(),
                 user.updated_at().to_rfc3339(),
             ),
@@ -79,20 +246,116 @@ impl UserRepository {
         }
     }
 
-    /// Busca um usuário pelo seu nome de login.
-    ///
-    /// ### Parâmetros
-    /// - `username`: Nome de usuário desejado.
-    ///
-    /// ### Retorno
-    /// - `Ok(Some(User))` se encontrado.
-    /// - `Ok(None)` quando não existe.
-    /// - `Err(anyhow)` quando ocorre erro de consulta ou parsing de dados.
-    pub fn find_by_username(username: &str) -> Result<Option<User>> {
+    fn update_password_hash(&self, user: &User) -> Result<()> {
+        let conn = self.db.connection()?;
+        Self::write_password_hash(&conn, user)
+    }
+
+    fn update_password(&self, user: &User, vaults: &[Vault]) -> Result<()> {
+        let mut conn = self.db.connection()?;
+        let tx = conn.transaction()?;
+        Self::write_password_hash(&tx, user)?;
+        for vault in vaults {
+            SqliteVaultRepository::write_key_cipher(&tx, vault, vault.key_generation())?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn update_key_pair(&self, user: &User) -> Result<()> {
+        info!(
+            "Gravando par de chaves do usuário. username='{}' id='{}'",
+            user.username(),
+            user.id()
+        );
+
+        let conn = self.db.connection()?;
+        let rows = conn.execute(
+            "UPDATE user SET public_key = ?1, private_key_cipher = ?2, updated_at = ?3
+             WHERE id = ?4",
+            (
+                user.public_key(),
+                user.private_key_cipher(),
+                user.updated_at().to_rfc3339(),
+                user.id().as_bytes(),
+            ),
+        )?;
+
+        if rows != 1 {
+            error!(
+                "Gravação do par de chaves não encontrou o usuário id='{}'",
+                user.id()
+            );
+            return Err(Error::not_found("Usuário não encontrado"));
+        }
+        Ok(())
+    }
+
+    fn update_username(&self, user: &User) -> Result<()> {
+        info!(
+            "Renomeando usuário id='{}' para username='{}'",
+            user.id(),
+            user.username()
+        );
+
+        let conn = self.db.connection()?;
+        let rows = conn.execute(
+            "UPDATE user SET username = ?1, updated_at = ?2 WHERE id = ?3",
+            (
+                user.username(),
+                user.updated_at().to_rfc3339(),
+                user.id().as_bytes(),
+            ),
+        )?;
+
+        if rows != 1 {
+            error!("Renomeação não encontrou o usuário id='{}'", user.id());
+            return Err(Error::not_found("Usuário não encontrado"));
+        }
+        Ok(())
+    }
+
+    fn delete(&self, id: Uuid) -> Result<()> {
+        let start = Instant::now();
+        info!("Excluindo usuário id='{}' e todos os seus dados", id);
+
+        let conn = self.db.connection()?;
+        let rows = conn.execute("DELETE FROM user WHERE id = ?1", [id.as_bytes()])?;
+
+        if rows != 1 {
+            error!("Exclusão não encontrou o usuário id='{}'", id);
+            return Err(Error::not_found("Usuário não encontrado"));
+        }
+
+        info!(
+            "Usuário id='{}' excluído ({} ms)",
+            id,
+            start.elapsed().as_millis()
+        );
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<User>> {
+        debug!("Listando usuários");
+
+        let conn = self.db.connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {USER_COLUMNS} FROM user ORDER BY username ASC"
+        ))?;
+
+        let mut rows = stmt.query([])?;
+        let mut users = Vec::new();
+        while let Some(row) = rows.next()? {
+            users.push(Self::row_to_model(row)?);
+        }
+        Ok(users)
+    }
+
+    fn find_by_username(&self, username: &str) -> Result<Option<User>> {
         let start = Instant::now();
         info!("Iniciando consulta de usuário pelo username='{}'", username);
 
-        let conn = match get_database_connection() {
+        let conn = match self.db.connection() {
             Ok(c) => c,
             Err(err) => {
                 error!(
@@ -103,28 +366,16 @@ impl UserRepository {
             }
         };
 
-        let mut stmt = conn.prepare(
-            "SELECT id, username, password_hash, created_at, updated_at
-             FROM user WHERE username = ?1",
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {USER_COLUMNS} FROM user WHERE username = ?1"
+        ))?;
 
         let mut rows = stmt.query([username])?;
 
         if let Some(row) = rows.next()? {
             debug!("Registro localizado para username='{}'", username);
 
-            let id_bytes: Vec<u8> = row.get(0)?;
-            let id = Uuid::from_slice(&id_bytes)
-                .map_err(|_| anyhow!("UUID inválido no banco de dados"))?;
-
-            let username: String = row.get(1)?;
-            let password_hash: Vec<u8> = row.get(2)?;
-
-            let created_at_str: String = row.get(3)?;
-            let created_at = DateTime::parse_from_rfc3339(&created_at_str)?.with_timezone(&Utc);
-
-            let updated_at_str: String = row.get(4)?;
-            let updated_at = DateTime::parse_from_rfc3339(&updated_at_str)?.with_timezone(&Utc);
+            let user = Self::row_to_model(row)?;
 
             let duration = start.elapsed();
             info!(
@@ -135,10 +386,11 @@ impl UserRepository {
 
             debug!(
                 "Dados carregados: id='{}', created_at='{}', updated_at='{}'",
-                id, created_at, updated_at
+                user.id(),
+                user.created_at(),
+                user.updated_at()
             );
 
-            let user = User::from_persisted(id, username, password_hash, created_at, updated_at);
             Ok(Some(user))
         } else {
             let duration = start.elapsed();
@@ -150,4 +402,17 @@ impl UserRepository {
             Ok(None)
         }
     }
+
+    fn find_by_id(&self, id: Uuid) -> Result<Option<User>> {
+        debug!("Consultando usuário id='{}'", id);
+
+        let conn = self.db.connection()?;
+        let mut stmt = conn.prepare(&format!("SELECT {USER_COLUMNS} FROM user WHERE id = ?1"))?;
+
+        let mut rows = stmt.query([id.as_bytes()])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(Self::row_to_model(row)?)),
+            None => Ok(None),
+        }
+    }
 }
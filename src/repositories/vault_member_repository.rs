@@ -0,0 +1,304 @@
+use chrono::{DateTime, Utc};
+use rusqlite::TransactionBehavior;
+use std::time::Instant;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::infrastructure::database::Database;
+use crate::models::{
+    credential::Credential,
+    credential_history::CredentialHistoryEntry,
+    vault::Vault,
+    vault_member::{VaultMember, VaultRole},
+};
+use crate::repositories::{
+    credential_history_repository::SqliteCredentialHistoryRepository,
+    credential_repository::SqliteCredentialRepository, vault_repository::SqliteVaultRepository,
+};
+
+/// Repositório responsável pelos membros de cofres compartilhados.
+pub trait VaultMemberRepository: Send + Sync {
+    /// Insere um membro no cofre.
+    ///
+    /// ### Parâmetros
+    /// - `member`: Membro com a chave do cofre embrulhada para ele.
+    /// - `key_generation`: Geração da chave do cofre embrulhada.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` em caso de sucesso.
+    /// - `Err(Error)` quando o usuário já for membro, em falha de gravação ou,
+    ///   como conflito, quando a chave do cofre tiver sido trocada.
+    fn create(&self, member: &VaultMember, key_generation: u32) -> Result<()>;
+
+    /// Atualiza o papel de um membro.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando exatamente um registro for atualizado.
+    /// - `Err(Error)` em falha de gravação ou membro inexistente.
+    ///
+    /// ### Aplicação
+    /// A chave embrulhada não é regravada, para que uma troca de chave
+    /// concorrente não seja desfeita; ela só muda em [`Self::revoke`].
+    fn update(&self, member: &VaultMember) -> Result<()>;
+
+    /// Busca o vínculo de um usuário com um cofre.
+    fn find(&self, vault_id: Uuid, user_id: Uuid) -> Result<Option<VaultMember>>;
+
+    /// Lista os membros de um cofre, do mais antigo para o mais recente.
+    fn find_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<VaultMember>>;
+
+    /// Lista os cofres de outros usuários dos quais o usuário é membro.
+    fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<VaultMember>>;
+
+    /// Remove um membro e aplica a nova chave do cofre.
+    ///
+    /// ### Parâmetros
+    /// - `vault`: Cofre com a nova chave já embrulhada para o dono.
+    /// - `previous_generation`: Geração da chave que está sendo substituída.
+    /// - `user_id`: Membro removido.
+    /// - `members`: Membros restantes, com a nova chave embrulhada para cada um.
+    /// - `reseal`: Recifra com a nova chave todas as credenciais do cofre
+    ///   (inclusive na lixeira) e todas as versões anteriores delas.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando todas as escritas forem aplicadas.
+    /// - `Err(Error)` em qualquer falha, ou como conflito se a chave ou os
+    ///   membros do cofre tiverem mudado desde a leitura; nesses casos nenhuma
+    ///   alteração é aplicada.
+    ///
+    /// ### Aplicação
+    /// A remoção, a releitura dos registros cifrados e a troca de chave ocorrem
+    /// na mesma transação, que bloqueia outras gravações até o fim: o cofre
+    /// nunca fica com parte dos registros cifrada com a chave antiga.
+    fn revoke(
+        &self,
+        vault: &Vault,
+        previous_generation: u32,
+        user_id: Uuid,
+        members: &[VaultMember],
+        reseal: &ResealFn<'_>,
+    ) -> Result<()>;
+}
+
+/// Recifra, no lugar, as credenciais e as versões do histórico de um cofre.
+pub type ResealFn<'a> = dyn Fn(&mut [Credential], &mut [CredentialHistoryEntry]) -> Result<()> + 'a;
+
+/// Implementação de [`VaultMemberRepository`] sobre o banco SQLite.
+///
+/// Mantém uma referência ao [`Database`] compartilhado da aplicação e empresta
+/// uma conexão do pool a cada operação.
+#[derive(Clone)]
+pub struct SqliteVaultMemberRepository {
+    db: Database,
+}
+
+impl SqliteVaultMemberRepository {
+    /// Cria o repositório sobre o banco compartilhado da aplicação.
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// Converte uma linha SQL em objeto de domínio.
+    fn row_to_model(row: &rusqlite::Row) -> Result<VaultMember> {
+        Ok(VaultMember::from_persisted(
+            Uuid::from_slice(&row.get::<_, Vec<u8>>(0)?)?,
+            Uuid::from_slice(&row.get::<_, Vec<u8>>(1)?)?,
+            row.get::<_, String>(2)?.parse::<VaultRole>()?,
+            row.get(3)?,
+            DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)?.with_timezone(&Utc),
+            DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)?.with_timezone(&Utc),
+        ))
+    }
+
+    /// Executa uma consulta de membros filtrada pela coluna informada.
+    fn query(&self, column: &str, id: Uuid) -> Result<Vec<VaultMember>> {
+        let conn = self.db.connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT vault_id, user_id, role, vault_key_cipher, created_at, updated_at
+             FROM vault_member WHERE {column} = ?1
+             ORDER BY created_at ASC, rowid ASC"
+        ))?;
+
+        let mut rows = stmt.query([id.as_bytes()])?;
+        let mut members = Vec::new();
+        while let Some(row) = rows.next()? {
+            members.push(Self::row_to_model(row)?);
+        }
+        Ok(members)
+    }
+
+    /// Atualiza o papel e a chave embrulhada de um membro utilizando a conexão
+    /// (ou transação) informada.
+    fn write_update(conn: &rusqlite::Connection, member: &VaultMember) -> Result<()> {
+        let rows = conn.execute(
+            "UPDATE vault_member SET role = ?1, vault_key_cipher = ?2, updated_at = ?3
+             WHERE vault_id = ?4 AND user_id = ?5",
+            (
+                member.role().as_str(),
+                member.vault_key_cipher(),
+                member.updated_at().to_rfc3339(),
+                member.vault_id().as_bytes(),
+                member.user_id().as_bytes(),
+            ),
+        )?;
+
+        if rows != 1 {
+            error!(
+                "Atualização não encontrou o membro user_id='{}' do cofre vault_id='{}'",
+                member.user_id(),
+                member.vault_id()
+            );
+            return Err(Error::not_found("Membro do cofre não encontrado"));
+        }
+        Ok(())
+    }
+}
+
+impl VaultMemberRepository for SqliteVaultMemberRepository {
+    fn create(&self, member: &VaultMember, key_generation: u32) -> Result<()> {
+        info!(
+            "Adicionando membro user_id='{}' ao cofre vault_id='{}' como {}",
+            member.user_id(),
+            member.vault_id(),
+            member.role()
+        );
+
+        let conn = self.db.connection()?;
+        let rows = conn.execute(
+            "INSERT INTO vault_member
+                (vault_id, user_id, role, vault_key_cipher, created_at, updated_at)
+             SELECT ?1, ?2, ?3, ?4, ?5, ?6
+             WHERE EXISTS (SELECT 1 FROM vault WHERE id = ?1 AND key_generation = ?7)",
+            (
+                member.vault_id().as_bytes(),
+                member.user_id().as_bytes(),
+                member.role().as_str(),
+                member.vault_key_cipher(),
+                member.created_at().to_rfc3339(),
+                member.updated_at().to_rfc3339(),
+                key_generation,
+            ),
+        )?;
+
+        if rows != 1 {
+            SqliteVaultRepository::check_key_generation(&conn, member.vault_id(), key_generation)?;
+            return Err(Error::not_found("Cofre do membro não encontrado"));
+        }
+        Ok(())
+    }
+
+    fn update(&self, member: &VaultMember) -> Result<()> {
+        debug!(
+            "Atualizando membro user_id='{}' do cofre vault_id='{}'",
+            member.user_id(),
+            member.vault_id()
+        );
+
+        let conn = self.db.connection()?;
+        let rows = conn.execute(
+            "UPDATE vault_member SET role = ?1, updated_at = ?2 WHERE vault_id = ?3 AND user_id = ?4",
+            (
+                member.role().as_str(),
+                member.updated_at().to_rfc3339(),
+                member.vault_id().as_bytes(),
+                member.user_id().as_bytes(),
+            ),
+        )?;
+
+        if rows != 1 {
+            return Err(Error::not_found("Membro do cofre não encontrado"));
+        }
+        Ok(())
+    }
+
+    fn find(&self, vault_id: Uuid, user_id: Uuid) -> Result<Option<VaultMember>> {
+        let conn = self.db.connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT vault_id, user_id, role, vault_key_cipher, created_at, updated_at
+             FROM vault_member WHERE vault_id = ?1 AND user_id = ?2",
+        )?;
+
+        let mut rows = stmt.query([vault_id.as_bytes(), user_id.as_bytes()])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(Self::row_to_model(row)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn find_by_vault_id(&self, vault_id: Uuid) -> Result<Vec<VaultMember>> {
+        self.query("vault_id", vault_id)
+    }
+
+    fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<VaultMember>> {
+        self.query("user_id", user_id)
+    }
+
+    fn revoke(
+        &self,
+        vault: &Vault,
+        previous_generation: u32,
+        user_id: Uuid,
+        members: &[VaultMember],
+        reseal: &ResealFn<'_>,
+    ) -> Result<()> {
+        let start = Instant::now();
+        info!(
+            "Revogando membro user_id='{}' e trocando a chave do cofre vault_id='{}'",
+            user_id,
+            vault.id()
+        );
+
+        let mut conn = self.db.connection()?;
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+
+        SqliteVaultRepository::write_key_cipher(&tx, vault, previous_generation)?;
+
+        let removed = tx.execute(
+            "DELETE FROM vault_member WHERE vault_id = ?1 AND user_id = ?2",
+            [vault.id().as_bytes(), user_id.as_bytes()],
+        )?;
+        if removed != 1 {
+            return Err(Error::not_found("Membro do cofre não encontrado"));
+        }
+
+        let remaining: usize = tx.query_row(
+            "SELECT COUNT(*) FROM vault_member WHERE vault_id = ?1",
+            [vault.id().as_bytes()],
+            |row| row.get(0),
+        )?;
+        if remaining != members.len() {
+            warn!(
+                "Membros do cofre vault_id='{}' mudaram durante a revogação",
+                vault.id()
+            );
+            return Err(Error::conflict(
+                "Os membros do cofre mudaram; tente novamente",
+            ));
+        }
+        for member in members {
+            Self::write_update(&tx, member)?;
+        }
+
+        let mut credentials = SqliteCredentialRepository::read_vault(&tx, vault.id())?;
+        let mut history = SqliteCredentialHistoryRepository::read_vault(&tx, vault.id())?;
+        reseal(&mut credentials, &mut history)?;
+        for credential in &credentials {
+            SqliteCredentialRepository::write_ciphers(&tx, credential)?;
+        }
+        for entry in &history {
+            SqliteCredentialHistoryRepository::write_ciphers(&tx, entry)?;
+        }
+        tx.commit()?;
+
+        info!(
+            "Chave do cofre vault_id='{}' trocada | membros={} | credenciais={} | versões={} | tempo={}ms",
+            vault.id(),
+            members.len(),
+            credentials.len(),
+            history.len(),
+            start.elapsed().as_millis()
+        );
+        Ok(())
+    }
+}
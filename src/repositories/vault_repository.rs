@@ -1,16 +1,19 @@
-use anyhow::Result;
 use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension};
 use std::time::Instant;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
-use crate::infrastructure::database::get_database_connection;
+use crate::error::{Error, Result};
+use crate::infrastructure::database::Database;
 use crate::models::vault::Vault;
 
-/// Repositório responsável pela persistência e consulta de cofres criptográficos.
-pub struct VaultRepository;
+/// Mensagem das gravações recusadas por terem sido cifradas com uma chave de
+/// cofre já substituída.
+pub(crate) const STALE_KEY_MESSAGE: &str = "A chave do cofre foi trocada; abra o cofre novamente";
 
-impl VaultRepository {
+/// Repositório responsável pela persistência e consulta de cofres criptográficos.
+pub trait VaultRepository: Send + Sync {
     /// Insere um cofre no banco de dados.
     ///
     /// ### Parâmetros
@@ -18,8 +21,174 @@ impl VaultRepository {
     ///
     /// ### Retorno
     /// - `Ok(())` em caso de sucesso.
-    /// - `Err(anyhow)` quando ocorrer erro na gravação.
-    pub fn create(vault: &Vault) -> Result<()> {
+    /// - `Err(Error)` quando ocorrer erro na gravação.
+    fn create(&self, vault: &Vault) -> Result<()>;
+
+    /// Atualiza o nome de um cofre.
+    ///
+    /// ### Parâmetros
+    /// - `vault`: Cofre com o novo nome já aplicado.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando exatamente um registro for atualizado.
+    /// - `Err(Error)` em falha de gravação ou cofre inexistente.
+    ///
+    /// ### Aplicação
+    /// A chave e a sua geração não são gravadas aqui: uma renomeação simultânea
+    /// a uma revogação não pode restaurar a chave antiga.
+    fn update(&self, vault: &Vault) -> Result<()>;
+
+    /// Remove um cofre e, em cascata, suas credenciais, pastas e tags.
+    ///
+    /// ### Parâmetros
+    /// - `id`: Identificador do cofre.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` em caso de sucesso (inclusive quando o cofre não existir).
+    /// - `Err(Error)` em falha de remoção.
+    fn delete(&self, id: Uuid) -> Result<()>;
+
+    /// Busca um cofre pelo ID.
+    ///
+    /// ### Retorno
+    /// - `Ok(Some(Vault))` quando encontrado.
+    /// - `Ok(None)` quando não existir.
+    /// - `Err(Error)` em falha de consulta ou desserialização.
+    fn find_by_id(&self, id: Uuid) -> Result<Option<Vault>>;
+
+    /// Lista os cofres de um usuário, do mais antigo para o mais recente.
+    ///
+    /// ### Parâmetros
+    /// - `user_id`: Identificador do usuário proprietário dos cofres.
+    ///
+    /// ### Retorno
+    /// - `Ok(Vec<Vault>)`: cofres do usuário (vazio quando não houver).
+    /// - `Err(Error)` em falha de consulta ou desserialização.
+    ///
+    /// ### Aplicação
+    /// O primeiro cofre da lista é o aberto por padrão no login.
+    fn find_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Vault>>;
+}
+
+/// Implementação de [`VaultRepository`] sobre o banco SQLite.
+///
+/// Mantém uma referência ao [`Database`] compartilhado da aplicação e empresta
+/// uma conexão do pool a cada operação.
+#[derive(Clone)]
+pub struct SqliteVaultRepository {
+    db: Database,
+}
+
+impl SqliteVaultRepository {
+    /// Cria o repositório sobre o banco compartilhado da aplicação.
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// Converte uma linha SQL em objeto de domínio.
+    fn row_to_model(row: &rusqlite::Row) -> Result<Vault> {
+        Ok(Vault::from_persisted(
+            Uuid::from_slice(&row.get::<_, Vec<u8>>(0)?)?,
+            Uuid::from_slice(&row.get::<_, Vec<u8>>(1)?)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)?.with_timezone(&Utc),
+            DateTime::parse_from_rfc3339(&row.get::<_, String>(6)?)?.with_timezone(&Utc),
+        ))
+    }
+
+    /// Atualiza a chave criptografada de um cofre utilizando a conexão informada.
+    ///
+    /// ### Parâmetros
+    /// - `conn`: Conexão (ou transação) em que a escrita deve ocorrer.
+    /// - `vault`: Cofre com a chave já reembrulhada.
+    /// - `previous_generation`: Geração da chave lida antes da alteração.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando exatamente um registro for atualizado.
+    /// - `Err(Error)` em falha de gravação, cofre inexistente ou, como conflito,
+    ///   quando a chave foi trocada por outra operação desde a leitura.
+    ///
+    /// ### Aplicação
+    /// Usado dentro das transações da migração para a chave da conta
+    /// ([`super::user_repository::SqliteUserRepository`]) e da troca de chave
+    /// de um cofre compartilhado
+    /// ([`super::vault_member_repository::SqliteVaultMemberRepository`]).
+    pub(crate) fn write_key_cipher(
+        conn: &Connection,
+        vault: &Vault,
+        previous_generation: u32,
+    ) -> Result<()> {
+        info!(
+            "Atualizando chave criptografada do cofre vault_id='{}'",
+            vault.id()
+        );
+
+        let rows = conn.execute(
+            "UPDATE vault SET vault_key_cipher = ?1, key_generation = ?2, updated_at = ?3
+             WHERE id = ?4 AND key_generation = ?5",
+            (
+                vault.vault_key_cipher(),
+                vault.key_generation(),
+                vault.updated_at().to_rfc3339(),
+                vault.id().as_bytes(),
+                previous_generation,
+            ),
+        )?;
+
+        if rows != 1 {
+            Self::check_key_generation(conn, vault.id(), previous_generation)?;
+            error!(
+                "Atualização não encontrou o cofre vault_id='{}'",
+                vault.id()
+            );
+            return Err(Error::not_found("Vault não encontrado"));
+        }
+
+        debug!("Chave do cofre atualizada para vault_id='{}'", vault.id());
+        Ok(())
+    }
+
+    /// Confere, na conexão informada, se o cofre ainda está na geração de
+    /// chave esperada.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando a geração coincide ou o cofre não existe (a gravação
+    ///   que consultou informa a ausência).
+    /// - `Err(Error)` como conflito quando a chave foi trocada.
+    ///
+    /// ### Aplicação
+    /// Chamado depois de uma gravação condicionada à geração que não afetou
+    /// nenhuma linha, para distinguir a chave trocada de um registro ausente.
+    pub(crate) fn check_key_generation(
+        conn: &Connection,
+        vault_id: Uuid,
+        key_generation: u32,
+    ) -> Result<()> {
+        let current: Option<u32> = conn
+            .query_row(
+                "SELECT key_generation FROM vault WHERE id = ?1",
+                [vault_id.as_bytes()],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        match current {
+            Some(current) if current != key_generation => {
+                warn!(
+                    "Gravação recusada: cofre vault_id='{}' na geração {} (esperada {})",
+                    vault_id, current, key_generation
+                );
+                Err(Error::conflict(STALE_KEY_MESSAGE))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl VaultRepository for SqliteVaultRepository {
+    fn create(&self, vault: &Vault) -> Result<()> {
         let start = Instant::now();
         info!(
             "Iniciando persistência do cofre. vault_id='{}' user_id='{}'",
@@ -27,7 +196,7 @@ impl VaultRepository {
             vault.user_id()
         );
 
-        let conn = match get_database_connection() {
+        let conn = match self.db.connection() {
             Ok(c) => c,
             Err(err) => {
                 error!("Falha ao obter conexão com banco: {}", err);
@@ -36,12 +205,15 @@ impl VaultRepository {
         };
 
         let result = conn.execute(
-            "INSERT INTO vault (id, user_id, vault_key_cipher, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
+            "INSERT INTO vault
+                (id, user_id, name, vault_key_cipher, key_generation, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
             (
                 vault.id().as_bytes(),
                 vault.user_id().as_bytes(),
+                vault.name(),
                 vault.vault_key_cipher(),
+                vault.key_generation(),
                 vault.created_at().to_rfc3339(),
                 vault.updated_at().to_rfc3339(),
             ),
@@ -63,20 +235,75 @@ impl VaultRepository {
         }
     }
 
-    /// Busca um cofre pelo ID do usuário associado.
-    ///
-    /// ### Parâmetros
-    /// - `user_id`: Identificador do usuário proprietário do cofre.
-    ///
-    /// ### Retorno
-    /// - `Ok(Some(Vault))` quando encontrado.
-    /// - `Ok(None)` quando não existir.
-    /// - `Err(anyhow)` em falha de consulta ou desserialização.
-    pub fn find_by_user_id(user_id: Uuid) -> Result<Option<Vault>> {
+    fn update(&self, vault: &Vault) -> Result<()> {
+        info!(
+            "Atualizando cofre vault_id='{}' name='{}'",
+            vault.id(),
+            vault.name()
+        );
+
+        let conn = self.db.connection()?;
+        let rows = conn.execute(
+            "UPDATE vault SET name = ?1, updated_at = ?2 WHERE id = ?3",
+            (
+                vault.name(),
+                vault.updated_at().to_rfc3339(),
+                vault.id().as_bytes(),
+            ),
+        )?;
+
+        if rows != 1 {
+            error!(
+                "Atualização não encontrou o cofre vault_id='{}'",
+                vault.id()
+            );
+            return Err(Error::not_found("Vault não encontrado"));
+        }
+
+        debug!("Cofre atualizado vault_id='{}'", vault.id());
+        Ok(())
+    }
+
+    fn delete(&self, id: Uuid) -> Result<()> {
+        let start = Instant::now();
+        info!("Removendo cofre vault_id='{}'", id);
+
+        let conn = self.db.connection()?;
+        let rows = conn.execute("DELETE FROM vault WHERE id = ?1", [id.as_bytes()])?;
+
+        info!(
+            "Remoção de cofre concluída vault_id='{}' linhas={} ({} ms)",
+            id,
+            rows,
+            start.elapsed().as_millis()
+        );
+        Ok(())
+    }
+
+    fn find_by_id(&self, id: Uuid) -> Result<Option<Vault>> {
+        debug!("Consultando cofre vault_id='{}'", id);
+
+        let conn = self.db.connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, user_id, name, vault_key_cipher, key_generation, created_at, updated_at
+             FROM vault WHERE id = ?1",
+        )?;
+
+        let mut rows = stmt.query([id.as_bytes()])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(Self::row_to_model(row)?)),
+            None => {
+                warn!("Nenhum cofre encontrado para vault_id='{}'", id);
+                Ok(None)
+            }
+        }
+    }
+
+    fn find_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Vault>> {
         let start = Instant::now();
-        info!("Iniciando consulta de cofre por user_id='{}'", user_id);
+        info!("Iniciando consulta de cofres por user_id='{}'", user_id);
 
-        let conn = match get_database_connection() {
+        let conn = match self.db.connection() {
             Ok(c) => c,
             Err(err) => {
                 error!("Falha ao obter conexão com banco: {}", err);
@@ -85,38 +312,23 @@ impl VaultRepository {
         };
 
         let mut stmt = conn.prepare(
-            "SELECT id, user_id, vault_key_cipher, created_at, updated_at
-             FROM vault WHERE user_id = ?1",
+            "SELECT id, user_id, name, vault_key_cipher, key_generation, created_at, updated_at
+             FROM vault WHERE user_id = ?1
+             ORDER BY created_at ASC, rowid ASC",
         )?;
 
         let mut rows = stmt.query([user_id.as_bytes()])?;
-
-        if let Some(row) = rows.next()? {
-            debug!("Registro localizado para user_id='{}'", user_id);
-
-            let vault = Vault::from_persisted(
-                Uuid::from_slice(&row.get::<_, Vec<u8>>(0)?)?,
-                Uuid::from_slice(&row.get::<_, Vec<u8>>(1)?)?,
-                row.get(2)?,
-                DateTime::parse_from_rfc3339(&row.get::<_, String>(3)?)?.with_timezone(&Utc),
-                DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)?.with_timezone(&Utc),
-            );
-
-            info!(
-                "Consulta concluída. Cofre encontrado para user_id='{}' ({} ms)",
-                user_id,
-                start.elapsed().as_millis()
-            );
-
-            return Ok(Some(vault));
+        let mut vaults = Vec::new();
+        while let Some(row) = rows.next()? {
+            vaults.push(Self::row_to_model(row)?);
         }
 
-        warn!(
-            "Nenhum cofre encontrado para user_id='{}' ({} ms)",
+        info!(
+            "Consulta concluída. {} cofre(s) encontrado(s) para user_id='{}' ({} ms)",
+            vaults.len(),
             user_id,
             start.elapsed().as_millis()
         );
-
-        Ok(None)
+        Ok(vaults)
     }
 }
@@ -1,40 +1,363 @@
-use anyhow::{Result, anyhow};
-use rand::RngCore;
-use rand_core::OsRng;
-use tracing::{debug, info};
+use chrono::{DateTime, TimeDelta, Utc};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::{
+    cell::Cell,
+    env,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 use zeroize::Zeroize;
 
 use crate::{
+    error::{Error, Result},
     infrastructure::crypto::{
-        decrypt_with_passphrase, encrypt_with_passphrase, hash_password, verify_password,
+        decrypt_with_identity, decrypt_with_key, decrypt_with_passphrase, encrypt_with_key,
+        encrypt_with_passphrase, generate_key_pair, generate_vault_key, hash_password,
+        password_hash_needs_rehash, verify_password,
+    },
+    models::{
+        login_attempt::LoginAttempt,
+        user::User,
+        vault::{DEFAULT_VAULT_NAME, Vault},
+        vault_member::{VaultMember, VaultRole},
     },
-    models::{user::User, vault::Vault},
-    repositories::{user_repository::UserRepository, vault_repository::VaultRepository},
+    repositories::{
+        login_attempt_repository::LoginAttemptRepository, user_repository::UserRepository,
+        vault_member_repository::VaultMemberRepository, vault_repository::VaultRepository,
+    },
+    services::credential_service::CredentialService,
 };
 
+/// Tempo padrão de inatividade até o bloqueio da sessão (5 minutos).
+const DEFAULT_IDLE_TIMEOUT_SECS: u64 = 5 * 60;
+
+/// Tempo de vida máximo padrão de uma sessão (8 horas).
+const DEFAULT_MAX_LIFETIME_SECS: u64 = 8 * 60 * 60;
+
+/// Mensagem única para qualquer falha de autenticação, evitando enumeração de usuários.
+const LOGIN_FAILED_MESSAGE: &str = "Usuário ou senha inválidos, ou login temporariamente bloqueado";
+
+/// Falhas consecutivas toleradas antes do primeiro bloqueio temporário.
+const FREE_LOGIN_ATTEMPTS: u32 = 3;
+
+/// Duração do primeiro bloqueio; dobra a cada nova falha.
+const BASE_LOCKOUT_SECS: i64 = 30;
+
+/// Duração máxima de um bloqueio (15 minutos).
+const MAX_LOCKOUT_SECS: i64 = 15 * 60;
+
+/// Hash Argon2 verificado quando o usuário não existe ou está bloqueado, para
+/// que todas as respostas de login tenham custo equivalente.
+static DUMMY_PASSWORD_HASH: Lazy<Vec<u8>> = Lazy::new(|| {
+    hash_password("password-manager-dummy").expect("Falha ao gerar hash de referência")
+});
+
+/// Calcula o bloqueio aplicado após `failed_count` falhas consecutivas.
+///
+/// Até [`FREE_LOGIN_ATTEMPTS`] não há bloqueio; a partir daí a duração cresce
+/// exponencialmente, limitada a [`MAX_LOCKOUT_SECS`].
+fn lockout_for(failed_count: u32) -> Option<TimeDelta> {
+    if failed_count < FREE_LOGIN_ATTEMPTS {
+        return None;
+    }
+
+    let exponent = (failed_count - FREE_LOGIN_ATTEMPTS).min(16);
+    let secs = BASE_LOCKOUT_SECS
+        .saturating_mul(1 << exponent)
+        .min(MAX_LOCKOUT_SECS);
+    Some(TimeDelta::seconds(secs))
+}
+
+/// Limites de tempo aplicados a uma sessão autenticada.
+///
+/// Os valores podem ser configurados pelas variáveis de ambiente:
+/// ```env
+/// SESSION_IDLE_TIMEOUT_SECS=300
+/// SESSION_MAX_LIFETIME_SECS=28800
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SessionPolicy {
+    /// Inatividade máxima antes de a sessão ser bloqueada.
+    pub idle_timeout: Duration,
+    /// Duração máxima da sessão, contada a partir do login.
+    pub max_lifetime: Duration,
+}
+
+impl SessionPolicy {
+    /// Carrega a política a partir do ambiente, usando os valores padrão quando
+    /// as variáveis estiverem ausentes ou forem inválidas.
+    pub fn from_env() -> Self {
+        Self {
+            idle_timeout: duration_from_env("SESSION_IDLE_TIMEOUT_SECS", DEFAULT_IDLE_TIMEOUT_SECS),
+            max_lifetime: duration_from_env("SESSION_MAX_LIFETIME_SECS", DEFAULT_MAX_LIFETIME_SECS),
+        }
+    }
+}
+
+impl Default for SessionPolicy {
+    fn default() -> Self {
+        Self {
+            idle_timeout: Duration::from_secs(DEFAULT_IDLE_TIMEOUT_SECS),
+            max_lifetime: Duration::from_secs(DEFAULT_MAX_LIFETIME_SECS),
+        }
+    }
+}
+
+/// Lê uma duração em segundos de uma variável de ambiente.
+fn duration_from_env(var: &str, default_secs: u64) -> Duration {
+    let secs = match env::var(var) {
+        Ok(value) => value.trim().parse::<u64>().unwrap_or_else(|_| {
+            warn!("Valor inválido para {var}='{value}', usando {default_secs}s");
+            default_secs
+        }),
+        Err(_) => default_secs,
+    };
+    Duration::from_secs(secs)
+}
+
+/// Estado de uma sessão em relação à sua política de tempo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionState {
+    /// Sessão utilizável.
+    Active,
+    /// Sessão bloqueada (por inatividade ou manualmente); requer a senha mestre.
+    Locked,
+    /// Tempo de vida máximo excedido; requer um novo login.
+    Expired,
+}
+
 /// Estrutura de sessão autenticada contendo dados necessários para operações seguras.
+///
+/// A senha mestre não é mantida na sessão: ela serve apenas para desembrulhar a
+/// chave da conta, que por sua vez abre a `vault_key` do cofre aberto — a chave
+/// efetivamente usada para cifrar as credenciais. A chave da conta permanece na
+/// sessão para permitir alternar entre os cofres do usuário sem a senha mestre.
+///
+/// Toda operação do [`CredentialService`] chama [`Session::ensure_active`], que
+/// renova o instante da última atividade ou recusa a operação quando a sessão
+/// está bloqueada ou expirada. As operações que alteram o cofre chamam
+/// [`Session::ensure_writable`], que também recusa o papel de leitor em cofres
+/// compartilhados; o serviço confere ainda, no armazenamento, se o papel
+/// guardado na sessão continua valendo, e o repositório só aplica gravações
+/// cifradas com a geração da chave guardada nela.
 #[derive(Debug, Clone)]
 pub struct Session {
     pub user: User,
     pub vault_id: Uuid,
     pub vault_key: Vec<u8>,
-    pub passphrase: String,
+    vault_role: VaultRole,
+    vault_generation: u32,
+    account_key: Vec<u8>,
+    policy: SessionPolicy,
+    created_at: Instant,
+    last_activity: Cell<Instant>,
+    locked: bool,
+}
+
+impl Session {
+    /// Cria uma sessão ativa, no papel de dono, a partir das chaves da conta e
+    /// do cofre já desembrulhadas.
+    fn new(
+        user: User,
+        account_key: Vec<u8>,
+        vault: &Vault,
+        vault_key: Vec<u8>,
+        policy: SessionPolicy,
+    ) -> Self {
+        let now = Instant::now();
+        Self {
+            user,
+            vault_id: vault.id(),
+            vault_key,
+            vault_role: VaultRole::Owner,
+            vault_generation: vault.key_generation(),
+            account_key,
+            policy,
+            created_at: now,
+            last_activity: Cell::new(now),
+            locked: false,
+        }
+    }
+
+    /// Retorna o estado atual da sessão sem registrar atividade.
+    pub fn state(&self) -> SessionState {
+        let now = Instant::now();
+
+        if now.duration_since(self.created_at) >= self.policy.max_lifetime {
+            SessionState::Expired
+        } else if self.locked
+            || now.duration_since(self.last_activity.get()) >= self.policy.idle_timeout
+        {
+            SessionState::Locked
+        } else {
+            SessionState::Active
+        }
+    }
+
+    /// Garante que a sessão está ativa e registra a atividade atual.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando a sessão pode ser utilizada.
+    /// - `Err(Error)` quando a sessão estiver bloqueada ou expirada.
+    pub fn ensure_active(&self) -> Result<()> {
+        match self.state() {
+            SessionState::Active => {
+                self.last_activity.set(Instant::now());
+                Ok(())
+            }
+            SessionState::Locked => Err(Error::unauthorized("Sessão bloqueada")),
+            SessionState::Expired => Err(Error::unauthorized("Sessão expirada")),
+        }
+    }
+
+    /// Papel do usuário no cofre aberto.
+    pub fn vault_role(&self) -> VaultRole {
+        self.vault_role
+    }
+
+    /// Garante que a sessão está ativa e que o papel no cofre aberto permite
+    /// alterá-lo.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` para o dono e editores de uma sessão ativa.
+    /// - `Err(Error)` quando a sessão estiver bloqueada ou expirada, ou para leitores.
+    pub fn ensure_writable(&self) -> Result<()> {
+        self.ensure_active()?;
+
+        if !self.vault_role.can_edit() {
+            return Err(Error::unauthorized("Acesso somente leitura a este cofre"));
+        }
+        Ok(())
+    }
+
+    /// Bloqueia a sessão, apagando as chaves da conta e do cofre da memória.
+    ///
+    /// O contexto (usuário e cofre) é preservado; as chaves são restauradas por
+    /// [`AuthService::unlock`] mediante a senha mestre.
+    pub fn lock(&mut self) {
+        if !self.locked {
+            debug!("Bloqueando sessão de '{}'", self.user.username());
+        }
+        self.vault_key.zeroize();
+        self.vault_key.clear();
+        self.account_key.zeroize();
+        self.account_key.clear();
+        self.locked = true;
+    }
+
+    /// Chave da conta, usada para desembrulhar as chaves dos cofres do usuário.
+    pub(crate) fn account_key(&self) -> &[u8] {
+        &self.account_key
+    }
+
+    /// Geração da chave do cofre aberto no momento em que a sessão o abriu.
+    pub(crate) fn vault_generation(&self) -> u32 {
+        self.vault_generation
+    }
+
+    /// Passa a sessão para outro cofre, apagando a chave do cofre anterior.
+    pub(crate) fn open_vault(&mut self, vault: &Vault, vault_key: Vec<u8>, role: VaultRole) {
+        self.vault_key.zeroize();
+        self.vault_id = vault.id();
+        self.vault_key = vault_key;
+        self.vault_role = role;
+        self.vault_generation = vault.key_generation();
+    }
 }
 
 /// Garante que informações sensíveis sejam apagadas da memória ao final da sessão.
 impl Drop for Session {
     fn drop(&mut self) {
         self.vault_key.zeroize();
-        self.passphrase.zeroize();
+        self.account_key.zeroize();
     }
 }
 
+/// Desembrulha a chave de um cofre com a chave da conta.
+///
+/// ### Retorno
+/// - `Ok(Vec<u8>)`: chave do cofre; o chamador deve aplicar `zeroize` após o uso.
+/// - `Err(Error)`: quando a chave da conta não abre o cofre.
+pub(crate) fn unwrap_vault_key(account_key: &[u8], vault: &Vault) -> Result<Vec<u8>> {
+    decrypt_with_key(account_key, vault.vault_key_cipher()).map_err(|_| {
+        Error::crypto(format!(
+            "Não foi possível abrir a chave do cofre '{}'",
+            vault.name()
+        ))
+    })
+}
+
+/// Desembrulha a chave de um cofre compartilhado com a chave privada do membro.
+///
+/// ### Parâmetros
+/// - `account_key`: Chave da conta do membro, que abre a sua chave privada.
+/// - `user`: Membro, com o par de chaves X25519.
+/// - `member`: Vínculo com a chave do cofre embrulhada para o membro.
+///
+/// ### Retorno
+/// - `Ok(Vec<u8>)`: chave do cofre; o chamador deve aplicar `zeroize` após o uso.
+/// - `Err(Error)`: usuário sem par de chaves ou chave embrulhada para outro destinatário.
+pub(crate) fn unwrap_member_key(
+    account_key: &[u8],
+    user: &User,
+    member: &VaultMember,
+) -> Result<Vec<u8>> {
+    let cipher = user
+        .private_key_cipher()
+        .ok_or_else(|| Error::crypto("Usuário sem chave de compartilhamento"))?;
+
+    let mut private_key = decrypt_with_key(account_key, cipher)?;
+    let vault_key = decrypt_with_identity(&private_key, member.vault_key_cipher());
+    private_key.zeroize();
+    vault_key.map_err(|_| Error::crypto("Não foi possível abrir a chave do cofre compartilhado"))
+}
+
+/// Usuário cadastrado, sem o hash da senha nem as chaves.
+#[derive(Debug, Clone, Serialize)]
+pub struct UserSummary {
+    pub id: Uuid,
+    pub username: String,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Serviço responsável pelos fluxos de autenticação e registro de usuários.
-pub struct AuthService;
+#[derive(Clone)]
+pub struct AuthService {
+    users: Arc<dyn UserRepository>,
+    vaults: Arc<dyn VaultRepository>,
+    members: Arc<dyn VaultMemberRepository>,
+    login_attempts: Arc<dyn LoginAttemptRepository>,
+    credentials: CredentialService,
+}
 
 impl AuthService {
-    /// Registra um novo usuário e cria automaticamente um cofre vinculado.
+    /// Cria o serviço sobre os repositórios informados.
+    ///
+    /// ### Parâmetros
+    /// - `users`, `vaults`, `members`, `login_attempts`: Repositórios consultados
+    ///   na autenticação e no desbloqueio.
+    /// - `credentials`: Serviço usado para recifrar credenciais legadas no login.
+    pub fn new(
+        users: Arc<dyn UserRepository>,
+        vaults: Arc<dyn VaultRepository>,
+        members: Arc<dyn VaultMemberRepository>,
+        login_attempts: Arc<dyn LoginAttemptRepository>,
+        credentials: CredentialService,
+    ) -> Self {
+        Self {
+            users,
+            vaults,
+            members,
+            login_attempts,
+            credentials,
+        }
+    }
+
+    /// Registra um novo usuário e cria automaticamente um cofre vinculado
+    /// ([`DEFAULT_VAULT_NAME`]).
     ///
     /// ### Parâmetros
     /// - `username`: Identificador textual do usuário.
@@ -42,43 +365,49 @@ impl AuthService {
     ///
     /// ### Retorno
     /// - `Ok(())` em caso de sucesso.
-    /// - `Err(anyhow)` quando o nome já estiver em uso ou ocorrer falha no processo.
+    /// - `Err(Error)` quando o nome já estiver em uso ou ocorrer falha no processo.
     ///
     /// ### Aplicação
     /// Utilizado no fluxo inicial de criação de contas, gerando o usuário e seu cofre seguro.
-    pub fn register(username: &str, password: &str) -> Result<()> {
+    pub fn register(&self, username: &str, password: &str) -> Result<()> {
         info!(
             "Iniciando processo de registro para username='{}'",
             username
         );
 
-        if UserRepository::find_by_username(username)?.is_some() {
+        if self.users.find_by_username(username)?.is_some() {
             info!("Registro interrompido: username '{}' já existe", username);
-            return Err(anyhow!("Nome de usuário já está em uso"));
+            return Err(Error::conflict("Nome de usuário já está em uso"));
         }
 
         info!("Gerando hash de senha para o novo usuário...");
         let password_hash = hash_password(password)?;
 
-        info!("Gerando chave do cofre...");
-        let mut vault_key = [0u8; 32];
-        OsRng.fill_bytes(&mut vault_key);
+        info!("Gerando chaves da conta, do cofre e de compartilhamento...");
+        let mut account_key = generate_vault_key();
+        let mut vault_key = generate_vault_key();
+        let (public_key, mut private_key) = generate_key_pair();
 
-        info!("Protegendo chave do cofre com a senha do usuário...");
-        let vault_cipher = encrypt_with_passphrase(password, &vault_key)?;
+        info!("Protegendo chave da conta com a senha do usuário...");
+        let account_cipher = encrypt_with_passphrase(password, &account_key);
+        let vault_cipher = encrypt_with_key(&account_key, &vault_key);
+        let private_cipher = encrypt_with_key(&account_key, &private_key);
+        account_key.zeroize();
         vault_key.zeroize();
+        private_key.zeroize();
 
         info!("Criando entidade de usuário no domínio...");
-        let user = User::new(username.to_string(), password_hash)?;
+        let mut user = User::new(username.to_string(), password_hash, account_cipher?)?;
+        user.set_key_pair(public_key, private_cipher?)?;
 
         info!("Persistindo usuário no repositório...");
-        UserRepository::create(&user)?;
+        self.users.create(&user)?;
 
         info!("Criando entidade de cofre no domínio...");
-        let vault = Vault::new(user.id(), vault_cipher)?;
+        let vault = Vault::new(user.id(), DEFAULT_VAULT_NAME.to_string(), vault_cipher?)?;
 
         info!("Persistindo cofre vinculado ao usuário...");
-        VaultRepository::create(&vault)?;
+        self.vaults.create(&vault)?;
 
         info!("Usuário '{}' registrado com sucesso.", username);
         Ok(())
@@ -92,60 +421,518 @@ impl AuthService {
     ///
     /// ### Retorno
     /// - `Ok(Session)` quando as credenciais estiverem corretas.
-    /// - `Err(anyhow)` quando o usuário não existir, a senha estiver incorreta,
-    ///   ou o cofre associado não for localizado.
+    /// - `Err(Error)` com uma mensagem genérica quando o usuário não existir, a senha
+    ///   estiver incorreta ou o login estiver temporariamente bloqueado; ou quando o
+    ///   cofre associado não for localizado.
+    ///
+    /// ### Segurança
+    /// Falhas consecutivas são persistidas por usuário e, após
+    /// [`FREE_LOGIN_ATTEMPTS`] tentativas, o login é bloqueado por um período que
+    /// dobra a cada nova falha. Usuários inexistentes passam por uma verificação
+    /// Argon2 fictícia, de modo que o tempo de resposta não revele sua existência.
     ///
     /// ### Aplicação
     /// Utilizado no acesso ao sistema, retornando a chave necessária para operações
-    /// criptográficas no cofre associado ao usuário autenticado.
-    pub fn login(username: &str, password: &str) -> Result<Session> {
+    /// criptográficas no cofre mais antigo do usuário; os demais são abertos com
+    /// [`crate::services::vault_service::VaultService::switch`]. Contas sem chave
+    /// da conta (criadas antes dos múltiplos cofres) são migradas neste momento.
+    pub fn login(&self, username: &str, password: &str) -> Result<Session> {
         info!("Iniciando processo de login para username='{}'", username);
 
-        let user = match UserRepository::find_by_username(username)? {
-            Some(u) => {
-                info!("Usuário encontrado. Verificando credenciais...");
-                u
-            }
-            None => {
-                info!("Falha de login: usuário '{}' não encontrado", username);
-                return Err(anyhow!("Usuário não encontrado"));
-            }
+        let Some(mut user) = self.users.find_by_username(username)? else {
+            info!("Falha de login: usuário '{}' não encontrado", username);
+            // Mantém o custo do Argon2 para que o tempo de resposta não revele
+            // a existência do usuário.
+            let _ = verify_password(password, &DUMMY_PASSWORD_HASH);
+            return Err(Error::unauthorized(LOGIN_FAILED_MESSAGE));
         };
 
-        if !verify_password(password, user.password_hash())? {
+        info!("Usuário encontrado. Verificando credenciais...");
+        self.verify_master_password(&user, password)?;
+
+        if let Err(err) = self.rehash_if_outdated(&mut user, password) {
+            warn!(
+                "Não foi possível atualizar o hash de senha de '{}': {}",
+                username, err
+            );
+        }
+
+        info!("Credenciais válidas. Localizando cofres vinculados...");
+        let mut vaults = self.vaults.find_all_by_user_id(user.id())?;
+        if vaults.is_empty() {
             info!(
-                "Falha de login: senha incorreta para username='{}'",
+                "Falha de login: nenhum cofre associado ao usuário '{}'",
                 username
             );
-            return Err(anyhow!("Senha incorreta"));
-        }
-
-        info!("Credenciais válidas. Localizando cofre vinculado...");
-        let vault = match VaultRepository::find_by_user_id(user.id())? {
-            Some(v) => v,
-            None => {
-                info!(
-                    "Falha de login: nenhum cofre associado ao usuário '{}'",
-                    username
-                );
-                return Err(anyhow!("Vault não encontrado para o usuário"));
+            return Err(Error::not_found("Vault não encontrado para o usuário"));
+        }
+
+        info!("Descriptografando chave da conta...");
+        let mut account_key = match user.account_key_cipher() {
+            Some(cipher) => decrypt_with_passphrase(password, cipher)?,
+            None => self.migrate_to_account_key(&mut user, &mut vaults, password)?,
+        };
+
+        if user.public_key().is_none()
+            && let Err(err) = self.create_key_pair(&mut user, &account_key)
+        {
+            account_key.zeroize();
+            return Err(err);
+        }
+
+        let vault = &vaults[0];
+        let vault_key = match unwrap_vault_key(&account_key, vault) {
+            Ok(key) => key,
+            Err(err) => {
+                account_key.zeroize();
+                return Err(err);
             }
         };
 
-        info!("Descriptografando chave do cofre...");
-        let mut vault_key = decrypt_with_passphrase(password, vault.vault_key_cipher())?;
+        let session = Session::new(
+            user,
+            account_key,
+            vault,
+            vault_key,
+            SessionPolicy::from_env(),
+        );
+        debug!("Chave do Vault descriptografada com sucesso na memória da sessão.");
+
+        let migrated = self
+            .credentials
+            .migrate_legacy_ciphers(&session, password)?;
+        if migrated > 0 {
+            info!(
+                "{} credencial(is) recifrada(s) com a chave do cofre para '{}'",
+                migrated, username
+            );
+        }
+        self.credentials.purge_expired_trash(&session)?;
 
         info!("Sessão autenticada criada com sucesso para '{}'", username);
+        Ok(session)
+    }
 
-        let session = Session {
-            user,
-            vault_id: vault.id(),
-            vault_key: vault_key.clone(),
-            passphrase: password.to_string(),
+    /// Altera a senha mestre do usuário autenticado.
+    ///
+    /// ### Parâmetros
+    /// - `session`: Sessão autenticada do usuário.
+    /// - `old_password`: Senha mestre atual, exigida como confirmação.
+    /// - `new_password`: Nova senha mestre.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando hash e chave da conta forem atualizados.
+    /// - `Err(Error)` quando a senha atual estiver incorreta, a nova senha for
+    ///   inválida ou ocorrer falha de persistência.
+    ///
+    /// ### Aplicação
+    /// Como as chaves dos cofres são protegidas pela chave da conta, basta gerar
+    /// um novo hash Argon2 e reembrulhar a mesma chave da conta com a nova senha;
+    /// nenhum cofre ou credencial é recifrado. As duas escritas ocorrem na mesma
+    /// transação: ou ambas são aplicadas, ou nenhuma.
+    pub fn change_password(
+        &self,
+        session: &Session,
+        old_password: &str,
+        new_password: &str,
+    ) -> Result<()> {
+        session.ensure_active()?;
+
+        let username = session.user.username();
+        info!("Iniciando troca de senha para username='{}'", username);
+
+        if new_password.is_empty() {
+            return Err(Error::validation(
+                "new_password",
+                "A nova senha não pode ser vazia",
+            ));
+        }
+
+        let mut user = self.confirm_password(session, old_password)?;
+
+        info!("Reembrulhando chave da conta com a nova senha...");
+        user.set_account_key_cipher(encrypt_with_passphrase(
+            new_password,
+            session.account_key(),
+        )?)?;
+
+        info!("Gerando novo hash de senha...");
+        user.set_password_hash(hash_password(new_password)?)?;
+
+        self.users.update_password(&user, &[])?;
+
+        info!("Senha alterada com sucesso para '{}'", username);
+        Ok(())
+    }
+
+    /// Lista os usuários cadastrados, ordenados pelo nome.
+    ///
+    /// ### Retorno
+    /// - `Ok(Vec<UserSummary>)`: usuários, sem hash nem chaves.
+    /// - `Err(Error)`: sessão inativa ou falha de consulta.
+    ///
+    /// ### Aplicação
+    /// Exige uma sessão ativa: listar as contas antes do login revelaria quais
+    /// nomes existem, o que a mensagem genérica do [`Self::login`] evita. Em
+    /// uma máquina compartilhada, quem já entrou vê as demais contas, por
+    /// exemplo para escolher a quem compartilhar um cofre.
+    pub fn list_users(&self, session: &Session) -> Result<Vec<UserSummary>> {
+        session.ensure_active()?;
+
+        Ok(self
+            .users
+            .list()?
+            .into_iter()
+            .map(|user| UserSummary {
+                id: user.id(),
+                username: user.username().to_string(),
+                created_at: user.created_at(),
+            })
+            .collect())
+    }
+
+    /// Altera o nome de usuário da conta autenticada.
+    ///
+    /// ### Parâmetros
+    /// - `session`: Sessão autenticada; passa a exibir o novo nome.
+    /// - `password`: Senha mestre, exigida como confirmação.
+    /// - `new_username`: Novo nome, único e sujeito às regras do cadastro.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando o nome for alterado.
+    /// - `Err(Error)` quando a senha estiver incorreta ou o nome for inválido
+    ///   ou já estiver em uso.
+    ///
+    /// ### Aplicação
+    /// O login passa a usar o novo nome; cofres, chaves e vínculos de membro
+    /// referenciam o ID do usuário e não são alterados.
+    pub fn rename_user(
+        &self,
+        session: &mut Session,
+        password: &str,
+        new_username: &str,
+    ) -> Result<()> {
+        session.ensure_active()?;
+
+        let mut user = self.confirm_password(session, password)?;
+        let new_username = new_username.trim();
+        if new_username == user.username() {
+            return Ok(());
+        }
+        if self.users.find_by_username(new_username)?.is_some() {
+            return Err(Error::conflict("Nome de usuário já está em uso"));
+        }
+
+        info!(
+            "Renomeando usuário '{}' para '{}'",
+            user.username(),
+            new_username
+        );
+        user.set_username(new_username.to_string())?;
+        self.users.update_username(&user)?;
+
+        session.user = user;
+        Ok(())
+    }
+
+    /// Exclui definitivamente a conta autenticada com todos os seus dados.
+    ///
+    /// ### Parâmetros
+    /// - `session`: Sessão autenticada; é bloqueada após a exclusão.
+    /// - `password`: Senha mestre, exigida como confirmação.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando a conta for excluída.
+    /// - `Err(Error)` quando a senha estiver incorreta ou em falha de remoção.
+    ///
+    /// ### Aplicação
+    /// Remove os cofres do usuário (com credenciais, histórico, pastas e tags),
+    /// as tentativas de login e os vínculos com cofres de outros usuários.
+    /// Os membros dos cofres compartilhados pelo usuário perdem o acesso a eles.
+    pub fn delete_account(&self, session: &mut Session, password: &str) -> Result<()> {
+        session.ensure_active()?;
+
+        let user = self.confirm_password(session, password)?;
+
+        let shared_members: usize = self
+            .vaults
+            .find_all_by_user_id(user.id())?
+            .iter()
+            .map(|vault| Ok(self.members.find_by_vault_id(vault.id())?.len()))
+            .sum::<Result<usize>>()?;
+        if shared_members > 0 {
+            warn!(
+                "Excluindo '{}': {} membro(s) perderão o acesso aos cofres compartilhados",
+                user.username(),
+                shared_members
+            );
+        }
+
+        info!("Excluindo conta do usuário '{}'", user.username());
+        self.users.delete(user.id())?;
+
+        session.lock();
+        Ok(())
+    }
+
+    /// Desbloqueia uma sessão bloqueada mediante a senha mestre.
+    ///
+    /// ### Parâmetros
+    /// - `session`: Sessão bloqueada (ou ociosa) a ser reativada.
+    /// - `password`: Senha mestre do usuário da sessão.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando as chaves da conta e do cofre forem restauradas.
+    /// - `Err(Error)` quando a sessão estiver expirada, ou com a mesma mensagem
+    ///   genérica do login quando a senha for incorreta ou o usuário estiver
+    ///   temporariamente bloqueado.
+    ///
+    /// ### Segurança
+    /// As falhas contam para o mesmo bloqueio progressivo do [`Self::login`]:
+    /// uma sessão bloqueada não permite tentar senhas sem limite.
+    ///
+    /// ### Aplicação
+    /// Permite que a interface retome o ponto em que estava, sem um novo login,
+    /// após o bloqueio automático por inatividade. Se o cofre aberto era
+    /// compartilhado e o acesso foi revogado (ou o cofre excluído) enquanto a
+    /// sessão estava bloqueada, o cofre mais antigo do usuário é aberto no lugar.
+    /// O usuário é localizado pelo ID, de modo que um nome alterado em outra
+    /// sessão é apenas atualizado nesta.
+    pub fn unlock(&self, session: &mut Session, password: &str) -> Result<()> {
+        let username = session.user.username().to_string();
+        info!(
+            "Iniciando desbloqueio de sessão para username='{}'",
+            username
+        );
+
+        if session.state() == SessionState::Expired {
+            session.lock();
+            return Err(Error::unauthorized("Sessão expirada"));
+        }
+
+        let user = self
+            .users
+            .find_by_id(session.user.id())?
+            .ok_or_else(|| Error::not_found("Usuário não encontrado"))?;
+
+        self.verify_master_password(&user, password)?;
+
+        let cipher = user
+            .account_key_cipher()
+            .ok_or_else(|| Error::not_found("Chave da conta não encontrada"))?;
+        let mut account_key = decrypt_with_passphrase(password, cipher)?;
+        let reopened = self.reopen_vault(&user, &account_key, session.vault_id);
+        let (vault, vault_key, role) = match reopened {
+            Ok(opened) => opened,
+            Err(err) => {
+                account_key.zeroize();
+                return Err(err);
+            }
         };
 
-        vault_key.zeroize();
-        debug!("Chave do Vault descriptografada com sucesso na memória da sessão.");
-        Ok(session)
+        session.account_key.zeroize();
+        session.account_key = std::mem::take(&mut account_key);
+        session.open_vault(&vault, vault_key, role);
+        session.user = user;
+        session.locked = false;
+        session.last_activity.set(Instant::now());
+
+        info!("Sessão desbloqueada para '{}'", session.user.username());
+        Ok(())
+    }
+
+    /// Verifica a senha mestre de um usuário aplicando o bloqueio progressivo
+    /// por falhas consecutivas.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando a senha estiver correta; o contador de falhas é zerado.
+    /// - `Err(Error)` com [`LOGIN_FAILED_MESSAGE`] quando a senha estiver
+    ///   incorreta (a falha é registrada) ou o usuário estiver bloqueado.
+    ///
+    /// ### Aplicação
+    /// Compartilhado por todos os fluxos que recebem a senha mestre, para que
+    /// nenhum deles sirva de atalho às tentativas limitadas do login.
+    fn verify_master_password(&self, user: &User, password: &str) -> Result<()> {
+        let now = Utc::now();
+        let mut attempt = self
+            .login_attempts
+            .find_by_user_id(user.id())?
+            .map_or_else(|| LoginAttempt::new(user.id()), Ok)?;
+
+        if attempt.is_locked_at(now) {
+            warn!(
+                "Senha recusada: username='{}' bloqueado até {:?} após {} falhas",
+                user.username(),
+                attempt.locked_until(),
+                attempt.failed_count()
+            );
+            let _ = verify_password(password, &DUMMY_PASSWORD_HASH);
+            return Err(Error::unauthorized(LOGIN_FAILED_MESSAGE));
+        }
+
+        if !verify_password(password, user.password_hash())? {
+            let locked_until = lockout_for(attempt.failed_count() + 1).map(|d| now + d);
+            attempt.record_failure(now, locked_until);
+            self.login_attempts.save(&attempt)?;
+
+            info!(
+                "Senha incorreta para username='{}' (falhas={})",
+                user.username(),
+                attempt.failed_count()
+            );
+            return Err(Error::unauthorized(LOGIN_FAILED_MESSAGE));
+        }
+
+        if attempt.failed_count() > 0 {
+            self.login_attempts.delete(user.id())?;
+        }
+        Ok(())
+    }
+
+    /// Recarrega o usuário da sessão, confirmando a senha mestre informada.
+    ///
+    /// ### Retorno
+    /// - `Ok(User)`: usuário atual, como persistido.
+    /// - `Err(Error)`: usuário inexistente ou, com [`LOGIN_FAILED_MESSAGE`],
+    ///   senha incorreta ou conta temporariamente bloqueada.
+    ///
+    /// ### Aplicação
+    /// As falhas contam para o mesmo bloqueio do login, pois uma sessão aberta
+    /// não deve permitir testar senhas sem limite.
+    fn confirm_password(&self, session: &Session, password: &str) -> Result<User> {
+        let user = self
+            .users
+            .find_by_id(session.user.id())?
+            .ok_or_else(|| Error::not_found("Usuário não encontrado"))?;
+
+        self.verify_master_password(&user, password)?;
+        Ok(user)
+    }
+
+    /// Abre novamente o cofre de uma sessão desbloqueada, com o papel atual do
+    /// usuário, ou o cofre mais antigo dele quando perdeu o acesso.
+    ///
+    /// ### Retorno
+    /// - `Ok((Vault, Vec<u8>, VaultRole))`: cofre aberto, sua chave e o papel do usuário.
+    /// - `Err(Error)`: nenhum cofre disponível ou falha ao desembrulhar a chave.
+    fn reopen_vault(
+        &self,
+        user: &User,
+        account_key: &[u8],
+        vault_id: Uuid,
+    ) -> Result<(Vault, Vec<u8>, VaultRole)> {
+        if let Some(vault) = self.vaults.find_by_id(vault_id)? {
+            if vault.user_id() == user.id() {
+                let key = unwrap_vault_key(account_key, &vault)?;
+                return Ok((vault, key, VaultRole::Owner));
+            }
+            if let Some(member) = self.members.find(vault.id(), user.id())? {
+                let key = unwrap_member_key(account_key, user, &member)?;
+                return Ok((vault, key, member.role()));
+            }
+        }
+
+        warn!(
+            "Cofre vault_id='{}' indisponível para '{}'; abrindo o cofre padrão",
+            vault_id,
+            user.username()
+        );
+        let vault = self
+            .vaults
+            .find_all_by_user_id(user.id())?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::not_found("Vault não encontrado para o usuário"))?;
+        let key = unwrap_vault_key(account_key, &vault)?;
+        Ok((vault, key, VaultRole::Owner))
+    }
+
+    /// Gera o par de chaves X25519 de um usuário anterior aos cofres
+    /// compartilhados, cifrando a chave privada com a chave da conta.
+    ///
+    /// ### Aplicação
+    /// Executado uma única vez, no primeiro login após a atualização; até lá o
+    /// usuário não pode ser convidado para cofres compartilhados.
+    fn create_key_pair(&self, user: &mut User, account_key: &[u8]) -> Result<()> {
+        info!(
+            "Gerando par de chaves de compartilhamento para '{}'...",
+            user.username()
+        );
+
+        let (public_key, mut private_key) = generate_key_pair();
+        let private_cipher = encrypt_with_key(account_key, &private_key);
+        private_key.zeroize();
+
+        user.set_key_pair(public_key, private_cipher?)?;
+        self.users.update_key_pair(user)
+    }
+
+    /// Cria a chave da conta de um usuário legado, cujas chaves de cofre ainda
+    /// estão embrulhadas diretamente com a senha mestre.
+    ///
+    /// ### Parâmetros
+    /// - `user`: Usuário sem chave da conta; recebe a nova chave embrulhada.
+    /// - `vaults`: Todos os cofres do usuário; suas chaves são reembrulhadas.
+    /// - `password`: Senha mestre já verificada.
+    ///
+    /// ### Retorno
+    /// - `Ok(Vec<u8>)`: chave da conta em texto puro, para a sessão.
+    /// - `Err(Error)`: falha ao abrir algum cofre ou ao persistir.
+    ///
+    /// ### Aplicação
+    /// Executado uma única vez, no primeiro login após a atualização. A chave da
+    /// conta e as chaves dos cofres são gravadas na mesma transação.
+    fn migrate_to_account_key(
+        &self,
+        user: &mut User,
+        vaults: &mut [Vault],
+        password: &str,
+    ) -> Result<Vec<u8>> {
+        info!(
+            "Criando chave da conta para '{}' e reembrulhando {} cofre(s)...",
+            user.username(),
+            vaults.len()
+        );
+
+        let mut account_key = generate_vault_key();
+
+        for vault in vaults.iter_mut() {
+            let mut vault_key = decrypt_with_passphrase(password, vault.vault_key_cipher())?;
+            let wrapped = encrypt_with_key(&account_key, &vault_key);
+            vault_key.zeroize();
+            vault.set_vault_key_cipher(wrapped?)?;
+        }
+
+        user.set_account_key_cipher(encrypt_with_passphrase(password, &account_key)?)?;
+        self.users.update_password(user, vaults)?;
+
+        let key = account_key.to_vec();
+        account_key.zeroize();
+        debug!("Chave da conta criada para '{}'", user.username());
+        Ok(key)
+    }
+
+    /// Recalcula e persiste o hash da senha quando ele foi gerado com parâmetros
+    /// Argon2 diferentes da configuração atual.
+    ///
+    /// ### Aplicação
+    /// Chamado após um login bem-sucedido, único momento em que a senha em
+    /// texto puro está disponível. Falhas não impedem o login.
+    fn rehash_if_outdated(&self, user: &mut User, password: &str) -> Result<()> {
+        if !password_hash_needs_rehash(user.password_hash())? {
+            return Ok(());
+        }
+
+        info!(
+            "Hash de senha de '{}' usa parâmetros desatualizados; recalculando...",
+            user.username()
+        );
+
+        user.set_password_hash(hash_password(password)?)?;
+
+        self.users.update_password_hash(user)?;
+
+        debug!("Hash de senha atualizado para '{}'", user.username());
+        Ok(())
     }
 }
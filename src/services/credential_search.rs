@@ -0,0 +1,288 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::credential::Credential;
+
+/// Pontuação de uma correspondência exata com o campo inteiro.
+const SCORE_EXACT: u32 = 1000;
+
+/// Pontuação de um campo que começa com o termo.
+const SCORE_PREFIX: u32 = 900;
+
+/// Pontuação de uma palavra do campo que começa com o termo.
+const SCORE_WORD_PREFIX: u32 = 800;
+
+/// Pontuação do termo contido em qualquer posição do campo.
+const SCORE_SUBSTRING: u32 = 700;
+
+/// Pontuação de uma palavra a uma edição do termo (descontada por edição).
+const SCORE_TYPO: u32 = 600;
+
+/// Pontuação máxima de uma subsequência (letras do termo em ordem, com lacunas).
+const SCORE_SUBSEQUENCE: u32 = 300;
+
+/// Pontuação mínima de uma subsequência, por mais espaçada que seja.
+const SCORE_SUBSEQUENCE_MIN: u32 = 100;
+
+/// Opções de uma pesquisa de credenciais.
+///
+/// Campos ausentes na desserialização assumem os valores de [`Default`]
+/// (apenas nome, usuário e URL, sem correspondência aproximada).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchOptions {
+    /// Inclui as notas, descriptografadas em memória com a chave da sessão.
+    pub include_notes: bool,
+    /// Aceita erros de digitação e letras fora de sequência.
+    pub fuzzy: bool,
+}
+
+/// Campo da credencial em que o termo foi encontrado.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchField {
+    Name,
+    Username,
+    Url,
+    Notes,
+}
+
+impl MatchField {
+    /// Nome legível do campo.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Name => "nome",
+            Self::Username => "usuário",
+            Self::Url => "URL",
+            Self::Notes => "notas",
+        }
+    }
+
+    /// Desconto aplicado à pontuação: o nome pesa mais que usuário e URL, e estes
+    /// mais que as notas.
+    fn penalty(&self) -> u32 {
+        match self {
+            Self::Name => 0,
+            Self::Username | Self::Url => 50,
+            Self::Notes => 150,
+        }
+    }
+}
+
+/// Credencial encontrada em uma pesquisa.
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub credential: Credential,
+    /// Campo com a melhor correspondência.
+    pub field: MatchField,
+    /// Relevância (maior é melhor).
+    pub score: u32,
+}
+
+/// Avaliador de um termo de pesquisa contra os campos de uma credencial.
+///
+/// Termo e campos são comparados sem diferenciar maiúsculas nem acentos.
+pub struct Matcher {
+    query: Vec<char>,
+    fuzzy: bool,
+}
+
+impl Matcher {
+    /// Prepara o termo de pesquisa.
+    ///
+    /// ### Parâmetros
+    /// - `query`: Termo digitado pelo usuário.
+    /// - `fuzzy`: Aceita erros de digitação e subsequências.
+    pub fn new(query: &str, fuzzy: bool) -> Self {
+        Self {
+            query: normalize(query.trim()),
+            fuzzy,
+        }
+    }
+
+    /// Pontua um texto contra o termo.
+    ///
+    /// ### Retorno
+    /// - `Some(u32)`: relevância da correspondência.
+    /// - `None`: o texto não corresponde ao termo.
+    pub fn score(&self, text: &str) -> Option<u32> {
+        let query = &self.query;
+        if query.is_empty() {
+            return None;
+        }
+
+        let text = normalize(text);
+
+        if text == *query {
+            return Some(SCORE_EXACT);
+        }
+        if text.starts_with(query) {
+            return Some(SCORE_PREFIX);
+        }
+
+        let mut occurrences = text
+            .windows(query.len())
+            .enumerate()
+            .filter(|(_, window)| window == query)
+            .map(|(index, _)| index)
+            .peekable();
+
+        if occurrences.peek().is_some() {
+            let at_word_start =
+                occurrences.any(|index| index == 0 || !text[index - 1].is_alphanumeric());
+            return Some(if at_word_start {
+                SCORE_WORD_PREFIX
+            } else {
+                SCORE_SUBSTRING
+            });
+        }
+
+        if !self.fuzzy {
+            return None;
+        }
+
+        self.typo_score(&text)
+            .or_else(|| subsequence_score(&text, query))
+    }
+
+    /// Pontua uma credencial, escolhendo o campo com a melhor correspondência.
+    ///
+    /// ### Parâmetros
+    /// - `credential`: Credencial avaliada (nome, usuário e URL).
+    /// - `notes`: Notas já descriptografadas, quando a pesquisa as inclui.
+    pub fn best_match(
+        &self,
+        credential: &Credential,
+        notes: Option<&str>,
+    ) -> Option<(MatchField, u32)> {
+        [
+            (MatchField::Name, Some(credential.name())),
+            (MatchField::Username, credential.username()),
+            (MatchField::Url, credential.url()),
+            (MatchField::Notes, notes),
+        ]
+        .into_iter()
+        .filter_map(|(field, text)| {
+            let score = self.score(text?)?;
+            Some((field, score.saturating_sub(field.penalty())))
+        })
+        .max_by_key(|&(_, score)| score)
+    }
+
+    /// Compara o termo com cada palavra do texto (e com o início das palavras
+    /// mais longas), tolerando uma edição em termos de quatro a sete letras e
+    /// duas a partir de oito.
+    fn typo_score(&self, text: &[char]) -> Option<u32> {
+        let query = &self.query;
+        let max_edits = match query.len() {
+            0..4 => return None,
+            4..8 => 1,
+            _ => 2,
+        };
+
+        words(text)
+            .filter_map(|word| {
+                let whole = edit_distance(query, word);
+                let head = word
+                    .get(..query.len())
+                    .map_or(whole, |head| edit_distance(query, head));
+                Some(whole.min(head)).filter(|&d| d <= max_edits)
+            })
+            .min()
+            .map(|edits| SCORE_TYPO - 100 * edits as u32)
+    }
+}
+
+/// Ordena os resultados por relevância e, em caso de empate, pelo nome.
+pub fn rank(hits: &mut [SearchHit]) {
+    hits.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.credential.name().cmp(b.credential.name()))
+    });
+}
+
+/// Converte para minúsculas e remove acentos, como nas comparações do [`Matcher`].
+///
+/// ### Aplicação
+/// Registrada como a função SQL `fold` em cada conexão do banco, para que a
+/// consulta de candidatos da pesquisa ignore acentos da mesma forma.
+pub fn fold(text: &str) -> String {
+    normalize(text).into_iter().collect()
+}
+
+/// Converte para minúsculas e remove acentos.
+fn normalize(text: &str) -> Vec<char> {
+    text.chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ã' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'õ' | 'ö' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'ç' => 'c',
+            'ñ' => 'n',
+            other => other,
+        })
+        .collect()
+}
+
+/// Palavras (sequências alfanuméricas) do texto.
+fn words(text: &[char]) -> impl Iterator<Item = &[char]> {
+    text.split(|c| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+}
+
+/// Pontua o termo como subsequência do texto, descontando as letras intercaladas.
+///
+/// Termos com menos de três letras não são avaliados, pois corresponderiam a
+/// quase qualquer texto.
+fn subsequence_score(text: &[char], query: &[char]) -> Option<u32> {
+    if query.len() < 3 {
+        return None;
+    }
+
+    let mut chars = text.iter().enumerate();
+    let mut previous: Option<usize> = None;
+    let mut gaps = 0;
+
+    for wanted in query {
+        let (index, _) = chars.find(|(_, c)| *c == wanted)?;
+        if let Some(previous) = previous {
+            gaps += index - previous - 1;
+        }
+        previous = Some(index);
+    }
+
+    let penalty = (gaps as u32 * 10).min(SCORE_SUBSEQUENCE - SCORE_SUBSEQUENCE_MIN);
+    Some(SCORE_SUBSEQUENCE - penalty)
+}
+
+/// Distância de edição entre dois textos, contando a troca de duas letras vizinhas
+/// como uma única edição (distância OSA).
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
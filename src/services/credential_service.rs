@@ -1,24 +1,222 @@
-use anyhow::{Result, anyhow};
-use tracing::info;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 use zeroize::Zeroize;
 
 use crate::{
-    infrastructure::crypto::{decrypt_with_passphrase, encrypt_with_passphrase},
+    error::{Error, Result},
+    importers::{ImportIssue, ImportReport, ParsedImport},
+    infrastructure::crypto::{
+        decrypt_with_key, decrypt_with_passphrase, encrypt_with_key, encrypt_with_passphrase,
+        is_passphrase_cipher,
+    },
+    infrastructure::pwned_passwords::PwnedPasswords,
+    infrastructure::totp::{Totp, TotpCode},
     models::credential::Credential,
+    models::credential_history::CredentialHistoryEntry,
+    models::folder::{Folder, PATH_SEPARATOR},
+    models::tag::Tag,
+    models::vault::Vault,
+    repositories::credential_history_repository::CredentialHistoryRepository,
     repositories::credential_repository::CredentialRepository,
+    repositories::folder_repository::FolderRepository,
+    repositories::tag_repository::TagRepository,
+    repositories::vault_member_repository::VaultMemberRepository,
+    repositories::vault_repository::VaultRepository,
     services::auth_service::Session,
+    services::credential_search::{self, Matcher, SearchHit, SearchOptions},
+    services::security_report::{ReportBuilder, ReportOptions, SecurityReport},
+    services::vault_archive::{ArchivedCredential, ImportSummary, VaultArchive},
+    services::vault_service::TransferMode,
 };
 
+/// Quantidade padrão de versões anteriores mantidas por credencial.
+pub const DEFAULT_HISTORY_RETENTION: usize = 10;
+
+/// Regras do histórico de senhas das credenciais.
+///
+/// Os valores podem ser configurados pelas variáveis de ambiente:
+/// ```env
+/// PASSWORD_HISTORY_RETENTION=10
+/// PASSWORD_HISTORY_NOTES=false
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct HistoryPolicy {
+    /// Versões anteriores mantidas por credencial (zero desativa o histórico).
+    pub retention: usize,
+    /// Registra também as notas substituídas, além da senha.
+    pub include_notes: bool,
+}
+
+impl HistoryPolicy {
+    /// Carrega a política a partir do ambiente, usando os valores padrão quando
+    /// as variáveis estiverem ausentes ou forem inválidas.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let retention = match env::var("PASSWORD_HISTORY_RETENTION") {
+            Ok(value) => value.trim().parse::<usize>().unwrap_or_else(|_| {
+                warn!(
+                    "Valor inválido para PASSWORD_HISTORY_RETENTION='{value}', usando {}",
+                    defaults.retention
+                );
+                defaults.retention
+            }),
+            Err(_) => defaults.retention,
+        };
+
+        let include_notes = env::var("PASSWORD_HISTORY_NOTES")
+            .map(|value| matches!(value.trim(), "1" | "true" | "TRUE" | "True"))
+            .unwrap_or(defaults.include_notes);
+
+        Self {
+            retention,
+            include_notes,
+        }
+    }
+}
+
+impl Default for HistoryPolicy {
+    fn default() -> Self {
+        Self {
+            retention: DEFAULT_HISTORY_RETENTION,
+            include_notes: false,
+        }
+    }
+}
+
+/// Dias padrão que uma credencial permanece na lixeira antes da exclusão definitiva.
+pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+
+/// Regras da lixeira de credenciais.
+///
+/// O prazo pode ser configurado pela variável de ambiente:
+/// ```env
+/// TRASH_RETENTION_DAYS=30
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TrashPolicy {
+    /// Dias na lixeira antes da exclusão automática (zero mantém até esvaziar manualmente).
+    pub retention_days: u32,
+}
+
+impl TrashPolicy {
+    /// Carrega a política a partir do ambiente, usando o valor padrão quando a
+    /// variável estiver ausente ou for inválida.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let retention_days = match env::var("TRASH_RETENTION_DAYS") {
+            Ok(value) => value.trim().parse::<u32>().unwrap_or_else(|_| {
+                warn!(
+                    "Valor inválido para TRASH_RETENTION_DAYS='{value}', usando {}",
+                    defaults.retention_days
+                );
+                defaults.retention_days
+            }),
+            Err(_) => defaults.retention_days,
+        };
+
+        Self { retention_days }
+    }
+}
+
+impl Default for TrashPolicy {
+    fn default() -> Self {
+        Self {
+            retention_days: DEFAULT_TRASH_RETENTION_DAYS,
+        }
+    }
+}
+
+/// Versão anterior de uma credencial, já descriptografada para exibição.
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryVersion {
+    pub id: Uuid,
+    /// Senha substituída (`None` quando a senha não mudou nesta versão).
+    pub password: Option<String>,
+    /// Notas substituídas (`None` quando as notas não mudaram ou não são registradas).
+    pub notes: Option<String>,
+    /// Instante em que os valores foram substituídos.
+    pub replaced_at: DateTime<Utc>,
+}
+
+/// Pasta do cofre com os dados usados para exibi-la em árvore.
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderSummary {
+    pub folder: Folder,
+    /// Caminho completo a partir da raiz (ex.: `Trabalho/Servidores`).
+    pub path: String,
+    /// Profundidade na árvore (zero para pastas da raiz).
+    pub depth: usize,
+    /// Quantidade de credenciais diretamente na pasta.
+    pub credentials: usize,
+}
+
+/// Tag do cofre com a quantidade de credenciais marcadas.
+#[derive(Debug, Clone, Serialize)]
+pub struct TagSummary {
+    pub tag: Tag,
+    pub credentials: usize,
+}
+
 /// Serviço responsável pelas operações de criação, atualização, consulta,
-/// remoção e exposição controlada de credenciais protegidas em um cofre.
-pub struct CredentialService;
+/// remoção e exposição controlada de credenciais protegidas em um cofre,
+/// incluindo sua organização em pastas e tags, o histórico de senhas e a lixeira.
+#[derive(Clone)]
+pub struct CredentialService {
+    credentials: Arc<dyn CredentialRepository>,
+    history: Arc<dyn CredentialHistoryRepository>,
+    folders: Arc<dyn FolderRepository>,
+    tags: Arc<dyn TagRepository>,
+    vaults: Arc<dyn VaultRepository>,
+    members: Arc<dyn VaultMemberRepository>,
+    history_policy: HistoryPolicy,
+    trash_policy: TrashPolicy,
+    pwned: Option<Arc<PwnedPasswords>>,
+}
 
 impl CredentialService {
+    /// Cria o serviço sobre os repositórios de credenciais, histórico, pastas e
+    /// tags informados.
+    ///
+    /// `vaults` e `members` são consultados antes de cada alteração para
+    /// conferir o acesso guardado na sessão. `pwned` é a base local de senhas
+    /// vazadas; sem ela, as verificações de vazamento ficam desativadas.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        credentials: Arc<dyn CredentialRepository>,
+        history: Arc<dyn CredentialHistoryRepository>,
+        folders: Arc<dyn FolderRepository>,
+        tags: Arc<dyn TagRepository>,
+        vaults: Arc<dyn VaultRepository>,
+        members: Arc<dyn VaultMemberRepository>,
+        history_policy: HistoryPolicy,
+        trash_policy: TrashPolicy,
+        pwned: Option<PwnedPasswords>,
+    ) -> Self {
+        Self {
+            credentials,
+            history,
+            folders,
+            tags,
+            vaults,
+            members,
+            history_policy,
+            trash_policy,
+            pwned: pwned.map(Arc::new),
+        }
+    }
+
     /// Registra uma nova credencial no cofre do usuário autenticado.
     ///
     /// ### Parâmetros
-    /// - `session`: Sessão autenticada que contém o `vault_id` e a passphrase.
+    /// - `session`: Sessão autenticada que contém o `vault_id` e a chave do cofre.
     /// - `name`: Nome da credencial (ex.: "GitHub").
     /// - `username`: Nome de usuário associado (opcional).
     /// - `url`: URL de acesso à aplicação ou serviço (opcional).
@@ -27,11 +225,12 @@ impl CredentialService {
     ///
     /// ### Retorno
     /// - `Ok(Credential)`: credencial criada e persistida com sucesso.
-    /// - `Err(anyhow)`: falha de validação, criptografia ou persistência.
+    /// - `Err(Error)`: falha de validação, criptografia ou persistência.
     ///
     /// ### Aplicação
     /// Utilizado durante o processo de inclusão de uma credencial pelo usuário.
     pub fn create(
+        &self,
         session: &Session,
         name: &str,
         username: Option<String>,
@@ -39,22 +238,21 @@ impl CredentialService {
         notes: Option<Vec<u8>>,
         password: Option<&str>,
     ) -> Result<Credential> {
+        self.ensure_writable(session)?;
+
         info!(
             "Criando credencial name='{}' para vault_id='{}'",
             name, session.vault_id
         );
 
         let cipher = if let Some(pwd) = password {
-            Some(encrypt_with_passphrase(
-                &session.passphrase,
-                pwd.as_bytes(),
-            )?)
+            Some(encrypt_with_key(&session.vault_key, pwd.as_bytes())?)
         } else {
             None
         };
 
         let notes_cipher = if let Some(n) = notes {
-            Some(encrypt_with_passphrase(&session.passphrase, &n)?)
+            Some(encrypt_with_key(&session.vault_key, &n)?)
         } else {
             None
         };
@@ -68,7 +266,8 @@ impl CredentialService {
             cipher,
         )?;
 
-        CredentialRepository::create(&credential)?;
+        self.credentials
+            .create(&credential, session.vault_generation())?;
         info!(
             "Credencial criada com sucesso id='{}' name='{}'",
             credential.id(),
@@ -90,11 +289,15 @@ impl CredentialService {
     ///
     /// ### Retorno
     /// - `Ok(())`: atualização realizada com sucesso.
-    /// - `Err(anyhow)`: credencial pertencente a outro cofre ou falha de persistência.
+    /// - `Err(Error)`: credencial pertencente a outro cofre ou falha de persistência.
     ///
     /// ### Aplicação
-    /// Utilizado quando o usuário edita qualquer informação da credencial.
+    /// Utilizado quando o usuário edita qualquer informação da credencial. Uma
+    /// senha (ou notas, conforme a [`HistoryPolicy`]) diferente da atual tem o
+    /// valor substituído guardado no histórico antes da gravação.
+    #[allow(clippy::too_many_arguments)]
     pub fn update(
+        &self,
         session: &Session,
         mut credential: Credential,
         name: Option<String>,
@@ -103,6 +306,8 @@ impl CredentialService {
         notes: Option<Vec<u8>>,
         password: Option<&str>,
     ) -> Result<()> {
+        self.ensure_writable(session)?;
+
         info!(
             "Atualizando credencial id='{}' name='{}'",
             credential.id(),
@@ -110,9 +315,27 @@ impl CredentialService {
         );
 
         if credential.vault_id() != session.vault_id {
-            return Err(anyhow!("Registro não disponível no momento"));
+            return Err(Error::not_found("Registro não disponível no momento"));
         }
 
+        let password_changed = match password {
+            Some(pwd) => {
+                !Self::cipher_matches(session, credential.password_cipher(), pwd.as_bytes())?
+            }
+            None => false,
+        };
+        let notes_changed = match &notes {
+            Some(value) => !Self::cipher_matches(session, credential.notes(), value)?,
+            None => false,
+        };
+
+        self.record_history(
+            session,
+            &credential,
+            password_changed,
+            notes_changed && self.history_policy.include_notes,
+        )?;
+
         if let Some(value) = name {
             credential.set_name(value)?;
         }
@@ -122,73 +345,179 @@ impl CredentialService {
         if let Some(value) = url {
             credential.set_url(Some(value));
         }
-        if let Some(value) = notes {
-            credential.set_notes(Some(encrypt_with_passphrase(&session.passphrase, &value)?));
+        if let Some(value) = notes.filter(|_| notes_changed) {
+            credential.set_notes(Some(encrypt_with_key(&session.vault_key, &value)?));
         }
-        if let Some(pwd) = password {
-            credential.set_password_cipher(Some(encrypt_with_passphrase(
-                &session.passphrase,
-                pwd.as_bytes(),
-            )?));
+        if let Some(pwd) = password.filter(|_| password_changed) {
+            credential
+                .set_password_cipher(Some(encrypt_with_key(&session.vault_key, pwd.as_bytes())?));
         }
 
-        CredentialRepository::update(&credential)?;
+        self.credentials
+            .update(&credential, session.vault_generation())?;
         info!("Credencial atualizada com sucesso id='{}'", credential.id());
         Ok(())
     }
 
-    /// Remove uma credencial do cofre.
+    /// Move uma credencial do cofre para a lixeira.
     ///
     /// ### Parâmetros
     /// - `id`: Identificador único da credencial.
     ///
     /// ### Retorno
-    /// - `Ok(())`: registro removido (ou inexistente sem erro).
-    /// - `Err(anyhow)`: falha no processo de remoção.
-    pub fn delete(session: &Session, id: Uuid) -> Result<()> {
+    /// - `Ok(())`: registro movido para a lixeira (ou inexistente sem erro).
+    /// - `Err(Error)`: falha no processo de remoção.
+    ///
+    /// ### Aplicação
+    /// A credencial deixa de aparecer em listagens e buscas, mas pode ser
+    /// recuperada com [`CredentialService::restore`] até ser excluída
+    /// definitivamente ([`CredentialService::purge`]) ou expirar na lixeira.
+    pub fn delete(&self, session: &Session, id: Uuid) -> Result<()> {
+        self.ensure_writable(session)?;
+
         info!("Solicitação de remoção credencial id='{}'", id);
 
-        if let Some(cred) = CredentialRepository::find_by_id(id)? {
+        if let Some(mut cred) = self.credentials.find_by_id(id)? {
             if cred.vault_id() != session.vault_id {
-                return Err(anyhow!("Registro não disponível no momento"));
+                return Err(Error::not_found("Registro não disponível no momento"));
+            }
+            if cred.is_trashed() {
+                return Ok(());
             }
 
-            CredentialRepository::delete(id)?;
-            info!("Credencial removida com sucesso id='{}'", id);
+            cred.set_deleted_at(Some(Utc::now()));
+            self.credentials.update(&cred, session.vault_generation())?;
+            info!("Credencial movida para a lixeira id='{}'", id);
             return Ok(());
         }
 
         Ok(())
     }
 
+    /// Lista as credenciais na lixeira, das removidas mais recentemente às mais antigas.
+    ///
+    /// ### Retorno
+    /// - `Ok(Vec<Credential>)`: podendo retornar lista vazia.
+    /// - `Err(Error)`: falha durante a consulta.
+    ///
+    /// ### Aplicação
+    /// Antes da consulta, exclui definitivamente as credenciais cujo prazo na
+    /// lixeira ([`TrashPolicy`]) expirou.
+    pub fn list_trash(&self, session: &Session) -> Result<Vec<Credential>> {
+        session.ensure_active()?;
+
+        self.purge_expired_trash(session)?;
+
+        info!("Listando lixeira para vault_id='{}'", session.vault_id);
+        self.credentials.find_trashed_by_vault_id(session.vault_id)
+    }
+
+    /// Retira uma credencial da lixeira, devolvendo-a ao cofre.
+    ///
+    /// ### Retorno
+    /// - `Ok(Credential)`: credencial restaurada.
+    /// - `Err(Error)`: credencial inexistente, de outro cofre ou fora da lixeira.
+    pub fn restore(&self, session: &Session, cred_id: Uuid) -> Result<Credential> {
+        self.ensure_writable(session)?;
+        let mut cred = self.trashed(session, cred_id)?;
+
+        info!("Restaurando credencial da lixeira id='{}'", cred_id);
+        cred.set_deleted_at(None);
+        self.credentials.update(&cred, session.vault_generation())?;
+        Ok(cred)
+    }
+
+    /// Exclui definitivamente uma credencial da lixeira, com seu histórico e tags.
+    ///
+    /// ### Retorno
+    /// - `Ok(())`: credencial excluída.
+    /// - `Err(Error)`: credencial inexistente, de outro cofre ou fora da lixeira.
+    pub fn purge(&self, session: &Session, cred_id: Uuid) -> Result<()> {
+        self.ensure_writable(session)?;
+        let cred = self.trashed(session, cred_id)?;
+
+        info!("Excluindo definitivamente credencial id='{}'", cred.id());
+        self.credentials.delete(cred.id())
+    }
+
+    /// Exclui definitivamente todas as credenciais da lixeira.
+    ///
+    /// ### Retorno
+    /// - `Ok(usize)`: quantidade de credenciais excluídas.
+    /// - `Err(Error)`: falha de remoção.
+    pub fn empty_trash(&self, session: &Session) -> Result<usize> {
+        self.ensure_writable(session)?;
+
+        info!("Esvaziando lixeira do vault_id='{}'", session.vault_id);
+        self.credentials.purge_trashed(session.vault_id, None)
+    }
+
+    /// Exclui definitivamente as credenciais que estão na lixeira há mais tempo
+    /// que o prazo da [`TrashPolicy`].
+    ///
+    /// ### Retorno
+    /// - `Ok(usize)`: quantidade de credenciais excluídas (zero quando o prazo é zero).
+    /// - `Err(Error)`: falha de remoção.
+    ///
+    /// ### Aplicação
+    /// Executado automaticamente no login e ao listar a lixeira. Leitores de um
+    /// cofre compartilhado (e sessões que já não podem alterá-lo) não excluem
+    /// nada; a limpeza ocorre quando o dono ou um editor abre o cofre.
+    pub fn purge_expired_trash(&self, session: &Session) -> Result<usize> {
+        session.ensure_active()?;
+
+        if self.trash_policy.retention_days == 0 || self.ensure_writable(session).is_err() {
+            return Ok(0);
+        }
+
+        let before =
+            Utc::now() - chrono::Duration::days(i64::from(self.trash_policy.retention_days));
+        let purged = self
+            .credentials
+            .purge_trashed(session.vault_id, Some(before))?;
+        if purged > 0 {
+            info!(
+                "{} credencial(is) expirada(s) excluída(s) da lixeira do vault_id='{}'",
+                purged, session.vault_id
+            );
+        }
+        Ok(purged)
+    }
+
     /// Lista todas as credenciais pertencentes ao cofre do usuário autenticado.
     ///
     /// ### Retorno
     /// - `Ok(Vec<Credential>)`: podendo retornar lista vazia.
-    /// - `Err(anyhow)`: falha durante a consulta.
-    pub fn list(session: &Session) -> Result<Vec<Credential>> {
+    /// - `Err(Error)`: falha durante a consulta.
+    pub fn list(&self, session: &Session) -> Result<Vec<Credential>> {
+        session.ensure_active()?;
+
         info!("Listando credenciais para vault_id='{}'", session.vault_id);
-        Ok(CredentialRepository::find_all_by_vault_id(
-            session.vault_id,
-        )?)
+        self.credentials.find_all_by_vault_id(session.vault_id)
     }
 
     /// Recupera uma credencial específica, garantindo propriedade do cofre.
     ///
+    /// Credenciais na lixeira não são retornadas.
+    ///
     /// ### Parâmetros
     /// - `cred_id`: Identificador da credencial consultada.
     ///
     /// ### Retorno
     /// - `Ok(Credential)` quando localizada.
-    /// - `Err(anyhow)` quando não existir ou não pertencer ao usuário logado.
-    pub fn get(session: &Session, cred_id: Uuid) -> Result<Credential> {
+    /// - `Err(Error)` quando não existir ou não pertencer ao usuário logado.
+    pub fn get(&self, session: &Session, cred_id: Uuid) -> Result<Credential> {
+        session.ensure_active()?;
+
         info!("Consultando dados da credencial id='{}'", cred_id);
 
-        let cred = CredentialRepository::find_by_id(cred_id)?
-            .ok_or_else(|| anyhow!("Registro não disponível no momento"))?;
+        let cred = self
+            .credentials
+            .find_by_id(cred_id)?
+            .ok_or_else(|| Error::not_found("Registro não disponível no momento"))?;
 
-        if cred.vault_id() != session.vault_id {
-            return Err(anyhow!("Registro não disponível no momento"));
+        if cred.vault_id() != session.vault_id || cred.is_trashed() {
+            return Err(Error::not_found("Registro não disponível no momento"));
         }
 
         Ok(cred)
@@ -202,22 +531,20 @@ impl CredentialService {
     /// ### Retorno
     /// - `Ok(Some(String))`: senha revelada.
     /// - `Ok(None)`: credencial sem senha armazenada.
-    /// - `Err(anyhow)`: falha ao consultar ou descriptografar.
-    pub fn reveal_password(session: &Session, cred_id: Uuid) -> Result<Option<String>> {
+    /// - `Err(Error)`: credencial inexistente, de outro cofre ou na lixeira, ou
+    ///   falha ao descriptografar.
+    pub fn reveal_password(&self, session: &Session, cred_id: Uuid) -> Result<Option<String>> {
+        session.ensure_active()?;
+
         info!(
             "Solicitação de exibição de senha credencial id='{}'",
             cred_id
         );
 
-        let cred = CredentialRepository::find_by_id(cred_id)?
-            .ok_or_else(|| anyhow!("Registro não disponível no momento"))?;
-
-        if cred.vault_id() != session.vault_id {
-            return Err(anyhow!("Registro não disponível no momento"));
-        }
+        let cred = self.get(session, cred_id)?;
 
         if let Some(cipher) = cred.password_cipher() {
-            let mut plain = decrypt_with_passphrase(&session.passphrase, cipher)?;
+            let mut plain = decrypt_with_key(&session.vault_key, cipher)?;
             let output = String::from_utf8_lossy(&plain).to_string();
             plain.zeroize();
             return Ok(Some(output));
@@ -234,22 +561,165 @@ impl CredentialService {
     /// ### Retorno
     /// - `Ok(Some(String))`: notas reveladas.
     /// - `Ok(None)`: credencial sem notas armazenadas.
-    /// - `Err(anyhow)`: falha ao consultar ou descriptografar.
-    pub fn reveal_notes(session: &Session, cred_id: Uuid) -> Result<Option<String>> {
+    /// - `Err(Error)`: credencial inexistente, de outro cofre ou na lixeira, ou
+    ///   falha ao descriptografar.
+    pub fn reveal_notes(&self, session: &Session, cred_id: Uuid) -> Result<Option<String>> {
+        session.ensure_active()?;
+
         info!(
             "Solicitação de exibição de notas credencial id='{}'",
             cred_id
         );
 
-        let cred = CredentialRepository::find_by_id(cred_id)?
-            .ok_or_else(|| anyhow!("Registro não disponível no momento"))?;
+        let cred = self.get(session, cred_id)?;
 
-        if cred.vault_id() != session.vault_id {
-            return Err(anyhow!("Registro não disponível no momento"));
+        if let Some(cipher) = cred.notes() {
+            let mut plain = decrypt_with_key(&session.vault_key, cipher)?;
+            let output = String::from_utf8_lossy(&plain).to_string();
+            plain.zeroize();
+            return Ok(Some(output));
         }
 
-        if let Some(cipher) = cred.notes() {
-            let mut plain = decrypt_with_passphrase(&session.passphrase, cipher)?;
+        Ok(None)
+    }
+
+    /// Lista as versões anteriores de uma credencial, da mais recente para a mais antiga.
+    ///
+    /// ### Parâmetros
+    /// - `cred_id`: Identificador da credencial.
+    ///
+    /// ### Retorno
+    /// - `Ok(Vec<HistoryVersion>)`: versões com senha e notas descriptografadas,
+    ///   podendo retornar lista vazia.
+    /// - `Err(Error)`: credencial inexistente, de outro cofre ou falha ao
+    ///   descriptografar.
+    pub fn password_history(
+        &self,
+        session: &Session,
+        cred_id: Uuid,
+    ) -> Result<Vec<HistoryVersion>> {
+        let cred = self.get(session, cred_id)?;
+
+        info!(
+            "Consultando histórico de senhas credencial id='{}'",
+            cred_id
+        );
+
+        self.history
+            .find_by_credential_id(cred.id())?
+            .iter()
+            .map(|entry| {
+                Ok(HistoryVersion {
+                    id: entry.id(),
+                    password: Self::open_field(session, entry.password_cipher())?,
+                    notes: Self::open_field(session, entry.notes_cipher())?,
+                    replaced_at: entry.created_at(),
+                })
+            })
+            .collect()
+    }
+
+    /// Restaura os valores de uma versão anterior na credencial.
+    ///
+    /// ### Parâmetros
+    /// - `cred_id`: Identificador da credencial.
+    /// - `version_id`: Versão do histórico a restaurar.
+    ///
+    /// ### Retorno
+    /// - `Ok(())`: senha (e notas, quando presentes na versão) restauradas.
+    /// - `Err(Error)`: credencial ou versão inexistente, de outro cofre ou falha
+    ///   de persistência.
+    ///
+    /// ### Aplicação
+    /// Os valores atuais entram no histórico antes de serem substituídos, de modo
+    /// que a restauração também pode ser desfeita.
+    pub fn restore_version(
+        &self,
+        session: &Session,
+        cred_id: Uuid,
+        version_id: Uuid,
+    ) -> Result<()> {
+        self.ensure_writable(session)?;
+        let mut cred = self.get(session, cred_id)?;
+
+        let entry = self
+            .history
+            .find_by_id(version_id)?
+            .filter(|e| e.credential_id() == cred.id())
+            .ok_or_else(|| Error::not_found("Versão não encontrada no histórico"))?;
+
+        info!(
+            "Restaurando versão id='{}' da credencial id='{}'",
+            version_id, cred_id
+        );
+
+        let password_changed = entry.password_cipher().is_some();
+        let notes_changed = entry.notes_cipher().is_some();
+        self.record_history(session, &cred, password_changed, notes_changed)?;
+
+        if let Some(cipher) = entry.password_cipher() {
+            cred.set_password_cipher(Some(cipher.to_vec()));
+        }
+        if let Some(cipher) = entry.notes_cipher() {
+            cred.set_notes(Some(cipher.to_vec()));
+        }
+
+        self.credentials.update(&cred, session.vault_generation())
+    }
+
+    /// Define ou remove a configuração TOTP de uma credencial.
+    ///
+    /// ### Parâmetros
+    /// - `cred_id`: Identificador da credencial.
+    /// - `otpauth`: URI `otpauth://totp/...` ou segredo Base32; `None` (ou texto em
+    ///   branco) remove o TOTP da credencial.
+    ///
+    /// ### Retorno
+    /// - `Ok(())`: configuração validada, cifrada e persistida.
+    /// - `Err(Error)`: URI/segredo inválido, credencial de outro cofre ou falha de persistência.
+    ///
+    /// ### Aplicação
+    /// A entrada é normalizada para uma URI completa antes de ser cifrada com a chave
+    /// do cofre, preservando dígitos, período e algoritmo.
+    pub fn set_totp(&self, session: &Session, cred_id: Uuid, otpauth: Option<&str>) -> Result<()> {
+        self.ensure_writable(session)?;
+        let mut cred = self.get(session, cred_id)?;
+
+        let cipher = match otpauth.map(str::trim).filter(|v| !v.is_empty()) {
+            Some(value) => {
+                let mut uri = Totp::parse(value)?.to_uri();
+                let cipher = encrypt_with_key(&session.vault_key, uri.as_bytes());
+                uri.zeroize();
+                Some(cipher?)
+            }
+            None => None,
+        };
+
+        info!(
+            "{} TOTP da credencial id='{}'",
+            if cipher.is_some() {
+                "Definindo"
+            } else {
+                "Removendo"
+            },
+            cred_id
+        );
+
+        cred.set_totp_cipher(cipher);
+        self.credentials.update(&cred, session.vault_generation())
+    }
+
+    /// Retorna a URI `otpauth` descriptografada de uma credencial, quando existir.
+    ///
+    /// ### Retorno
+    /// - `Ok(Some(String))`: URI normalizada.
+    /// - `Ok(None)`: credencial sem TOTP configurado.
+    /// - `Err(Error)`: falha ao consultar ou descriptografar.
+    pub fn reveal_totp(&self, session: &Session, cred_id: Uuid) -> Result<Option<String>> {
+        let cred = self.get(session, cred_id)?;
+
+        if let Some(cipher) = cred.totp_cipher() {
+            let mut plain = decrypt_with_key(&session.vault_key, cipher)?;
             let output = String::from_utf8_lossy(&plain).to_string();
             plain.zeroize();
             return Ok(Some(output));
@@ -258,16 +728,1282 @@ impl CredentialService {
         Ok(None)
     }
 
-    /// Pesquisa credenciais pelo nome dentro do cofre do usuário autenticado.
+    /// Calcula o código TOTP vigente de uma credencial.
+    ///
+    /// ### Retorno
+    /// - `Ok(Some(TotpCode))`: código atual e segundos restantes até a troca.
+    /// - `Ok(None)`: credencial sem TOTP configurado.
+    /// - `Err(Error)`: falha ao consultar, descriptografar ou interpretar a configuração.
+    pub fn current_totp(&self, session: &Session, cred_id: Uuid) -> Result<Option<TotpCode>> {
+        let Some(mut uri) = self.reveal_totp(session, cred_id)? else {
+            return Ok(None);
+        };
+
+        let totp = Totp::parse(&uri);
+        uri.zeroize();
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| Error::storage("Relógio do sistema anterior a 1970"))?
+            .as_secs();
+
+        debug!("Calculando código TOTP da credencial id='{}'", cred_id);
+        Ok(Some(totp?.code_at(now)))
+    }
+
+    /// Pesquisa credenciais por nome, usuário ou URL dentro do cofre do usuário
+    /// autenticado, ordenadas por relevância.
     ///
     /// ### Retorno
     /// - `Ok(Vec<Credential>)`: lista com os resultados encontrados.
-    /// - `Err(anyhow)`: falha de consulta.
-    pub fn search(session: &Session, query: &str) -> Result<Vec<Credential>> {
+    /// - `Err(Error)`: falha de consulta.
+    pub fn search(&self, session: &Session, query: &str) -> Result<Vec<Credential>> {
+        Ok(self
+            .search_with(session, query, &SearchOptions::default())?
+            .into_iter()
+            .map(|hit| hit.credential)
+            .collect())
+    }
+
+    /// Pesquisa credenciais com opções de correspondência aproximada e de busca
+    /// nas notas.
+    ///
+    /// ### Parâmetros
+    /// - `session`: Sessão autenticada do cofre pesquisado.
+    /// - `query`: Termo de pesquisa; em branco não retorna resultados.
+    /// - `options`: Inclusão das notas e correspondência aproximada.
+    ///
+    /// ### Retorno
+    /// - `Ok(Vec<SearchHit>)`: resultados com o campo correspondente e a
+    ///   relevância, do mais para o menos relevante.
+    /// - `Err(Error)`: falha de consulta ou ao descriptografar as notas.
+    ///
+    /// ### Aplicação
+    /// Sem opções, os candidatos vêm da consulta por trecho no repositório, que
+    /// já ignora maiúsculas e acentos. A correspondência aproximada e a busca
+    /// nas notas (cifradas no banco) exigem avaliar todas as credenciais do
+    /// cofre; as notas são descriptografadas apenas em memória, com a chave da
+    /// sessão, e apagadas após a comparação.
+    pub fn search_with(
+        &self,
+        session: &Session,
+        query: &str,
+        options: &SearchOptions,
+    ) -> Result<Vec<SearchHit>> {
+        session.ensure_active()?;
+
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        info!(
+            "Pesquisando credenciais vault_id='{}' termo='{}' notas={} aproximada={}",
+            session.vault_id, query, options.include_notes, options.fuzzy
+        );
+
+        let candidates = if options.include_notes || options.fuzzy {
+            self.credentials.find_all_by_vault_id(session.vault_id)?
+        } else {
+            self.credentials.search(session.vault_id, query)?
+        };
+
+        let matcher = Matcher::new(query, options.fuzzy);
+        let mut hits = Vec::new();
+
+        for credential in candidates {
+            let mut notes = if options.include_notes {
+                Self::open_field(session, credential.notes())?
+            } else {
+                None
+            };
+
+            let best = matcher.best_match(&credential, notes.as_deref());
+            notes.zeroize();
+
+            if let Some((field, score)) = best {
+                hits.push(SearchHit {
+                    credential,
+                    field,
+                    score,
+                });
+            }
+        }
+
+        credential_search::rank(&mut hits);
+
+        debug!("Pesquisa retornou {} resultado(s)", hits.len());
+        Ok(hits)
+    }
+
+    /// Verifica se uma senha aparece na base local de senhas vazadas.
+    ///
+    /// ### Parâmetros
+    /// - `password`: Senha em texto plano; é convertida em hash apenas em memória.
+    ///
+    /// ### Retorno
+    /// - `Ok(None)`: nenhuma base configurada (`PWNED_PASSWORDS_PATH`).
+    /// - `Ok(Some(0))`: senha não encontrada.
+    /// - `Ok(Some(n))`: senha encontrada `n` vezes em vazamentos conhecidos.
+    /// - `Err(Error)`: falha ao ler a base.
+    ///
+    /// ### Aplicação
+    /// Chamado pelas interfaces antes de criar ou atualizar uma credencial, para
+    /// alertar o usuário; não impede a gravação.
+    pub fn breach_occurrences(&self, password: &str) -> Result<Option<u64>> {
+        let Some(pwned) = &self.pwned else {
+            return Ok(None);
+        };
+        if password.is_empty() {
+            return Ok(Some(0));
+        }
+
+        pwned.occurrences(password).map(Some)
+    }
+
+    /// Gera o relatório de segurança das senhas do cofre.
+    ///
+    /// ### Parâmetros
+    /// - `session`: Sessão autenticada do cofre auditado.
+    /// - `options`: Idade máxima das senhas e pontuação mínima de força.
+    ///
+    /// ### Retorno
+    /// - `Ok(SecurityReport)`: senhas fracas, repetidas, antigas e vazadas e
+    ///   credenciais sem usuário ou URL.
+    /// - `Err(Error)`: falha de consulta ou ao descriptografar as senhas.
+    ///
+    /// ### Aplicação
+    /// Todas as senhas do cofre (fora da lixeira) são descriptografadas apenas em
+    /// memória, com a chave da sessão, e apagadas logo após a avaliação. A idade
+    /// de cada senha é contada a partir de `password_changed_at`, a última troca
+    /// da senha; alterar outros campos da credencial não a renova. A consulta de
+    /// vazamentos só ocorre quando há uma base local configurada.
+    pub fn security_report(
+        &self,
+        session: &Session,
+        options: &ReportOptions,
+    ) -> Result<SecurityReport> {
+        session.ensure_active()?;
+
+        info!(
+            "Gerando relatório de segurança vault_id='{}' idade máxima={} dias pontuação mínima={}",
+            session.vault_id, options.max_age_days, options.min_score
+        );
+
+        let mut builder = ReportBuilder::new(*options, Utc::now(), self.pwned.is_some());
+        for credential in self.credentials.find_all_by_vault_id(session.vault_id)? {
+            let mut password = Self::open_field(session, credential.password_cipher())?;
+            let breaches = match password.as_deref() {
+                Some(value) => self.breach_occurrences(value)?.unwrap_or(0),
+                None => 0,
+            };
+            builder.add(credential, password.as_deref(), breaches);
+            password.zeroize();
+        }
+
+        let report = builder.finish();
+        debug!(
+            "Relatório de segurança: {} fraca(s), {} grupo(s) repetido(s), {} antiga(s), {} vazada(s), {} incompleta(s)",
+            report.weak.len(),
+            report.reused.len(),
+            report.stale.len(),
+            report.breached.len(),
+            report.incomplete.len()
+        );
+        Ok(report)
+    }
+
+    /// Cria uma pasta no cofre do usuário autenticado.
+    ///
+    /// ### Parâmetros
+    /// - `session`: Sessão autenticada.
+    /// - `name`: Nome da pasta (único entre as pastas irmãs, sem `/`).
+    /// - `parent_id`: Pasta superior (`None` para a raiz).
+    ///
+    /// ### Retorno
+    /// - `Ok(Folder)`: pasta criada.
+    /// - `Err(Error)`: nome inválido ou repetido, pasta superior inexistente ou
+    ///   falha de persistência.
+    pub fn create_folder(
+        &self,
+        session: &Session,
+        name: &str,
+        parent_id: Option<Uuid>,
+    ) -> Result<Folder> {
+        self.ensure_writable(session)?;
+
+        if let Some(parent_id) = parent_id {
+            self.folder(session, parent_id)?;
+        }
+
+        let folder = Folder::new(session.vault_id, parent_id, name.to_string())?;
+        self.ensure_unique_folder_name(session, &folder)?;
+
+        info!(
+            "Criando pasta '{}' no vault_id='{}'",
+            folder.name(),
+            session.vault_id
+        );
+        self.folders.create(&folder)?;
+        Ok(folder)
+    }
+
+    /// Renomeia uma pasta.
+    ///
+    /// ### Retorno
+    /// - `Ok(Folder)`: pasta atualizada.
+    /// - `Err(Error)`: pasta inexistente, nome inválido ou já usado por uma pasta irmã.
+    pub fn rename_folder(&self, session: &Session, folder_id: Uuid, name: &str) -> Result<Folder> {
+        self.ensure_writable(session)?;
+
+        let mut folder = self.folder(session, folder_id)?;
+        folder.set_name(name.to_string())?;
+        self.ensure_unique_folder_name(session, &folder)?;
+
+        info!(
+            "Renomeando pasta id='{}' para '{}'",
+            folder_id,
+            folder.name()
+        );
+        self.folders.update(&folder)?;
+        Ok(folder)
+    }
+
+    /// Move uma pasta (com todo o seu conteúdo) para dentro de outra ou para a raiz.
+    ///
+    /// ### Parâmetros
+    /// - `folder_id`: Pasta movida.
+    /// - `parent_id`: Novo destino (`None` para a raiz).
+    ///
+    /// ### Retorno
+    /// - `Ok(Folder)`: pasta atualizada.
+    /// - `Err(Error)`: destino inexistente, dentro da própria pasta (ciclo) ou já
+    ///   contendo uma pasta com o mesmo nome.
+    pub fn move_folder(
+        &self,
+        session: &Session,
+        folder_id: Uuid,
+        parent_id: Option<Uuid>,
+    ) -> Result<Folder> {
+        self.ensure_writable(session)?;
+
+        let mut folder = self.folder(session, folder_id)?;
+
+        if let Some(target) = parent_id {
+            self.folder(session, target)?;
+
+            let parents: HashMap<Uuid, Option<Uuid>> = self
+                .folders
+                .find_all_by_vault_id(session.vault_id)?
+                .iter()
+                .map(|f| (f.id(), f.parent_id()))
+                .collect();
+
+            let mut current = Some(target);
+            while let Some(id) = current {
+                if id == folder_id {
+                    return Err(Error::validation(
+                        "parent_id",
+                        "Não é possível mover uma pasta para dentro dela mesma",
+                    ));
+                }
+                current = parents.get(&id).copied().flatten();
+            }
+        }
+
+        folder.set_parent_id(parent_id);
+        self.ensure_unique_folder_name(session, &folder)?;
+
         info!(
-            "Pesquisando credenciais vault_id='{}' termo='{}'",
-            session.vault_id, query
+            "Movendo pasta id='{}' para parent_id={:?}",
+            folder_id, parent_id
         );
-        Ok(CredentialRepository::search(session.vault_id, query)?)
+        self.folders.update(&folder)?;
+        Ok(folder)
     }
+
+    /// Remove uma pasta; subpastas e credenciais passam para a pasta superior.
+    ///
+    /// ### Retorno
+    /// - `Ok(())`: pasta removida.
+    /// - `Err(Error)`: pasta inexistente, de outro cofre ou com nome repetido no
+    ///   destino de alguma subpasta.
+    pub fn delete_folder(&self, session: &Session, folder_id: Uuid) -> Result<()> {
+        self.ensure_writable(session)?;
+
+        let folder = self.folder(session, folder_id)?;
+        let folders = self.folders.find_all_by_vault_id(session.vault_id)?;
+
+        let siblings: HashSet<String> = folders
+            .iter()
+            .filter(|f| f.parent_id() == folder.parent_id() && f.id() != folder_id)
+            .map(|f| f.name().to_lowercase())
+            .collect();
+        if let Some(clash) = folders.iter().find(|f| {
+            f.parent_id() == Some(folder_id) && siblings.contains(&f.name().to_lowercase())
+        }) {
+            return Err(Error::conflict(format!(
+                "A pasta de destino já possui uma pasta chamada '{}'",
+                clash.name()
+            )));
+        }
+
+        info!("Removendo pasta id='{}'", folder_id);
+        self.folders.delete(&folder)
+    }
+
+    /// Lista as pastas do cofre em ordem de árvore (cada pasta seguida de suas
+    /// subpastas, irmãs em ordem alfabética).
+    ///
+    /// ### Retorno
+    /// - `Ok(Vec<FolderSummary>)`: pastas com caminho, profundidade e quantidade
+    ///   de credenciais.
+    /// - `Err(Error)`: falha de consulta.
+    pub fn list_folders(&self, session: &Session) -> Result<Vec<FolderSummary>> {
+        session.ensure_active()?;
+
+        let folders = self.folders.find_all_by_vault_id(session.vault_id)?;
+
+        let mut counts: HashMap<Uuid, usize> = HashMap::new();
+        for cred in self.credentials.find_all_by_vault_id(session.vault_id)? {
+            if let Some(folder_id) = cred.folder_id() {
+                *counts.entry(folder_id).or_default() += 1;
+            }
+        }
+
+        let mut children: HashMap<Option<Uuid>, Vec<&Folder>> = HashMap::new();
+        for folder in &folders {
+            children.entry(folder.parent_id()).or_default().push(folder);
+        }
+
+        // Percurso em profundidade a partir da raiz; a pilha guarda as irmãs em
+        // ordem inversa para que a primeira em ordem alfabética saia antes.
+        let mut summaries = Vec::with_capacity(folders.len());
+        let mut stack: Vec<(&Folder, usize, String)> = children
+            .get(&None)
+            .into_iter()
+            .flatten()
+            .rev()
+            .map(|f| (*f, 0, f.name().to_string()))
+            .collect();
+
+        while let Some((folder, depth, path)) = stack.pop() {
+            for child in children.get(&Some(folder.id())).into_iter().flatten().rev() {
+                stack.push((
+                    child,
+                    depth + 1,
+                    format!("{path}{PATH_SEPARATOR}{}", child.name()),
+                ));
+            }
+
+            summaries.push(FolderSummary {
+                folder: folder.clone(),
+                path,
+                depth,
+                credentials: counts.get(&folder.id()).copied().unwrap_or(0),
+            });
+        }
+
+        Ok(summaries)
+    }
+
+    /// Move uma credencial para uma pasta ou a retira de qualquer pasta.
+    ///
+    /// ### Parâmetros
+    /// - `cred_id`: Credencial movida.
+    /// - `folder_id`: Pasta de destino (`None` para a raiz).
+    ///
+    /// ### Retorno
+    /// - `Ok(())`: credencial movida.
+    /// - `Err(Error)`: credencial ou pasta inexistente ou de outro cofre.
+    pub fn move_to_folder(
+        &self,
+        session: &Session,
+        cred_id: Uuid,
+        folder_id: Option<Uuid>,
+    ) -> Result<()> {
+        self.ensure_writable(session)?;
+        let mut cred = self.get(session, cred_id)?;
+
+        if let Some(folder_id) = folder_id {
+            self.folder(session, folder_id)?;
+        }
+
+        info!(
+            "Movendo credencial id='{}' para folder_id={:?}",
+            cred_id, folder_id
+        );
+        cred.set_folder_id(folder_id);
+        self.credentials.update(&cred, session.vault_generation())
+    }
+
+    /// Lista as credenciais de uma pasta, ordenadas pelo nome.
+    ///
+    /// ### Parâmetros
+    /// - `folder_id`: Pasta consultada (`None` para as credenciais fora de pastas).
+    /// - `include_subfolders`: Inclui as credenciais de todas as subpastas.
+    ///
+    /// ### Retorno
+    /// - `Ok(Vec<Credential>)`: podendo retornar lista vazia.
+    /// - `Err(Error)`: pasta inexistente ou falha de consulta.
+    pub fn list_by_folder(
+        &self,
+        session: &Session,
+        folder_id: Option<Uuid>,
+        include_subfolders: bool,
+    ) -> Result<Vec<Credential>> {
+        session.ensure_active()?;
+
+        if let Some(folder_id) = folder_id {
+            self.folder(session, folder_id)?;
+        }
+
+        let mut wanted: HashSet<Option<Uuid>> = HashSet::from([folder_id]);
+
+        if include_subfolders {
+            let folders = self.folders.find_all_by_vault_id(session.vault_id)?;
+
+            // Acrescenta as pastas cujo pai já foi incluído, até não haver novas.
+            loop {
+                let before = wanted.len();
+                for folder in &folders {
+                    if wanted.contains(&folder.parent_id()) {
+                        wanted.insert(Some(folder.id()));
+                    }
+                }
+                if wanted.len() == before {
+                    break;
+                }
+            }
+        }
+
+        let found = self
+            .credentials
+            .find_all_by_vault_id(session.vault_id)?
+            .into_iter()
+            .filter(|c| wanted.contains(&c.folder_id()))
+            .collect();
+
+        Ok(sorted_by_name(found))
+    }
+
+    /// Define o conjunto completo de tags de uma credencial.
+    ///
+    /// ### Parâmetros
+    /// - `cred_id`: Credencial marcada.
+    /// - `names`: Nomes das tags; as inexistentes são criadas e repetições (sem
+    ///   diferenciar maiúsculas) são ignoradas. Lista vazia remove todas as tags.
+    ///
+    /// ### Retorno
+    /// - `Ok(Vec<Tag>)`: tags da credencial após a alteração, ordenadas pelo nome.
+    /// - `Err(Error)`: credencial inexistente, nome de tag inválido ou falha de
+    ///   persistência.
+    pub fn set_tags(&self, session: &Session, cred_id: Uuid, names: &[String]) -> Result<Vec<Tag>> {
+        self.ensure_writable(session)?;
+        let cred = self.get(session, cred_id)?;
+        let tag_ids = self.resolve_tags(session.vault_id, names)?;
+
+        info!(
+            "Definindo {} tag(s) na credencial id='{}'",
+            tag_ids.len(),
+            cred.id()
+        );
+        self.tags.replace_links(cred.id(), &tag_ids)?;
+        self.tags.find_by_credential_id(cred.id())
+    }
+
+    /// Converte nomes de tags nos IDs das tags do cofre, criando as inexistentes.
+    ///
+    /// Repetições (sem diferenciar maiúsculas) são ignoradas e a ordem dos nomes
+    /// é preservada.
+    fn resolve_tags(&self, vault_id: Uuid, names: &[String]) -> Result<Vec<Uuid>> {
+        let mut existing: HashMap<String, Tag> = self
+            .tags
+            .find_all_by_vault_id(vault_id)?
+            .into_iter()
+            .map(|t| (t.name().to_lowercase(), t))
+            .collect();
+
+        let mut tag_ids = Vec::new();
+        for name in names {
+            let tag = Tag::new(vault_id, name.clone())?;
+            let key = tag.name().to_lowercase();
+
+            let id = match existing.get(&key) {
+                Some(found) => found.id(),
+                None => {
+                    self.tags.create(&tag)?;
+                    let id = tag.id();
+                    existing.insert(key, tag);
+                    id
+                }
+            };
+
+            if !tag_ids.contains(&id) {
+                tag_ids.push(id);
+            }
+        }
+
+        Ok(tag_ids)
+    }
+
+    /// Lista as tags de uma credencial, ordenadas pelo nome.
+    pub fn tags_of(&self, session: &Session, cred_id: Uuid) -> Result<Vec<Tag>> {
+        let cred = self.get(session, cred_id)?;
+        self.tags.find_by_credential_id(cred.id())
+    }
+
+    /// Lista as tags do cofre com a quantidade de credenciais de cada uma.
+    ///
+    /// ### Retorno
+    /// - `Ok(Vec<TagSummary>)`: tags ordenadas pelo nome, inclusive as sem uso.
+    /// - `Err(Error)`: falha de consulta.
+    pub fn list_tags(&self, session: &Session) -> Result<Vec<TagSummary>> {
+        session.ensure_active()?;
+
+        let active: HashSet<Uuid> = self
+            .credentials
+            .find_all_by_vault_id(session.vault_id)?
+            .iter()
+            .map(Credential::id)
+            .collect();
+
+        let mut counts: HashMap<Uuid, usize> = HashMap::new();
+        for (cred_id, tag_id) in self.tags.find_links_by_vault_id(session.vault_id)? {
+            if active.contains(&cred_id) {
+                *counts.entry(tag_id).or_default() += 1;
+            }
+        }
+
+        Ok(self
+            .tags
+            .find_all_by_vault_id(session.vault_id)?
+            .into_iter()
+            .map(|tag| TagSummary {
+                credentials: counts.get(&tag.id()).copied().unwrap_or(0),
+                tag,
+            })
+            .collect())
+    }
+
+    /// Renomeia uma tag em todas as credenciais marcadas.
+    ///
+    /// ### Retorno
+    /// - `Ok(Tag)`: tag atualizada.
+    /// - `Err(Error)`: tag inexistente, nome inválido ou já usado por outra tag.
+    pub fn rename_tag(&self, session: &Session, tag_id: Uuid, name: &str) -> Result<Tag> {
+        self.ensure_writable(session)?;
+
+        let mut tags = self.tags.find_all_by_vault_id(session.vault_id)?;
+        let index = tags
+            .iter()
+            .position(|t| t.id() == tag_id)
+            .ok_or_else(|| Error::not_found("Tag não encontrada"))?;
+
+        let mut tag = tags.swap_remove(index);
+        tag.set_name(name.to_string())?;
+
+        if tags
+            .iter()
+            .any(|t| t.name().to_lowercase() == tag.name().to_lowercase())
+        {
+            return Err(Error::conflict(format!(
+                "Já existe uma tag chamada '{}'",
+                tag.name()
+            )));
+        }
+
+        info!("Renomeando tag id='{}' para '{}'", tag_id, tag.name());
+        self.tags.update(&tag)?;
+        Ok(tag)
+    }
+
+    /// Remove uma tag de todas as credenciais e do cofre.
+    ///
+    /// ### Retorno
+    /// - `Ok(())`: tag removida.
+    /// - `Err(Error)`: tag inexistente ou falha de remoção.
+    pub fn delete_tag(&self, session: &Session, tag_id: Uuid) -> Result<()> {
+        self.ensure_writable(session)?;
+        self.tag(session, tag_id)?;
+
+        info!("Removendo tag id='{}'", tag_id);
+        self.tags.delete(tag_id)
+    }
+
+    /// Lista as credenciais marcadas com uma tag, ordenadas pelo nome.
+    ///
+    /// ### Retorno
+    /// - `Ok(Vec<Credential>)`: podendo retornar lista vazia.
+    /// - `Err(Error)`: tag inexistente ou falha de consulta.
+    pub fn list_by_tag(&self, session: &Session, tag_id: Uuid) -> Result<Vec<Credential>> {
+        self.tag(session, tag_id)?;
+
+        let tagged: HashSet<Uuid> = self
+            .tags
+            .find_links_by_vault_id(session.vault_id)?
+            .into_iter()
+            .filter(|&(_, tag)| tag == tag_id)
+            .map(|(credential, _)| credential)
+            .collect();
+
+        let found = self
+            .credentials
+            .find_all_by_vault_id(session.vault_id)?
+            .into_iter()
+            .filter(|c| tagged.contains(&c.id()))
+            .collect();
+
+        Ok(sorted_by_name(found))
+    }
+
+    /// Exporta todas as credenciais do cofre para um arquivo cifrado portátil.
+    ///
+    /// ### Parâmetros
+    /// - `session`: Sessão autenticada do cofre a ser exportado.
+    /// - `passphrase`: Senha que protegerá o arquivo (independente da senha mestre).
+    ///
+    /// ### Retorno
+    /// - `Ok(Vec<u8>)`: conteúdo do arquivo (JSON versionado cifrado com AGE/passphrase).
+    /// - `Err(Error)`: senha vazia ou falha ao descriptografar/cifrar.
+    ///
+    /// ### Aplicação
+    /// Backup do cofre e migração entre instalações; o arquivo pode ser importado em
+    /// qualquer cofre com [`CredentialService::import`].
+    pub fn export(&self, session: &Session, passphrase: &str) -> Result<Vec<u8>> {
+        session.ensure_active()?;
+
+        if passphrase.is_empty() {
+            return Err(Error::validation(
+                "passphrase",
+                "Informe uma senha para proteger o arquivo exportado",
+            ));
+        }
+
+        info!("Exportando credenciais do vault_id='{}'", session.vault_id);
+
+        let credentials = self
+            .credentials
+            .find_all_by_vault_id(session.vault_id)?
+            .iter()
+            .map(|cred| {
+                Ok(ArchivedCredential {
+                    name: cred.name().to_string(),
+                    username: cred.username().map(str::to_string),
+                    url: cred.url().map(str::to_string),
+                    notes: Self::open_field(session, cred.notes())?,
+                    password: Self::open_field(session, cred.password_cipher())?,
+                    totp: Self::open_field(session, cred.totp_cipher())?,
+                    created_at: cred.created_at(),
+                    updated_at: cred.updated_at(),
+                    password_changed_at: Some(cred.password_changed_at()),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let archive = VaultArchive::new(session.user.username(), session.vault_id, credentials);
+        let mut json = archive.to_json()?;
+        let sealed = encrypt_with_passphrase(passphrase, &json);
+        json.zeroize();
+
+        info!(
+            "Exportação concluída vault_id='{}' credenciais={}",
+            session.vault_id,
+            archive.credentials.len()
+        );
+        sealed
+    }
+
+    /// Importa um arquivo gerado por [`CredentialService::export`] para o cofre da sessão.
+    ///
+    /// ### Parâmetros
+    /// - `session`: Sessão autenticada do cofre de destino.
+    /// - `archive`: Conteúdo do arquivo exportado.
+    /// - `passphrase`: Senha definida na exportação.
+    ///
+    /// ### Retorno
+    /// - `Ok(ImportSummary)`: quantidade importada e metadados do arquivo.
+    /// - `Err(Error)`: senha incorreta, arquivo inválido ou de versão mais nova,
+    ///   credencial inválida ou falha de persistência.
+    ///
+    /// ### Aplicação
+    /// Cada credencial é recriada com um novo identificador e seus campos sensíveis
+    /// são cifrados com a chave do cofre de destino; as datas de criação e de
+    /// modificação do arquivo são preservadas. A importação é atômica: se
+    /// qualquer registro falhar, nenhum é gravado.
+    pub fn import(
+        &self,
+        session: &Session,
+        archive: &[u8],
+        passphrase: &str,
+    ) -> Result<ImportSummary> {
+        self.ensure_writable(session)?;
+
+        let mut json = decrypt_with_passphrase(passphrase, archive)
+            .map_err(|_| Error::crypto("Senha incorreta ou arquivo de exportação corrompido"))?;
+        let parsed = VaultArchive::from_json(&json);
+        json.zeroize();
+        let archive = parsed?;
+
+        info!(
+            "Importando {} credencial(is) exportadas por '{}' para vault_id='{}'",
+            archive.credentials.len(),
+            archive.exported_by,
+            session.vault_id
+        );
+
+        let seal = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(|v| encrypt_with_key(&session.vault_key, v.as_bytes()))
+                .transpose()
+        };
+
+        let mut credentials = Vec::with_capacity(archive.credentials.len());
+        for item in &archive.credentials {
+            let mut cred = Credential::new(
+                session.vault_id,
+                item.name.clone(),
+                item.username.clone(),
+                item.url.clone(),
+                seal(&item.notes)?,
+                seal(&item.password)?,
+            )?;
+
+            if let Some(totp) = &item.totp {
+                let mut uri = Totp::parse(totp)?.to_uri();
+                let cipher = encrypt_with_key(&session.vault_key, uri.as_bytes());
+                uri.zeroize();
+                cred.set_totp_cipher(Some(cipher?));
+            }
+            cred.set_timestamps(item.created_at, item.updated_at);
+            cred.set_password_changed_at(item.password_changed_at.unwrap_or(item.updated_at));
+
+            credentials.push(cred);
+        }
+
+        self.credentials
+            .create_many(&credentials, session.vault_generation())?;
+        info!(
+            "Importação concluída vault_id='{}' credenciais={}",
+            session.vault_id,
+            credentials.len()
+        );
+
+        Ok(ImportSummary {
+            imported: credentials.len(),
+            exported_by: archive.exported_by.clone(),
+            exported_at: archive.exported_at,
+        })
+    }
+
+    /// Grava no cofre as entradas lidas de um arquivo de outro gerenciador.
+    ///
+    /// ### Parâmetros
+    /// - `session`: Sessão autenticada do cofre de destino.
+    /// - `parsed`: Resultado de [`crate::importers::parse`].
+    /// - `dry_run`: Quando verdadeiro, apenas gera o relatório, sem gravar nada.
+    ///
+    /// ### Retorno
+    /// - `Ok(ImportReport)`: quantidades importadas e entradas duplicadas, descartadas
+    ///   ou importadas com ressalvas.
+    /// - `Err(Error)`: falha de criptografia ou persistência.
+    ///
+    /// ### Aplicação
+    /// Uma entrada é considerada duplicada quando nome (sem diferenciar maiúsculas),
+    /// usuário e URL coincidem com uma credencial do cofre ou com outra entrada do
+    /// mesmo arquivo. TOTP inválido não impede a importação da entrada: o segredo é
+    /// descartado e relatado em `warnings`. A gravação ocorre em uma única transação.
+    pub fn import_external(
+        &self,
+        session: &Session,
+        parsed: &ParsedImport,
+        dry_run: bool,
+    ) -> Result<ImportReport> {
+        session.ensure_active()?;
+        if !dry_run {
+            self.ensure_writable(session)?;
+        }
+
+        info!(
+            "Importando {} entrada(s) de {} para vault_id='{}' (simulação={})",
+            parsed.entries.len(),
+            parsed.format.label(),
+            session.vault_id,
+            dry_run
+        );
+
+        let key = |name: &str, username: Option<&str>, url: Option<&str>| {
+            (
+                name.to_lowercase(),
+                username.unwrap_or_default().to_string(),
+                url.unwrap_or_default().to_string(),
+            )
+        };
+
+        let mut known: std::collections::HashSet<_> = self
+            .credentials
+            .find_all_by_vault_id(session.vault_id)?
+            .iter()
+            .map(|c| key(c.name(), c.username(), c.url()))
+            .collect();
+
+        let mut report = ImportReport {
+            format: parsed.format,
+            dry_run,
+            total: parsed.entries.len() + parsed.skipped.len(),
+            imported: 0,
+            duplicates: Vec::new(),
+            skipped: parsed.skipped.clone(),
+            warnings: Vec::new(),
+        };
+
+        let seal = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(|v| encrypt_with_key(&session.vault_key, v.as_bytes()))
+                .transpose()
+        };
+
+        let mut credentials = Vec::new();
+        for entry in &parsed.entries {
+            if !known.insert(key(
+                &entry.name,
+                entry.username.as_deref(),
+                entry.url.as_deref(),
+            )) {
+                report.duplicates.push(ImportIssue {
+                    entry: entry.name.clone(),
+                    reason: "Credencial com mesmo nome, usuário e URL já existe".to_string(),
+                });
+                continue;
+            }
+
+            let totp = match entry.totp.as_deref().map(Totp::parse).transpose() {
+                Ok(totp) => totp,
+                Err(e) => {
+                    report.warnings.push(ImportIssue {
+                        entry: entry.name.clone(),
+                        reason: format!("TOTP descartado: {e}"),
+                    });
+                    None
+                }
+            };
+
+            report.imported += 1;
+            if dry_run {
+                continue;
+            }
+
+            let mut cred = Credential::new(
+                session.vault_id,
+                entry.name.clone(),
+                entry.username.clone(),
+                entry.url.clone(),
+                seal(&entry.notes)?,
+                seal(&entry.password)?,
+            )?;
+
+            if let Some(totp) = totp {
+                let mut uri = totp.to_uri();
+                let cipher = encrypt_with_key(&session.vault_key, uri.as_bytes());
+                uri.zeroize();
+                cred.set_totp_cipher(Some(cipher?));
+            }
+
+            credentials.push(cred);
+        }
+
+        if !dry_run {
+            self.credentials
+                .create_many(&credentials, session.vault_generation())?;
+        }
+
+        info!(
+            "Importação externa concluída vault_id='{}' importadas={} duplicadas={} descartadas={} (simulação={})",
+            session.vault_id,
+            report.imported,
+            report.duplicates.len(),
+            report.skipped.len(),
+            dry_run
+        );
+        Ok(report)
+    }
+
+    /// Transfere uma credencial do cofre da sessão para outro cofre do usuário.
+    ///
+    /// ### Parâmetros
+    /// - `session`: Sessão autenticada no cofre de origem.
+    /// - `cred_id`: Credencial transferida (fora da lixeira).
+    /// - `target`: Cofre de destino, tal como lido ao desembrulhar a chave.
+    /// - `target_key`: Chave do cofre de destino, já desembrulhada.
+    /// - `mode`: [`TransferMode::Move`] mantém o ID e o histórico da credencial;
+    ///   [`TransferMode::Copy`] cria uma nova credencial, sem histórico.
+    ///
+    /// ### Retorno
+    /// - `Ok(Credential)`: credencial gravada no cofre de destino.
+    /// - `Err(Error)`: credencial inexistente, cofre de origem somente leitura
+    ///   ao mover, falha de criptografia ou de persistência.
+    ///
+    /// ### Aplicação
+    /// Senha, notas, TOTP e, ao mover, as versões do histórico são recifrados com
+    /// a chave de destino. Pastas pertencem ao cofre de origem, então a credencial
+    /// chega à raiz do destino; as tags são recriadas lá pelo nome.
+    pub(crate) fn transfer(
+        &self,
+        session: &Session,
+        cred_id: Uuid,
+        target: &Vault,
+        target_key: &[u8],
+        mode: TransferMode,
+    ) -> Result<Credential> {
+        let target_vault_id = target.id();
+        let target_generation = target.key_generation();
+        if mode == TransferMode::Move {
+            self.ensure_writable(session)?;
+        }
+
+        let mut cred = self.get(session, cred_id)?;
+        let tag_names: Vec<String> = self
+            .tags
+            .find_by_credential_id(cred.id())?
+            .iter()
+            .map(|t| t.name().to_string())
+            .collect();
+
+        info!(
+            "Transferindo credencial id='{}' de vault_id='{}' para vault_id='{}' ({:?})",
+            cred.id(),
+            session.vault_id,
+            target_vault_id,
+            mode
+        );
+
+        let reseal = |cipher: Option<&[u8]>| -> Result<Option<Vec<u8>>> {
+            cipher
+                .map(|c| {
+                    let mut plain = decrypt_with_key(&session.vault_key, c)?;
+                    let sealed = encrypt_with_key(target_key, &plain);
+                    plain.zeroize();
+                    sealed
+                })
+                .transpose()
+        };
+
+        let transferred = match mode {
+            TransferMode::Move => {
+                let mut history = self.history.find_by_credential_id(cred.id())?;
+                for entry in &mut history {
+                    let password = reseal(entry.password_cipher())?;
+                    let notes = reseal(entry.notes_cipher())?;
+                    entry.set_ciphers(password, notes);
+                }
+
+                let password = reseal(cred.password_cipher())?;
+                let notes = reseal(cred.notes())?;
+                let totp = reseal(cred.totp_cipher())?;
+                cred.set_ciphers(password, notes, totp);
+                cred.set_folder_id(None);
+                cred.set_vault_id(target_vault_id);
+
+                self.credentials
+                    .move_to_vault(&cred, &history, target_generation)?;
+                cred
+            }
+            TransferMode::Copy => {
+                let mut copy = Credential::new(
+                    target_vault_id,
+                    cred.name().to_string(),
+                    cred.username().map(str::to_string),
+                    cred.url().map(str::to_string),
+                    reseal(cred.notes())?,
+                    reseal(cred.password_cipher())?,
+                )?;
+                copy.set_totp_cipher(reseal(cred.totp_cipher())?);
+                copy.set_password_changed_at(cred.password_changed_at());
+
+                self.credentials.create(&copy, target_generation)?;
+                copy
+            }
+        };
+
+        let tag_ids = self.resolve_tags(target_vault_id, &tag_names)?;
+        self.tags.replace_links(transferred.id(), &tag_ids)?;
+        Ok(transferred)
+    }
+
+    /// Recifra, no lugar, credenciais e versões do histórico com uma nova chave.
+    ///
+    /// ### Parâmetros
+    /// - `credentials`: Todas as credenciais do cofre, inclusive na lixeira.
+    /// - `history`: Todas as versões anteriores dessas credenciais.
+    /// - `old_key`: Chave atual do cofre.
+    /// - `new_key`: Nova chave do cofre.
+    ///
+    /// ### Retorno
+    /// - `Ok(())`: todos os campos foram recifrados.
+    /// - `Err(Error)`: algum campo não pôde ser aberto com a chave atual.
+    ///
+    /// ### Aplicação
+    /// Usado na revogação de um membro, sobre os registros relidos dentro da
+    /// transação que grava a nova chave embrulhada.
+    pub(crate) fn reseal_vault(
+        credentials: &mut [Credential],
+        history: &mut [CredentialHistoryEntry],
+        old_key: &[u8],
+        new_key: &[u8],
+    ) -> Result<()> {
+        let reseal = |cipher: Option<&[u8]>| -> Result<Option<Vec<u8>>> {
+            cipher
+                .map(|c| {
+                    let mut plain = decrypt_with_key(old_key, c)?;
+                    let sealed = encrypt_with_key(new_key, &plain);
+                    plain.zeroize();
+                    sealed
+                })
+                .transpose()
+        };
+
+        for entry in history.iter_mut() {
+            let password = reseal(entry.password_cipher())?;
+            let notes = reseal(entry.notes_cipher())?;
+            entry.set_ciphers(password, notes);
+        }
+        for cred in credentials.iter_mut() {
+            let password = reseal(cred.password_cipher())?;
+            let notes = reseal(cred.notes())?;
+            let totp = reseal(cred.totp_cipher())?;
+            cred.set_ciphers(password, notes, totp);
+        }
+
+        Ok(())
+    }
+
+    /// Conta as credenciais de um cofre que não estão na lixeira.
+    pub(crate) fn count_in_vault(&self, vault_id: Uuid) -> Result<usize> {
+        Ok(self.credentials.find_all_by_vault_id(vault_id)?.len())
+    }
+
+    /// Garante que a sessão ainda pode alterar o cofre aberto, conferindo no
+    /// armazenamento o acesso guardado nela.
+    ///
+    /// ### Retorno
+    /// - `Ok(())` quando o usuário segue como dono ou editor do cofre.
+    /// - `Err(Error)` quando a sessão estiver inativa ou o acesso tiver sido
+    ///   revogado ou reduzido a leitura.
+    ///
+    /// ### Aplicação
+    /// O papel da [`Session`] é obtido ao abrir o cofre. Sem esta verificação,
+    /// um membro removido ou rebaixado continuaria gravando com ele. A chave
+    /// guardada na sessão não é conferida aqui: cada gravação envia a
+    /// [`Session::vault_generation`] ao repositório, que a recusa com conflito
+    /// se o cofre tiver sido recifrado nesse meio-tempo.
+    fn ensure_writable(&self, session: &Session) -> Result<()> {
+        session.ensure_writable()?;
+
+        let vault = self
+            .vaults
+            .find_by_id(session.vault_id)?
+            .ok_or_else(|| Error::not_found("Cofre não encontrado"))?;
+        if vault.user_id() != session.user.id() {
+            let member = self
+                .members
+                .find(vault.id(), session.user.id())?
+                .ok_or_else(|| Error::unauthorized("Acesso ao cofre revogado"))?;
+            if !member.role().can_edit() {
+                return Err(Error::unauthorized("Acesso somente leitura a este cofre"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Recupera uma pasta, garantindo que pertence ao cofre da sessão.
+    fn folder(&self, session: &Session, folder_id: Uuid) -> Result<Folder> {
+        self.folders
+            .find_by_id(folder_id)?
+            .filter(|f| f.vault_id() == session.vault_id)
+            .ok_or_else(|| Error::not_found("Pasta não encontrada"))
+    }
+
+    /// Recupera uma credencial da lixeira, garantindo que pertence ao cofre da sessão.
+    fn trashed(&self, session: &Session, cred_id: Uuid) -> Result<Credential> {
+        session.ensure_active()?;
+
+        self.credentials
+            .find_by_id(cred_id)?
+            .filter(|c| c.vault_id() == session.vault_id && c.is_trashed())
+            .ok_or_else(|| Error::not_found("Credencial não encontrada na lixeira"))
+    }
+
+    /// Recupera uma tag, garantindo que pertence ao cofre da sessão.
+    fn tag(&self, session: &Session, tag_id: Uuid) -> Result<Tag> {
+        session.ensure_active()?;
+
+        self.tags
+            .find_all_by_vault_id(session.vault_id)?
+            .into_iter()
+            .find(|t| t.id() == tag_id)
+            .ok_or_else(|| Error::not_found("Tag não encontrada"))
+    }
+
+    /// Recusa nomes repetidos (sem diferenciar maiúsculas) entre pastas irmãs.
+    fn ensure_unique_folder_name(&self, session: &Session, folder: &Folder) -> Result<()> {
+        let name = folder.name().to_lowercase();
+
+        if self
+            .folders
+            .find_all_by_vault_id(session.vault_id)?
+            .iter()
+            .any(|f| {
+                f.id() != folder.id()
+                    && f.parent_id() == folder.parent_id()
+                    && f.name().to_lowercase() == name
+            })
+        {
+            return Err(Error::conflict(format!(
+                "Já existe uma pasta chamada '{}' neste local",
+                folder.name()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Guarda no histórico os valores atuais dos campos que serão substituídos.
+    ///
+    /// Não grava nada quando o histórico está desativado ou quando os campos
+    /// alterados ainda não possuíam valor.
+    fn record_history(
+        &self,
+        session: &Session,
+        credential: &Credential,
+        password: bool,
+        notes: bool,
+    ) -> Result<()> {
+        if self.history_policy.retention == 0 {
+            return Ok(());
+        }
+
+        let password_cipher = credential.password_cipher().filter(|_| password);
+        let notes_cipher = credential.notes().filter(|_| notes);
+        if password_cipher.is_none() && notes_cipher.is_none() {
+            return Ok(());
+        }
+
+        debug!(
+            "Registrando versão anterior da credencial id='{}' no vault_id='{}'",
+            credential.id(),
+            session.vault_id
+        );
+        let entry = CredentialHistoryEntry::new(
+            credential.id(),
+            password_cipher.map(<[u8]>::to_vec),
+            notes_cipher.map(<[u8]>::to_vec),
+        )?;
+        self.history.record(
+            &entry,
+            self.history_policy.retention,
+            session.vault_generation(),
+        )
+    }
+
+    /// Indica se um campo cifrado contém exatamente o valor informado.
+    fn cipher_matches(session: &Session, cipher: Option<&[u8]>, value: &[u8]) -> Result<bool> {
+        let Some(cipher) = cipher else {
+            return Ok(false);
+        };
+
+        let mut plain = decrypt_with_key(&session.vault_key, cipher)?;
+        let matches = plain == value;
+        plain.zeroize();
+        Ok(matches)
+    }
+
+    /// Descriptografa um campo cifrado com a chave do cofre, quando presente.
+    fn open_field(session: &Session, cipher: Option<&[u8]>) -> Result<Option<String>> {
+        let Some(cipher) = cipher else {
+            return Ok(None);
+        };
+
+        let mut plain = decrypt_with_key(&session.vault_key, cipher)?;
+        let output = String::from_utf8_lossy(&plain).to_string();
+        plain.zeroize();
+        Ok(Some(output))
+    }
+
+    /// Recifra com a chave do cofre os campos ainda protegidos pelo formato legado
+    /// (AGE com a senha mestre).
+    ///
+    /// ### Parâmetros
+    /// - `session`: Sessão autenticada com a chave do cofre já desembrulhada.
+    /// - `passphrase`: Senha mestre, necessária apenas para abrir os campos legados.
+    ///
+    /// ### Retorno
+    /// - `Ok(usize)`: quantidade de credenciais recifradas (zero quando não há pendências).
+    /// - `Err(Error)`: falha ao descriptografar ou persistir algum registro.
+    ///
+    /// ### Aplicação
+    /// Executado automaticamente no login; é idempotente. Todas as credenciais
+    /// recifradas são persistidas em uma única transação.
+    pub(crate) fn migrate_legacy_ciphers(
+        &self,
+        session: &Session,
+        passphrase: &str,
+    ) -> Result<usize> {
+        let mut resealed = Vec::new();
+
+        let mut credentials = self.credentials.find_all_by_vault_id(session.vault_id)?;
+        credentials.extend(
+            self.credentials
+                .find_trashed_by_vault_id(session.vault_id)?,
+        );
+
+        for mut cred in credentials {
+            let legacy_password = cred.password_cipher().filter(|c| is_passphrase_cipher(c));
+            let legacy_notes = cred.notes().filter(|c| is_passphrase_cipher(c));
+
+            if legacy_password.is_none() && legacy_notes.is_none() {
+                continue;
+            }
+
+            debug!("Recifrando credencial legada id='{}'", cred.id());
+
+            let password_cipher = legacy_password
+                .map(|c| Self::reseal_legacy(session, passphrase, c))
+                .transpose()?;
+            let notes_cipher = legacy_notes
+                .map(|c| Self::reseal_legacy(session, passphrase, c))
+                .transpose()?;
+
+            // Mesma senha com outra cifra: não conta como troca de senha.
+            cred.set_ciphers(
+                password_cipher.or_else(|| cred.password_cipher().map(<[u8]>::to_vec)),
+                notes_cipher.or_else(|| cred.notes().map(<[u8]>::to_vec)),
+                cred.totp_cipher().map(<[u8]>::to_vec),
+            );
+
+            resealed.push(cred);
+        }
+
+        if !resealed.is_empty() {
+            self.credentials
+                .update_many(&resealed, session.vault_generation())?;
+        }
+
+        Ok(resealed.len())
+    }
+
+    /// Abre um campo legado com a senha mestre e o recifra com a chave do cofre.
+    fn reseal_legacy(session: &Session, passphrase: &str, cipher: &[u8]) -> Result<Vec<u8>> {
+        let mut plain = decrypt_with_passphrase(passphrase, cipher)?;
+        let sealed = encrypt_with_key(&session.vault_key, &plain);
+        plain.zeroize();
+        sealed
+    }
+}
+
+/// Ordena credenciais pelo nome.
+fn sorted_by_name(mut credentials: Vec<Credential>) -> Vec<Credential> {
+    credentials.sort_by(|a, b| a.name().cmp(b.name()));
+    credentials
 }
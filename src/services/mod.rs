@@ -31,6 +31,9 @@
 //! - Registro de novos usuários
 //! - Validação de credenciais de login
 //! - Criação e gerenciamento de sessões autenticadas
+//! - Chave da conta, que protege as chaves de todos os cofres do usuário
+//! - Par de chaves X25519, usado para receber cofres compartilhados
+//! - Listagem de usuários, renomeação e exclusão da conta (com a senha mestre)
 //!
 //! Este serviço garante que apenas usuários autenticados tenham acesso
 //! às operações sensíveis.
@@ -43,14 +46,81 @@
 //!
 //! - Criação de credenciais
 //! - Listagem e busca
-//! - Atualização de dados sensíveis
-//! - Remoção de credenciais
+//! - Atualização de dados sensíveis, com histórico das senhas anteriores
+//! - Remoção para a lixeira, com restauração e exclusão definitiva
 //!
 //! Todas as operações exigem uma **sessão válida**, garantindo
 //! isolamento e segurança entre usuários.
 //!
 //! ---
 //!
+//! ### [`vault_service`]
+//!
+//! Gestão dos cofres de um usuário:
+//!
+//! - Criação, renomeação, listagem e exclusão de cofres nomeados
+//! - Troca do cofre aberto na sessão, sem pedir a senha mestre
+//! - Transferência (mover ou copiar) de credenciais entre cofres
+//! - Compartilhamento com outros usuários como editor ou leitor, com troca
+//!   da chave do cofre ao revogar um membro
+//!
+//! Cada cofre tem a sua própria chave, embrulhada com a chave da conta do
+//! dono e com a chave pública de cada membro; a chave da conta é aberta pela
+//! senha mestre no [`auth_service`].
+//!
+//! ---
+//!
+//! ### [`credential_search`]
+//!
+//! Relevância dos resultados de pesquisa de credenciais:
+//!
+//! - Pontuação por tipo de correspondência (exata, prefixo, início de palavra, trecho)
+//! - Correspondência aproximada opcional (erros de digitação e subsequências)
+//! - Peso por campo (nome, usuário, URL e notas)
+//!
+//! Comparações ignoram maiúsculas e acentos. Não acessa repositórios: a
+//! seleção dos candidatos e a abertura das notas ficam no [`credential_service`].
+//!
+//! ---
+//!
+//! ### [`vault_archive`]
+//!
+//! Formato do arquivo de exportação de cofres:
+//!
+//! - JSON versionado com metadados (usuário, cofre de origem, data)
+//! - Credenciais com campos sensíveis em texto plano, cifradas em conjunto
+//!   com AGE/passphrase pelo [`credential_service`] antes de irem para o disco
+//!
+//! ---
+//!
+//! ### [`password_generator`]
+//!
+//! Geração de senhas fortes a partir do gerador aleatório do sistema:
+//!
+//! - Senhas de caracteres com comprimento e classes configuráveis
+//! - Exclusão de caracteres ambíguos e garantia de cada classe exigida
+//! - Frases secretas (diceware) a partir de uma lista de palavras embutida
+//! - Entropia estimada de cada resultado
+//!
+//! Não depende de sessão nem de repositórios.
+//!
+//! ---
+//!
+//! ### [`security_report`]
+//!
+//! Auditoria da higiene das senhas de um cofre:
+//!
+//! - Estimativa de força no estilo do zxcvbn (senhas comuns, palavras,
+//!   repetições, sequências, padrões de teclado e anos)
+//! - Senhas fracas, repetidas entre credenciais e sem atualização há N dias
+//! - Senhas presentes na base local de senhas vazadas, quando configurada
+//! - Credenciais sem usuário ou URL
+//!
+//! As senhas são abertas pelo [`credential_service`] apenas em memória; o
+//! relatório guarda somente pontuações e as credenciais afetadas.
+//!
+//! ---
+//!
 //! ## Fluxo de uso típico
 //!
 //! ```text
@@ -59,6 +129,7 @@
 //!     ▼
 //! AuthService
 //!     │
+//!     ├──► VaultService
 //!     ▼
 //! CredentialService
 //! ```
@@ -94,4 +165,9 @@
 //! e devem preservar invariantes de segurança e domínio.
 
 pub mod auth_service;
+pub mod credential_search;
 pub mod credential_service;
+pub mod password_generator;
+pub mod security_report;
+pub mod vault_archive;
+pub mod vault_service;
@@ -0,0 +1,285 @@
+use once_cell::sync::Lazy;
+use rand::{Rng, rngs::OsRng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::error::{Error, Result};
+
+/// Comprimento padrão das senhas geradas.
+pub const DEFAULT_PASSWORD_LENGTH: usize = 20;
+
+/// Comprimento mínimo aceito para senhas geradas.
+pub const MIN_PASSWORD_LENGTH: usize = 4;
+
+/// Comprimento máximo aceito para senhas geradas.
+pub const MAX_PASSWORD_LENGTH: usize = 128;
+
+/// Quantidade padrão de palavras em uma frase secreta.
+pub const DEFAULT_PASSPHRASE_WORDS: usize = 6;
+
+/// Quantidade mínima de palavras em uma frase secreta.
+pub const MIN_PASSPHRASE_WORDS: usize = 3;
+
+/// Quantidade máxima de palavras em uma frase secreta.
+pub const MAX_PASSPHRASE_WORDS: usize = 20;
+
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const SYMBOLS: &str = "!@#$%^&*()-_=+[]{};:,.<>/?~|";
+
+/// Caracteres facilmente confundidos entre si em determinadas fontes.
+const AMBIGUOUS: &str = "Il1|O0o";
+
+/// Lista de palavras para frases secretas (lista BIP-39 em português, domínio
+/// público): 2048 palavras sem acentos, cada uma contribuindo com 11 bits.
+pub(crate) static WORDLIST: Lazy<Vec<&'static str>> = Lazy::new(|| {
+    include_str!("../../assets/wordlist_pt.txt")
+        .lines()
+        .map(str::trim)
+        .filter(|w| !w.is_empty())
+        .collect()
+});
+
+/// Regras para a geração de senhas aleatórias de caracteres.
+///
+/// Campos ausentes na desserialização assumem os valores de [`Default`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PasswordPolicy {
+    /// Quantidade de caracteres.
+    pub length: usize,
+    /// Inclui letras minúsculas.
+    pub lowercase: bool,
+    /// Inclui letras maiúsculas.
+    pub uppercase: bool,
+    /// Inclui dígitos.
+    pub digits: bool,
+    /// Inclui símbolos.
+    pub symbols: bool,
+    /// Remove caracteres ambíguos (`I`, `l`, `1`, `|`, `O`, `0`, `o`).
+    pub exclude_ambiguous: bool,
+    /// Garante ao menos um caractere de cada classe habilitada.
+    pub require_each_class: bool,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            length: DEFAULT_PASSWORD_LENGTH,
+            lowercase: true,
+            uppercase: true,
+            digits: true,
+            symbols: true,
+            exclude_ambiguous: false,
+            require_each_class: true,
+        }
+    }
+}
+
+/// Regras para a geração de frases secretas (diceware).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PassphrasePolicy {
+    /// Quantidade de palavras.
+    pub words: usize,
+    /// Texto inserido entre as palavras.
+    pub separator: String,
+    /// Inicia cada palavra com letra maiúscula.
+    pub capitalize: bool,
+}
+
+impl Default for PassphrasePolicy {
+    fn default() -> Self {
+        Self {
+            words: DEFAULT_PASSPHRASE_WORDS,
+            separator: "-".to_string(),
+            capitalize: false,
+        }
+    }
+}
+
+/// Senha gerada acompanhada da entropia correspondente à política utilizada.
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedPassword {
+    /// Senha em texto plano.
+    pub value: String,
+    /// Entropia em bits, considerando que o atacante conhece a política.
+    pub entropy_bits: f64,
+}
+
+/// Gerador de senhas e frases secretas baseado no gerador aleatório do sistema (`OsRng`).
+pub struct PasswordGenerator;
+
+impl PasswordGenerator {
+    /// Gera uma senha aleatória de caracteres.
+    ///
+    /// ### Parâmetros
+    /// - `policy`: Comprimento e classes de caracteres desejadas.
+    ///
+    /// ### Retorno
+    /// - `Ok(GeneratedPassword)`: senha e entropia em bits.
+    /// - `Err(Error)`: comprimento fora dos limites, nenhuma classe habilitada ou
+    ///   comprimento insuficiente para conter todas as classes exigidas.
+    ///
+    /// ### Aplicação
+    /// Cada caractere é sorteado uniformemente do conjunto habilitado. Quando
+    /// `require_each_class` está ativo, senhas sem alguma das classes são
+    /// descartadas e sorteadas novamente, o que mantém a distribuição uniforme
+    /// entre todas as senhas válidas.
+    pub fn generate(policy: &PasswordPolicy) -> Result<GeneratedPassword> {
+        if !(MIN_PASSWORD_LENGTH..=MAX_PASSWORD_LENGTH).contains(&policy.length) {
+            return Err(Error::validation(
+                "length",
+                format!(
+                    "O comprimento deve estar entre {MIN_PASSWORD_LENGTH} e {MAX_PASSWORD_LENGTH} caracteres"
+                ),
+            ));
+        }
+
+        let classes = Self::classes(policy);
+        if classes.is_empty() {
+            return Err(Error::validation(
+                "charset",
+                "Selecione ao menos um tipo de caractere",
+            ));
+        }
+        if policy.require_each_class && policy.length < classes.len() {
+            return Err(Error::validation(
+                "length",
+                "O comprimento é menor que a quantidade de tipos de caractere exigidos",
+            ));
+        }
+
+        let pool: Vec<char> = classes.iter().flatten().copied().collect();
+        let mut rng = OsRng;
+
+        let value = loop {
+            let candidate: String = (0..policy.length)
+                .map(|_| *pool.choose(&mut rng).expect("conjunto não vazio"))
+                .collect();
+
+            if !policy.require_each_class
+                || classes
+                    .iter()
+                    .all(|class| candidate.chars().any(|c| class.contains(&c)))
+            {
+                break candidate;
+            }
+        };
+
+        let entropy_bits =
+            Self::password_entropy(policy.length, &classes, policy.require_each_class);
+        debug!(
+            "Senha gerada com {} caracteres (~{:.0} bits)",
+            policy.length, entropy_bits
+        );
+
+        Ok(GeneratedPassword {
+            value,
+            entropy_bits,
+        })
+    }
+
+    /// Gera uma frase secreta com palavras sorteadas da lista embutida.
+    ///
+    /// ### Parâmetros
+    /// - `policy`: Quantidade de palavras, separador e capitalização.
+    ///
+    /// ### Retorno
+    /// - `Ok(GeneratedPassword)`: frase e entropia em bits (11 bits por palavra).
+    /// - `Err(Error)`: quantidade de palavras fora dos limites.
+    pub fn passphrase(policy: &PassphrasePolicy) -> Result<GeneratedPassword> {
+        if !(MIN_PASSPHRASE_WORDS..=MAX_PASSPHRASE_WORDS).contains(&policy.words) {
+            return Err(Error::validation(
+                "words",
+                format!(
+                    "A frase deve ter entre {MIN_PASSPHRASE_WORDS} e {MAX_PASSPHRASE_WORDS} palavras"
+                ),
+            ));
+        }
+
+        let mut rng = OsRng;
+        let words: Vec<String> = (0..policy.words)
+            .map(|_| {
+                let word = WORDLIST[rng.gen_range(0..WORDLIST.len())];
+                if policy.capitalize {
+                    capitalize(word)
+                } else {
+                    word.to_string()
+                }
+            })
+            .collect();
+
+        let entropy_bits = policy.words as f64 * (WORDLIST.len() as f64).log2();
+        debug!(
+            "Frase secreta gerada com {} palavras (~{:.0} bits)",
+            policy.words, entropy_bits
+        );
+
+        Ok(GeneratedPassword {
+            value: words.join(&policy.separator),
+            entropy_bits,
+        })
+    }
+
+    /// Retorna os conjuntos de caracteres habilitados pela política.
+    fn classes(policy: &PasswordPolicy) -> Vec<Vec<char>> {
+        [
+            (policy.lowercase, LOWERCASE),
+            (policy.uppercase, UPPERCASE),
+            (policy.digits, DIGITS),
+            (policy.symbols, SYMBOLS),
+        ]
+        .into_iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, chars)| {
+            chars
+                .chars()
+                .filter(|c| !policy.exclude_ambiguous || !AMBIGUOUS.contains(*c))
+                .collect::<Vec<char>>()
+        })
+        .filter(|class| !class.is_empty())
+        .collect()
+    }
+
+    /// Calcula a entropia exata (em bits) de uma senha uniforme sobre as classes.
+    ///
+    /// Sem exigência de classes, há `n^L` senhas possíveis. Com a exigência, a
+    /// fração de senhas válidas é obtida por inclusão-exclusão sobre as classes
+    /// ausentes: `Σ (-1)^|S| ((n - |S|) / n)^L`.
+    fn password_entropy(length: usize, classes: &[Vec<char>], require_each_class: bool) -> f64 {
+        let pool = classes.iter().map(Vec::len).sum::<usize>() as f64;
+        let mut bits = length as f64 * pool.log2();
+
+        if require_each_class {
+            let mut valid_fraction = 0.0;
+            for subset in 0u32..(1 << classes.len()) {
+                let missing: usize = classes
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| subset & (1 << i) != 0)
+                    .map(|(_, class)| class.len())
+                    .sum();
+                let sign = if subset.count_ones() % 2 == 0 {
+                    1.0
+                } else {
+                    -1.0
+                };
+                valid_fraction += sign * ((pool - missing as f64) / pool).powi(length as i32);
+            }
+            bits += valid_fraction.log2();
+        }
+
+        bits
+    }
+}
+
+/// Converte a primeira letra da palavra para maiúscula.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,493 @@
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+
+use crate::{models::credential::Credential, services::password_generator::WORDLIST};
+
+/// Idade máxima padrão de uma senha, em dias, antes de ser considerada antiga.
+pub const DEFAULT_MAX_PASSWORD_AGE_DAYS: u32 = 365;
+
+/// Pontuação mínima padrão (0 a 4) para uma senha não ser considerada fraca.
+pub const DEFAULT_MIN_STRENGTH_SCORE: u8 = 3;
+
+/// Comprimento abaixo do qual a senha recebe o aviso [`StrengthWarning::TooShort`].
+const SHORT_PASSWORD: usize = 8;
+
+/// Menor trecho reconhecido como palavra de dicionário ou padrão de teclado.
+const MIN_WORD_LENGTH: usize = 4;
+
+/// Maior trecho procurado nos dicionários.
+const MAX_WORD_LENGTH: usize = 24;
+
+/// Limites de entropia (bits) das pontuações 1 a 4.
+const SCORE_THRESHOLDS: [f64; 4] = [25.0, 40.0, 55.0, 70.0];
+
+/// Senhas e palavras mais usadas em vazamentos, em minúsculas e sem acentos.
+static COMMON_PASSWORDS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
+    include_str!("../../assets/common_passwords.txt")
+        .lines()
+        .map(str::trim)
+        .filter(|w| !w.is_empty())
+        .collect()
+});
+
+/// Palavras da lista usada nas frases secretas geradas.
+static WORDS: Lazy<HashSet<&'static str>> = Lazy::new(|| WORDLIST.iter().copied().collect());
+
+/// Linhas do teclado QWERTY usadas na detecção de padrões de teclado.
+const KEYBOARD_ROWS: [&str; 4] = ["1234567890", "qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// Opções do relatório de segurança.
+///
+/// Campos ausentes na desserialização assumem os valores de [`Default`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportOptions {
+    /// Dias sem troca de senha a partir dos quais uma senha é considerada antiga.
+    pub max_age_days: u32,
+    /// Pontuação mínima (0 a 4) para uma senha não ser considerada fraca.
+    pub min_score: u8,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            max_age_days: DEFAULT_MAX_PASSWORD_AGE_DAYS,
+            min_score: DEFAULT_MIN_STRENGTH_SCORE,
+        }
+    }
+}
+
+/// Motivo que reduziu a força estimada de uma senha.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StrengthWarning {
+    /// Contém uma senha ou palavra comum.
+    Common,
+    /// Contém uma palavra de dicionário.
+    Dictionary,
+    /// Contém um caractere repetido (ex.: `aaaa`).
+    Repeated,
+    /// Contém uma sequência (ex.: `abcd`, `4321`).
+    Sequence,
+    /// Contém teclas vizinhas no teclado (ex.: `qwer`, `asdf`).
+    KeyboardPattern,
+    /// Contém um ano.
+    Date,
+    /// Tem menos de oito caracteres.
+    TooShort,
+    /// Usa um único tipo de caractere.
+    SingleClass,
+}
+
+impl StrengthWarning {
+    /// Descrição legível do aviso.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Common => "contém uma senha comum",
+            Self::Dictionary => "contém palavras de dicionário",
+            Self::Repeated => "contém caracteres repetidos",
+            Self::Sequence => "contém uma sequência",
+            Self::KeyboardPattern => "contém um padrão de teclado",
+            Self::Date => "contém um ano",
+            Self::TooShort => "é curta",
+            Self::SingleClass => "usa um único tipo de caractere",
+        }
+    }
+}
+
+/// Força estimada de uma senha.
+#[derive(Debug, Clone, Serialize)]
+pub struct PasswordStrength {
+    /// Pontuação de 0 (muito fraca) a 4 (muito forte).
+    pub score: u8,
+    /// Entropia estimada em bits, considerando os padrões encontrados.
+    pub entropy_bits: f64,
+    /// Padrões que reduziram a estimativa, sem repetições.
+    pub warnings: Vec<StrengthWarning>,
+}
+
+/// Estima a força de uma senha no estilo do zxcvbn.
+///
+/// ### Parâmetros
+/// - `password`: Senha em texto plano.
+///
+/// ### Retorno
+/// - [`PasswordStrength`] com pontuação, entropia e avisos.
+///
+/// ### Aplicação
+/// A senha é dividida, da esquerda para a direita, no maior padrão conhecido
+/// em cada posição (senha comum, palavra da lista de frases secretas, repetição,
+/// sequência, teclas vizinhas ou ano). Cada padrão custa apenas os bits
+/// necessários para um atacante adivinhá-lo; os caracteres restantes custam o
+/// logaritmo do alfabeto usado pela senha.
+pub fn estimate_strength(password: &str) -> PasswordStrength {
+    let chars: Vec<char> = password.chars().collect();
+    let brute_force_bits = (alphabet_size(&chars) as f64).log2();
+
+    let mut warnings = Vec::new();
+    let mut bits = 0.0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (length, cost, warning) =
+            best_pattern(&chars[i..]).unwrap_or((1, brute_force_bits, None));
+
+        bits += cost;
+        i += length;
+        if let Some(warning) = warning.filter(|w| !warnings.contains(w)) {
+            warnings.push(warning);
+        }
+    }
+
+    if chars.len() < SHORT_PASSWORD {
+        warnings.push(StrengthWarning::TooShort);
+    }
+    if !chars.is_empty() && character_classes(&chars) == 1 {
+        warnings.push(StrengthWarning::SingleClass);
+    }
+
+    let score = SCORE_THRESHOLDS.iter().filter(|&&t| bits >= t).count() as u8;
+
+    PasswordStrength {
+        score,
+        entropy_bits: bits,
+        warnings,
+    }
+}
+
+/// Maior padrão que começa no início do trecho: `(comprimento, bits, aviso)`.
+fn best_pattern(chars: &[char]) -> Option<(usize, f64, Option<StrengthWarning>)> {
+    [
+        dictionary_match(chars),
+        repeat_match(chars),
+        sequence_match(chars),
+        keyboard_match(chars),
+        year_match(chars),
+    ]
+    .into_iter()
+    .flatten()
+    .max_by(|a, b| a.0.cmp(&b.0).then(b.1.total_cmp(&a.1)))
+}
+
+/// Senha comum ou palavra da lista de frases secretas, aceitando maiúsculas e
+/// substituições como `@` → `a` e `0` → `o`.
+fn dictionary_match(chars: &[char]) -> Option<(usize, f64, Option<StrengthWarning>)> {
+    let longest = chars.len().min(MAX_WORD_LENGTH);
+
+    (MIN_WORD_LENGTH..=longest).rev().find_map(|length| {
+        let token = &chars[..length];
+        let uppercase = f64::from(u8::from(token.iter().any(|c| c.is_uppercase())));
+
+        let plain: String = token.iter().map(char::to_ascii_lowercase).collect();
+        let unleeted: String = token.iter().map(|&c| unleet(c)).collect();
+
+        [(plain, uppercase), (unleeted, uppercase + 1.0)]
+            .into_iter()
+            .find_map(|(word, variations)| {
+                if COMMON_PASSWORDS.contains(word.as_str()) {
+                    let bits = (COMMON_PASSWORDS.len() as f64).log2() + variations;
+                    Some((length, bits, Some(StrengthWarning::Common)))
+                } else if WORDS.contains(word.as_str()) {
+                    let bits = (WORDS.len() as f64).log2() + variations;
+                    Some((length, bits, Some(StrengthWarning::Dictionary)))
+                } else {
+                    None
+                }
+            })
+    })
+}
+
+/// Mesmo caractere repetido três ou mais vezes.
+fn repeat_match(chars: &[char]) -> Option<(usize, f64, Option<StrengthWarning>)> {
+    let first = *chars.first()?;
+    let length = chars.iter().take_while(|&&c| c == first).count();
+
+    (length >= 3).then(|| {
+        let bits = (alphabet_size(&[first]) as f64).log2() + (length as f64).log2();
+        (length, bits, Some(StrengthWarning::Repeated))
+    })
+}
+
+/// Letras ou dígitos consecutivos, em ordem crescente ou decrescente.
+fn sequence_match(chars: &[char]) -> Option<(usize, f64, Option<StrengthWarning>)> {
+    let [first, second, ..] = chars else {
+        return None;
+    };
+    if !first.is_ascii_alphanumeric() {
+        return None;
+    }
+
+    let step = *second as i32 - *first as i32;
+    if step.abs() != 1 {
+        return None;
+    }
+
+    let length = 1 + chars
+        .windows(2)
+        .take_while(|w| w[1].is_ascii_alphanumeric() && w[1] as i32 - w[0] as i32 == step)
+        .count();
+
+    (length >= 3).then(|| {
+        // alfabeto, ponto de partida e direção
+        let bits = (alphabet_size(&[*first]) as f64).log2() + (length as f64).log2() + 1.0;
+        (length, bits, Some(StrengthWarning::Sequence))
+    })
+}
+
+/// Teclas vizinhas em uma linha do teclado, em qualquer direção.
+fn keyboard_match(chars: &[char]) -> Option<(usize, f64, Option<StrengthWarning>)> {
+    let lower: Vec<char> = chars
+        .iter()
+        .take(MAX_WORD_LENGTH)
+        .map(char::to_ascii_lowercase)
+        .collect();
+
+    let keys: usize = KEYBOARD_ROWS.iter().map(|row| row.len()).sum();
+    let length = KEYBOARD_ROWS
+        .iter()
+        .flat_map(|row| [row.to_string(), row.chars().rev().collect()])
+        .filter_map(|row| {
+            (MIN_WORD_LENGTH..=lower.len())
+                .rev()
+                .find(|&n| row.contains(&lower[..n].iter().collect::<String>()))
+        })
+        .max()?;
+
+    let bits = (keys as f64).log2() + (length as f64).log2() + 1.0;
+    Some((length, bits, Some(StrengthWarning::KeyboardPattern)))
+}
+
+/// Ano entre 1900 e 2099.
+fn year_match(chars: &[char]) -> Option<(usize, f64, Option<StrengthWarning>)> {
+    let year: String = chars.iter().take(4).collect();
+    let year: u32 = year.parse().ok().filter(|_| year.len() == 4)?;
+
+    (1900..=2099)
+        .contains(&year)
+        .then(|| (4, 200f64.log2(), Some(StrengthWarning::Date)))
+}
+
+/// Desfaz substituições comuns de letras por dígitos e símbolos.
+fn unleet(c: char) -> char {
+    match c {
+        '@' | '4' => 'a',
+        '3' => 'e',
+        '1' | '!' => 'i',
+        '0' => 'o',
+        '$' | '5' => 's',
+        '7' => 't',
+        other => other.to_ascii_lowercase(),
+    }
+}
+
+/// Quantidade de tipos de caractere usados (minúsculas, maiúsculas, dígitos,
+/// símbolos ASCII e demais caracteres).
+fn character_classes(chars: &[char]) -> usize {
+    class_sizes(chars).iter().filter(|size| **size > 0).count()
+}
+
+/// Tamanho do alfabeto formado pelos tipos de caractere usados.
+fn alphabet_size(chars: &[char]) -> usize {
+    class_sizes(chars).iter().sum::<usize>().max(1)
+}
+
+/// Tamanho de cada tipo de caractere presente (zero quando ausente).
+fn class_sizes(chars: &[char]) -> [usize; 5] {
+    let has = |f: fn(&char) -> bool| chars.iter().any(f);
+    [
+        if has(char::is_ascii_lowercase) { 26 } else { 0 },
+        if has(char::is_ascii_uppercase) { 26 } else { 0 },
+        if has(char::is_ascii_digit) { 10 } else { 0 },
+        if has(|c| c.is_ascii() && !c.is_ascii_alphanumeric()) {
+            33
+        } else {
+            0
+        },
+        if has(|c| !c.is_ascii()) { 100 } else { 0 },
+    ]
+}
+
+/// Credencial com senha abaixo da pontuação mínima.
+#[derive(Debug, Clone, Serialize)]
+pub struct WeakPassword {
+    pub credential: Credential,
+    pub strength: PasswordStrength,
+}
+
+/// Grupo de credenciais que compartilham a mesma senha.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReusedPassword {
+    /// Credenciais do grupo, ordenadas pelo nome.
+    pub credentials: Vec<Credential>,
+}
+
+/// Credencial cuja senha não é atualizada há mais tempo que o limite.
+#[derive(Debug, Clone, Serialize)]
+pub struct StalePassword {
+    pub credential: Credential,
+    /// Dias desde a última troca da senha ([`Credential::password_changed_at`]).
+    pub age_days: i64,
+}
+
+/// Credencial cuja senha consta na base local de senhas vazadas.
+#[derive(Debug, Clone, Serialize)]
+pub struct BreachedPassword {
+    pub credential: Credential,
+    /// Quantidade de vezes que a senha aparece em vazamentos conhecidos.
+    pub occurrences: u64,
+}
+
+/// Credencial sem usuário ou sem URL.
+#[derive(Debug, Clone, Serialize)]
+pub struct IncompleteCredential {
+    pub credential: Credential,
+    pub missing_username: bool,
+    pub missing_url: bool,
+}
+
+/// Relatório da higiene das senhas de um cofre.
+#[derive(Debug, Clone, Serialize)]
+pub struct SecurityReport {
+    pub generated_at: DateTime<Utc>,
+    pub options: ReportOptions,
+    /// Credenciais avaliadas (fora da lixeira).
+    pub total: usize,
+    /// Senhas fracas, da mais fraca para a mais forte.
+    pub weak: Vec<WeakPassword>,
+    /// Senhas repetidas, dos maiores grupos para os menores.
+    pub reused: Vec<ReusedPassword>,
+    /// Senhas antigas, da mais antiga para a mais recente.
+    pub stale: Vec<StalePassword>,
+    /// Indica se as senhas foram comparadas com a base de senhas vazadas.
+    pub breach_checked: bool,
+    /// Senhas vazadas, das mais frequentes para as menos frequentes.
+    pub breached: Vec<BreachedPassword>,
+    /// Credenciais sem usuário ou URL, ordenadas pelo nome.
+    pub incomplete: Vec<IncompleteCredential>,
+}
+
+impl SecurityReport {
+    /// Indica se nenhum problema foi encontrado.
+    pub fn is_clean(&self) -> bool {
+        self.weak.is_empty()
+            && self.reused.is_empty()
+            && self.stale.is_empty()
+            && self.breached.is_empty()
+            && self.incomplete.is_empty()
+    }
+}
+
+/// Monta um [`SecurityReport`] a partir das credenciais e senhas abertas.
+///
+/// As senhas não são guardadas: apenas a pontuação e um SHA-256, usado para
+/// agrupar as repetidas.
+pub(crate) struct ReportBuilder {
+    report: SecurityReport,
+    by_digest: HashMap<[u8; 32], Vec<Credential>>,
+}
+
+impl ReportBuilder {
+    pub(crate) fn new(options: ReportOptions, now: DateTime<Utc>, breach_checked: bool) -> Self {
+        Self {
+            report: SecurityReport {
+                generated_at: now,
+                options,
+                total: 0,
+                weak: Vec::new(),
+                reused: Vec::new(),
+                stale: Vec::new(),
+                breach_checked,
+                breached: Vec::new(),
+                incomplete: Vec::new(),
+            },
+            by_digest: HashMap::new(),
+        }
+    }
+
+    /// Avalia uma credencial com sua senha em texto plano (quando houver) e a
+    /// quantidade de ocorrências da senha em vazamentos.
+    pub(crate) fn add(&mut self, credential: Credential, password: Option<&str>, breaches: u64) {
+        let report = &mut self.report;
+        report.total += 1;
+
+        let missing_username = credential.username().is_none_or(|u| u.trim().is_empty());
+        let missing_url = credential.url().is_none_or(|u| u.trim().is_empty());
+        if missing_username || missing_url {
+            report.incomplete.push(IncompleteCredential {
+                credential: credential.clone(),
+                missing_username,
+                missing_url,
+            });
+        }
+
+        let Some(password) = password.filter(|p| !p.is_empty()) else {
+            return;
+        };
+
+        let age_days = (report.generated_at - credential.password_changed_at()).num_days();
+        if age_days >= i64::from(report.options.max_age_days) {
+            report.stale.push(StalePassword {
+                credential: credential.clone(),
+                age_days,
+            });
+        }
+
+        if breaches > 0 {
+            report.breached.push(BreachedPassword {
+                credential: credential.clone(),
+                occurrences: breaches,
+            });
+        }
+
+        let strength = estimate_strength(password);
+        if strength.score < report.options.min_score {
+            report.weak.push(WeakPassword {
+                credential: credential.clone(),
+                strength,
+            });
+        }
+
+        let digest: [u8; 32] = Sha256::digest(password.as_bytes()).into();
+        self.by_digest.entry(digest).or_default().push(credential);
+    }
+
+    /// Agrupa as senhas repetidas e ordena as seções do relatório.
+    pub(crate) fn finish(self) -> SecurityReport {
+        let mut report = self.report;
+
+        report.reused = self
+            .by_digest
+            .into_values()
+            .filter(|group| group.len() > 1)
+            .map(|mut credentials| {
+                credentials.sort_by(|a, b| a.name().cmp(b.name()));
+                ReusedPassword { credentials }
+            })
+            .collect();
+        report.reused.sort_by(|a, b| {
+            b.credentials
+                .len()
+                .cmp(&a.credentials.len())
+                .then_with(|| a.credentials[0].name().cmp(b.credentials[0].name()))
+        });
+
+        report.weak.sort_by(|a, b| {
+            a.strength
+                .entropy_bits
+                .total_cmp(&b.strength.entropy_bits)
+                .then_with(|| a.credential.name().cmp(b.credential.name()))
+        });
+        report.stale.sort_by_key(|s| std::cmp::Reverse(s.age_days));
+        report
+            .breached
+            .sort_by_key(|b| std::cmp::Reverse(b.occurrences));
+        report
+            .incomplete
+            .sort_by(|a, b| a.credential.name().cmp(b.credential.name()));
+
+        report
+    }
+}
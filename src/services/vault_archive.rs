@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use zeroize::Zeroize;
+
+use crate::error::{Error, Result};
+
+/// Identificador gravado em todo arquivo exportado.
+pub const ARCHIVE_FORMAT: &str = "password_manager/vault-archive";
+
+/// Versão atual do formato do arquivo exportado.
+///
+/// Deve ser incrementada sempre que a estrutura de [`VaultArchive`] mudar de forma
+/// incompatível; arquivos com versão maior que esta são recusados na importação.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// Conteúdo (em texto plano) de um arquivo de exportação de cofre.
+///
+/// É serializado em JSON e cifrado com AGE/passphrase antes de ser gravado em disco,
+/// de forma que o arquivo só pode ser aberto com a senha escolhida na exportação.
+#[derive(Serialize, Deserialize)]
+pub struct VaultArchive {
+    /// Identificador do formato ([`ARCHIVE_FORMAT`]).
+    pub format: String,
+    /// Versão do formato ([`ARCHIVE_VERSION`]).
+    pub version: u32,
+    /// Momento da exportação.
+    pub exported_at: DateTime<Utc>,
+    /// Nome do usuário dono do cofre exportado.
+    pub exported_by: String,
+    /// Identificador do cofre de origem.
+    pub source_vault_id: Uuid,
+    /// Credenciais com os campos sensíveis já descriptografados.
+    pub credentials: Vec<ArchivedCredential>,
+}
+
+/// Credencial exportada, com senha, notas e TOTP em texto plano.
+///
+/// Os campos sensíveis são apagados da memória quando a instância é descartada.
+#[derive(Serialize, Deserialize)]
+pub struct ArchivedCredential {
+    pub name: String,
+    pub username: Option<String>,
+    pub url: Option<String>,
+    pub notes: Option<String>,
+    pub password: Option<String>,
+    /// URI `otpauth://totp/...` normalizada.
+    pub totp: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Última troca de senha; ausente em arquivos anteriores a este campo, em
+    /// que vale `updated_at`.
+    #[serde(default)]
+    pub password_changed_at: Option<DateTime<Utc>>,
+}
+
+impl Drop for ArchivedCredential {
+    fn drop(&mut self) {
+        self.notes.zeroize();
+        self.password.zeroize();
+        self.totp.zeroize();
+    }
+}
+
+/// Resultado de uma importação.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportSummary {
+    /// Quantidade de credenciais criadas no cofre de destino.
+    pub imported: usize,
+    /// Nome do usuário que gerou o arquivo.
+    pub exported_by: String,
+    /// Momento em que o arquivo foi gerado.
+    pub exported_at: DateTime<Utc>,
+}
+
+impl VaultArchive {
+    /// Cria um arquivo na versão atual do formato.
+    pub fn new(
+        exported_by: &str,
+        source_vault_id: Uuid,
+        credentials: Vec<ArchivedCredential>,
+    ) -> Self {
+        Self {
+            format: ARCHIVE_FORMAT.to_string(),
+            version: ARCHIVE_VERSION,
+            exported_at: Utc::now(),
+            exported_by: exported_by.to_string(),
+            source_vault_id,
+            credentials,
+        }
+    }
+
+    /// Serializa o arquivo em JSON.
+    ///
+    /// O buffer retornado contém segredos em texto plano e deve ser apagado
+    /// (`zeroize`) assim que for cifrado.
+    pub fn to_json(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self)
+            .map_err(|e| Error::storage(format!("Falha ao serializar exportação: {e}")))
+    }
+
+    /// Interpreta o JSON de um arquivo exportado, validando formato e versão.
+    ///
+    /// ### Retorno
+    /// - `Ok(VaultArchive)`: arquivo reconhecido e compatível.
+    /// - `Err(Error)`: conteúdo que não é uma exportação do gerenciador ou versão
+    ///   gerada por uma versão mais nova da aplicação.
+    pub fn from_json(json: &[u8]) -> Result<Self> {
+        let archive: Self = serde_json::from_slice(json).map_err(|_| {
+            Error::validation("file", "Arquivo de exportação inválido ou corrompido")
+        })?;
+
+        if archive.format != ARCHIVE_FORMAT {
+            return Err(Error::validation(
+                "file",
+                "Arquivo não é uma exportação de cofre",
+            ));
+        }
+        if archive.version > ARCHIVE_VERSION {
+            return Err(Error::validation(
+                "file",
+                format!(
+                    "Arquivo exportado no formato v{}, mas esta versão da aplicação suporta até v{}",
+                    archive.version, ARCHIVE_VERSION
+                ),
+            ));
+        }
+
+        Ok(archive)
+    }
+}
@@ -0,0 +1,590 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+use zeroize::Zeroize;
+
+use crate::{
+    error::{Error, Result},
+    infrastructure::crypto::{encrypt_for_recipient, encrypt_with_key, generate_vault_key},
+    models::{
+        credential::Credential,
+        user::User,
+        vault::Vault,
+        vault_member::{VaultMember, VaultRole},
+    },
+    repositories::{
+        user_repository::UserRepository, vault_member_repository::VaultMemberRepository,
+        vault_repository::VaultRepository,
+    },
+    services::{
+        auth_service::{Session, unwrap_member_key, unwrap_vault_key},
+        credential_service::CredentialService,
+    },
+};
+
+/// Forma de transferência de credenciais entre cofres.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferMode {
+    /// Retira a credencial do cofre de origem, preservando ID e histórico.
+    Move,
+    /// Mantém a original e cria uma cópia independente no destino.
+    Copy,
+}
+
+/// Cofre acessível ao usuário com os dados usados para exibi-lo em listas.
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultSummary {
+    pub vault: Vault,
+    /// Quantidade de credenciais fora da lixeira.
+    pub credentials: usize,
+    /// Indica se é o cofre aberto na sessão.
+    pub current: bool,
+    /// Papel do usuário da sessão no cofre.
+    pub role: VaultRole,
+    /// Nome do dono, quando o cofre foi compartilhado por outro usuário.
+    pub owner: Option<String>,
+    /// Quantidade de membros, além do dono.
+    pub members: usize,
+}
+
+/// Usuário com acesso a um cofre.
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultMemberSummary {
+    pub user_id: Uuid,
+    pub username: String,
+    pub role: VaultRole,
+}
+
+/// Serviço responsável pelos cofres de um usuário: criação, renomeação,
+/// exclusão, troca do cofre aberto na sessão, transferência de credenciais
+/// entre cofres e compartilhamento com outros usuários.
+///
+/// Cada cofre possui a sua própria chave, embrulhada com a chave da conta do
+/// dono e, para cada membro, com a chave pública X25519 dele. As duas formas
+/// são abertas a partir da chave da conta mantida na [`Session`]; por isso
+/// nenhuma operação exige a senha mestre.
+#[derive(Clone)]
+pub struct VaultService {
+    users: Arc<dyn UserRepository>,
+    vaults: Arc<dyn VaultRepository>,
+    members: Arc<dyn VaultMemberRepository>,
+    credentials: CredentialService,
+}
+
+impl VaultService {
+    /// Cria o serviço sobre os repositórios de usuários, cofres e membros.
+    ///
+    /// ### Parâmetros
+    /// - `users`: Repositório consultado para localizar convidados.
+    /// - `vaults`: Repositório de cofres.
+    /// - `members`: Repositório de membros de cofres compartilhados.
+    /// - `credentials`: Serviço usado para contar, transferir e recifrar credenciais.
+    pub fn new(
+        users: Arc<dyn UserRepository>,
+        vaults: Arc<dyn VaultRepository>,
+        members: Arc<dyn VaultMemberRepository>,
+        credentials: CredentialService,
+    ) -> Self {
+        Self {
+            users,
+            vaults,
+            members,
+            credentials,
+        }
+    }
+
+    /// Lista os cofres acessíveis ao usuário da sessão: primeiro os próprios,
+    /// depois os compartilhados com ele, cada grupo do mais antigo para o mais
+    /// recente.
+    ///
+    /// ### Retorno
+    /// - `Ok(Vec<VaultSummary>)`: cofres com a quantidade de credenciais, o
+    ///   papel do usuário e a indicação do cofre aberto.
+    /// - `Err(Error)`: sessão inativa ou falha de consulta.
+    pub fn list(&self, session: &Session) -> Result<Vec<VaultSummary>> {
+        session.ensure_active()?;
+
+        let mut summaries = Vec::new();
+        for vault in self.vaults.find_all_by_user_id(session.user.id())? {
+            summaries.push(self.summary(session, vault, VaultRole::Owner, None)?);
+        }
+
+        for member in self.members.find_by_user_id(session.user.id())? {
+            let Some(vault) = self.vaults.find_by_id(member.vault_id())? else {
+                continue;
+            };
+            let owner = self
+                .users
+                .find_by_id(vault.user_id())?
+                .map(|u| u.username().to_string());
+            summaries.push(self.summary(session, vault, member.role(), owner)?);
+        }
+
+        Ok(summaries)
+    }
+
+    /// Retorna o cofre aberto na sessão.
+    pub fn current(&self, session: &Session) -> Result<Vault> {
+        Ok(self.access(session, session.vault_id)?.0)
+    }
+
+    /// Cria um novo cofre vazio para o usuário da sessão.
+    ///
+    /// ### Parâmetros
+    /// - `name`: Nome do cofre, único na conta (sem diferenciar maiúsculas).
+    ///
+    /// ### Retorno
+    /// - `Ok(Vault)`: cofre criado; a sessão continua no cofre atual.
+    /// - `Err(Error)`: nome inválido ou repetido, ou falha de persistência.
+    ///
+    /// ### Aplicação
+    /// Uma nova chave aleatória é gerada e embrulhada com a chave da conta.
+    pub fn create(&self, session: &Session, name: &str) -> Result<Vault> {
+        session.ensure_active()?;
+
+        let mut vault_key = generate_vault_key();
+        let cipher = encrypt_with_key(session.account_key(), &vault_key);
+        vault_key.zeroize();
+
+        let vault = Vault::new(session.user.id(), name.to_string(), cipher?)?;
+        self.ensure_unique_name(session, &vault)?;
+
+        info!(
+            "Criando cofre name='{}' para username='{}'",
+            vault.name(),
+            session.user.username()
+        );
+        self.vaults.create(&vault)?;
+        Ok(vault)
+    }
+
+    /// Renomeia um cofre do usuário.
+    ///
+    /// ### Retorno
+    /// - `Ok(Vault)`: cofre com o novo nome.
+    /// - `Err(Error)`: cofre inexistente ou de outro dono, nome inválido ou repetido.
+    pub fn rename(&self, session: &Session, vault_id: Uuid, name: &str) -> Result<Vault> {
+        let mut vault = self.owned(session, vault_id)?;
+        vault.set_name(name.to_string())?;
+        self.ensure_unique_name(session, &vault)?;
+
+        info!(
+            "Renomeando cofre id='{}' para '{}'",
+            vault.id(),
+            vault.name()
+        );
+        self.vaults.update(&vault)?;
+        Ok(vault)
+    }
+
+    /// Exclui um cofre com todas as suas credenciais, pastas e tags.
+    ///
+    /// ### Retorno
+    /// - `Ok(())`: cofre excluído definitivamente.
+    /// - `Err(Error)`: cofre inexistente, de outro dono ou aberto na sessão.
+    ///
+    /// ### Aplicação
+    /// O cofre aberto não pode ser excluído; como o usuário precisa alternar
+    /// para outro antes, a conta nunca fica sem cofres. Os membros de um cofre
+    /// compartilhado perdem o acesso junto com ele.
+    pub fn delete(&self, session: &Session, vault_id: Uuid) -> Result<()> {
+        let vault = self.owned(session, vault_id)?;
+
+        if vault.id() == session.vault_id {
+            return Err(Error::conflict(
+                "O cofre aberto não pode ser excluído; alterne para outro cofre antes",
+            ));
+        }
+
+        info!(
+            "Excluindo cofre id='{}' name='{}' de username='{}'",
+            vault.id(),
+            vault.name(),
+            session.user.username()
+        );
+        self.vaults.delete(vault.id())
+    }
+
+    /// Abre outro cofre acessível ao usuário na sessão.
+    ///
+    /// ### Retorno
+    /// - `Ok(Vault)`: cofre agora aberto.
+    /// - `Err(Error)`: sessão inativa, cofre inexistente ou sem acesso.
+    ///
+    /// ### Aplicação
+    /// A chave do cofre é desembrulhada com a chave da conta (cofres próprios)
+    /// ou com a chave privada do usuário (cofres compartilhados), sem a senha
+    /// mestre; a chave do cofre anterior é apagada da memória. Itens expirados
+    /// da lixeira do cofre aberto são excluídos, como no login.
+    pub fn switch(&self, session: &mut Session, vault_id: Uuid) -> Result<Vault> {
+        let (vault, role, vault_key) = self.open(session, vault_id)?;
+
+        info!(
+            "Alternando sessão de '{}' para o cofre '{}' ({})",
+            session.user.username(),
+            vault.name(),
+            role
+        );
+        session.open_vault(&vault, vault_key, role);
+        self.credentials.purge_expired_trash(session)?;
+        Ok(vault)
+    }
+
+    /// Move ou copia credenciais do cofre aberto para outro cofre acessível ao
+    /// usuário.
+    ///
+    /// ### Parâmetros
+    /// - `cred_ids`: Credenciais do cofre aberto.
+    /// - `target_vault_id`: Cofre de destino, diferente do aberto.
+    /// - `mode`: Mover ou copiar.
+    ///
+    /// ### Retorno
+    /// - `Ok(Vec<Credential>)`: credenciais gravadas no destino, na ordem informada.
+    /// - `Err(Error)`: destino inválido ou somente leitura, origem somente
+    ///   leitura ao mover, credencial inexistente ou falha de persistência. Cada
+    ///   credencial é transferida de forma independente: as anteriores à falha
+    ///   permanecem no destino.
+    pub fn transfer(
+        &self,
+        session: &Session,
+        cred_ids: &[Uuid],
+        target_vault_id: Uuid,
+        mode: TransferMode,
+    ) -> Result<Vec<Credential>> {
+        if target_vault_id == session.vault_id {
+            return Err(Error::validation(
+                "vault_id",
+                "Escolha um cofre de destino diferente do atual",
+            ));
+        }
+
+        let (target, role, mut target_key) = self.open(session, target_vault_id)?;
+        if !role.can_edit() {
+            target_key.zeroize();
+            return Err(Error::unauthorized(
+                "Acesso somente leitura ao cofre de destino",
+            ));
+        }
+
+        let transferred = cred_ids
+            .iter()
+            .map(|&id| {
+                self.credentials
+                    .transfer(session, id, &target, &target_key, mode)
+            })
+            .collect();
+        target_key.zeroize();
+        transferred
+    }
+
+    /// Lista o dono e os membros de um cofre acessível ao usuário da sessão.
+    ///
+    /// ### Retorno
+    /// - `Ok(Vec<VaultMemberSummary>)`: dono seguido dos membros, do mais antigo
+    ///   para o mais recente.
+    /// - `Err(Error)`: sessão inativa, cofre inexistente ou sem acesso.
+    pub fn members(&self, session: &Session, vault_id: Uuid) -> Result<Vec<VaultMemberSummary>> {
+        let (vault, _, _) = self.access(session, vault_id)?;
+
+        let owner = self
+            .users
+            .find_by_id(vault.user_id())?
+            .ok_or_else(|| Error::not_found("Dono do cofre não encontrado"))?;
+        let mut summaries = vec![VaultMemberSummary {
+            user_id: owner.id(),
+            username: owner.username().to_string(),
+            role: VaultRole::Owner,
+        }];
+
+        for member in self.members.find_by_vault_id(vault.id())? {
+            summaries.push(self.member_summary(&member)?);
+        }
+        Ok(summaries)
+    }
+
+    /// Compartilha um cofre do usuário da sessão com outro usuário.
+    ///
+    /// ### Parâmetros
+    /// - `vault_id`: Cofre do usuário da sessão.
+    /// - `username`: Usuário convidado.
+    /// - `role`: [`VaultRole::Editor`] ou [`VaultRole::Viewer`].
+    ///
+    /// ### Retorno
+    /// - `Ok(VaultMemberSummary)`: novo membro.
+    /// - `Err(Error)`: cofre de outro dono, usuário inexistente, sem chave de
+    ///   compartilhamento, já membro ou papel de dono.
+    ///
+    /// ### Aplicação
+    /// A chave do cofre é embrulhada com a chave pública do convidado; apenas a
+    /// chave privada dele, cifrada com a chave da conta, consegue abri-la. O
+    /// par de chaves é criado no registro ou no primeiro login após a
+    /// atualização, então contas que ainda não entraram não podem ser convidadas.
+    pub fn invite(
+        &self,
+        session: &Session,
+        vault_id: Uuid,
+        username: &str,
+        role: VaultRole,
+    ) -> Result<VaultMemberSummary> {
+        let vault = self.owned(session, vault_id)?;
+
+        let user = self
+            .users
+            .find_by_username(username.trim())?
+            .ok_or_else(|| {
+                Error::not_found(format!("Usuário '{}' não encontrado", username.trim()))
+            })?;
+        if user.id() == vault.user_id() {
+            return Err(Error::conflict("O dono já tem acesso ao cofre"));
+        }
+        if self.members.find(vault.id(), user.id())?.is_some() {
+            return Err(Error::conflict(format!(
+                "'{}' já é membro do cofre",
+                user.username()
+            )));
+        }
+        let public_key = user.public_key().ok_or_else(|| {
+            Error::conflict(format!(
+                "'{}' ainda não possui chave de compartilhamento; peça que faça login uma vez",
+                user.username()
+            ))
+        })?;
+
+        let mut vault_key = unwrap_vault_key(session.account_key(), &vault)?;
+        let cipher = encrypt_for_recipient(public_key, &vault_key);
+        vault_key.zeroize();
+
+        let member = VaultMember::new(vault.id(), user.id(), role, cipher?)?;
+
+        info!(
+            "Compartilhando cofre '{}' com '{}' como {}",
+            vault.name(),
+            user.username(),
+            role
+        );
+        self.members.create(&member, vault.key_generation())?;
+        self.member_summary(&member)
+    }
+
+    /// Altera o papel de um membro de um cofre do usuário da sessão.
+    ///
+    /// ### Retorno
+    /// - `Ok(VaultMemberSummary)`: membro com o novo papel.
+    /// - `Err(Error)`: cofre de outro dono, usuário que não é membro ou papel de dono.
+    ///
+    /// ### Aplicação
+    /// O rebaixamento vale de imediato, inclusive para sessões em que o membro
+    /// já está com o cofre aberto; uma promoção só é percebida quando ele abre
+    /// o cofre novamente.
+    pub fn set_role(
+        &self,
+        session: &Session,
+        vault_id: Uuid,
+        username: &str,
+        role: VaultRole,
+    ) -> Result<VaultMemberSummary> {
+        let vault = self.owned(session, vault_id)?;
+        let (user, mut member) = self.member(&vault, username)?;
+
+        member.set_role(role)?;
+
+        info!(
+            "Alterando papel de '{}' no cofre '{}' para {}",
+            user.username(),
+            vault.name(),
+            role
+        );
+        self.members.update(&member)?;
+        self.member_summary(&member)
+    }
+
+    /// Remove um membro de um cofre do usuário da sessão, trocando a chave do cofre.
+    ///
+    /// ### Retorno
+    /// - `Ok(())`: membro removido e conteúdo recifrado com a nova chave.
+    /// - `Err(Error)`: cofre de outro dono, usuário que não é membro ou falha
+    ///   de criptografia ou persistência; nesse caso nada é alterado.
+    ///
+    /// ### Aplicação
+    /// Remover o vínculo não basta: o ex-membro pode ter guardado a chave do
+    /// cofre. Por isso uma nova chave é gerada, todas as credenciais (inclusive
+    /// na lixeira) e versões do histórico são recifradas com ela, e a chave é
+    /// embrulhada novamente para o dono e os membros restantes, tudo em uma
+    /// única transação. A geração da chave avança: sessões que abriram o cofre
+    /// antes (do ex-membro, dos membros restantes ou do próprio dono) têm as
+    /// gravações recusadas até abrirem o cofre de novo e receberem a nova chave.
+    pub fn revoke(&self, session: &mut Session, vault_id: Uuid, username: &str) -> Result<()> {
+        let mut vault = self.owned(session, vault_id)?;
+        let (user, _) = self.member(&vault, username)?;
+
+        info!(
+            "Revogando acesso de '{}' ao cofre '{}'",
+            user.username(),
+            vault.name()
+        );
+
+        let mut old_key = unwrap_vault_key(session.account_key(), &vault)?;
+        let mut new_key = generate_vault_key();
+        let rotated = self.rotate(session, &mut vault, user.id(), &old_key, &new_key);
+        old_key.zeroize();
+
+        if rotated.is_ok() && session.vault_id == vault.id() {
+            session.open_vault(&vault, new_key.to_vec(), VaultRole::Owner);
+        }
+        new_key.zeroize();
+        rotated
+    }
+
+    /// Recifra o cofre com a nova chave e grava a revogação do membro.
+    fn rotate(
+        &self,
+        session: &Session,
+        vault: &mut Vault,
+        revoked: Uuid,
+        old_key: &[u8],
+        new_key: &[u8],
+    ) -> Result<()> {
+        let previous_generation = vault.key_generation();
+        vault.rotate_key(encrypt_with_key(session.account_key(), new_key)?)?;
+
+        let mut remaining = Vec::new();
+        for mut member in self.members.find_by_vault_id(vault.id())? {
+            if member.user_id() == revoked {
+                continue;
+            }
+            let public_key = self
+                .users
+                .find_by_id(member.user_id())?
+                .and_then(|u| u.public_key().map(str::to_string))
+                .ok_or_else(|| Error::not_found("Chave pública de um membro não encontrada"))?;
+            member.set_vault_key_cipher(encrypt_for_recipient(&public_key, new_key)?)?;
+            remaining.push(member);
+        }
+
+        self.members.revoke(
+            vault,
+            previous_generation,
+            revoked,
+            &remaining,
+            &|credentials, history| {
+                CredentialService::reseal_vault(credentials, history, old_key, new_key)
+            },
+        )
+    }
+
+    /// Monta o resumo de um cofre para a listagem.
+    fn summary(
+        &self,
+        session: &Session,
+        vault: Vault,
+        role: VaultRole,
+        owner: Option<String>,
+    ) -> Result<VaultSummary> {
+        Ok(VaultSummary {
+            credentials: self.credentials.count_in_vault(vault.id())?,
+            current: vault.id() == session.vault_id,
+            members: self.members.find_by_vault_id(vault.id())?.len(),
+            role,
+            owner,
+            vault,
+        })
+    }
+
+    /// Monta o resumo de um membro com o nome do usuário.
+    fn member_summary(&self, member: &VaultMember) -> Result<VaultMemberSummary> {
+        let user = self
+            .users
+            .find_by_id(member.user_id())?
+            .ok_or_else(|| Error::not_found("Membro do cofre não encontrado"))?;
+
+        Ok(VaultMemberSummary {
+            user_id: user.id(),
+            username: user.username().to_string(),
+            role: member.role(),
+        })
+    }
+
+    /// Localiza um membro do cofre pelo nome de usuário.
+    fn member(&self, vault: &Vault, username: &str) -> Result<(User, VaultMember)> {
+        let not_member =
+            || Error::not_found(format!("'{}' não é membro do cofre", username.trim()));
+
+        let user = self
+            .users
+            .find_by_username(username.trim())?
+            .ok_or_else(not_member)?;
+        let member = self
+            .members
+            .find(vault.id(), user.id())?
+            .ok_or_else(not_member)?;
+        Ok((user, member))
+    }
+
+    /// Recupera um cofre acessível ao usuário da sessão, com o papel dele e,
+    /// em cofres compartilhados, o vínculo de membro.
+    fn access(
+        &self,
+        session: &Session,
+        vault_id: Uuid,
+    ) -> Result<(Vault, VaultRole, Option<VaultMember>)> {
+        session.ensure_active()?;
+
+        let vault = self
+            .vaults
+            .find_by_id(vault_id)?
+            .ok_or_else(|| Error::not_found("Cofre não encontrado"))?;
+        if vault.user_id() == session.user.id() {
+            return Ok((vault, VaultRole::Owner, None));
+        }
+
+        let member = self
+            .members
+            .find(vault.id(), session.user.id())?
+            .ok_or_else(|| Error::not_found("Cofre não encontrado"))?;
+        Ok((vault, member.role(), Some(member)))
+    }
+
+    /// Recupera um cofre acessível com a sua chave já desembrulhada.
+    fn open(&self, session: &Session, vault_id: Uuid) -> Result<(Vault, VaultRole, Vec<u8>)> {
+        let (vault, role, member) = self.access(session, vault_id)?;
+
+        let vault_key = match &member {
+            Some(member) => unwrap_member_key(session.account_key(), &session.user, member)?,
+            None => unwrap_vault_key(session.account_key(), &vault)?,
+        };
+        Ok((vault, role, vault_key))
+    }
+
+    /// Recupera um cofre, garantindo que o usuário da sessão é o dono.
+    fn owned(&self, session: &Session, vault_id: Uuid) -> Result<Vault> {
+        let (vault, role, _) = self.access(session, vault_id)?;
+
+        if role != VaultRole::Owner {
+            return Err(Error::unauthorized(
+                "Somente o dono pode administrar o cofre",
+            ));
+        }
+        Ok(vault)
+    }
+
+    /// Recusa nomes repetidos (sem diferenciar maiúsculas) entre os cofres do usuário.
+    fn ensure_unique_name(&self, session: &Session, vault: &Vault) -> Result<()> {
+        let name = vault.name().to_lowercase();
+
+        if self
+            .vaults
+            .find_all_by_user_id(session.user.id())?
+            .iter()
+            .any(|v| v.id() != vault.id() && v.name().to_lowercase() == name)
+        {
+            return Err(Error::conflict(format!(
+                "Já existe um cofre chamado '{}'",
+                vault.name()
+            )));
+        }
+
+        Ok(())
+    }
+}
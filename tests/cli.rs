@@ -0,0 +1,285 @@
+//! Testes dos subcomandos não interativos do binário `password_manager_cli`.
+
+use anyhow::Result;
+use serde_json::Value;
+use std::{
+    io::Write,
+    path::Path,
+    process::{Command, Output, Stdio},
+};
+
+/// Executa o binário com um banco e um log isolados no diretório informado.
+fn run(dir: &Path, args: &[&str], stdin: Option<&str>) -> Result<Output> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_password_manager_cli"))
+        .args(args)
+        .current_dir(dir)
+        .env("DATABASE_URL", dir.join("vault.db"))
+        .env("LOG_FILE_PATH", dir.join("cli.log"))
+        .env("PM_USER", "alice")
+        .env("PM_MASTER_PASSWORD", "senha-mestre-forte")
+        .env_remove("PM_ARCHIVE_PASSPHRASE")
+        .env("CLIPBOARD_BACKEND", "file")
+        .env("CLIPBOARD_FILE", dir.join("clipboard.txt"))
+        .env("CLIPBOARD_CLEAR_SECS", "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    if let Some(input) = stdin {
+        child.stdin.take().unwrap().write_all(input.as_bytes())?;
+    }
+    Ok(child.wait_with_output()?)
+}
+
+fn json(output: &Output) -> Result<Value> {
+    Ok(serde_json::from_slice(&output.stdout)?)
+}
+
+#[test]
+fn manages_credentials_with_json_output() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let dir = dir.path();
+
+    assert!(run(dir, &["register"], None)?.status.success());
+
+    let added = run(
+        dir,
+        &[
+            "--json",
+            "add",
+            "Banco",
+            "--username",
+            "joao",
+            "--tag",
+            "financas",
+            "--password-stdin",
+        ],
+        Some("x7$Kq!v9Lm#2pR8z\n"),
+    )?;
+    assert!(added.status.success());
+    let added = json(&added)?;
+    assert_eq!(added["name"], "Banco");
+    assert_eq!(added["tags"], serde_json::json!(["financas"]));
+
+    let password = run(dir, &["get", "banco", "--field", "password"], None)?;
+    assert_eq!(String::from_utf8(password.stdout)?, "x7$Kq!v9Lm#2pR8z\n");
+
+    let edited = run(
+        dir,
+        &["--json", "edit", "Banco", "--url", "https://banco.example"],
+        None,
+    )?;
+    assert_eq!(json(&edited)?["url"], "https://banco.example");
+
+    let found = json(&run(dir, &["--json", "search", "banc"], None)?)?;
+    assert_eq!(found[0]["credential"]["id"], added["id"]);
+
+    let copied = json(&run(dir, &["--json", "get", "Banco", "--copy"], None)?)?;
+    assert_eq!(copied["field"], "password");
+    assert_eq!(copied["clear_after_secs"], 1);
+    // O processo aguarda a limpeza antes de encerrar.
+    assert!(!dir.join("clipboard.txt").exists());
+
+    assert!(run(dir, &["rm", "Banco"], None)?.status.success());
+    let listed = json(&run(dir, &["--json", "list"], None)?)?;
+    assert_eq!(listed, serde_json::json!([]));
+    Ok(())
+}
+
+#[test]
+fn reports_failures_through_exit_codes() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let dir = dir.path();
+
+    assert!(run(dir, &["register"], None)?.status.success());
+
+    let missing = run(dir, &["--json", "get", "Inexistente"], None)?;
+    assert_eq!(missing.status.code(), Some(4));
+    let error: Value = serde_json::from_slice(&missing.stderr)?;
+    assert_eq!(error["exit_code"], 4);
+
+    let wrong = run(
+        dir,
+        &["--json", "login", "--password-fd", "0"],
+        Some("senha-errada\n"),
+    )?;
+    assert_eq!(wrong.status.code(), Some(3));
+    let error: Value = serde_json::from_slice(&wrong.stderr)?;
+    assert_eq!(error["code"], "unauthorized");
+
+    let usage = run(dir, &["generate", "--words", "3"], None)?;
+    assert_eq!(usage.status.code(), Some(2));
+    Ok(())
+}
+
+#[test]
+fn manages_vaults_and_moves_credentials() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let dir = dir.path();
+
+    assert!(run(dir, &["register"], None)?.status.success());
+    assert!(
+        run(dir, &["vault", "create", "Trabalho"], None)?
+            .status
+            .success()
+    );
+    assert!(
+        run(dir, &["add", "Jira", "--generate"], None)?
+            .status
+            .success()
+    );
+
+    let moved = run(dir, &["--json", "vault", "move", "Jira", "trabalho"], None)?;
+    assert!(moved.status.success());
+    assert_eq!(json(&moved)?["mode"], "move");
+
+    let listed = json(&run(dir, &["--json", "list"], None)?)?;
+    assert_eq!(listed, serde_json::json!([]));
+    let listed = json(&run(dir, &["--json", "--vault", "Trabalho", "list"], None)?)?;
+    assert_eq!(listed[0]["name"], "Jira");
+
+    let vaults = json(&run(dir, &["--json", "vault", "list"], None)?)?;
+    assert_eq!(vaults[0]["name"], "Pessoal");
+    assert_eq!(vaults[0]["current"], true);
+    assert_eq!(vaults[1]["credentials"], 1);
+
+    let open = run(
+        dir,
+        &["--vault", "Trabalho", "vault", "rm", "Trabalho"],
+        None,
+    )?;
+    assert!(!open.status.success());
+    let missing = run(dir, &["--vault", "Outro", "list"], None)?;
+    assert_eq!(missing.status.code(), Some(4));
+
+    assert!(
+        run(dir, &["vault", "rm", "Trabalho"], None)?
+            .status
+            .success()
+    );
+    let vaults = json(&run(dir, &["--json", "vault", "list"], None)?)?;
+    assert_eq!(vaults.as_array().map(Vec::len), Some(1));
+    Ok(())
+}
+
+#[test]
+fn shares_vaults_with_other_users() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let dir = dir.path();
+
+    assert!(run(dir, &["register"], None)?.status.success());
+    assert!(
+        run(dir, &["--user", "bob", "register"], None)?
+            .status
+            .success()
+    );
+    assert!(
+        run(dir, &["vault", "create", "Equipe"], None)?
+            .status
+            .success()
+    );
+    assert!(
+        run(
+            dir,
+            &["--vault", "Equipe", "add", "Servidor", "--generate"],
+            None
+        )?
+        .status
+        .success()
+    );
+
+    let invited = run(
+        dir,
+        &[
+            "--json", "vault", "invite", "Equipe", "bob", "--role", "viewer",
+        ],
+        None,
+    )?;
+    assert!(invited.status.success());
+    assert_eq!(json(&invited)?["role"], "viewer");
+
+    let vaults = json(&run(
+        dir,
+        &["--json", "--user", "bob", "vault", "list"],
+        None,
+    )?)?;
+    assert_eq!(vaults[1]["name"], "Equipe");
+    assert_eq!(vaults[1]["owner"], "alice");
+
+    let shared = ["--json", "--user", "bob", "--vault", "Equipe"];
+    let listed = json(&run(dir, &[&shared[..], &["list"]].concat(), None)?)?;
+    assert_eq!(listed[0]["name"], "Servidor");
+    let denied = run(dir, &[&shared[..], &["add", "Outro"]].concat(), None)?;
+    assert!(!denied.status.success());
+
+    let members = json(&run(dir, &["--json", "vault", "members", "Equipe"], None)?)?;
+    assert_eq!(members.as_array().map(Vec::len), Some(2));
+
+    assert!(
+        run(dir, &["vault", "revoke", "Equipe", "bob"], None)?
+            .status
+            .success()
+    );
+    let revoked = run(dir, &["--user", "bob", "--vault", "Equipe", "list"], None)?;
+    assert_eq!(revoked.status.code(), Some(4));
+    Ok(())
+}
+
+#[test]
+fn renames_and_deletes_accounts() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let dir = dir.path();
+
+    assert!(run(dir, &["register"], None)?.status.success());
+    assert!(
+        run(dir, &["--user", "bob", "register"], None)?
+            .status
+            .success()
+    );
+
+    let users = json(&run(dir, &["--json", "account", "list"], None)?)?;
+    assert_eq!(users[0]["username"], "alice");
+    assert_eq!(users[1]["username"], "bob");
+
+    let renamed = run(dir, &["--json", "account", "rename", "alicia"], None)?;
+    assert!(renamed.status.success());
+    assert_eq!(json(&renamed)?["username"], "alicia");
+    assert_eq!(run(dir, &["login"], None)?.status.code(), Some(3));
+
+    let deleted = run(
+        dir,
+        &["--json", "--user", "alicia", "account", "delete"],
+        None,
+    )?;
+    assert!(deleted.status.success());
+    assert_eq!(json(&deleted)?["deleted"], true);
+
+    let users = json(&run(
+        dir,
+        &["--json", "--user", "bob", "account", "list"],
+        None,
+    )?)?;
+    assert_eq!(users.as_array().map(Vec::len), Some(1));
+    assert_eq!(users[0]["username"], "bob");
+    Ok(())
+}
+
+#[test]
+fn generates_passwords_without_a_vault() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = run(
+        dir.path(),
+        &["generate", "--length", "24", "--no-symbols"],
+        None,
+    )?;
+    assert!(output.status.success());
+
+    let password = String::from_utf8(output.stdout)?;
+    let password = password.trim_end();
+    assert_eq!(password.chars().count(), 24);
+    assert!(password.chars().all(|c| c.is_ascii_alphanumeric()));
+    assert!(!dir.path().join("vault.db").exists());
+    Ok(())
+}
@@ -0,0 +1,74 @@
+//! Testes da área de transferência com limpeza automática.
+
+use anyhow::Result;
+use std::{sync::Arc, thread, time::Duration};
+
+use password_manager::infrastructure::clipboard::{
+    Clipboard, ClipboardBackend, FileBackend, MemoryBackend,
+};
+
+const CLEAR_AFTER: Duration = Duration::from_millis(100);
+
+#[test]
+fn clears_the_copied_value_after_the_timeout() -> Result<()> {
+    let backend = Arc::new(MemoryBackend::new());
+    let clipboard = Clipboard::new(backend.clone(), Some(CLEAR_AFTER));
+
+    let copy = clipboard.copy("s3nh@")?;
+    assert_eq!(copy.clear_after(), Some(CLEAR_AFTER));
+    assert_eq!(backend.get_text()?.as_deref(), Some("s3nh@"));
+
+    copy.wait();
+    assert_eq!(backend.get_text()?, None);
+    Ok(())
+}
+
+#[test]
+fn keeps_content_replaced_before_the_timeout() -> Result<()> {
+    let backend = Arc::new(MemoryBackend::new());
+    let clipboard = Clipboard::new(backend.clone(), Some(CLEAR_AFTER));
+
+    let copy = clipboard.copy("s3nh@")?;
+    backend.set_text("copiado pelo usuário")?;
+    copy.wait();
+    assert_eq!(backend.get_text()?.as_deref(), Some("copiado pelo usuário"));
+
+    // Uma nova cópia também não é apagada pela limpeza da anterior.
+    let first = clipboard.copy("primeira")?;
+    thread::sleep(CLEAR_AFTER / 2);
+    let second = clipboard.copy("segunda")?;
+    first.wait();
+    assert_eq!(backend.get_text()?.as_deref(), Some("segunda"));
+    second.wait();
+    assert_eq!(backend.get_text()?, None);
+    Ok(())
+}
+
+#[test]
+fn clears_pending_copies_on_demand() -> Result<()> {
+    let backend = Arc::new(MemoryBackend::new());
+    let clipboard = Clipboard::new(backend.clone(), None);
+
+    let copy = clipboard.copy("123456")?;
+    assert_eq!(copy.clear_after(), None);
+    copy.wait();
+    assert_eq!(backend.get_text()?.as_deref(), Some("123456"));
+
+    clipboard.clear_pending();
+    assert_eq!(backend.get_text()?, None);
+    Ok(())
+}
+
+#[test]
+fn file_backend_round_trips() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let backend = FileBackend::new(dir.path().join("clipboard.txt"));
+
+    assert_eq!(backend.get_text()?, None);
+    backend.set_text("valor")?;
+    assert_eq!(backend.get_text()?.as_deref(), Some("valor"));
+    backend.clear()?;
+    assert_eq!(backend.get_text()?, None);
+    backend.clear()?;
+    Ok(())
+}
@@ -0,0 +1,41 @@
+//! Testes da pontuação de relevância da pesquisa de credenciais.
+
+use password_manager::services::credential_search::Matcher;
+
+#[test]
+fn ranks_match_kinds() {
+    let matcher = Matcher::new("bank", false);
+
+    let exact = matcher.score("Bank").unwrap();
+    let prefix = matcher.score("Banking").unwrap();
+    let word = matcher.score("Meu bank").unwrap();
+    let substring = matcher.score("Databank").unwrap();
+
+    assert!(exact > prefix && prefix > word && word > substring);
+    assert_eq!(matcher.score("Bnak"), None);
+}
+
+#[test]
+fn ignores_case_and_accents() {
+    let matcher = Matcher::new("cartao", false);
+    assert!(matcher.score("Cartão de crédito").is_some());
+
+    let matcher = Matcher::new("CRÉDITO", false);
+    assert!(matcher.score("cartao de credito").is_some());
+}
+
+#[test]
+fn fuzzy_accepts_typos_and_subsequences() {
+    let matcher = Matcher::new("bnak", true);
+    let typo = matcher.score("Bank").unwrap();
+
+    let matcher = Matcher::new("gthb", true);
+    let subsequence = matcher.score("GitHub").unwrap();
+
+    assert!(typo > subsequence);
+    assert!(subsequence < Matcher::new("git", true).score("GitHub").unwrap());
+
+    // termos curtos não aceitam erros
+    assert_eq!(Matcher::new("gti", true).score("xyz"), None);
+    assert_eq!(Matcher::new("abcd", true).score("wxyz"), None);
+}
@@ -0,0 +1,211 @@
+//! Testes dos importadores de arquivos de outros gerenciadores de senhas.
+
+use password_manager::importers::{ImportFormat, ParsedImport, parse};
+use std::io::Write;
+
+fn names(parsed: &ParsedImport) -> Vec<&str> {
+    parsed.entries.iter().map(|e| e.name.as_str()).collect()
+}
+
+const BITWARDEN: &str = r#"{
+  "encrypted": false,
+  "folders": [],
+  "items": [
+    { "type": 1, "name": "GitHub", "notes": "conta pessoal",
+      "login": { "username": "alice", "password": "s3gr3d0", "totp": "JBSWY3DPEHPK3PXP",
+                 "uris": [ { "match": null, "uri": "https://github.com/login" } ] } },
+    { "type": 2, "name": "Wi-Fi", "notes": "senha: 1234", "secureNote": { "type": 0 } },
+    { "type": 3, "name": "Cartão", "card": { "number": "4111111111111111" } },
+    { "type": 1, "name": "", "login": { "username": "bob", "password": "x",
+      "uris": [ { "uri": "https://www.example.com:8443/app" } ] } }
+  ]
+}"#;
+
+#[test]
+fn parses_bitwarden_json() {
+    let parsed = parse(None, BITWARDEN.as_bytes()).unwrap();
+    assert_eq!(parsed.format, ImportFormat::Bitwarden);
+    assert_eq!(names(&parsed), ["GitHub", "Wi-Fi", "example.com"]);
+
+    let github = &parsed.entries[0];
+    assert_eq!(github.username.as_deref(), Some("alice"));
+    assert_eq!(github.password.as_deref(), Some("s3gr3d0"));
+    assert_eq!(github.url.as_deref(), Some("https://github.com/login"));
+    assert_eq!(github.notes.as_deref(), Some("conta pessoal"));
+    assert_eq!(github.totp.as_deref(), Some("JBSWY3DPEHPK3PXP"));
+
+    assert_eq!(parsed.skipped.len(), 1);
+    assert_eq!(parsed.skipped[0].entry, "Cartão");
+}
+
+#[test]
+fn rejects_encrypted_bitwarden_export() {
+    let data = br#"{ "encrypted": true, "encKeyValidation_DO_NOT_EDIT": "x", "items": [] }"#;
+    assert!(parse(Some(ImportFormat::Bitwarden), data).is_err());
+}
+
+const KEEPASS: &str = r#"<?xml version="1.0" encoding="utf-8" standalone="yes"?>
+<KeePassFile>
+  <Meta>
+    <RecycleBinEnabled>True</RecycleBinEnabled>
+    <RecycleBinUUID>bGl4ZWlyYQ==</RecycleBinUUID>
+  </Meta>
+  <Root>
+    <Group>
+      <UUID>cmFpeg==</UUID>
+      <Name>Banco</Name>
+      <Entry>
+        <UUID>ZTE=</UUID>
+        <String><Key>Title</Key><Value>E-mail &amp; agenda</Value></String>
+        <String><Key>UserName</Key><Value>alice@example.com</Value></String>
+        <String><Key>Password</Key><Value Protected="True">atual</Value></String>
+        <String><Key>URL</Key><Value>https://mail.example.com</Value></String>
+        <String><Key>Notes</Key><Value /></String>
+        <String><Key>otp</Key><Value>otpauth://totp/Mail?secret=JBSWY3DPEHPK3PXP</Value></String>
+        <History>
+          <Entry>
+            <UUID>ZTE=</UUID>
+            <String><Key>Title</Key><Value>E-mail antigo</Value></String>
+            <String><Key>Password</Key><Value>antiga</Value></String>
+          </Entry>
+        </History>
+      </Entry>
+      <Group>
+        <UUID>c3Vi</UUID>
+        <Name>Trabalho</Name>
+        <Entry>
+          <String><Key>Title</Key><Value>VPN</Value></String>
+          <String><Key>Password</Key><Value>vpn-123</Value></String>
+        </Entry>
+      </Group>
+      <Group>
+        <UUID>bGl4ZWlyYQ==</UUID>
+        <Name>Lixeira</Name>
+        <Entry>
+          <String><Key>Title</Key><Value>Removida</Value></String>
+          <String><Key>Password</Key><Value>x</Value></String>
+        </Entry>
+      </Group>
+    </Group>
+  </Root>
+</KeePassFile>"#;
+
+#[test]
+fn parses_keepass_xml() {
+    let parsed = parse(None, KEEPASS.as_bytes()).unwrap();
+    assert_eq!(parsed.format, ImportFormat::KeepassXml);
+    assert_eq!(names(&parsed), ["E-mail & agenda", "VPN"]);
+
+    let mail = &parsed.entries[0];
+    assert_eq!(mail.password.as_deref(), Some("atual"));
+    assert_eq!(mail.username.as_deref(), Some("alice@example.com"));
+    assert_eq!(mail.notes, None);
+    assert!(mail.totp.as_deref().unwrap().starts_with("otpauth://"));
+
+    assert_eq!(parsed.skipped.len(), 1);
+    assert_eq!(parsed.skipped[0].entry, "Removida");
+}
+
+const CHROME_CSV: &str = "name,url,username,password,note\n\
+GitHub,https://github.com/,alice,s3gr3d0,\n\
+,https://accounts.google.com/,alice@gmail.com,\"senha, com vírgula\",recuperação\n";
+
+const FIREFOX_CSV: &str = "\"url\",\"username\",\"password\",\"httpRealm\",\"formActionOrigin\",\"guid\",\"timeCreated\",\"timeLastUsed\",\"timePasswordChanged\"\n\
+\"https://www.mozilla.org\",\"alice\",\"raposa\",,\"https://www.mozilla.org\",\"{1}\",\"1\",\"1\",\"1\"\n\
+\"chrome://FirefoxAccounts\",\"x\",\"y\",\"Firefox Accounts credentials\",,\"{2}\",\"1\",\"1\",\"1\"\n";
+
+#[test]
+fn parses_browser_csv() {
+    let chrome = parse(None, CHROME_CSV.as_bytes()).unwrap();
+    assert_eq!(chrome.format, ImportFormat::BrowserCsv);
+    assert_eq!(names(&chrome), ["GitHub", "accounts.google.com"]);
+    assert_eq!(
+        chrome.entries[1].password.as_deref(),
+        Some("senha, com vírgula")
+    );
+    assert_eq!(chrome.entries[1].notes.as_deref(), Some("recuperação"));
+
+    let firefox = parse(None, FIREFOX_CSV.as_bytes()).unwrap();
+    assert_eq!(firefox.format, ImportFormat::BrowserCsv);
+    assert_eq!(names(&firefox), ["mozilla.org"]);
+    assert_eq!(firefox.skipped.len(), 1);
+}
+
+#[test]
+fn parses_onepassword_csv() {
+    let data = "Title,Url,Username,Password,OTPAuth,Favorite,Archived,Tags,Notes\n\
+Dropbox,https://dropbox.com,alice,caixa,otpauth://totp/Dropbox?secret=JBSWY3DPEHPK3PXP,false,false,,\n\
+Antigo,https://old.example.com,alice,velha,,false,true,,\n";
+
+    let parsed = parse(None, data.as_bytes()).unwrap();
+    assert_eq!(parsed.format, ImportFormat::OnePasswordCsv);
+    assert_eq!(names(&parsed), ["Dropbox"]);
+    assert!(parsed.entries[0].totp.is_some());
+    assert_eq!(parsed.skipped[0].reason, "Item arquivado");
+}
+
+#[test]
+fn parses_onepassword_1pux() {
+    let export_data = r#"{
+      "accounts": [ { "attrs": { "name": "Alice" }, "vaults": [ { "attrs": { "name": "Pessoal" }, "items": [
+        { "uuid": "1", "state": "active", "categoryUuid": "001",
+          "overview": { "title": "Slack", "url": "https://slack.com" },
+          "details": {
+            "loginFields": [
+              { "value": "alice", "designation": "username" },
+              { "value": "canal#1", "designation": "password" }
+            ],
+            "notesPlain": "workspace da equipe",
+            "sections": [ { "fields": [ { "title": "one-time password",
+                                          "value": { "totp": "otpauth://totp/Slack?secret=JBSWY3DPEHPK3PXP" } } ] } ]
+          } },
+        { "uuid": "2", "state": "active", "categoryUuid": "005",
+          "overview": { "title": "Roteador" }, "details": { "password": "admin123" } },
+        { "uuid": "3", "state": "active", "categoryUuid": "002",
+          "overview": { "title": "Visa" }, "details": {} },
+        { "uuid": "4", "state": "archived", "categoryUuid": "001",
+          "overview": { "title": "Arquivado" }, "details": {} }
+      ] } ] } ]
+    }"#;
+
+    let mut zip_bytes = Vec::new();
+    {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(&mut zip_bytes));
+        writer
+            .start_file(
+                "export.attributes",
+                zip::write::SimpleFileOptions::default(),
+            )
+            .unwrap();
+        writer.write_all(b"{}").unwrap();
+        writer
+            .start_file("export.data", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(export_data.as_bytes()).unwrap();
+        writer.finish().unwrap();
+    }
+
+    let parsed = parse(None, &zip_bytes).unwrap();
+    assert_eq!(parsed.format, ImportFormat::OnePassword1pux);
+    assert_eq!(names(&parsed), ["Slack", "Roteador"]);
+
+    let slack = &parsed.entries[0];
+    assert_eq!(slack.username.as_deref(), Some("alice"));
+    assert_eq!(slack.password.as_deref(), Some("canal#1"));
+    assert_eq!(slack.notes.as_deref(), Some("workspace da equipe"));
+    assert!(slack.totp.is_some());
+    assert_eq!(parsed.entries[1].password.as_deref(), Some("admin123"));
+    assert_eq!(parsed.skipped.len(), 2);
+}
+
+#[test]
+fn truncates_long_names_and_rejects_unknown_files() {
+    let long_name = "á".repeat(40);
+    let data = format!("name,url,username,password\n{long_name},,alice,x\n");
+
+    let parsed = parse(Some(ImportFormat::BrowserCsv), data.as_bytes()).unwrap();
+    assert!(parsed.entries[0].name.len() <= 64);
+
+    assert!(parse(None, b"apenas texto").is_err());
+    assert!(parse(Some(ImportFormat::BrowserCsv), b"a,b,c\n1,2,3\n").is_err());
+}
@@ -0,0 +1,110 @@
+//! Testes do gerador de senhas e frases secretas.
+
+use password_manager::services::password_generator::{
+    PassphrasePolicy, PasswordGenerator, PasswordPolicy,
+};
+
+#[test]
+fn generates_requested_length_with_every_class() {
+    for _ in 0..50 {
+        let generated = PasswordGenerator::generate(&PasswordPolicy {
+            length: 8,
+            ..PasswordPolicy::default()
+        })
+        .unwrap();
+
+        let value = &generated.value;
+        assert_eq!(value.chars().count(), 8);
+        assert!(value.chars().any(|c| c.is_ascii_lowercase()));
+        assert!(value.chars().any(|c| c.is_ascii_uppercase()));
+        assert!(value.chars().any(|c| c.is_ascii_digit()));
+        assert!(value.chars().any(|c| !c.is_ascii_alphanumeric()));
+    }
+}
+
+#[test]
+fn respects_disabled_classes_and_ambiguous_exclusion() {
+    let policy = PasswordPolicy {
+        length: 64,
+        uppercase: false,
+        symbols: false,
+        exclude_ambiguous: true,
+        ..PasswordPolicy::default()
+    };
+
+    for _ in 0..20 {
+        let value = PasswordGenerator::generate(&policy).unwrap().value;
+        assert!(
+            value
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        );
+        assert!(!value.contains(['l', '1', 'o', '0']));
+    }
+}
+
+#[test]
+fn rejects_invalid_policies() {
+    let no_classes = PasswordPolicy {
+        lowercase: false,
+        uppercase: false,
+        digits: false,
+        symbols: false,
+        ..PasswordPolicy::default()
+    };
+    assert!(PasswordGenerator::generate(&no_classes).is_err());
+
+    let too_short = PasswordPolicy {
+        length: 2,
+        ..PasswordPolicy::default()
+    };
+    assert!(PasswordGenerator::generate(&too_short).is_err());
+
+    let few_words = PassphrasePolicy {
+        words: 1,
+        ..PassphrasePolicy::default()
+    };
+    assert!(PasswordGenerator::passphrase(&few_words).is_err());
+}
+
+#[test]
+fn reports_entropy() {
+    let digits_only = PasswordPolicy {
+        length: 10,
+        lowercase: false,
+        uppercase: false,
+        symbols: false,
+        ..PasswordPolicy::default()
+    };
+    let generated = PasswordGenerator::generate(&digits_only).unwrap();
+    assert!((generated.entropy_bits - 10.0 * 10f64.log2()).abs() < 1e-9);
+
+    // A exigência de classes reduz o espaço de senhas válidas.
+    let required = PasswordGenerator::generate(&PasswordPolicy::default()).unwrap();
+    let unrestricted = PasswordGenerator::generate(&PasswordPolicy {
+        require_each_class: false,
+        ..PasswordPolicy::default()
+    })
+    .unwrap();
+    assert!(required.entropy_bits < unrestricted.entropy_bits);
+    assert!(required.entropy_bits > 100.0);
+}
+
+#[test]
+fn builds_passphrases_from_wordlist() {
+    let generated = PasswordGenerator::passphrase(&PassphrasePolicy {
+        words: 5,
+        separator: " ".to_string(),
+        capitalize: true,
+    })
+    .unwrap();
+
+    let words: Vec<&str> = generated.value.split(' ').collect();
+    assert_eq!(words.len(), 5);
+    assert!(
+        words
+            .iter()
+            .all(|w| w.chars().next().unwrap().is_uppercase())
+    );
+    assert!((generated.entropy_bits - 55.0).abs() < 1e-9);
+}
@@ -0,0 +1,162 @@
+//! Testes da consulta offline à base Pwned Passwords e de sua integração com o
+//! relatório de segurança.
+
+use anyhow::Result;
+use std::{fs, path::Path, sync::Arc};
+
+use password_manager::{
+    infrastructure::pwned_passwords::{PwnedHash, PwnedPasswords},
+    repositories::in_memory_repository::InMemoryRepository,
+    services::{
+        auth_service::AuthService,
+        credential_service::{CredentialService, HistoryPolicy, TrashPolicy},
+        security_report::ReportOptions,
+    },
+};
+
+/// Grava uma base com as senhas vazadas informadas e hashes fictícios ao redor,
+/// em ordem, no formato `HASH:OCORRÊNCIAS`.
+fn write_database(path: &Path, hash: PwnedHash, leaked: &[(&str, u64)]) -> Result<()> {
+    let width = hash.hash("").len();
+    let mut lines: Vec<String> = (0..500u32)
+        .map(|i| {
+            format!(
+                "{:0width$X}:{}",
+                u128::from(i) * 0x9E37_79B9_7F4A_7C15,
+                i + 1
+            )
+        })
+        .collect();
+    lines.extend(
+        leaked
+            .iter()
+            .map(|(password, count)| format!("{}:{count}", hash.hash(password))),
+    );
+    lines.sort();
+
+    fs::write(path, lines.join("\r\n") + "\r\n")?;
+    Ok(())
+}
+
+#[test]
+fn hashes_match_known_vectors() {
+    assert_eq!(
+        PwnedHash::Sha1.hash("password"),
+        "5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8"
+    );
+    assert_eq!(
+        PwnedHash::Ntlm.hash("password"),
+        "8846F7EAEE8FB117AD06BDD830B7586C"
+    );
+}
+
+#[test]
+fn finds_passwords_in_a_sorted_file() -> Result<()> {
+    for hash in [PwnedHash::Sha1, PwnedHash::Ntlm] {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("pwned.txt");
+        write_database(
+            &path,
+            hash,
+            &[("password", 9_545_824), ("123456", 37_359_195)],
+        )?;
+
+        let pwned = PwnedPasswords::open(&path)?;
+        assert_eq!(pwned.hash(), hash);
+        assert_eq!(pwned.occurrences("password")?, 9_545_824);
+        assert_eq!(pwned.occurrences("123456")?, 37_359_195);
+        assert_eq!(pwned.occurrences("x7$Kq!v9Lm#2pR8z")?, 0);
+    }
+    Ok(())
+}
+
+#[test]
+fn finds_passwords_in_range_files() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let hash = PwnedHash::Sha1.hash("password");
+    let (prefix, suffix) = hash.split_at(5);
+
+    fs::write(
+        dir.path().join("00000.txt"),
+        "0005AD76BD555C1D6D771DE417A4B87E4B4:10\n",
+    )?;
+    fs::write(
+        dir.path().join(format!("{prefix}.txt")),
+        format!(
+            "0018A45C4D1DEF81644B54AB7F969B88D65:1\n{suffix}:42\nFFFFF00000000000000000000000000000F:3\n"
+        ),
+    )?;
+
+    let pwned = PwnedPasswords::open(dir.path())?;
+    assert_eq!(pwned.hash(), PwnedHash::Sha1);
+    assert_eq!(pwned.occurrences("password")?, 42);
+    assert_eq!(pwned.occurrences("outra senha")?, 0);
+    Ok(())
+}
+
+#[test]
+fn rejects_unknown_formats() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("pwned.txt");
+    fs::write(&path, "nao e um hash\n")?;
+
+    assert!(PwnedPasswords::open(&path).is_err());
+    assert!(PwnedPasswords::open(dir.path().join("ausente.txt")).is_err());
+    Ok(())
+}
+
+#[test]
+fn security_report_flags_breached_passwords() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("pwned.txt");
+    write_database(
+        &path,
+        PwnedHash::Sha1,
+        &[("correct horse battery staple", 7)],
+    )?;
+
+    let store = InMemoryRepository::new();
+    let credentials = CredentialService::new(
+        Arc::new(store.clone()),
+        Arc::new(store.clone()),
+        Arc::new(store.clone()),
+        Arc::new(store.clone()),
+        Arc::new(store.clone()),
+        Arc::new(store.clone()),
+        HistoryPolicy::default(),
+        TrashPolicy::default(),
+        Some(PwnedPasswords::open(&path)?),
+    );
+    let auth = AuthService::new(
+        Arc::new(store.clone()),
+        Arc::new(store.clone()),
+        Arc::new(store.clone()),
+        Arc::new(store),
+        credentials.clone(),
+    );
+
+    auth.register("alice", "senha-forte")?;
+    let session = auth.login("alice", "senha-forte")?;
+    for (name, password) in [
+        ("Banco", "correct horse battery staple"),
+        ("Email", "x7$Kq!v9Lm#2pR8z"),
+    ] {
+        credentials.create(&session, name, None, None, None, Some(password))?;
+    }
+
+    assert_eq!(
+        credentials.breach_occurrences("correct horse battery staple")?,
+        Some(7)
+    );
+    assert_eq!(credentials.breach_occurrences("x7$Kq!v9Lm#2pR8z")?, Some(0));
+
+    let report = credentials.security_report(&session, &ReportOptions::default())?;
+    assert!(report.breach_checked);
+    let breached: Vec<(&str, u64)> = report
+        .breached
+        .iter()
+        .map(|b| (b.credential.name(), b.occurrences))
+        .collect();
+    assert_eq!(breached, [("Banco", 7)]);
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//! Testes da estimativa de força de senhas usada no relatório de segurança.
+
+use password_manager::services::security_report::{StrengthWarning, estimate_strength};
+
+#[test]
+fn common_passwords_and_patterns_are_weak() {
+    for password in ["123456", "password", "Senha123", "P@ssw0rd", "qwerty2024"] {
+        let strength = estimate_strength(password);
+        assert!(strength.score <= 1, "{password}: {strength:?}");
+    }
+
+    assert!(
+        estimate_strength("P@ssw0rd")
+            .warnings
+            .contains(&StrengthWarning::Common)
+    );
+    assert!(
+        estimate_strength("aaaaaaaaaaaa")
+            .warnings
+            .contains(&StrengthWarning::Repeated)
+    );
+    assert!(
+        estimate_strength("abcdefghijkl")
+            .warnings
+            .contains(&StrengthWarning::Sequence)
+    );
+    assert!(
+        estimate_strength("asdfghjkl")
+            .warnings
+            .contains(&StrengthWarning::KeyboardPattern)
+    );
+}
+
+#[test]
+fn random_passwords_and_long_passphrases_are_strong() {
+    assert_eq!(estimate_strength("x7$Kq!v9Lm#2pR8z").score, 4);
+    assert_eq!(
+        estimate_strength("abacate-abaixo-abalar-abater-abduzir-abelha").score,
+        4
+    );
+}
+
+#[test]
+fn pattern_bits_stay_below_brute_force() {
+    let sequence = estimate_strength("abcdefghij");
+    let random = estimate_strength("qzmxnwbvep");
+    assert!(sequence.entropy_bits < random.entropy_bits);
+    assert!(
+        estimate_strength("")
+            .warnings
+            .contains(&StrengthWarning::TooShort)
+    );
+    assert_eq!(estimate_strength("").score, 0);
+}
@@ -0,0 +1,1753 @@
+//! Testes dos serviços executados contra as duas implementações de repositórios:
+//! SQLite (em um diretório temporário) e memória.
+//!
+//! Cada caso é escrito uma única vez como função que recebe um [`AppContext`];
+//! a macro [`service_tests!`] gera um `#[test]` por backend.
+
+use anyhow::Result;
+use argon2::{
+    Algorithm, Argon2, Params, PasswordHash, PasswordHasher, Version,
+    password_hash::{SaltString, rand_core::OsRng},
+};
+use password_manager::{
+    app_context::AppContext,
+    error::ErrorCode,
+    importers::{self, ImportFormat},
+    infrastructure::crypto::{
+        argon2_params, decrypt_with_key, decrypt_with_passphrase, encrypt_with_passphrase,
+        password_hash_needs_rehash, verify_password,
+    },
+    infrastructure::database::Database,
+    services::auth_service::SessionState,
+    services::credential_search::{MatchField, SearchOptions},
+    services::credential_service::DEFAULT_HISTORY_RETENTION,
+    models::vault_member::VaultRole,
+    services::security_report::ReportOptions,
+    services::vault_service::TransferMode,
+};
+
+/// Gera, para cada caso listado, um teste no módulo `sqlite` e outro no módulo `in_memory`.
+macro_rules! service_tests {
+    ($($case:ident),* $(,)?) => {
+        mod sqlite {
+            use password_manager::{app_context::AppContext, infrastructure::database::Database};
+
+            $(
+                #[test]
+                fn $case() {
+                    let dir = tempfile::tempdir().expect("diretório temporário");
+                    let path = dir.path().join("vault.db");
+                    let database = Database::open(path.to_str().unwrap(), 2).expect("banco de teste");
+                    super::$case(&AppContext::new(database)).unwrap();
+                }
+            )*
+        }
+
+        mod in_memory {
+            use password_manager::app_context::AppContext;
+
+            $(
+                #[test]
+                fn $case() {
+                    super::$case(&AppContext::in_memory()).unwrap();
+                }
+            )*
+        }
+    };
+}
+
+service_tests!(
+    register_and_login,
+    register_rejects_duplicate_username,
+    login_failures_share_generic_message,
+    repeated_failures_lock_login,
+    credential_round_trip,
+    update_credential_fields,
+    updates_keep_password_history,
+    password_history_is_trimmed_to_retention,
+    delete_credential,
+    trashed_secrets_cannot_be_revealed,
+    trash_restores_and_purges_credentials,
+    search_by_name,
+    search_matches_fields_literally_ignoring_case_and_accents,
+    search_ranks_results_and_accepts_typos,
+    search_in_notes_is_opt_in,
+    folders_form_a_tree_and_filter_credentials,
+    deleting_a_folder_keeps_its_contents,
+    tags_are_assigned_renamed_and_filtered,
+    security_report_flags_weak_reused_old_and_incomplete,
+    password_age_ignores_edits_that_keep_the_password,
+    credentials_are_isolated_between_users,
+    change_password_keeps_credentials_readable,
+    vaults_are_created_renamed_switched_and_deleted,
+    credentials_move_and_copy_between_vaults,
+    change_password_keeps_every_vault_readable,
+    shared_vault_roles_limit_what_members_can_do,
+    revoking_a_member_rotates_the_vault_key,
+    unlock_reopens_own_vault_after_access_is_revoked,
+    open_sessions_lose_write_access_when_revoked_or_demoted,
+    users_are_listed_and_renamed,
+    deleting_an_account_removes_its_data,
+    locked_session_requires_unlock,
+    unlock_failures_count_towards_login_lockout,
+    unlock_follows_a_rename_from_another_session,
+    password_confirmations_count_towards_login_lockout,
+    errors_are_categorised,
+    totp_secret_is_encrypted_and_generates_codes,
+    export_then_import_into_another_vault,
+    import_rejects_foreign_or_newer_archives,
+    external_import_reports_duplicates_and_supports_dry_run,
+);
+
+fn register_and_login(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    assert_eq!(session.user.username(), "alice");
+    assert_eq!(session.state(), SessionState::Active);
+    assert!(ctx.credentials().list(&session)?.is_empty());
+    Ok(())
+}
+
+fn register_rejects_duplicate_username(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+
+    assert!(ctx.auth().register("alice", "outra-senha").is_err());
+    Ok(())
+}
+
+fn login_failures_share_generic_message(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+
+    let wrong_password = ctx.auth().login("alice", "errada").unwrap_err();
+    let unknown_user = ctx.auth().login("bruno", "senha-forte").unwrap_err();
+    assert_eq!(wrong_password.to_string(), unknown_user.to_string());
+    Ok(())
+}
+
+fn repeated_failures_lock_login(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+
+    for _ in 0..3 {
+        assert!(ctx.auth().login("alice", "errada").is_err());
+    }
+
+    assert!(ctx.auth().login("alice", "senha-forte").is_err());
+    Ok(())
+}
+
+fn credential_round_trip(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+
+    let created = ctx.credentials().create(
+        &session,
+        "GitHub",
+        Some("alice@example.com".into()),
+        Some("https://github.com".into()),
+        Some("chave de recuperação".as_bytes().to_vec()),
+        Some("s3gr3d0"),
+    )?;
+
+    let loaded = ctx.credentials().get(&session, created.id())?;
+    assert_eq!(loaded.name(), "GitHub");
+    assert_eq!(loaded.username(), Some("alice@example.com"));
+    assert_eq!(loaded.url(), Some("https://github.com"));
+    assert_ne!(loaded.password_cipher(), Some(b"s3gr3d0".as_slice()));
+
+    assert_eq!(
+        ctx.credentials().reveal_password(&session, created.id())?,
+        Some("s3gr3d0".to_string())
+    );
+    assert_eq!(
+        ctx.credentials().reveal_notes(&session, created.id())?,
+        Some("chave de recuperação".to_string())
+    );
+    assert_eq!(ctx.credentials().list(&session)?.len(), 1);
+    Ok(())
+}
+
+fn update_credential_fields(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    let created = ctx
+        .credentials()
+        .create(&session, "Email", None, None, None, Some("antiga"))?;
+
+    ctx.credentials().update(
+        &session,
+        created.clone(),
+        Some("Email pessoal".into()),
+        Some("alice".into()),
+        None,
+        None,
+        Some("nova"),
+    )?;
+
+    let loaded = ctx.credentials().get(&session, created.id())?;
+    assert_eq!(loaded.name(), "Email pessoal");
+    assert_eq!(loaded.username(), Some("alice"));
+    assert_eq!(
+        ctx.credentials().reveal_password(&session, created.id())?,
+        Some("nova".to_string())
+    );
+    Ok(())
+}
+
+fn updates_keep_password_history(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    let created = ctx
+        .credentials()
+        .create(&session, "Email", None, None, None, Some("primeira"))?;
+
+    let update = |password: &str| {
+        let cred = ctx.credentials().get(&session, created.id())?;
+        ctx.credentials()
+            .update(&session, cred, None, None, None, None, Some(password))
+    };
+    update("segunda")?;
+    // Reenviar a senha atual não cria uma nova versão.
+    update("segunda")?;
+    update("terceira")?;
+
+    let history = ctx.credentials().password_history(&session, created.id())?;
+    let passwords: Vec<_> = history.iter().map(|v| v.password.as_deref()).collect();
+    assert_eq!(passwords, [Some("segunda"), Some("primeira")]);
+    assert!(history.iter().all(|v| v.notes.is_none()));
+
+    ctx.credentials()
+        .restore_version(&session, created.id(), history[1].id)?;
+    assert_eq!(
+        ctx.credentials().reveal_password(&session, created.id())?,
+        Some("primeira".to_string())
+    );
+
+    // A senha substituída pela restauração também fica no histórico.
+    let history = ctx.credentials().password_history(&session, created.id())?;
+    assert_eq!(history[0].password.as_deref(), Some("terceira"));
+    assert_eq!(history.len(), 3);
+
+    ctx.auth().register("bob", "outra-senha")?;
+    let other = ctx.auth().login("bob", "outra-senha")?;
+    assert!(ctx.credentials().password_history(&other, created.id()).is_err());
+    assert!(
+        ctx.credentials()
+            .restore_version(&other, created.id(), history[0].id)
+            .is_err()
+    );
+    Ok(())
+}
+
+fn password_history_is_trimmed_to_retention(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    let created = ctx
+        .credentials()
+        .create(&session, "Banco", None, None, None, Some("senha-0"))?;
+
+    for i in 1..=DEFAULT_HISTORY_RETENTION + 3 {
+        let cred = ctx.credentials().get(&session, created.id())?;
+        ctx.credentials().update(
+            &session,
+            cred,
+            None,
+            None,
+            None,
+            None,
+            Some(&format!("senha-{i}")),
+        )?;
+    }
+
+    let history = ctx.credentials().password_history(&session, created.id())?;
+    assert_eq!(history.len(), DEFAULT_HISTORY_RETENTION);
+    assert_eq!(
+        history[0].password,
+        Some(format!("senha-{}", DEFAULT_HISTORY_RETENTION + 2))
+    );
+    assert_eq!(history.last().unwrap().password.as_deref(), Some("senha-3"));
+
+    ctx.credentials().delete(&session, created.id())?;
+    assert!(ctx.credentials().password_history(&session, created.id()).is_err());
+    Ok(())
+}
+
+fn delete_credential(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    let created = ctx
+        .credentials()
+        .create(&session, "Banco", None, None, None, None)?;
+
+    ctx.credentials().delete(&session, created.id())?;
+
+    assert!(ctx.credentials().get(&session, created.id()).is_err());
+    assert!(ctx.credentials().list(&session)?.is_empty());
+    Ok(())
+}
+
+fn trashed_secrets_cannot_be_revealed(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    let credentials = ctx.credentials();
+    let created = credentials.create(
+        &session,
+        "Banco",
+        None,
+        None,
+        Some(b"agencia 0001".to_vec()),
+        Some("s3nha"),
+    )?;
+
+    credentials.delete(&session, created.id())?;
+
+    let err = credentials
+        .reveal_password(&session, created.id())
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+    let err = credentials
+        .reveal_notes(&session, created.id())
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+    Ok(())
+}
+
+fn trash_restores_and_purges_credentials(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    let banco = ctx
+        .credentials()
+        .create(&session, "Banco", None, None, None, Some("s3nha"))?;
+    let email = ctx
+        .credentials()
+        .create(&session, "Email", None, None, None, None)?;
+    let tags = ctx
+        .credentials()
+        .set_tags(&session, banco.id(), &["financeiro".to_string()])?;
+
+    ctx.credentials().delete(&session, banco.id())?;
+    ctx.credentials().delete(&session, email.id())?;
+
+    assert!(ctx.credentials().list(&session)?.is_empty());
+    assert!(ctx.credentials().search(&session, "banco")?.is_empty());
+    assert_eq!(ctx.credentials().list_tags(&session)?[0].credentials, 0);
+    let trash: Vec<String> = ctx
+        .credentials()
+        .list_trash(&session)?
+        .iter()
+        .map(|c| c.name().to_string())
+        .collect();
+    assert_eq!(trash, ["Email", "Banco"]);
+
+    let restored = ctx.credentials().restore(&session, banco.id())?;
+    assert!(!restored.is_trashed());
+    assert_eq!(
+        ctx.credentials()
+            .reveal_password(&session, banco.id())?
+            .as_deref(),
+        Some("s3nha")
+    );
+    assert_eq!(
+        ctx.credentials().tags_of(&session, banco.id())?[0].id(),
+        tags[0].id()
+    );
+    assert!(ctx.credentials().restore(&session, banco.id()).is_err());
+
+    ctx.auth().register("bob", "outra-senha")?;
+    let other = ctx.auth().login("bob", "outra-senha")?;
+    assert!(ctx.credentials().restore(&other, email.id()).is_err());
+    assert!(ctx.credentials().purge(&other, email.id()).is_err());
+
+    ctx.credentials().purge(&session, email.id())?;
+    assert!(ctx.credentials().list_trash(&session)?.is_empty());
+    assert!(ctx.credentials().restore(&session, email.id()).is_err());
+
+    ctx.credentials().delete(&session, banco.id())?;
+    assert_eq!(ctx.credentials().empty_trash(&session)?, 1);
+    assert!(ctx.credentials().list_trash(&session)?.is_empty());
+    assert!(ctx.credentials().list(&session)?.is_empty());
+    Ok(())
+}
+
+fn search_by_name(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    for name in ["GitLab", "Banco", "GitHub"] {
+        ctx.credentials()
+            .create(&session, name, None, None, None, None)?;
+    }
+
+    let names: Vec<String> = ctx
+        .credentials()
+        .search(&session, "git")?
+        .iter()
+        .map(|c| c.name().to_string())
+        .collect();
+    assert_eq!(names, ["GitHub", "GitLab"]);
+    Ok(())
+}
+
+fn search_matches_fields_literally_ignoring_case_and_accents(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    let credentials = ctx.credentials();
+    credentials.create(
+        &session,
+        "Repositórios",
+        Some("alice@example.com".into()),
+        Some("https://github.com/login".into()),
+        None,
+        None,
+    )?;
+    credentials.create(
+        &session,
+        "Banco",
+        Some("joao_silva".into()),
+        None,
+        None,
+        None,
+    )?;
+    credentials.create(&session, "Cupom 100%", None, None, None, None)?;
+    credentials.create(&session, "Metrô São Paulo", None, None, None, None)?;
+    credentials.create(&session, "Sao Bernardo", None, None, None, None)?;
+
+    let names = |query: &str| -> Result<Vec<String>> {
+        let mut names: Vec<String> = credentials
+            .search(&session, query)?
+            .iter()
+            .map(|c| c.name().to_string())
+            .collect();
+        names.sort();
+        Ok(names)
+    };
+
+    assert_eq!(names("github.com")?, ["Repositórios"]);
+    assert_eq!(names("EXAMPLE")?, ["Repositórios"]);
+    assert_eq!(names("_")?, ["Banco"]);
+    assert_eq!(names("%")?, ["Cupom 100%"]);
+    assert_eq!(names("sao")?, ["Metrô São Paulo", "Sao Bernardo"]);
+    assert_eq!(names("SÃO")?, ["Metrô São Paulo", "Sao Bernardo"]);
+    assert_eq!(names("repositorios")?, ["Repositórios"]);
+    assert!(names("  ")?.is_empty());
+    Ok(())
+}
+
+fn search_ranks_results_and_accepts_typos(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    for (name, url) in [
+        ("Servidor git", None),
+        ("GitHub", None),
+        ("Git", None),
+        ("Forge", Some("https://git.example.com")),
+        ("Legit", None),
+    ] {
+        ctx.credentials()
+            .create(&session, name, None, url.map(Into::into), None, None)?;
+    }
+
+    let names: Vec<String> = ctx
+        .credentials()
+        .search(&session, "git")?
+        .iter()
+        .map(|c| c.name().to_string())
+        .collect();
+    assert_eq!(names, ["Git", "GitHub", "Servidor git", "Forge", "Legit"]);
+
+    assert!(ctx.credentials().search(&session, "githbu")?.is_empty());
+
+    let fuzzy = SearchOptions {
+        fuzzy: true,
+        ..SearchOptions::default()
+    };
+    let hits = ctx.credentials().search_with(&session, "githbu", &fuzzy)?;
+    assert_eq!(hits[0].credential.name(), "GitHub");
+    assert_eq!(hits[0].field, MatchField::Name);
+
+    let hits = ctx.credentials().search_with(&session, "srvgit", &fuzzy)?;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].credential.name(), "Servidor git");
+    Ok(())
+}
+
+fn search_in_notes_is_opt_in(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    ctx.credentials().create(
+        &session,
+        "Cofre do escritório",
+        None,
+        None,
+        Some(b"combinacao 4521".to_vec()),
+        None,
+    )?;
+
+    assert!(ctx.credentials().search(&session, "4521")?.is_empty());
+
+    let with_notes = SearchOptions {
+        include_notes: true,
+        ..SearchOptions::default()
+    };
+    let hits = ctx
+        .credentials()
+        .search_with(&session, "4521", &with_notes)?;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].field, MatchField::Notes);
+
+    let hits = ctx
+        .credentials()
+        .search_with(&session, "escritorio", &with_notes)?;
+    assert_eq!(hits[0].field, MatchField::Name);
+    Ok(())
+}
+
+fn folders_form_a_tree_and_filter_credentials(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    let credentials = ctx.credentials();
+
+    let trabalho = credentials.create_folder(&session, "Trabalho", None)?;
+    let servidores = credentials.create_folder(&session, "Servidores", Some(trabalho.id()))?;
+    let pessoal = credentials.create_folder(&session, "Pessoal", None)?;
+
+    assert!(
+        credentials
+            .create_folder(&session, "trabalho", None)
+            .is_err()
+    );
+    assert!(credentials.create_folder(&session, "a/b", None).is_err());
+
+    let vpn = credentials.create(&session, "VPN", None, None, None, None)?;
+    let ssh = credentials.create(&session, "SSH", None, None, None, None)?;
+    credentials.create(&session, "Solta", None, None, None, None)?;
+    credentials.move_to_folder(&session, vpn.id(), Some(trabalho.id()))?;
+    credentials.move_to_folder(&session, ssh.id(), Some(servidores.id()))?;
+
+    let tree: Vec<(String, usize, usize)> = credentials
+        .list_folders(&session)?
+        .into_iter()
+        .map(|f| (f.path, f.depth, f.credentials))
+        .collect();
+    assert_eq!(
+        tree,
+        [
+            ("Pessoal".to_string(), 0, 0),
+            ("Trabalho".to_string(), 0, 1),
+            ("Trabalho/Servidores".to_string(), 1, 1),
+        ]
+    );
+
+    let names = |list: Vec<password_manager::models::credential::Credential>| -> Vec<String> {
+        list.iter().map(|c| c.name().to_string()).collect()
+    };
+    assert_eq!(
+        names(credentials.list_by_folder(&session, Some(trabalho.id()), false)?),
+        ["VPN"]
+    );
+    assert_eq!(
+        names(credentials.list_by_folder(&session, Some(trabalho.id()), true)?),
+        ["SSH", "VPN"]
+    );
+    assert_eq!(
+        names(credentials.list_by_folder(&session, None, false)?),
+        ["Solta"]
+    );
+
+    // mover para dentro da própria subpasta criaria um ciclo
+    assert!(
+        credentials
+            .move_folder(&session, trabalho.id(), Some(servidores.id()))
+            .is_err()
+    );
+
+    credentials.move_folder(&session, servidores.id(), Some(pessoal.id()))?;
+    credentials.rename_folder(&session, pessoal.id(), "Casa")?;
+    let paths: Vec<String> = credentials
+        .list_folders(&session)?
+        .into_iter()
+        .map(|f| f.path)
+        .collect();
+    assert_eq!(paths, ["Casa", "Casa/Servidores", "Trabalho"]);
+    assert_eq!(
+        credentials.get(&session, ssh.id())?.folder_id(),
+        Some(servidores.id())
+    );
+    Ok(())
+}
+
+fn deleting_a_folder_keeps_its_contents(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    ctx.auth().register("bruno", "outra-senha")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    let bruno = ctx.auth().login("bruno", "outra-senha")?;
+    let credentials = ctx.credentials();
+
+    let trabalho = credentials.create_folder(&session, "Trabalho", None)?;
+    let antigos = credentials.create_folder(&session, "Antigos", Some(trabalho.id()))?;
+    let arquivo = credentials.create_folder(&session, "Arquivo", Some(antigos.id()))?;
+    let ftp = credentials.create(&session, "FTP", None, None, None, None)?;
+    credentials.move_to_folder(&session, ftp.id(), Some(antigos.id()))?;
+
+    // pastas de outro cofre não são visíveis
+    assert!(credentials.delete_folder(&bruno, antigos.id()).is_err());
+    assert!(
+        credentials
+            .move_to_folder(&session, ftp.id(), None)
+            .and(credentials.move_to_folder(&bruno, ftp.id(), Some(trabalho.id())))
+            .is_err()
+    );
+    credentials.move_to_folder(&session, ftp.id(), Some(antigos.id()))?;
+
+    credentials.delete_folder(&session, antigos.id())?;
+
+    assert_eq!(
+        credentials.get(&session, ftp.id())?.folder_id(),
+        Some(trabalho.id())
+    );
+    let folders = credentials.list_folders(&session)?;
+    assert_eq!(folders.len(), 2);
+    assert_eq!(folders[1].folder.id(), arquivo.id());
+    assert_eq!(folders[1].path, "Trabalho/Arquivo");
+    Ok(())
+}
+
+fn tags_are_assigned_renamed_and_filtered(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    let credentials = ctx.credentials();
+
+    let github = credentials.create(&session, "GitHub", None, None, None, None)?;
+    let banco = credentials.create(&session, "Banco", None, None, None, None)?;
+
+    let tags = credentials.set_tags(
+        &session,
+        github.id(),
+        &["trabalho".into(), " 2FA ".into(), "Trabalho".into()],
+    )?;
+    let names: Vec<&str> = tags.iter().map(|t| t.name()).collect();
+    assert_eq!(names, ["2FA", "trabalho"]);
+
+    credentials.set_tags(&session, banco.id(), &["2fa".into(), "finanças".into()])?;
+    assert!(
+        credentials
+            .set_tags(&session, banco.id(), &["a,b".into()])
+            .is_err()
+    );
+
+    let summary: Vec<(String, usize)> = credentials
+        .list_tags(&session)?
+        .into_iter()
+        .map(|t| (t.tag.name().to_string(), t.credentials))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("2FA".to_string(), 2),
+            ("finanças".to_string(), 1),
+            ("trabalho".to_string(), 1),
+        ]
+    );
+
+    let two_fa = credentials.list_tags(&session)?[0].tag.clone();
+    let tagged: Vec<String> = credentials
+        .list_by_tag(&session, two_fa.id())?
+        .iter()
+        .map(|c| c.name().to_string())
+        .collect();
+    assert_eq!(tagged, ["Banco", "GitHub"]);
+
+    assert!(
+        credentials
+            .rename_tag(&session, two_fa.id(), "TRABALHO")
+            .is_err()
+    );
+    credentials.rename_tag(&session, two_fa.id(), "mfa")?;
+    assert_eq!(credentials.tags_of(&session, banco.id())?[1].name(), "mfa");
+
+    credentials.delete(&session, github.id())?;
+    credentials.delete_tag(&session, two_fa.id())?;
+    let summary: Vec<(String, usize)> = credentials
+        .list_tags(&session)?
+        .into_iter()
+        .map(|t| (t.tag.name().to_string(), t.credentials))
+        .collect();
+    assert_eq!(
+        summary,
+        [("finanças".to_string(), 1), ("trabalho".to_string(), 0)]
+    );
+
+    credentials.set_tags(&session, banco.id(), &[])?;
+    assert!(credentials.tags_of(&session, banco.id())?.is_empty());
+    Ok(())
+}
+
+fn security_report_flags_weak_reused_old_and_incomplete(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    let strong = "x7$Kq!v9Lm#2pR8z";
+    for (name, username, url, password) in [
+        ("Banco", Some("alice"), Some("https://banco.example"), Some("123456")),
+        ("Email", Some("alice"), Some("https://mail.example"), Some(strong)),
+        ("Forum", Some("alice"), None, Some(strong)),
+        ("Wi-Fi", None, None, None),
+    ] {
+        ctx.credentials().create(
+            &session,
+            name,
+            username.map(str::to_string),
+            url.map(str::to_string),
+            None,
+            password,
+        )?;
+    }
+    let trashed = ctx
+        .credentials()
+        .create(&session, "Antiga", None, None, None, Some("abc"))?;
+    ctx.credentials().delete(&session, trashed.id())?;
+
+    let report = ctx
+        .credentials()
+        .security_report(&session, &ReportOptions::default())?;
+    assert_eq!(report.total, 4);
+    assert!(!report.is_clean());
+
+    let weak: Vec<&str> = report.weak.iter().map(|w| w.credential.name()).collect();
+    assert_eq!(weak, ["Banco"]);
+
+    assert_eq!(report.reused.len(), 1);
+    let reused: Vec<&str> = report.reused[0]
+        .credentials
+        .iter()
+        .map(|c| c.name())
+        .collect();
+    assert_eq!(reused, ["Email", "Forum"]);
+
+    assert!(report.stale.is_empty());
+
+    let incomplete: Vec<(&str, bool, bool)> = report
+        .incomplete
+        .iter()
+        .map(|i| (i.credential.name(), i.missing_username, i.missing_url))
+        .collect();
+    assert_eq!(incomplete, [("Forum", false, true), ("Wi-Fi", true, true)]);
+
+    let report = ctx.credentials().security_report(
+        &session,
+        &ReportOptions {
+            max_age_days: 0,
+            ..ReportOptions::default()
+        },
+    )?;
+    assert_eq!(report.stale.len(), 3);
+    Ok(())
+}
+
+fn password_age_ignores_edits_that_keep_the_password(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    let cred = ctx
+        .credentials()
+        .create(&session, "Banco", None, None, None, Some("antiga"))?;
+    let changed_at = cred.password_changed_at();
+
+    let folder = ctx.credentials().create_folder(&session, "Finanças", None)?;
+    ctx.credentials()
+        .move_to_folder(&session, cred.id(), Some(folder.id()))?;
+    ctx.credentials().set_totp(
+        &session,
+        cred.id(),
+        Some("otpauth://totp/Banco?secret=JBSWY3DPEHPK3PXP"),
+    )?;
+    let current = ctx.credentials().get(&session, cred.id())?;
+    ctx.credentials().update(
+        &session,
+        current,
+        Some("Banco Central".into()),
+        Some("alice".into()),
+        None,
+        None,
+        Some("antiga"),
+    )?;
+    let edited = ctx.credentials().get(&session, cred.id())?;
+    assert!(edited.updated_at() > cred.updated_at());
+    assert_eq!(edited.password_changed_at(), changed_at);
+
+    ctx.credentials()
+        .update(&session, edited, None, None, None, None, Some("nova"))?;
+    let changed = ctx.credentials().get(&session, cred.id())?;
+    assert!(changed.password_changed_at() > changed_at);
+    Ok(())
+}
+
+fn credentials_are_isolated_between_users(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    ctx.auth().register("bruno", "outra-senha")?;
+    let alice = ctx.auth().login("alice", "senha-forte")?;
+    let bruno = ctx.auth().login("bruno", "outra-senha")?;
+
+    let secret = ctx
+        .credentials()
+        .create(&alice, "Privada", None, None, None, Some("x"))?;
+
+    assert!(ctx.credentials().list(&bruno)?.is_empty());
+    assert!(ctx.credentials().get(&bruno, secret.id()).is_err());
+    assert!(ctx.credentials().reveal_password(&bruno, secret.id()).is_err());
+    assert!(ctx.credentials().delete(&bruno, secret.id()).is_err());
+    assert!(ctx.credentials().get(&alice, secret.id()).is_ok());
+    Ok(())
+}
+
+fn change_password_keeps_credentials_readable(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    let created = ctx
+        .credentials()
+        .create(&session, "GitHub", None, None, None, Some("s3gr3d0"))?;
+
+    assert!(
+        ctx.auth()
+            .change_password(&session, "errada", "nova-senha")
+            .is_err()
+    );
+    ctx.auth()
+        .change_password(&session, "senha-forte", "nova-senha")?;
+
+    assert!(ctx.auth().login("alice", "senha-forte").is_err());
+    let session = ctx.auth().login("alice", "nova-senha")?;
+    assert_eq!(
+        ctx.credentials().reveal_password(&session, created.id())?,
+        Some("s3gr3d0".to_string())
+    );
+    Ok(())
+}
+
+fn vaults_are_created_renamed_switched_and_deleted(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    ctx.auth().register("bruno", "senha-forte")?;
+    let mut session = ctx.auth().login("alice", "senha-forte")?;
+    let vaults = ctx.vaults();
+
+    let pessoal = vaults.current(&session)?;
+    assert_eq!(pessoal.name(), "Pessoal");
+    ctx.credentials()
+        .create(&session, "Banco", None, None, None, Some("s3gr3d0"))?;
+
+    let trabalho = vaults.create(&session, " Trabalho ")?;
+    assert_eq!(trabalho.name(), "Trabalho");
+    assert!(vaults.create(&session, "trabalho").is_err());
+    assert!(vaults.create(&session, "  ").is_err());
+
+    let summary: Vec<(String, usize, bool)> = vaults
+        .list(&session)?
+        .into_iter()
+        .map(|v| (v.vault.name().to_string(), v.credentials, v.current))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("Pessoal".to_string(), 1, true),
+            ("Trabalho".to_string(), 0, false),
+        ]
+    );
+
+    vaults.switch(&mut session, trabalho.id())?;
+    assert!(ctx.credentials().list(&session)?.is_empty());
+    let jira = ctx
+        .credentials()
+        .create(&session, "Jira", None, None, None, Some("j1r4"))?;
+    assert!(vaults.delete(&session, trabalho.id()).is_err());
+
+    assert!(vaults.rename(&session, trabalho.id(), "PESSOAL").is_err());
+    vaults.rename(&session, trabalho.id(), "Empresa")?;
+    assert_eq!(vaults.current(&session)?.name(), "Empresa");
+
+    // Outro usuário não enxerga nem abre os cofres de alice.
+    let mut bruno = ctx.auth().login("bruno", "senha-forte")?;
+    assert_eq!(vaults.list(&bruno)?.len(), 1);
+    assert!(vaults.switch(&mut bruno, trabalho.id()).is_err());
+    assert!(vaults.delete(&bruno, trabalho.id()).is_err());
+
+    vaults.switch(&mut session, pessoal.id())?;
+    assert!(ctx.credentials().get(&session, jira.id()).is_err());
+    vaults.delete(&session, trabalho.id())?;
+    assert_eq!(vaults.list(&session)?.len(), 1);
+    assert!(vaults.switch(&mut session, trabalho.id()).is_err());
+
+    // O login abre o cofre mais antigo.
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    assert_eq!(session.vault_id, pessoal.id());
+    Ok(())
+}
+
+fn credentials_move_and_copy_between_vaults(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let mut session = ctx.auth().login("alice", "senha-forte")?;
+    let vaults = ctx.vaults();
+    let credentials = ctx.credentials();
+    let pessoal = vaults.current(&session)?;
+    let trabalho = vaults.create(&session, "Trabalho")?;
+
+    let folder = credentials.create_folder(&session, "Contas", None)?;
+    let github = credentials.create(
+        &session,
+        "GitHub",
+        Some("alice".into()),
+        None,
+        Some(b"notas".to_vec()),
+        Some("v1"),
+    )?;
+    let current = credentials.get(&session, github.id())?;
+    credentials.update(&session, current, None, None, None, None, Some("v2"))?;
+    credentials.move_to_folder(&session, github.id(), Some(folder.id()))?;
+    credentials.set_tags(&session, github.id(), &["dev".into()])?;
+    credentials.set_totp(&session, github.id(), Some("JBSWY3DPEHPK3PXP"))?;
+    let banco = credentials.create(&session, "Banco", None, None, None, Some("b4nc0"))?;
+
+    assert!(
+        vaults
+            .transfer(&session, &[banco.id()], pessoal.id(), TransferMode::Move)
+            .is_err()
+    );
+
+    let copied = vaults.transfer(&session, &[banco.id()], trabalho.id(), TransferMode::Copy)?;
+    assert_ne!(copied[0].id(), banco.id());
+    let moved = vaults.transfer(&session, &[github.id()], trabalho.id(), TransferMode::Move)?;
+    assert_eq!(moved[0].id(), github.id());
+
+    let names: Vec<String> = credentials
+        .list(&session)?
+        .iter()
+        .map(|c| c.name().to_string())
+        .collect();
+    assert_eq!(names, ["Banco"]);
+    assert_eq!(credentials.list_tags(&session)?[0].credentials, 0);
+
+    vaults.switch(&mut session, trabalho.id())?;
+    assert_eq!(credentials.list(&session)?.len(), 2);
+    assert_eq!(
+        credentials.reveal_password(&session, copied[0].id())?,
+        Some("b4nc0".to_string())
+    );
+    assert_eq!(
+        credentials.reveal_password(&session, github.id())?,
+        Some("v2".to_string())
+    );
+    assert_eq!(
+        credentials.reveal_notes(&session, github.id())?,
+        Some("notas".to_string())
+    );
+    assert!(credentials.reveal_totp(&session, github.id())?.is_some());
+    assert_eq!(
+        credentials.password_history(&session, github.id())?[0]
+            .password
+            .as_deref(),
+        Some("v1")
+    );
+    assert_eq!(credentials.get(&session, github.id())?.folder_id(), None);
+    let tags: Vec<String> = credentials
+        .tags_of(&session, github.id())?
+        .iter()
+        .map(|t| t.name().to_string())
+        .collect();
+    assert_eq!(tags, ["dev"]);
+    Ok(())
+}
+
+fn change_password_keeps_every_vault_readable(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let mut session = ctx.auth().login("alice", "senha-forte")?;
+    let trabalho = ctx.vaults().create(&session, "Trabalho")?;
+    ctx.vaults().switch(&mut session, trabalho.id())?;
+    let created = ctx
+        .credentials()
+        .create(&session, "Jira", None, None, None, Some("j1r4"))?;
+
+    ctx.auth()
+        .change_password(&session, "senha-forte", "nova-senha")?;
+
+    let mut session = ctx.auth().login("alice", "nova-senha")?;
+    ctx.vaults().switch(&mut session, trabalho.id())?;
+    assert_eq!(
+        ctx.credentials().reveal_password(&session, created.id())?,
+        Some("j1r4".to_string())
+    );
+
+    session.lock();
+    ctx.auth().unlock(&mut session, "nova-senha")?;
+    assert_eq!(session.vault_id, trabalho.id());
+    assert_eq!(ctx.credentials().list(&session)?.len(), 1);
+    Ok(())
+}
+
+fn shared_vault_roles_limit_what_members_can_do(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    ctx.auth().register("bob", "senha-do-bob")?;
+    let mut alice = ctx.auth().login("alice", "senha-forte")?;
+    let equipe = ctx.vaults().create(&alice, "Equipe")?;
+    ctx.vaults().switch(&mut alice, equipe.id())?;
+    let created = ctx
+        .credentials()
+        .create(&alice, "Servidor", None, None, None, Some("r00t"))?;
+
+    assert!(
+        ctx.vaults()
+            .invite(&alice, equipe.id(), "carol", VaultRole::Viewer)
+            .is_err()
+    );
+    assert!(
+        ctx.vaults()
+            .invite(&alice, equipe.id(), "bob", VaultRole::Owner)
+            .is_err()
+    );
+    let member = ctx
+        .vaults()
+        .invite(&alice, equipe.id(), "bob", VaultRole::Viewer)?;
+    assert_eq!(member.username, "bob");
+    assert!(
+        ctx.vaults()
+            .invite(&alice, equipe.id(), "bob", VaultRole::Editor)
+            .is_err()
+    );
+
+    let mut bob = ctx.auth().login("bob", "senha-do-bob")?;
+    let shared = ctx
+        .vaults()
+        .list(&bob)?
+        .into_iter()
+        .find(|v| v.vault.id() == equipe.id())
+        .expect("cofre compartilhado listado");
+    assert_eq!(shared.role, VaultRole::Viewer);
+    assert_eq!(shared.owner.as_deref(), Some("alice"));
+
+    ctx.vaults().switch(&mut bob, equipe.id())?;
+    assert_eq!(bob.vault_role(), VaultRole::Viewer);
+    assert_eq!(
+        ctx.credentials().reveal_password(&bob, created.id())?,
+        Some("r00t".to_string())
+    );
+    assert!(
+        ctx.credentials()
+            .create(&bob, "Outro", None, None, None, None)
+            .is_err()
+    );
+    assert!(ctx.credentials().delete(&bob, created.id()).is_err());
+    assert!(ctx.vaults().rename(&bob, equipe.id(), "Minha").is_err());
+    assert!(ctx.vaults().delete(&bob, equipe.id()).is_err());
+    assert!(
+        ctx.vaults()
+            .invite(&bob, equipe.id(), "alice", VaultRole::Editor)
+            .is_err()
+    );
+
+    ctx.vaults()
+        .set_role(&alice, equipe.id(), "bob", VaultRole::Editor)?;
+    ctx.vaults().switch(&mut bob, equipe.id())?;
+    let added = ctx
+        .credentials()
+        .create(&bob, "Banco", None, None, None, Some("b4nc0"))?;
+    assert_eq!(
+        ctx.credentials().reveal_password(&alice, added.id())?,
+        Some("b4nc0".to_string())
+    );
+
+    let members: Vec<(String, VaultRole)> = ctx
+        .vaults()
+        .members(&bob, equipe.id())?
+        .into_iter()
+        .map(|m| (m.username, m.role))
+        .collect();
+    assert_eq!(
+        members,
+        [
+            ("alice".to_string(), VaultRole::Owner),
+            ("bob".to_string(), VaultRole::Editor)
+        ]
+    );
+    Ok(())
+}
+
+fn revoking_a_member_rotates_the_vault_key(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    ctx.auth().register("bob", "senha-do-bob")?;
+    ctx.auth().register("carol", "senha-da-carol")?;
+    let mut alice = ctx.auth().login("alice", "senha-forte")?;
+    let equipe = ctx.vaults().create(&alice, "Equipe")?;
+    ctx.vaults().switch(&mut alice, equipe.id())?;
+
+    let credentials = ctx.credentials();
+    let created = credentials.create(&alice, "Servidor", None, None, None, Some("antiga"))?;
+    credentials.update(
+        &alice,
+        created.clone(),
+        None,
+        None,
+        None,
+        None,
+        Some("atual"),
+    )?;
+    let trashed = credentials.create(&alice, "Antigo", None, None, None, Some("lixo"))?;
+    credentials.delete(&alice, trashed.id())?;
+
+    ctx.auth().login("bob", "senha-do-bob")?;
+    ctx.auth().login("carol", "senha-da-carol")?;
+    ctx.vaults()
+        .invite(&alice, equipe.id(), "bob", VaultRole::Editor)?;
+    ctx.vaults()
+        .invite(&alice, equipe.id(), "carol", VaultRole::Viewer)?;
+
+    let mut bob = ctx.auth().login("bob", "senha-do-bob")?;
+    ctx.vaults().switch(&mut bob, equipe.id())?;
+    assert!(ctx.vaults().revoke(&mut alice, equipe.id(), "alice").is_err());
+
+    ctx.vaults().revoke(&mut alice, equipe.id(), "bob")?;
+
+    assert!(credentials.reveal_password(&bob, created.id()).is_err());
+    assert!(ctx.vaults().switch(&mut bob, equipe.id()).is_err());
+    assert!(
+        ctx.vaults()
+            .list(&bob)?
+            .iter()
+            .all(|v| v.vault.id() != equipe.id())
+    );
+
+    assert_eq!(
+        credentials.reveal_password(&alice, created.id())?,
+        Some("atual".to_string())
+    );
+
+    let mut carol = ctx.auth().login("carol", "senha-da-carol")?;
+    ctx.vaults().switch(&mut carol, equipe.id())?;
+    assert_eq!(
+        credentials.reveal_password(&carol, created.id())?,
+        Some("atual".to_string())
+    );
+    let history = credentials.password_history(&carol, created.id())?;
+    assert_eq!(history[0].password.as_deref(), Some("antiga"));
+
+    credentials.restore(&alice, trashed.id())?;
+    assert_eq!(
+        credentials.reveal_password(&alice, trashed.id())?,
+        Some("lixo".to_string())
+    );
+    assert_eq!(ctx.vaults().members(&alice, equipe.id())?.len(), 2);
+    Ok(())
+}
+
+fn unlock_reopens_own_vault_after_access_is_revoked(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    ctx.auth().register("bob", "senha-do-bob")?;
+    let mut alice = ctx.auth().login("alice", "senha-forte")?;
+    let equipe = ctx.vaults().create(&alice, "Equipe")?;
+    ctx.vaults()
+        .invite(&alice, equipe.id(), "bob", VaultRole::Editor)?;
+
+    let mut bob = ctx.auth().login("bob", "senha-do-bob")?;
+    let own = bob.vault_id;
+    ctx.vaults().switch(&mut bob, equipe.id())?;
+    bob.lock();
+
+    ctx.vaults().revoke(&mut alice, equipe.id(), "bob")?;
+
+    ctx.auth().unlock(&mut bob, "senha-do-bob")?;
+    assert_eq!(bob.vault_id, own);
+    assert_eq!(bob.vault_role(), VaultRole::Owner);
+    assert!(ctx.credentials().list(&bob)?.is_empty());
+    Ok(())
+}
+
+fn open_sessions_lose_write_access_when_revoked_or_demoted(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    ctx.auth().register("bob", "senha-do-bob")?;
+    ctx.auth().register("carol", "senha-da-carol")?;
+    let credentials = ctx.credentials();
+    let mut alice = ctx.auth().login("alice", "senha-forte")?;
+    let equipe = ctx.vaults().create(&alice, "Equipe")?;
+    ctx.vaults().switch(&mut alice, equipe.id())?;
+    let mut other_alice = ctx.auth().login("alice", "senha-forte")?;
+    ctx.vaults().switch(&mut other_alice, equipe.id())?;
+
+    ctx.auth().login("bob", "senha-do-bob")?;
+    ctx.auth().login("carol", "senha-da-carol")?;
+    ctx.vaults()
+        .invite(&alice, equipe.id(), "bob", VaultRole::Editor)?;
+    ctx.vaults()
+        .invite(&alice, equipe.id(), "carol", VaultRole::Editor)?;
+    let mut bob = ctx.auth().login("bob", "senha-do-bob")?;
+    ctx.vaults().switch(&mut bob, equipe.id())?;
+    let mut carol = ctx.auth().login("carol", "senha-da-carol")?;
+    ctx.vaults().switch(&mut carol, equipe.id())?;
+    let created = credentials.create(&carol, "Servidor", None, None, None, Some("s3rv"))?;
+
+    ctx.vaults()
+        .set_role(&alice, equipe.id(), "bob", VaultRole::Viewer)?;
+    assert_eq!(bob.vault_role(), VaultRole::Editor);
+    let err = credentials
+        .create(&bob, "Roteador", None, None, None, Some("r0t"))
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Unauthorized);
+    ctx.vaults()
+        .set_role(&alice, equipe.id(), "bob", VaultRole::Editor)?;
+
+    ctx.vaults().revoke(&mut alice, equipe.id(), "carol")?;
+    let err = credentials
+        .create(&carol, "Roteador", None, None, None, Some("r0t"))
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Unauthorized);
+    let err = credentials.delete(&carol, created.id()).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Unauthorized);
+
+    for stale in [&bob, &other_alice] {
+        let err = credentials
+            .create(stale, "Roteador", None, None, None, Some("r0t"))
+            .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Conflict);
+        let err = credentials.delete(stale, created.id()).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Conflict);
+    }
+    assert_eq!(
+        credentials.reveal_password(&alice, created.id())?,
+        Some("s3rv".to_string())
+    );
+
+    ctx.vaults().switch(&mut bob, equipe.id())?;
+    let router = credentials.create(&bob, "Roteador", None, None, None, Some("r0t"))?;
+    assert_eq!(
+        credentials.reveal_password(&alice, router.id())?,
+        Some("r0t".to_string())
+    );
+    assert_eq!(credentials.list(&alice)?.len(), 2);
+    Ok(())
+}
+
+fn users_are_listed_and_renamed(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("bob", "senha-do-bob")?;
+    ctx.auth().register("alice", "senha-forte")?;
+    let mut alice = ctx.auth().login("alice", "senha-forte")?;
+    let names: Vec<String> = ctx
+        .auth()
+        .list_users(&alice)?
+        .into_iter()
+        .map(|u| u.username)
+        .collect();
+    assert_eq!(names, ["alice", "bob"]);
+
+    let created = ctx
+        .credentials()
+        .create(&alice, "Banco", None, None, None, Some("b4nc0"))?;
+
+    assert!(ctx.auth().rename_user(&mut alice, "errada", "alicia").is_err());
+    assert!(ctx.auth().rename_user(&mut alice, "senha-forte", "bob").is_err());
+    assert!(ctx.auth().rename_user(&mut alice, "senha-forte", "a").is_err());
+
+    ctx.auth().rename_user(&mut alice, "senha-forte", "alicia")?;
+    assert_eq!(alice.user.username(), "alicia");
+    assert!(ctx.auth().login("alice", "senha-forte").is_err());
+
+    let alicia = ctx.auth().login("alicia", "senha-forte")?;
+    assert_eq!(
+        ctx.credentials().reveal_password(&alicia, created.id())?,
+        Some("b4nc0".to_string())
+    );
+    assert_eq!(ctx.auth().list_users(&alicia)?.len(), 2);
+
+    alice.lock();
+    let err = ctx.auth().list_users(&alice).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Unauthorized);
+    Ok(())
+}
+
+fn deleting_an_account_removes_its_data(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    ctx.auth().register("bob", "senha-do-bob")?;
+    let alice = ctx.auth().login("alice", "senha-forte")?;
+    let mut bob = ctx.auth().login("bob", "senha-do-bob")?;
+
+    let equipe = ctx.vaults().create(&alice, "Equipe")?;
+    ctx.vaults()
+        .invite(&alice, equipe.id(), "bob", VaultRole::Editor)?;
+    let projeto = ctx.vaults().create(&bob, "Projeto")?;
+    ctx.vaults()
+        .invite(&bob, projeto.id(), "alice", VaultRole::Viewer)?;
+    ctx.credentials()
+        .create(&bob, "Servidor", None, None, None, Some("r00t"))?;
+
+    assert!(ctx.auth().delete_account(&mut bob, "errada").is_err());
+    ctx.auth().delete_account(&mut bob, "senha-do-bob")?;
+    assert_eq!(bob.state(), SessionState::Locked);
+
+    assert!(ctx.auth().login("bob", "senha-do-bob").is_err());
+    assert!(
+        ctx.vaults()
+            .list(&alice)?
+            .iter()
+            .all(|v| v.vault.id() != projeto.id())
+    );
+    assert_eq!(ctx.vaults().members(&alice, equipe.id())?.len(), 1);
+
+    ctx.auth().register("bob", "outra-senha")?;
+    let bob = ctx.auth().login("bob", "outra-senha")?;
+    assert!(ctx.credentials().list(&bob)?.is_empty());
+    assert_eq!(ctx.vaults().list(&bob)?.len(), 1);
+    Ok(())
+}
+
+/// Contas anteriores aos cofres múltiplos não têm chave da conta e guardam a
+/// chave do cofre cifrada diretamente com a senha mestre.
+#[test]
+fn legacy_account_receives_an_account_key_at_login() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("vault.db");
+    let ctx = AppContext::new(Database::open(path.to_str().unwrap(), 2)?);
+
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    let created = ctx
+        .credentials()
+        .create(&session, "Banco", None, None, None, Some("s3gr3d0"))?;
+    drop(session);
+
+    let conn = rusqlite::Connection::open(&path)?;
+    let (account_cipher, vault_cipher): (Vec<u8>, Vec<u8>) = conn.query_row(
+        "SELECT u.account_key_cipher, v.vault_key_cipher
+         FROM user u JOIN vault v ON v.user_id = u.id",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let account_key = decrypt_with_passphrase("senha-forte", &account_cipher)?;
+    let vault_key = decrypt_with_key(&account_key, &vault_cipher)?;
+    conn.execute(
+        "UPDATE user SET account_key_cipher = NULL, public_key = NULL, private_key_cipher = NULL",
+        [],
+    )?;
+    conn.execute(
+        "UPDATE vault SET vault_key_cipher = ?1",
+        [encrypt_with_passphrase("senha-forte", &vault_key)?],
+    )?;
+
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    assert_eq!(
+        ctx.credentials().reveal_password(&session, created.id())?,
+        Some("s3gr3d0".to_string())
+    );
+
+    let (migrated, public_key): (Option<Vec<u8>>, Option<String>) = conn.query_row(
+        "SELECT account_key_cipher, public_key FROM user",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    assert!(migrated.is_some());
+    assert!(public_key.is_some_and(|k| k.starts_with("age1")));
+    let trabalho = ctx.vaults().create(&session, "Trabalho")?;
+    assert_eq!(ctx.vaults().list(&session)?.len(), 2);
+    assert_ne!(trabalho.id(), session.vault_id);
+    Ok(())
+}
+
+/// Campos gravados antes da chave do cofre ficavam cifrados com AGE e a senha
+/// mestre; o login os recifra no formato `[v1][nonce][conteúdo]`.
+#[test]
+fn legacy_credential_ciphers_are_resealed_at_login() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("vault.db");
+    let ctx = AppContext::new(Database::open(path.to_str().unwrap(), 2)?);
+
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    let created = ctx
+        .credentials()
+        .create(&session, "Banco", None, None, None, None)?;
+    drop(session);
+
+    let conn = rusqlite::Connection::open(&path)?;
+    conn.execute(
+        "UPDATE credential SET password_cipher = ?1, notes = ?2 WHERE id = ?3",
+        (
+            encrypt_with_passphrase("senha-forte", b"s3gr3d0")?,
+            encrypt_with_passphrase("senha-forte", b"agencia 0001")?,
+            created.id().as_bytes(),
+        ),
+    )?;
+
+    let session = ctx.auth().login("alice", "senha-forte")?;
+
+    let (password, notes): (Vec<u8>, Vec<u8>) = conn.query_row(
+        "SELECT password_cipher, notes FROM credential WHERE id = ?1",
+        [created.id().as_bytes()],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    for (cipher, plain) in [(&password, &b"s3gr3d0"[..]), (&notes, &b"agencia 0001"[..])] {
+        assert_eq!(cipher[0], 1, "versão do formato");
+        assert!(cipher.len() > 1 + 24, "nonce e conteúdo");
+        assert_eq!(decrypt_with_key(&session.vault_key, cipher)?, plain);
+    }
+    assert_eq!(
+        ctx.credentials().reveal_password(&session, created.id())?,
+        Some("s3gr3d0".to_string())
+    );
+    assert_eq!(
+        ctx.credentials().reveal_notes(&session, created.id())?,
+        Some("agencia 0001".to_string())
+    );
+    Ok(())
+}
+
+/// Hashes gerados com custos do Argon2 mais fracos que os vigentes são
+/// recalculados no login, enquanto a senha em texto puro está disponível.
+#[test]
+fn outdated_password_hash_is_rehashed_at_login() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("vault.db");
+    let ctx = AppContext::new(Database::open(path.to_str().unwrap(), 2)?);
+    ctx.auth().register("alice", "senha-forte")?;
+
+    let weak_params = Params::new(Params::MIN_M_COST, 1, 1, None).map_err(anyhow::Error::msg)?;
+    let weak = Argon2::new(Algorithm::Argon2id, Version::V0x13, weak_params)
+        .hash_password(b"senha-forte", &SaltString::generate(&mut OsRng))
+        .map_err(anyhow::Error::msg)?
+        .to_string();
+    let conn = rusqlite::Connection::open(&path)?;
+    conn.execute("UPDATE user SET password_hash = ?1", [weak.as_bytes()])?;
+    assert!(password_hash_needs_rehash(weak.as_bytes())?);
+
+    ctx.auth().login("alice", "senha-forte")?;
+
+    let stored: Vec<u8> = conn.query_row("SELECT password_hash FROM user", [], |row| row.get(0))?;
+    assert_ne!(stored, weak.as_bytes());
+    assert!(!password_hash_needs_rehash(&stored)?);
+    let current = argon2_params()?;
+    let params = PasswordHash::new(std::str::from_utf8(&stored)?)
+        .and_then(|hash| Params::try_from(&hash))
+        .map_err(anyhow::Error::msg)?;
+    assert_eq!(
+        (params.m_cost(), params.t_cost(), params.p_cost()),
+        (current.m_cost(), current.t_cost(), current.p_cost())
+    );
+    assert!(verify_password("senha-forte", &stored)?);
+    ctx.auth().login("alice", "senha-forte")?;
+    Ok(())
+}
+
+/// Um banco migrado por uma versão mais nova da aplicação não é aberto nem
+/// alterado.
+#[test]
+fn newer_schema_version_is_rejected_untouched() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("vault.db");
+    let conn = rusqlite::Connection::open(&path)?;
+    conn.pragma_update(None, "user_version", 11)?;
+
+    let Err(err) = Database::open(path.to_str().unwrap(), 2) else {
+        panic!("banco com schema mais novo que o suportado foi aberto");
+    };
+    assert_eq!(err.code(), ErrorCode::Storage);
+
+    let version: u32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    assert_eq!(version, 11);
+    let tables: u32 = conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| row.get(0))?;
+    assert_eq!(tables, 0);
+    Ok(())
+}
+
+fn locked_session_requires_unlock(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let mut session = ctx.auth().login("alice", "senha-forte")?;
+
+    session.lock();
+    assert_eq!(session.state(), SessionState::Locked);
+    assert!(ctx.credentials().list(&session).is_err());
+
+    assert!(ctx.auth().unlock(&mut session, "errada").is_err());
+    ctx.auth().unlock(&mut session, "senha-forte")?;
+
+    assert_eq!(session.state(), SessionState::Active);
+    assert!(ctx.credentials().list(&session)?.is_empty());
+    Ok(())
+}
+
+fn unlock_failures_count_towards_login_lockout(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let mut session = ctx.auth().login("alice", "senha-forte")?;
+    session.lock();
+
+    let wrong = ctx.auth().unlock(&mut session, "errada").unwrap_err();
+    let login = ctx.auth().login("alice", "errada").unwrap_err();
+    assert_eq!(wrong.to_string(), login.to_string());
+
+    for _ in 0..2 {
+        assert!(ctx.auth().unlock(&mut session, "errada").is_err());
+    }
+
+    assert!(ctx.auth().unlock(&mut session, "senha-forte").is_err());
+    assert_eq!(session.state(), SessionState::Locked);
+    assert!(ctx.auth().login("alice", "senha-forte").is_err());
+    Ok(())
+}
+
+fn unlock_follows_a_rename_from_another_session(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let mut locked = ctx.auth().login("alice", "senha-forte")?;
+    locked.lock();
+
+    let mut other = ctx.auth().login("alice", "senha-forte")?;
+    ctx.auth().rename_user(&mut other, "senha-forte", "alicia")?;
+
+    ctx.auth().unlock(&mut locked, "senha-forte")?;
+    assert_eq!(locked.state(), SessionState::Active);
+    assert_eq!(locked.user.username(), "alicia");
+    assert!(ctx.credentials().list(&locked)?.is_empty());
+    Ok(())
+}
+
+fn password_confirmations_count_towards_login_lockout(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let mut session = ctx.auth().login("alice", "senha-forte")?;
+
+    let wrong = ctx
+        .auth()
+        .rename_user(&mut session, "errada", "alicia")
+        .unwrap_err();
+    let login = ctx.auth().login("alice", "errada").unwrap_err();
+    assert_eq!(wrong.to_string(), login.to_string());
+
+    assert!(
+        ctx.auth()
+            .change_password(&session, "errada", "nova-senha")
+            .is_err()
+    );
+    assert!(ctx.auth().delete_account(&mut session, "errada").is_err());
+
+    assert!(
+        ctx.auth()
+            .rename_user(&mut session, "senha-forte", "alicia")
+            .is_err()
+    );
+    assert_eq!(session.user.username(), "alice");
+    assert!(ctx.auth().login("alice", "senha-forte").is_err());
+    Ok(())
+}
+
+fn errors_are_categorised(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    ctx.auth().register("bob", "senha-do-bob")?;
+
+    let err = ctx.auth().register("alice", "outra-senha").unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+    let err = ctx.auth().login("alice", "errada").unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Unauthorized);
+
+    let mut alice = ctx.auth().login("alice", "senha-forte")?;
+    let err = ctx
+        .credentials()
+        .get(&alice, uuid::Uuid::new_v4())
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    let err = ctx
+        .credentials()
+        .create(&alice, " ", None, None, None, None)
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Validation);
+    assert_eq!(err.field(), Some("name"));
+    let json = serde_json::to_value(&err)?;
+    assert_eq!(json["code"], "validation");
+    assert_eq!(json["field"], "name");
+    assert_eq!(json["message"], err.to_string());
+
+    ctx.credentials().create_folder(&alice, "Trabalho", None)?;
+    let err = ctx
+        .credentials()
+        .create_folder(&alice, "Trabalho", None)
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Conflict);
+    assert!(serde_json::to_value(&err)?.get("field").is_none());
+
+    let equipe = ctx.vaults().create(&alice, "Equipe")?;
+    ctx.vaults()
+        .invite(&alice, equipe.id(), "bob", VaultRole::Viewer)?;
+    let mut bob = ctx.auth().login("bob", "senha-do-bob")?;
+    ctx.vaults().switch(&mut bob, equipe.id())?;
+    let err = ctx
+        .credentials()
+        .create(&bob, "Servidor", None, None, None, None)
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Unauthorized);
+
+    alice.lock();
+    let err = ctx.credentials().list(&alice).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Unauthorized);
+    Ok(())
+}
+
+fn totp_secret_is_encrypted_and_generates_codes(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    let created = ctx
+        .credentials()
+        .create(&session, "GitHub", None, None, None, None)?;
+
+    assert!(
+        ctx.credentials()
+            .current_totp(&session, created.id())?
+            .is_none()
+    );
+    assert!(
+        ctx.credentials()
+            .set_totp(&session, created.id(), Some("otpauth://hotp/x?secret=AAAA"))
+            .is_err()
+    );
+
+    let uri = "otpauth://totp/GitHub:alice?secret=JBSWY3DPEHPK3PXP&digits=8&issuer=GitHub";
+    ctx.credentials()
+        .set_totp(&session, created.id(), Some(uri))?;
+
+    let stored = ctx.credentials().get(&session, created.id())?;
+    let cipher = stored.totp_cipher().expect("segredo TOTP persistido");
+    assert!(!String::from_utf8_lossy(cipher).contains("JBSWY3DPEHPK3PXP"));
+
+    let code = ctx
+        .credentials()
+        .current_totp(&session, created.id())?
+        .expect("código TOTP");
+    assert_eq!(code.code.len(), 8);
+    assert!((1..=30).contains(&code.remaining_seconds));
+
+    ctx.credentials().set_totp(&session, created.id(), None)?;
+    assert!(
+        ctx.credentials()
+            .reveal_totp(&session, created.id())?
+            .is_none()
+    );
+    Ok(())
+}
+
+fn export_then_import_into_another_vault(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    ctx.auth().register("bruno", "outra-senha")?;
+    let alice = ctx.auth().login("alice", "senha-forte")?;
+    let bruno = ctx.auth().login("bruno", "outra-senha")?;
+
+    let github = ctx.credentials().create(
+        &alice,
+        "GitHub",
+        Some("alice".into()),
+        Some("https://github.com".into()),
+        Some("códigos de recuperação".as_bytes().to_vec()),
+        Some("s3gr3d0"),
+    )?;
+    ctx.credentials()
+        .set_totp(&alice, github.id(), Some("JBSWY3DPEHPK3PXP"))?;
+    ctx.credentials()
+        .create(&alice, "Banco", None, None, None, None)?;
+
+    let archive = ctx.credentials().export(&alice, "senha-do-backup")?;
+    assert!(!String::from_utf8_lossy(&archive).contains("s3gr3d0"));
+
+    assert!(
+        ctx.credentials()
+            .import(&bruno, &archive, "errada")
+            .is_err()
+    );
+    let summary = ctx
+        .credentials()
+        .import(&bruno, &archive, "senha-do-backup")?;
+    assert_eq!(summary.imported, 2);
+    assert_eq!(summary.exported_by, "alice");
+
+    let imported = ctx.credentials().search(&bruno, "GitHub")?;
+    assert_eq!(imported.len(), 1);
+    let copy = &imported[0];
+    assert_ne!(copy.id(), github.id());
+    let original = ctx.credentials().get(&alice, github.id())?;
+    assert_eq!(copy.created_at(), original.created_at());
+    assert_eq!(copy.updated_at(), original.updated_at());
+    assert_eq!(copy.username(), Some("alice"));
+    assert_eq!(copy.url(), Some("https://github.com"));
+    assert_eq!(
+        ctx.credentials().reveal_password(&bruno, copy.id())?,
+        Some("s3gr3d0".to_string())
+    );
+    assert_eq!(
+        ctx.credentials().reveal_notes(&bruno, copy.id())?,
+        Some("códigos de recuperação".to_string())
+    );
+    assert_eq!(
+        ctx.credentials().reveal_totp(&bruno, copy.id())?,
+        ctx.credentials().reveal_totp(&alice, github.id())?
+    );
+    assert_eq!(ctx.credentials().list(&alice)?.len(), 2);
+    Ok(())
+}
+
+fn import_rejects_foreign_or_newer_archives(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+
+    let foreign = encrypt_with_passphrase("x", br#"{"qualquer": "coisa"}"#)?;
+    assert!(ctx.credentials().import(&session, &foreign, "x").is_err());
+
+    let newer = encrypt_with_passphrase(
+        "x",
+        br#"{"format": "password_manager/vault-archive", "version": 99,
+             "exported_at": "2030-01-01T00:00:00Z", "exported_by": "alice",
+             "source_vault_id": "00000000-0000-0000-0000-000000000001", "credentials": []}"#,
+    )?;
+    let err = ctx.credentials().import(&session, &newer, "x").unwrap_err();
+    assert!(err.to_string().contains("v99"));
+
+    assert!(ctx.credentials().list(&session)?.is_empty());
+    Ok(())
+}
+
+fn external_import_reports_duplicates_and_supports_dry_run(ctx: &AppContext) -> Result<()> {
+    ctx.auth().register("alice", "senha-forte")?;
+    let session = ctx.auth().login("alice", "senha-forte")?;
+    ctx.credentials().create(
+        &session,
+        "GitHub",
+        Some("alice".into()),
+        Some("https://github.com/".into()),
+        None,
+        Some("antiga"),
+    )?;
+
+    let csv = "name,url,username,password,note,totp\n\
+github,https://github.com/,alice,nova,,\n\
+Banco,https://banco.example,alice,b4nc0,agência 1,JBSWY3DPEHPK3PXP\n\
+Banco,https://banco.example,alice,b4nc0,,\n\
+Loja,https://loja.example,alice,l0j4,,não-é-totp!\n\
+,,,,,\n";
+    let parsed = importers::parse(Some(ImportFormat::BrowserCsv), csv.as_bytes())?;
+
+    let preview = ctx.credentials().import_external(&session, &parsed, true)?;
+    assert!(preview.dry_run);
+    assert_eq!(preview.total, 5);
+    assert_eq!(preview.imported, 2);
+    assert_eq!(preview.duplicates.len(), 2);
+    assert_eq!(preview.skipped.len(), 1);
+    assert_eq!(preview.warnings.len(), 1);
+    assert_eq!(ctx.credentials().list(&session)?.len(), 1);
+
+    let report = ctx
+        .credentials()
+        .import_external(&session, &parsed, false)?;
+    assert_eq!(report.imported, 2);
+    assert_eq!(ctx.credentials().list(&session)?.len(), 3);
+
+    let banco = &ctx.credentials().search(&session, "Banco")?[0];
+    assert_eq!(
+        ctx.credentials().reveal_password(&session, banco.id())?,
+        Some("b4nc0".to_string())
+    );
+    assert!(
+        ctx.credentials()
+            .current_totp(&session, banco.id())?
+            .is_some()
+    );
+
+    let again = ctx
+        .credentials()
+        .import_external(&session, &parsed, false)?;
+    assert_eq!(again.imported, 0);
+    assert_eq!(ctx.credentials().list(&session)?.len(), 3);
+    Ok(())
+}
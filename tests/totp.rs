@@ -0,0 +1,89 @@
+//! Testes do cálculo TOTP com os vetores do apêndice B da RFC 6238.
+
+use password_manager::infrastructure::totp::{Totp, TotpAlgorithm};
+
+/// Segredos ASCII da RFC, já codificados em Base32.
+const SECRET_SHA1: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+const SECRET_SHA256: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZA";
+const SECRET_SHA512: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNA";
+
+fn rfc_totp(secret: &str, algorithm: &str) -> Totp {
+    Totp::parse(&format!(
+        "otpauth://totp/RFC:teste?secret={secret}&digits=8&algorithm={algorithm}"
+    ))
+    .unwrap()
+}
+
+#[test]
+fn matches_rfc6238_vectors() {
+    let cases = [
+        (59, "94287082", "46119246", "90693936"),
+        (1111111109, "07081804", "68084774", "25091201"),
+        (1111111111, "14050471", "67062674", "99943326"),
+        (1234567890, "89005924", "91819424", "93441116"),
+        (2000000000, "69279037", "90698825", "38618901"),
+        (20000000000, "65353130", "77737706", "47863826"),
+    ];
+
+    let sha1 = rfc_totp(SECRET_SHA1, "SHA1");
+    let sha256 = rfc_totp(SECRET_SHA256, "SHA256");
+    let sha512 = rfc_totp(SECRET_SHA512, "SHA512");
+
+    for (time, expected_sha1, expected_sha256, expected_sha512) in cases {
+        assert_eq!(sha1.code_at(time).code, expected_sha1, "SHA1 t={time}");
+        assert_eq!(
+            sha256.code_at(time).code,
+            expected_sha256,
+            "SHA256 t={time}"
+        );
+        assert_eq!(
+            sha512.code_at(time).code,
+            expected_sha512,
+            "SHA512 t={time}"
+        );
+    }
+}
+
+#[test]
+fn reports_remaining_seconds() {
+    let totp = Totp::parse(SECRET_SHA1).unwrap();
+
+    assert_eq!(totp.code_at(59).remaining_seconds, 1);
+    assert_eq!(totp.code_at(60).remaining_seconds, 30);
+    assert_eq!(totp.code_at(60).code.len(), 6);
+}
+
+#[test]
+fn accepts_bare_secret_with_defaults() {
+    let totp = Totp::parse("gezd gnbv gy3t qojq").unwrap();
+
+    assert_eq!(totp.digits(), 6);
+    assert_eq!(totp.period(), 30);
+    assert_eq!(totp.algorithm(), TotpAlgorithm::Sha1);
+}
+
+#[test]
+fn uri_round_trip_preserves_parameters() {
+    let totp = Totp::parse(
+        "otpauth://totp/Exemplo%20SA:alice%40example.com?secret=JBSWY3DPEHPK3PXP&period=60&digits=7&algorithm=SHA256&issuer=Exemplo%20SA",
+    )
+    .unwrap();
+
+    let reparsed = Totp::parse(&totp.to_uri()).unwrap();
+    assert_eq!(reparsed.digits(), 7);
+    assert_eq!(reparsed.period(), 60);
+    assert_eq!(reparsed.algorithm(), TotpAlgorithm::Sha256);
+    assert_eq!(
+        reparsed.code_at(1_000_000).code,
+        totp.code_at(1_000_000).code
+    );
+}
+
+#[test]
+fn rejects_invalid_input() {
+    assert!(Totp::parse("otpauth://hotp/x?secret=JBSWY3DPEHPK3PXP&counter=1").is_err());
+    assert!(Totp::parse("otpauth://totp/x?digits=6").is_err());
+    assert!(Totp::parse("otpauth://totp/x?secret=JBSWY3DPEHPK3PXP&digits=4").is_err());
+    assert!(Totp::parse("otpauth://totp/x?secret=JBSWY3DPEHPK3PXP&algorithm=MD5").is_err());
+    assert!(Totp::parse("não é base32!").is_err());
+}
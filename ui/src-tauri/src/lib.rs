@@ -1,43 +1,39 @@
+mod session_store;
+
 use serde::{Serialize, Deserialize};
-use base64::{engine::general_purpose, Engine as _};
 use dotenvy;
+use tauri::State;
 
 use password_manager::{
-    infrastructure::{database::get_database_connection, logger::init_logger},
-    services::{
-        auth_service::{AuthService, Session},
-        credential_service::CredentialService,
-    },
-    models::{
-        credential::Credential,
-        user::User,
+    app_context::AppContext,
+    error::{Error, Result},
+    importers::{self, ImportFormat, ImportReport},
+    infrastructure::logger::init_logger,
+    infrastructure::totp::TotpCode,
+    services::auth_service::{SessionState, UserSummary},
+    services::credential_search::{SearchHit, SearchOptions},
+    services::credential_service::{FolderSummary, HistoryVersion, TagSummary},
+    services::password_generator::{
+        GeneratedPassword, PassphrasePolicy, PasswordGenerator, PasswordPolicy,
     },
+    services::security_report::{ReportOptions, SecurityReport},
+    services::vault_archive::ImportSummary,
+    services::vault_service::{TransferMode, VaultMemberSummary, VaultSummary},
+    models::credential::Credential,
+    models::folder::Folder,
+    models::tag::Tag,
+    models::vault::Vault,
+    models::vault_member::VaultRole,
 };
 
+use session_store::SessionStore;
 use uuid::Uuid;
 
+/// Dados de sessão expostos ao webview: apenas um token opaco e o nome do usuário.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SessionDTO {
+    pub token: String,
     pub username: String,
-    pub vault_id: String,
-    pub vault_key: String,
-    pub passphrase: String,
-}
-
-impl SessionDTO {
-    pub fn into_session(self) -> Result<Session, String> {
-        let vault_key_bytes = general_purpose::STANDARD
-            .decode(&self.vault_key)
-            .map_err(|e| format!("Erro ao decodificar vault_key: {}", e))?;
-
-        Ok(Session {
-            user: User::from_session(self.username.clone()),
-            vault_id: Uuid::parse_str(&self.vault_id)
-                .map_err(|e| format!("vault_id inválido: {}", e))?,
-            vault_key: vault_key_bytes,
-            passphrase: self.passphrase,
-        })
-    }
 }
 
 fn load_env() {
@@ -47,127 +43,936 @@ fn load_env() {
 }
 
 #[tauri::command]
-fn register_user(username: String, password: String) -> Result<(), String> {
-    AuthService::register(&username, &password).map_err(|e| e.to_string())
+fn register_user(
+    ctx: State<'_, AppContext>,
+    username: String,
+    password: String,
+) -> Result<()> {
+    ctx.auth().register(&username, &password)
 }
 
 #[tauri::command]
-fn login_user(username: String, password: String) -> Result<SessionDTO, String> {
-    let session = AuthService::login(&username, &password)
-        .map_err(|e| e.to_string())?;
+fn login_user(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    username: String,
+    password: String,
+) -> Result<SessionDTO> {
+    let session = ctx.auth().login(&username, &password)?;
+
+    let username = session.user.username().to_string();
+    let token = sessions.insert(session);
+
+    Ok(SessionDTO { token, username })
+}
+
+#[tauri::command]
+fn logout_user(ctx: State<'_, AppContext>, sessions: State<'_, SessionStore>, token: String) {
+    sessions.remove(&token);
+    if let Some(clipboard) = ctx.clipboard() {
+        clipboard.clear_pending();
+    }
+}
+
+/// Estado da sessão para o webview: `active`, `locked` ou `expired`.
+#[tauri::command]
+fn session_status(sessions: State<'_, SessionStore>, token: String) -> String {
+    match sessions.status(&token) {
+        SessionState::Active => "active",
+        SessionState::Locked => "locked",
+        SessionState::Expired => "expired",
+    }
+    .to_string()
+}
+
+#[tauri::command]
+fn lock_session(sessions: State<'_, SessionStore>, token: String) -> Result<()> {
+    sessions.with_session_mut(&token, |session| {
+        session.lock();
+        Ok(())
+    })
+}
 
-    Ok(SessionDTO {
-        username: session.user.username().to_string(),
-        vault_id: session.vault_id.to_string(),
-        vault_key: general_purpose::STANDARD.encode(&session.vault_key),
-        passphrase: session.passphrase.clone(),
+#[tauri::command]
+fn unlock_session(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    password: String,
+) -> Result<()> {
+    sessions.with_session_mut(&token, |session| {
+        ctx.auth().unlock(session, &password)
     })
 }
 
 #[tauri::command]
 fn create_credential(
-    session: SessionDTO,
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
     name: String,
     username: Option<String>,
     url: Option<String>,
     notes: Option<String>,
     password: Option<String>,
-) -> Result<(), String> {
-    let session = session.into_session()?;
+    totp: Option<String>,
+    folder_id: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<()> {
     let notes_bytes = notes.map(|n| n.into_bytes());
+    let folder = parse_optional_id(folder_id)?;
+
+    sessions.with_session(&token, |session| {
+        let credential = ctx.credentials().create(
+            session,
+            &name,
+            username,
+            url,
+            notes_bytes,
+            password.as_deref(),
+        )?;
 
-    CredentialService::create(
-        &session,
-        &name,
-        username,
-        url,
-        notes_bytes,
-        password.as_deref(),
-    )
-    .map(|_| ())
-    .map_err(|e| e.to_string())
+        if totp.is_some() {
+            ctx.credentials().set_totp(session, credential.id(), totp.as_deref())?;
+        }
+        if folder.is_some() {
+            ctx.credentials().move_to_folder(session, credential.id(), folder)?;
+        }
+        if let Some(tags) = tags.filter(|t| !t.is_empty()) {
+            ctx.credentials().set_tags(session, credential.id(), &tags)?;
+        }
+        Ok(())
+    })
 }
 
 #[tauri::command]
-fn list_credentials(session: SessionDTO) -> Result<Vec<Credential>, String> {
-    let session = session.into_session()?;
-    CredentialService::list(&session).map_err(|e| e.to_string())
+fn list_credentials(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+) -> Result<Vec<Credential>> {
+    sessions.with_session(&token, |session| {
+        ctx.credentials().list(session)
+    })
+}
+
+/// Pesquisa credenciais do cofre da sessão, do resultado mais relevante ao menos.
+#[tauri::command]
+fn search_credentials(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    query: String,
+    options: SearchOptions,
+) -> Result<Vec<SearchHit>> {
+    sessions.with_session(&token, |session| {
+        ctx.credentials().search_with(session, &query, &options)
+    })
+}
+
+/// Converte um ID vindo do webview, rejeitando textos que não sejam UUIDs.
+fn parse_id(id: &str) -> Result<Uuid> {
+    Uuid::parse_str(id).map_err(|_| Error::validation("id", format!("ID inválido: '{id}'")))
+}
+
+/// Converte um ID opcional vindo do webview (`null` representa a raiz do cofre).
+fn parse_optional_id(id: Option<String>) -> Result<Option<Uuid>> {
+    id.map(|id| parse_id(&id)).transpose()
+}
+
+/// Pastas do cofre da sessão em ordem de árvore.
+#[tauri::command]
+fn list_folders(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+) -> Result<Vec<FolderSummary>> {
+    sessions.with_session(&token, |session| {
+        ctx.credentials().list_folders(session)
+    })
+}
+
+#[tauri::command]
+fn create_folder(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    name: String,
+    parent_id: Option<String>,
+) -> Result<Folder> {
+    let parent = parse_optional_id(parent_id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().create_folder(session, &name, parent)
+    })
+}
+
+#[tauri::command]
+fn rename_folder(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+    name: String,
+) -> Result<Folder> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().rename_folder(session, uuid, &name)
+    })
+}
+
+#[tauri::command]
+fn move_folder(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+    parent_id: Option<String>,
+) -> Result<Folder> {
+    let uuid = parse_id(&id)?;
+    let parent = parse_optional_id(parent_id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().move_folder(session, uuid, parent)
+    })
+}
+
+/// Remove uma pasta; subpastas e credenciais passam para a pasta superior.
+#[tauri::command]
+fn delete_folder(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+) -> Result<()> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().delete_folder(session, uuid)
+    })
+}
+
+/// Move uma credencial para uma pasta (`folder_id` nulo retira de qualquer pasta).
+#[tauri::command]
+fn move_credential(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+    folder_id: Option<String>,
+) -> Result<()> {
+    let uuid = parse_id(&id)?;
+    let folder = parse_optional_id(folder_id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().move_to_folder(session, uuid, folder)
+    })
+}
+
+/// Credenciais de uma pasta (`folder_id` nulo para as que estão fora de pastas).
+#[tauri::command]
+fn list_credentials_by_folder(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    folder_id: Option<String>,
+    include_subfolders: bool,
+) -> Result<Vec<Credential>> {
+    let folder = parse_optional_id(folder_id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().list_by_folder(session, folder, include_subfolders)
+    })
+}
+
+/// Tags do cofre da sessão com a quantidade de credenciais de cada uma.
+#[tauri::command]
+fn list_tags(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+) -> Result<Vec<TagSummary>> {
+    sessions.with_session(&token, |session| {
+        ctx.credentials().list_tags(session)
+    })
+}
+
+#[tauri::command]
+fn get_credential_tags(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+) -> Result<Vec<Tag>> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().tags_of(session, uuid)
+    })
+}
+
+/// Substitui as tags de uma credencial, criando as que ainda não existem.
+#[tauri::command]
+fn set_credential_tags(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+    tags: Vec<String>,
+) -> Result<Vec<Tag>> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().set_tags(session, uuid, &tags)
+    })
+}
+
+#[tauri::command]
+fn rename_tag(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+    name: String,
+) -> Result<Tag> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().rename_tag(session, uuid, &name)
+    })
+}
+
+#[tauri::command]
+fn delete_tag(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+) -> Result<()> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().delete_tag(session, uuid)
+    })
+}
+
+#[tauri::command]
+fn list_credentials_by_tag(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+) -> Result<Vec<Credential>> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().list_by_tag(session, uuid)
+    })
 }
 
 #[derive(Serialize)]
 struct CredentialFullDTO {
     password: Option<String>,
     notes: Option<String>,
+    totp: Option<String>,
 }
 
 #[tauri::command]
-fn get_credential_full(session: SessionDTO, id: String) -> Result<CredentialFullDTO, String> {
-    let session = session.into_session()?;
-    let uuid = Uuid::parse_str(&id).map_err(|e| e.to_string())?;
-
-    let password = CredentialService::reveal_password(&session, uuid)
-        .map_err(|e| e.to_string())?;
+fn get_credential_full(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+) -> Result<CredentialFullDTO> {
+    let uuid = parse_id(&id)?;
 
-    let notes = CredentialService::reveal_notes(&session, uuid)
-        .map_err(|e| e.to_string())?;
+    sessions.with_session(&token, |session| {
+        let password = ctx.credentials().reveal_password(session, uuid)?;
+        let notes = ctx.credentials().reveal_notes(session, uuid)?;
+        let totp = ctx.credentials().reveal_totp(session, uuid)?;
 
-    Ok(CredentialFullDTO { password, notes })
+        Ok(CredentialFullDTO { password, notes, totp })
+    })
 }
 
 #[tauri::command]
 fn update_credential(
-    session: SessionDTO,
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
     id: String,
     name: Option<String>,
     username: Option<String>,
     url: Option<String>,
     notes: Option<String>,
     password: Option<String>,
-) -> Result<(), String> {
-    let session = session.into_session()?;
-    let uuid = Uuid::parse_str(&id).map_err(|e| e.to_string())?;
+) -> Result<()> {
+    let uuid = parse_id(&id)?;
+    let notes_bytes = notes.map(|n| n.into_bytes());
 
-    let credential = CredentialService::get(&session, uuid)
-        .map_err(|e| e.to_string())?;
+    sessions.with_session(&token, |session| {
+        let credential = ctx.credentials().get(session, uuid)?;
 
-    let notes_bytes = notes.map(|n| n.into_bytes());
+        ctx.credentials().update(
+            session,
+            credential,
+            name,
+            username,
+            url,
+            notes_bytes,
+            password.as_deref(),
+        )
+    })
+}
+
+/// Define (ou remove, com `otpauth` nulo/vazio) a configuração TOTP de uma credencial.
+#[tauri::command]
+fn set_credential_totp(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+    otpauth: Option<String>,
+) -> Result<()> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().set_totp(session, uuid, otpauth.as_deref())
+    })
+}
+
+/// Versões anteriores da senha (e notas) da credencial, da mais recente para a mais antiga.
+#[tauri::command]
+fn get_password_history(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+) -> Result<Vec<HistoryVersion>> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().password_history(session, uuid)
+    })
+}
+
+#[tauri::command]
+fn restore_password_version(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+    version_id: String,
+) -> Result<()> {
+    let uuid = parse_id(&id)?;
+    let version = parse_id(&version_id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().restore_version(session, uuid, version)
+    })
+}
+
+/// Código TOTP vigente da credencial (`null` quando não configurado).
+#[tauri::command]
+fn get_totp_code(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+) -> Result<Option<TotpCode>> {
+    let uuid = parse_id(&id)?;
 
-    CredentialService::update(
-        &session,
-        credential,
-        name,
-        username,
-        url,
-        notes_bytes,
-        password.as_deref(),
-    )
-    .map_err(|e| e.to_string())
+    sessions.with_session(&token, |session| {
+        ctx.credentials().current_totp(session, uuid)
+    })
+}
+
+#[tauri::command]
+fn delete_credential(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+) -> Result<()> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().delete(session, uuid)
+    })
+}
+
+/// Relatório de senhas fracas, repetidas e antigas e de credenciais incompletas.
+#[tauri::command]
+fn security_report(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    options: ReportOptions,
+) -> Result<SecurityReport> {
+    sessions.with_session(&token, |session| {
+        ctx.credentials().security_report(session, &options)
+    })
+}
+
+/// Ocorrências da senha na base local de senhas vazadas (`null` quando não há base configurada).
+#[tauri::command]
+fn check_password_breach(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    password: String,
+) -> Result<Option<u64>> {
+    sessions.with_session(&token, |_| {
+        ctx.credentials().breach_occurrences(&password)
+    })
+}
+
+/// Campo de uma credencial copiado por [`copy_to_clipboard`].
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CopyField {
+    Username,
+    Password,
+    /// Código TOTP vigente.
+    Totp,
+}
+
+/// Copia um campo de uma credencial para a área de transferência do sistema,
+/// com limpeza automática.
+///
+/// O valor é lido e descriptografado aqui, como no `get --copy` da CLI: a
+/// senha e o código TOTP não trafegam pela interface só para serem copiados.
+/// Retorna em quantos segundos a área de transferência será limpa (`null` quando
+/// a limpeza está desativada).
+#[tauri::command]
+fn copy_to_clipboard(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+    field: CopyField,
+) -> Result<Option<u64>> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        let credentials = ctx.credentials();
+        let value = match field {
+            CopyField::Username => credentials
+                .get(session, uuid)?
+                .username()
+                .map(str::to_string),
+            CopyField::Password => credentials.reveal_password(session, uuid)?,
+            CopyField::Totp => credentials
+                .current_totp(session, uuid)?
+                .map(|totp| totp.code),
+        }
+        .ok_or_else(|| Error::not_found("A credencial não possui o campo solicitado"))?;
+
+        let clipboard = ctx.clipboard().ok_or_else(|| {
+            Error::storage(
+                "Área de transferência indisponível (instale wl-clipboard, xclip ou xsel)",
+            )
+        })?;
+        let copy = clipboard.copy(&value)?;
+        Ok(copy.clear_after().map(|d| d.as_secs()))
+    })
+}
+
+/// Credenciais na lixeira, das removidas mais recentemente às mais antigas.
+#[tauri::command]
+fn list_trash(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+) -> Result<Vec<Credential>> {
+    sessions.with_session(&token, |session| {
+        ctx.credentials().list_trash(session)
+    })
+}
+
+#[tauri::command]
+fn restore_credential(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+) -> Result<Credential> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().restore(session, uuid)
+    })
 }
 
 #[tauri::command]
-fn delete_credential(session: SessionDTO, id: String) -> Result<(), String> {
-    let session = session.into_session()?;
-    let uuid = Uuid::parse_str(&id).map_err(|e| e.to_string())?;
+fn purge_credential(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+) -> Result<()> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().purge(session, uuid)
+    })
+}
+
+/// Esvazia a lixeira, retornando a quantidade de credenciais excluídas.
+#[tauri::command]
+fn empty_trash(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+) -> Result<usize> {
+    sessions.with_session(&token, |session| {
+        ctx.credentials().empty_trash(session)
+    })
+}
+
+#[tauri::command]
+fn change_password(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    old_password: String,
+    new_password: String,
+) -> Result<()> {
+    sessions.with_session(&token, |session| {
+        ctx.auth().change_password(session, &old_password, &new_password)
+    })
+}
+
+/// Usuários cadastrados, sugeridos ao compartilhar um cofre.
+#[tauri::command]
+fn list_users(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+) -> Result<Vec<UserSummary>> {
+    sessions.with_session(&token, |session| ctx.auth().list_users(session))
+}
+
+/// Renomeia o usuário da sessão, confirmando a senha mestre; devolve o novo nome.
+#[tauri::command]
+fn rename_user(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    password: String,
+    new_username: String,
+) -> Result<String> {
+    sessions.with_session_mut(&token, |session| {
+        ctx.auth().rename_user(session, &password, &new_username)?;
+        Ok(session.user.username().to_string())
+    })
+}
+
+/// Exclui a conta da sessão, confirmando a senha mestre, e encerra a sessão.
+#[tauri::command]
+fn delete_account(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    password: String,
+) -> Result<()> {
+    sessions.with_session_mut(&token, |session| {
+        ctx.auth().delete_account(session, &password)
+    })?;
+
+    sessions.remove(&token);
+    if let Some(clipboard) = ctx.clipboard() {
+        clipboard.clear_pending();
+    }
+    Ok(())
+}
+
+/// Exporta o cofre da sessão para um arquivo cifrado com `passphrase`.
+#[tauri::command]
+fn export_vault(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    path: String,
+    passphrase: String,
+) -> Result<()> {
+    let data = sessions.with_session(&token, |session| {
+        ctx.credentials().export(session, &passphrase)
+    })?;
+
+    std::fs::write(&path, data)
+        .map_err(|e| Error::storage(format!("Falha ao gravar o arquivo: {e}")))
+}
+
+/// Importa para o cofre da sessão um arquivo gerado por `export_vault`.
+#[tauri::command]
+fn import_vault(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    path: String,
+    passphrase: String,
+) -> Result<ImportSummary> {
+    let data = std::fs::read(&path)
+        .map_err(|e| Error::storage(format!("Falha ao ler o arquivo: {e}")))?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().import(session, &data, &passphrase)
+    })
+}
 
-    CredentialService::delete(&session, uuid).map_err(|e| e.to_string())
+/// Importa um arquivo exportado por outro gerenciador de senhas.
+///
+/// Com `dry_run` verdadeiro apenas simula a importação, devolvendo o relatório
+/// sem gravar nada.
+#[tauri::command]
+fn import_external(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    path: String,
+    format: Option<ImportFormat>,
+    dry_run: bool,
+) -> Result<ImportReport> {
+    let data = std::fs::read(&path)
+        .map_err(|e| Error::storage(format!("Falha ao ler o arquivo: {e}")))?;
+    let parsed = importers::parse(format, &data)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.credentials().import_external(session, &parsed, dry_run)
+    })
+}
+
+/// Cofres próprios e compartilhados com o usuário, indicando o aberto na sessão.
+#[tauri::command]
+fn list_vaults(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+) -> Result<Vec<VaultSummary>> {
+    sessions.with_session(&token, |session| {
+        ctx.vaults().list(session)
+    })
+}
+
+#[tauri::command]
+fn create_vault(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    name: String,
+) -> Result<Vault> {
+    sessions.with_session(&token, |session| {
+        ctx.vaults().create(session, &name)
+    })
+}
+
+#[tauri::command]
+fn rename_vault(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+    name: String,
+) -> Result<Vault> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.vaults().rename(session, uuid, &name)
+    })
+}
+
+/// Exclui definitivamente um cofre que não esteja aberto na sessão.
+#[tauri::command]
+fn delete_vault(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+) -> Result<()> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.vaults().delete(session, uuid)
+    })
+}
+
+/// Abre outro cofre da conta na sessão; as demais chamadas passam a operar nele.
+#[tauri::command]
+fn switch_vault(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+) -> Result<Vault> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session_mut(&token, |session| {
+        ctx.vaults().switch(session, uuid)
+    })
+}
+
+/// Move ou copia credenciais do cofre aberto para outro cofre da conta.
+#[tauri::command]
+fn transfer_credentials(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    ids: Vec<String>,
+    target_vault_id: String,
+    mode: TransferMode,
+) -> Result<Vec<Credential>> {
+    let ids = ids
+        .iter()
+        .map(|id| parse_id(id))
+        .collect::<Result<Vec<_>>>()?;
+    let target = parse_id(&target_vault_id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.vaults().transfer(session, &ids, target, mode)
+    })
+}
+
+/// Dono e membros de um cofre acessível ao usuário.
+#[tauri::command]
+fn list_vault_members(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+) -> Result<Vec<VaultMemberSummary>> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.vaults().members(session, uuid)
+    })
+}
+
+#[tauri::command]
+fn invite_vault_member(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+    username: String,
+    role: VaultRole,
+) -> Result<VaultMemberSummary> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.vaults().invite(session, uuid, &username, role)
+    })
+}
+
+#[tauri::command]
+fn set_vault_member_role(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+    username: String,
+    role: VaultRole,
+) -> Result<VaultMemberSummary> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session(&token, |session| {
+        ctx.vaults().set_role(session, uuid, &username, role)
+    })
+}
+
+/// Remove um membro do cofre; a chave do cofre é trocada e as credenciais recifradas.
+#[tauri::command]
+fn revoke_vault_member(
+    ctx: State<'_, AppContext>,
+    sessions: State<'_, SessionStore>,
+    token: String,
+    id: String,
+    username: String,
+) -> Result<()> {
+    let uuid = parse_id(&id)?;
+
+    sessions.with_session_mut(&token, |session| {
+        ctx.vaults().revoke(session, uuid, &username)
+    })
+}
+
+#[tauri::command]
+fn generate_password(policy: PasswordPolicy) -> Result<GeneratedPassword> {
+    PasswordGenerator::generate(&policy)
+}
+
+#[tauri::command]
+fn generate_passphrase(policy: PassphrasePolicy) -> Result<GeneratedPassword> {
+    PasswordGenerator::passphrase(&policy)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     load_env();
     init_logger();
-    get_database_connection().expect("Falha ao inicializar banco de dados");
+    let ctx = AppContext::from_env().expect("Falha ao inicializar banco de dados");
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .manage(ctx)
+        .manage(SessionStore::default())
         .invoke_handler(tauri::generate_handler![
             register_user,
             login_user,
+            logout_user,
+            session_status,
+            lock_session,
+            unlock_session,
             create_credential,
             list_credentials,
+            search_credentials,
             get_credential_full,
             update_credential,
-            delete_credential
+            delete_credential,
+            list_trash,
+            restore_credential,
+            purge_credential,
+            empty_trash,
+            security_report,
+            check_password_breach,
+            copy_to_clipboard,
+            set_credential_totp,
+            get_totp_code,
+            get_password_history,
+            restore_password_version,
+            list_folders,
+            create_folder,
+            rename_folder,
+            move_folder,
+            delete_folder,
+            move_credential,
+            list_credentials_by_folder,
+            list_tags,
+            get_credential_tags,
+            set_credential_tags,
+            rename_tag,
+            delete_tag,
+            list_credentials_by_tag,
+            change_password,
+            list_users,
+            rename_user,
+            delete_account,
+            export_vault,
+            import_vault,
+            import_external,
+            list_vaults,
+            create_vault,
+            rename_vault,
+            delete_vault,
+            switch_vault,
+            transfer_credentials,
+            list_vault_members,
+            invite_vault_member,
+            set_vault_member_role,
+            revoke_vault_member,
+            generate_password,
+            generate_passphrase
         ])
         .run(tauri::generate_context!())
         .expect("erro ao executar aplicação Tauri");
@@ -0,0 +1,108 @@
+//! Armazenamento em memória das sessões autenticadas do aplicativo desktop.
+//!
+//! A `Session` (com a chave do cofre) permanece exclusivamente no processo Rust.
+//! O webview recebe apenas um token opaco e aleatório, que é trocado pela sessão
+//! correspondente a cada comando invocado.
+//!
+//! A validade do token acompanha a política da própria sessão: sessões ociosas
+//! são bloqueadas (a chave do cofre é apagada, mas o token continua válido para
+//! o desbloqueio) e sessões que excederam o tempo de vida máximo são descartadas.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use base64::{engine::general_purpose, Engine as _};
+use password_manager::{
+    error::{Error, Result},
+    services::auth_service::{Session, SessionState},
+};
+use rand::{rngs::OsRng, RngCore};
+
+/// Quantidade de bytes aleatórios que compõem cada token.
+const TOKEN_LEN: usize = 32;
+
+/// Estado gerenciado pelo Tauri que associa tokens opacos a sessões ativas.
+#[derive(Default)]
+pub struct SessionStore {
+    sessions: Mutex<HashMap<String, Session>>,
+}
+
+impl SessionStore {
+    /// Registra uma sessão recém-autenticada e retorna o token que a identifica.
+    pub fn insert(&self, session: Session) -> String {
+        let mut bytes = [0u8; TOKEN_LEN];
+        OsRng.fill_bytes(&mut bytes);
+        let token = general_purpose::URL_SAFE_NO_PAD.encode(bytes);
+
+        let mut sessions = self.guard();
+        sessions.retain(|_, s| s.state() != SessionState::Expired);
+        sessions.insert(token.clone(), session);
+
+        token
+    }
+
+    /// Executa `f` com a sessão associada ao token.
+    ///
+    /// Tokens desconhecidos ou expirados resultam em [`Error::Unauthorized`]; sessões
+    /// ociosas são bloqueadas neste momento e também resultam nesse erro.
+    pub fn with_session<T>(
+        &self,
+        token: &str,
+        f: impl FnOnce(&Session) -> Result<T>,
+    ) -> Result<T> {
+        self.with_session_mut(token, |session| match Self::refresh(session) {
+            SessionState::Active => f(session),
+            SessionState::Locked => Err(Error::unauthorized("Sessão bloqueada")),
+            SessionState::Expired => Err(Error::unauthorized("Sessão expirada")),
+        })
+    }
+
+    /// Executa `f` com acesso mutável à sessão, independentemente do seu estado,
+    /// desde que o token exista e não tenha expirado.
+    ///
+    /// Utilizado pelos fluxos de bloqueio e desbloqueio.
+    pub fn with_session_mut<T>(
+        &self,
+        token: &str,
+        f: impl FnOnce(&mut Session) -> Result<T>,
+    ) -> Result<T> {
+        let mut sessions = self.guard();
+
+        let Some(session) = sessions.get_mut(token) else {
+            return Err(Error::unauthorized("Sessão inválida"));
+        };
+
+        if session.state() == SessionState::Expired {
+            sessions.remove(token);
+            return Err(Error::unauthorized("Sessão expirada"));
+        }
+
+        f(session)
+    }
+
+    /// Retorna o estado da sessão sem registrar atividade, bloqueando-a caso
+    /// o tempo de inatividade tenha sido atingido.
+    pub fn status(&self, token: &str) -> SessionState {
+        self.with_session_mut(token, |session| Ok(Self::refresh(session)))
+            .unwrap_or(SessionState::Expired)
+    }
+
+    /// Encerra a sessão associada ao token, caso exista.
+    pub fn remove(&self, token: &str) {
+        self.guard().remove(token);
+    }
+
+    /// Aplica o bloqueio efetivo (apagando a chave) quando a sessão estiver ociosa.
+    fn refresh(session: &mut Session) -> SessionState {
+        let state = session.state();
+        if state != SessionState::Active {
+            session.lock();
+        }
+        state
+    }
+
+    fn guard(&self) -> std::sync::MutexGuard<'_, HashMap<String, Session>> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
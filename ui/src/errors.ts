@@ -0,0 +1,29 @@
+/* ============================================================
+   ERROS DO BACKEND
+   Os comandos rejeitam com { code, message, field? }; a categoria
+   (code) orienta o tratamento e a mensagem já vem pronta para exibição.
+============================================================ */
+export type ErrorCode =
+  | "not_found"
+  | "unauthorized"
+  | "validation"
+  | "crypto"
+  | "storage"
+  | "conflict";
+
+export interface AppError {
+  code: ErrorCode;
+  message: string;
+  field?: string;
+}
+
+export function isAppError(err: unknown): err is AppError {
+  return (
+    typeof err === "object" && err !== null && "code" in err && "message" in err
+  );
+}
+
+/* Texto para exibição de qualquer valor rejeitado por invoke() */
+export function errorMessage(err: unknown): string {
+  return isAppError(err) ? err.message : String(err);
+}
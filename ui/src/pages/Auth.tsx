@@ -1,6 +1,7 @@
 import { useState } from "react";
 import { useNavigate } from "react-router-dom";
 import { invoke } from "@tauri-apps/api/core";
+import { errorMessage } from "../errors";
 import "./auth.css";
 
 export function Auth() {
@@ -81,8 +82,8 @@ export function Auth() {
       localStorage.setItem("session", JSON.stringify(session));
       navigate("/dashboard");
 
-    } catch (err: any) {
-      setError(String(err));
+    } catch (err) {
+      setError(errorMessage(err));
     }
   }
 
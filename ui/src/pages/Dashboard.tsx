@@ -1,6 +1,7 @@
 import { useEffect, useState, useMemo, useCallback } from "react";
 import { useOutletContext, useNavigate } from "react-router-dom";
 import { invoke } from "@tauri-apps/api/core";
+import { errorMessage, isAppError } from "../errors";
 import "./dashboard.css";
 
 export interface Credential {
@@ -8,14 +9,77 @@ export interface Credential {
   name: string;
   username?: string;
   url?: string;
+  folder_id?: string | null;
+  deleted_at?: string | null;
   updated_at: string;
 }
 
+export interface Folder {
+  id: string;
+  parent_id?: string | null;
+  name: string;
+}
+
+interface FolderSummary {
+  folder: Folder;
+  path: string;
+  depth: number;
+  credentials: number;
+}
+
+interface Tag {
+  id: string;
+  name: string;
+}
+
+interface TagSummary {
+  tag: Tag;
+  credentials: number;
+}
+
+interface Vault {
+  id: string;
+  name: string;
+}
+
+type VaultRole = "owner" | "editor" | "viewer";
+
+interface VaultSummary {
+  vault: Vault;
+  credentials: number;
+  current: boolean;
+  role: VaultRole;
+  owner: string | null;
+  members: number;
+}
+
+interface VaultMember {
+  user_id: string;
+  username: string;
+  role: VaultRole;
+}
+
+const ROLE_LABELS: Record<VaultRole, string> = {
+  owner: "dono",
+  editor: "editor",
+  viewer: "leitor",
+};
+
+/* Recorte da lista: todas, uma pasta (null = fora de pastas) ou uma tag */
+type Scope =
+  | { kind: "all" }
+  | { kind: "folder"; id: string | null }
+  | { kind: "tag"; id: string };
+
+interface SearchHit {
+  credential: Credential;
+  field: "name" | "username" | "url" | "notes";
+  score: number;
+}
+
 interface SessionDTO {
+  token: string;
   username: string;
-  vault_id: string;
-  vault_key: string;
-  passphrase: string;
 }
 
 type DetailsMode = "view" | "edit" | "new" | "loading";
@@ -43,14 +107,53 @@ function timeAgo(date: string) {
 export function Dashboard() {
   const { session } = useOutletContext<{ session: SessionDTO }>();
   const navigate = useNavigate();
+  const [username, setUsername] = useState(session.username);
+  const [accountOpen, setAccountOpen] = useState(false);
 
-  function handleLogout() {
-    localStorage.removeItem("session");
-    navigate("/");
+  async function handleLogout() {
+    try {
+      await invoke("logout_user", { token: session.token });
+    } finally {
+      localStorage.removeItem("session");
+      navigate("/");
+    }
   }
 
   const [search, setSearch] = useState("");
+  const [searchNotes, setSearchNotes] = useState(false);
+  const [searchResults, setSearchResults] = useState<Credential[] | null>(null);
   const [loading, setLoading] = useState(true);
+  const [locked, setLocked] = useState(false);
+  const [transfer, setTransfer] = useState<"export" | "import" | null>(null);
+  const [externalImport, setExternalImport] = useState(false);
+  const [organizing, setOrganizing] = useState(false);
+  const [trashOpen, setTrashOpen] = useState(false);
+  const [reportOpen, setReportOpen] = useState(false);
+  const [vaultsOpen, setVaultsOpen] = useState(false);
+
+  /* Auto-lock: consulta o estado da sessão sem contar como atividade */
+  useEffect(() => {
+    const timer = setInterval(async () => {
+      const status = await invoke<string>("session_status", {
+        token: session.token,
+      });
+
+      if (status === "locked") setLocked(true);
+      if (status === "expired") handleLogout();
+    }, 15000);
+
+    return () => clearInterval(timer);
+  }, [session]);
+
+  /* Erro "unauthorized": confirma no backend se a sessão foi bloqueada por inatividade */
+  async function sessionLocked(err: unknown): Promise<boolean> {
+    if (!isAppError(err) || err.code !== "unauthorized") return false;
+
+    const status = await invoke<string>("session_status", {
+      token: session.token,
+    });
+    return status === "locked";
+  }
 
   const [credentials, setCredentials] = useState<Credential[]>([]);
   const [selected, setSelected] = useState<Credential | null>(null);
@@ -62,30 +165,118 @@ export function Dashboard() {
     "all"
   );
 
+  /* PASTAS E TAGS */
+  const [folders, setFolders] = useState<FolderSummary[]>([]);
+  const [tags, setTags] = useState<TagSummary[]>([]);
+  const [scope, setScope] = useState<Scope>({ kind: "all" });
+  const [scopeIds, setScopeIds] = useState<Set<string> | null>(null);
+
+  /* COFRES DA CONTA */
+  const [vaults, setVaults] = useState<VaultSummary[]>([]);
+
   /* Load credentials */
   const loadCredentials = useCallback(async () => {
     try {
       setLoading(true);
-      const list = await invoke<Credential[]>("list_credentials", { session });
+      const list = await invoke<Credential[]>("list_credentials", {
+        token: session.token,
+      });
       setCredentials(list);
+
+      const [folderList, tagList, vaultList] = await Promise.all([
+        invoke<FolderSummary[]>("list_folders", { token: session.token }),
+        invoke<TagSummary[]>("list_tags", { token: session.token }),
+        invoke<VaultSummary[]>("list_vaults", { token: session.token }),
+      ]);
+      setFolders(folderList);
+      setTags(tagList);
+      setVaults(vaultList);
+    } catch (err) {
+      if (await sessionLocked(err)) {
+        setLocked(true);
+        return;
+      }
+
+      // token desconhecido ou expirado no backend: volta ao login
+      localStorage.removeItem("session");
+      navigate("/");
     } finally {
       setLoading(false);
     }
-  }, [session]);
+  }, [session, navigate]);
 
   useEffect(() => {
     loadCredentials();
   }, [loadCredentials]);
 
+  /* SEARCH: nome, usuário e URL (e notas, se marcado), por relevância */
+  useEffect(() => {
+    const query = search.trim();
+    if (!query) {
+      setSearchResults(null);
+      return;
+    }
+
+    const timer = setTimeout(async () => {
+      try {
+        const hits = await invoke<SearchHit[]>("search_credentials", {
+          token: session.token,
+          query,
+          options: { include_notes: searchNotes, fuzzy: true },
+        });
+        setSearchResults(hits.map((h) => h.credential));
+      } catch (err) {
+        if (await sessionLocked(err)) setLocked(true);
+      }
+    }, 200);
+
+    return () => clearTimeout(timer);
+  }, [search, searchNotes, credentials, session]);
+
+  /* PASTA OU TAG SELECIONADA: IDs das credenciais do recorte */
+  useEffect(() => {
+    if (scope.kind === "all") {
+      setScopeIds(null);
+      return;
+    }
+
+    const current = scope;
+    let cancelled = false;
+
+    async function loadScope() {
+      try {
+        const list =
+          current.kind === "folder"
+            ? await invoke<Credential[]>("list_credentials_by_folder", {
+                token: session.token,
+                folderId: current.id,
+                includeSubfolders: current.id !== null,
+              })
+            : await invoke<Credential[]>("list_credentials_by_tag", {
+                token: session.token,
+                id: current.id,
+              });
+
+        if (!cancelled) setScopeIds(new Set(list.map((c) => c.id)));
+      } catch (err) {
+        if (await sessionLocked(err)) setLocked(true);
+        // pasta ou tag removida: volta para todas as credenciais
+        else if (!cancelled) setScope({ kind: "all" });
+      }
+    }
+
+    loadScope();
+
+    return () => {
+      cancelled = true;
+    };
+  }, [scope, credentials, session]);
+
   /* FILTER + SORT + SEARCH */
   const filtered = useMemo(() => {
-    let list = [...credentials];
+    let list = [...(searchResults ?? credentials)];
 
-    if (search.trim()) {
-      list = list.filter((c) =>
-        c.name.toLowerCase().includes(search.toLowerCase())
-      );
-    }
+    if (scopeIds) list = list.filter((c) => scopeIds.has(c.id));
 
     switch (filter) {
       case "hasUser":
@@ -99,6 +290,9 @@ export function Dashboard() {
         break;
     }
 
+    // resultados de pesquisa já vêm ordenados por relevância
+    if (searchResults) return list;
+
     if (sort === "az") {
       list.sort((a, b) => a.name.localeCompare(b.name));
     } else if (sort === "recent") {
@@ -109,7 +303,7 @@ export function Dashboard() {
     }
 
     return list;
-  }, [credentials, search, filter, sort]);
+  }, [credentials, searchResults, scopeIds, filter, sort]);
 
   /* Select */
   function handleSelect(cred: Credential) {
@@ -121,11 +315,28 @@ export function Dashboard() {
     }, 220);
   }
 
+  const readOnlyVault = vaults.find((v) => v.current)?.role === "viewer";
+
   function handleNew() {
     setSelected(null);
     setMode("new");
   }
 
+  /* Troca o cofre aberto; pastas e tags pertencem a cada cofre */
+  async function handleSwitchVault(id: string) {
+    try {
+      await invoke("switch_vault", { token: session.token, id });
+    } catch (err) {
+      if (await sessionLocked(err)) setLocked(true);
+      return;
+    }
+    setSelected(null);
+    setMode("view");
+    setScope({ kind: "all" });
+    setSearch("");
+    await loadCredentials();
+  }
+
   return (
     <div className="dash-shell">
       <header className="dash-topbar">
@@ -135,12 +346,45 @@ export function Dashboard() {
         </div>
 
         <div className="dash-topbar-right">
-          <div className="topbar-user">
+          <button
+            className="topbar-user topbar-user-btn"
+            onClick={() => setAccountOpen(true)}
+            title="Conta"
+          >
             <div className="topbar-avatar">
-              {session.username.charAt(0).toUpperCase()}
+              {username.charAt(0).toUpperCase()}
             </div>
-            <span className="topbar-username">{session.username}</span>
-          </div>
+            <span className="topbar-username">{username}</span>
+          </button>
+
+          {/* BLOQUEAR */}
+          <button
+            className="topbar-logout-btn"
+            onClick={async () => {
+              await invoke("lock_session", { token: session.token });
+              setLocked(true);
+            }}
+            title="Bloquear"
+          >
+            <svg viewBox="0 0 24 24" className="icon-svg">
+              <rect
+                x="5"
+                y="11"
+                width="14"
+                height="9"
+                rx="2"
+                strokeWidth="1.7"
+                stroke="currentColor"
+                fill="none"
+              />
+              <path
+                d="M8 11V8a4 4 0 0 1 8 0v3"
+                strokeWidth="1.7"
+                stroke="currentColor"
+                fill="none"
+              />
+            </svg>
+          </button>
 
           {/* LOGOUT */}
           <button
@@ -184,17 +428,130 @@ export function Dashboard() {
               <p className="dash-panel-subtitle">Gerencie suas credenciais.</p>
             </div>
 
-            <button className="dash-btn-primary" onClick={handleNew}>
+            {vaults.length > 0 && (
+              <select
+                className="dash-vault-select"
+                value={vaults.find((v) => v.current)?.vault.id ?? ""}
+                onChange={(e) => handleSwitchVault(e.target.value)}
+                title="Cofre aberto"
+              >
+                {vaults.map((v) => (
+                  <option key={v.vault.id} value={v.vault.id}>
+                    {v.vault.name} ({v.credentials})
+                    {v.owner ? ` · ${v.owner}` : ""}
+                    {v.role === "viewer" ? " · leitura" : ""}
+                  </option>
+                ))}
+              </select>
+            )}
+
+            <button
+              className="dash-btn-primary"
+              onClick={handleNew}
+              disabled={readOnlyVault}
+              title={readOnlyVault ? "Acesso somente leitura a este cofre" : undefined}
+            >
               <span className="btn-plus">+</span>
               Nova credencial
             </button>
 
             <nav className="dash-nav">
-              <button className="dash-nav-item active">
+              <button
+                className={`dash-nav-item ${scope.kind === "all" ? "active" : ""}`}
+                onClick={() => setScope({ kind: "all" })}
+              >
                 <span className="nav-dot" />
                 Todas as credenciais
               </button>
+              <button
+                className="dash-nav-item"
+                onClick={() => setTransfer("export")}
+              >
+                Exportar cofre
+              </button>
+              <button
+                className="dash-nav-item"
+                onClick={() => setTransfer("import")}
+              >
+                Importar cofre
+              </button>
+              <button
+                className="dash-nav-item"
+                onClick={() => setExternalImport(true)}
+              >
+                Importar de outro gerenciador
+              </button>
+              <button
+                className="dash-nav-item"
+                onClick={() => setOrganizing(true)}
+              >
+                Organizar pastas e tags
+              </button>
+              <button
+                className="dash-nav-item"
+                onClick={() => setVaultsOpen(true)}
+              >
+                Gerenciar cofres
+              </button>
+              <button
+                className="dash-nav-item"
+                onClick={() => setTrashOpen(true)}
+              >
+                Lixeira
+              </button>
+              <button
+                className="dash-nav-item"
+                onClick={() => setReportOpen(true)}
+              >
+                Relatório de segurança
+              </button>
+            </nav>
+
+            <nav className="dash-nav">
+              <span className="dash-nav-title">Pastas</span>
+              <button
+                className={`dash-nav-item ${
+                  scope.kind === "folder" && scope.id === null ? "active" : ""
+                }`}
+                onClick={() => setScope({ kind: "folder", id: null })}
+              >
+                Sem pasta
+              </button>
+              {folders.map((f) => (
+                <button
+                  key={f.folder.id}
+                  className={`dash-nav-item ${
+                    scope.kind === "folder" && scope.id === f.folder.id
+                      ? "active"
+                      : ""
+                  }`}
+                  style={{ paddingLeft: 10 + f.depth * 14 }}
+                  title={f.path}
+                  onClick={() => setScope({ kind: "folder", id: f.folder.id })}
+                >
+                  {f.folder.name}
+                  <span className="dash-nav-count">{f.credentials}</span>
+                </button>
+              ))}
             </nav>
+
+            {tags.length > 0 && (
+              <nav className="dash-nav">
+                <span className="dash-nav-title">Tags</span>
+                {tags.map((t) => (
+                  <button
+                    key={t.tag.id}
+                    className={`dash-nav-item ${
+                      scope.kind === "tag" && scope.id === t.tag.id ? "active" : ""
+                    }`}
+                    onClick={() => setScope({ kind: "tag", id: t.tag.id })}
+                  >
+                    #{t.tag.name}
+                    <span className="dash-nav-count">{t.credentials}</span>
+                  </button>
+                ))}
+              </nav>
+            )}
           </div>
         </aside>
 
@@ -233,6 +590,14 @@ export function Dashboard() {
                 onChange={(e) => setSearch(e.target.value)}
               />
             </div>
+            <label className="dash-search-notes">
+              <input
+                type="checkbox"
+                checked={searchNotes}
+                onChange={(e) => setSearchNotes(e.target.checked)}
+              />
+              Pesquisar também nas notas
+            </label>
 
             {/* SORT + FILTER PREMIUM */}
             <div className="dash-filters-premium">
@@ -379,6 +744,10 @@ export function Dashboard() {
             <Details
               session={session}
               credential={null}
+              folders={folders}
+              defaultFolderId={
+                scope.kind === "folder" ? scope.id : null
+              }
               mode="new"
               onRefresh={loadCredentials}
               onClose={() => {
@@ -401,6 +770,8 @@ export function Dashboard() {
             <Details
               session={session}
               credential={selected}
+              folders={folders}
+              vaults={vaults}
               mode={mode}
               onRefresh={loadCredentials}
               onClose={() => {
@@ -412,6 +783,91 @@ export function Dashboard() {
           )}
         </section>
       </div>
+
+      {transfer && (
+        <VaultTransferModal
+          token={session.token}
+          mode={transfer}
+          onDone={() => {
+            if (transfer === "import") loadCredentials();
+          }}
+          onClose={() => setTransfer(null)}
+        />
+      )}
+
+      {organizing && (
+        <OrganizeModal
+          token={session.token}
+          folders={folders}
+          tags={tags}
+          onChanged={loadCredentials}
+          onClose={() => setOrganizing(false)}
+        />
+      )}
+
+      {vaultsOpen && (
+        <VaultsModal
+          token={session.token}
+          vaults={vaults}
+          onChanged={loadCredentials}
+          onClose={() => setVaultsOpen(false)}
+        />
+      )}
+
+      {trashOpen && (
+        <TrashModal
+          token={session.token}
+          onChanged={loadCredentials}
+          onClose={() => setTrashOpen(false)}
+        />
+      )}
+
+      {reportOpen && (
+        <SecurityReportModal
+          token={session.token}
+          onSelect={(cred) => {
+            setReportOpen(false);
+            handleSelect(cred);
+          }}
+          onClose={() => setReportOpen(false)}
+        />
+      )}
+
+      {externalImport && (
+        <ExternalImportModal
+          token={session.token}
+          onDone={loadCredentials}
+          onClose={() => setExternalImport(false)}
+        />
+      )}
+
+      {accountOpen && (
+        <AccountModal
+          token={session.token}
+          username={username}
+          onRenamed={(name) => {
+            setUsername(name);
+            localStorage.setItem(
+              "session",
+              JSON.stringify({ ...session, username: name })
+            );
+          }}
+          onDeleted={() => {
+            localStorage.removeItem("session");
+            navigate("/");
+          }}
+          onClose={() => setAccountOpen(false)}
+        />
+      )}
+
+      {locked && (
+        <UnlockModal
+          token={session.token}
+          username={username}
+          onUnlocked={() => setLocked(false)}
+          onLogout={handleLogout}
+        />
+      )}
     </div>
   );
 }
@@ -475,12 +931,13 @@ function ConfirmDeleteModal({
   return (
     <div className="dash-modal-backdrop">
       <div className="dash-modal">
-        <h3 className="dash-modal-title">Excluir credencial?</h3>
+        <h3 className="dash-modal-title">Mover para a lixeira?</h3>
 
         <p className="dash-modal-text">
           A credencial{" "}
           {credentialName ? <strong>{credentialName}</strong> : "selecionada"}{" "}
-          será removida do seu cofre. Esta ação não pode ser desfeita.
+          será movida para a lixeira, de onde pode ser restaurada até ser
+          excluída definitivamente.
         </p>
 
         <div className="dash-modal-footer">
@@ -505,404 +962,2334 @@ function ConfirmDeleteModal({
 }
 
 /* ============================================================
-   DETAILS COMPONENT
+   MODAL EXPORTAR / IMPORTAR COFRE
 ============================================================ */
-interface DetailsProps {
-  session: SessionDTO;
-  credential: Credential | null;
-  mode: DetailsMode;
-  onRefresh: () => void;
-  onClose: () => void;
-  onEditMode: () => void;
+interface ImportSummary {
+  imported: number;
+  exported_by: string;
+  exported_at: string;
 }
 
-function Details({
-  session,
-  credential,
-  mode,
-  onRefresh,
-  onClose,
-  onEditMode,
-}: DetailsProps) {
-  const isNew = mode === "new";
-  const isEditing = mode === "edit";
-
-  const [loading, setLoading] = useState(!isNew);
+interface VaultTransferModalProps {
+  token: string;
+  mode: "export" | "import";
+  onDone: () => void;
+  onClose: () => void;
+}
 
-  const [name, setName] = useState(credential?.name ?? "");
-  const [username, setUsername] = useState(credential?.username ?? "");
-  const [url, setUrl] = useState(credential?.url ?? "");
-  const [password, setPassword] = useState("");
-  const [notes, setNotes] = useState("");
-  const [showPassword, setShowPassword] = useState(false);
-  const [showDeleteModal, setShowDeleteModal] = useState(false);
+function VaultTransferModal({ token, mode, onDone, onClose }: VaultTransferModalProps) {
+  const isExport = mode === "export";
 
-  useEffect(() => {
-    if (isNew || !credential) return;
+  const [path, setPath] = useState("");
+  const [passphrase, setPassphrase] = useState("");
+  const [confirm, setConfirm] = useState("");
+  const [error, setError] = useState("");
+  const [result, setResult] = useState("");
+  const [busy, setBusy] = useState(false);
 
-    let cancelled = false;
-    const credentialId = credential.id;
+  async function handleSubmit() {
+    if (isExport && passphrase !== confirm) {
+      setError("As senhas não coincidem.");
+      return;
+    }
 
-    async function load() {
-      setLoading(true);
-      try {
-        const full = await invoke<{
-          password: string | null;
-          notes: string | null;
-        }>(
-          "get_credential_full",
-          { session, id: credentialId }
+    setBusy(true);
+    setError("");
+    try {
+      if (isExport) {
+        await invoke("export_vault", { token, path, passphrase });
+        setResult(`Cofre exportado para ${path}.`);
+      } else {
+        const summary = await invoke<ImportSummary>("import_vault", {
+          token,
+          path,
+          passphrase,
+        });
+        setResult(
+          `${summary.imported} credencial(is) importada(s), exportadas por ` +
+            `${summary.exported_by} em ${new Date(summary.exported_at).toLocaleString()}.`
         );
-
-        if (!cancelled) {
-          setPassword(full.password ?? "");
-          setNotes(full.notes ?? "");
-        }
-      } finally {
-        if (!cancelled) setLoading(false);
       }
+      setPassphrase("");
+      setConfirm("");
+      onDone();
+    } catch (err) {
+      setError(errorMessage(err));
+    } finally {
+      setBusy(false);
     }
+  }
 
-    load();
-
-    return () => {
-      cancelled = true;
-    };
-  }, [credential, session, isNew]);
+  return (
+    <div className="dash-modal-backdrop">
+      <div className="dash-modal">
+        <h3 className="dash-modal-title">
+          {isExport ? "Exportar cofre" : "Importar cofre"}
+        </h3>
 
-  const readOnly = !isNew && !isEditing;
+        {result ? (
+          <p className="dash-modal-text">{result}</p>
+        ) : (
+          <>
+            <p className="dash-modal-text">
+              {isExport
+                ? "Todas as credenciais serão gravadas em um arquivo cifrado. Guarde a senha do arquivo: sem ela não é possível restaurá-lo."
+                : "As credenciais do arquivo serão adicionadas ao seu cofre."}
+            </p>
+
+            <input
+              className="dash-modal-input"
+              placeholder="Caminho do arquivo"
+              value={path}
+              autoFocus
+              onChange={(e) => setPath(e.target.value)}
+            />
+            <input
+              type="password"
+              className="dash-modal-input"
+              placeholder="Senha do arquivo"
+              value={passphrase}
+              onChange={(e) => setPassphrase(e.target.value)}
+            />
+            {isExport && (
+              <input
+                type="password"
+                className="dash-modal-input"
+                placeholder="Confirme a senha do arquivo"
+                value={confirm}
+                onChange={(e) => setConfirm(e.target.value)}
+              />
+            )}
+          </>
+        )}
 
-  async function handleSave() {
-    const payload = {
-      session,
-      name,
-      username: username || null,
-      url: url || null,
-      password: password || null,
-      notes: notes || null,
-    };
+        {error && <p className="dash-modal-error">{error}</p>}
 
-    if (isNew) {
-      await invoke("create_credential", payload);
-    } else if (credential) {
-      await invoke("update_credential", { ...payload, id: credential.id });
-    }
+        <div className="dash-modal-footer">
+          <button type="button" className="modal-btn" onClick={onClose}>
+            {result ? "Fechar" : "Cancelar"}
+          </button>
+          {!result && (
+            <button
+              type="button"
+              className="modal-btn modal-btn-primary"
+              disabled={busy || !path || !passphrase}
+              onClick={handleSubmit}
+            >
+              {isExport ? "Exportar" : "Importar"}
+            </button>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
 
-    await onRefresh();
-    onClose();
-  }
+/* ============================================================
+   MODAL CONTA (renomear usuário ou excluir a conta)
+============================================================ */
+interface AccountModalProps {
+  token: string;
+  username: string;
+  onRenamed: (username: string) => void;
+  onDeleted: () => void;
+  onClose: () => void;
+}
 
-  function handleDeleteClick() {
-    if (!credential) return;
-    setShowDeleteModal(true);
+function AccountModal({
+  token,
+  username,
+  onRenamed,
+  onDeleted,
+  onClose,
+}: AccountModalProps) {
+  const [action, setAction] = useState<"rename" | "delete">("rename");
+  const [newUsername, setNewUsername] = useState(username);
+  const [confirmName, setConfirmName] = useState("");
+  const [password, setPassword] = useState("");
+  const [error, setError] = useState("");
+  const [busy, setBusy] = useState(false);
+
+  function switchTo(next: "rename" | "delete") {
+    setAction(next);
+    setPassword("");
+    setConfirmName("");
+    setError("");
   }
 
-  async function handleConfirmDelete() {
-    if (!credential) return;
-
-    setShowDeleteModal(false);
-    await invoke("delete_credential", { session, id: credential.id });
-    await onRefresh();
-    onClose();
+  async function handleRename() {
+    setBusy(true);
+    setError("");
+    try {
+      const name = await invoke<string>("rename_user", {
+        token,
+        password,
+        newUsername,
+      });
+      onRenamed(name);
+      onClose();
+    } catch (err) {
+      setError(errorMessage(err));
+    } finally {
+      setBusy(false);
+    }
   }
 
-  async function copy(val: string) {
-    if (val) await navigator.clipboard.writeText(val);
+  async function handleDelete() {
+    if (confirmName !== username) {
+      setError("Digite o nome de usuário para confirmar.");
+      return;
+    }
+    setBusy(true);
+    setError("");
+    try {
+      await invoke("delete_account", { token, password });
+      onDeleted();
+    } catch (err) {
+      setError(errorMessage(err));
+      setBusy(false);
+    }
   }
 
   return (
-    <>
-      <div className="details-card">
+    <div className="dash-modal-backdrop">
+      <div className="dash-modal">
+        <h3 className="dash-modal-title">Conta</h3>
+
+        <div className="account-tabs">
+          <button
+            type="button"
+            className={`account-tab ${action === "rename" ? "active" : ""}`}
+            onClick={() => switchTo("rename")}
+          >
+            Renomear
+          </button>
+          <button
+            type="button"
+            className={`account-tab ${action === "delete" ? "active" : ""}`}
+            onClick={() => switchTo("delete")}
+          >
+            Excluir conta
+          </button>
+        </div>
+
+        {action === "rename" ? (
+          <>
+            <input
+              className="dash-modal-input"
+              placeholder="Novo nome de usuário"
+              value={newUsername}
+              autoFocus
+              onChange={(e) => setNewUsername(e.target.value)}
+            />
+            <input
+              type="password"
+              className="dash-modal-input"
+              placeholder="Senha mestre"
+              value={password}
+              onChange={(e) => setPassword(e.target.value)}
+              onKeyDown={(e) => e.key === "Enter" && handleRename()}
+            />
+          </>
+        ) : (
+          <>
+            <p className="dash-modal-text account-warning">
+              Todos os cofres, credenciais e vínculos de <strong>{username}</strong>{" "}
+              serão apagados. Os membros dos seus cofres compartilhados perderão
+              o acesso. Esta ação não pode ser desfeita.
+            </p>
+            <input
+              className="dash-modal-input"
+              placeholder={`Digite "${username}" para confirmar`}
+              value={confirmName}
+              onChange={(e) => setConfirmName(e.target.value)}
+            />
+            <input
+              type="password"
+              className="dash-modal-input"
+              placeholder="Senha mestre"
+              value={password}
+              onChange={(e) => setPassword(e.target.value)}
+              onKeyDown={(e) => e.key === "Enter" && handleDelete()}
+            />
+          </>
+        )}
+
+        {error && <p className="dash-modal-error">{error}</p>}
+
+        <div className="dash-modal-footer">
+          <button type="button" className="modal-btn" onClick={onClose}>
+            Cancelar
+          </button>
+          {action === "rename" ? (
+            <button
+              type="button"
+              className="modal-btn modal-btn-primary"
+              disabled={busy || !password || !newUsername.trim()}
+              onClick={handleRename}
+            >
+              Salvar
+            </button>
+          ) : (
+            <button
+              type="button"
+              className="modal-btn modal-btn-danger"
+              disabled={busy || !password || confirmName !== username}
+              onClick={handleDelete}
+            >
+              Excluir conta
+            </button>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
+
+/* ============================================================
+   MODAL DESBLOQUEIO (sessão bloqueada por inatividade)
+============================================================ */
+interface UnlockModalProps {
+  token: string;
+  username: string;
+  onUnlocked: () => void;
+  onLogout: () => void;
+}
+
+function UnlockModal({ token, username, onUnlocked, onLogout }: UnlockModalProps) {
+  const [password, setPassword] = useState("");
+  const [error, setError] = useState("");
+
+  async function handleUnlock() {
+    try {
+      await invoke("unlock_session", { token, password });
+      setPassword("");
+      onUnlocked();
+    } catch (err) {
+      setError(errorMessage(err));
+    }
+  }
+
+  return (
+    <div className="dash-modal-backdrop">
+      <div className="dash-modal">
+        <h3 className="dash-modal-title">Cofre bloqueado</h3>
+
+        <p className="dash-modal-text">
+          Informe a senha mestre de <strong>{username}</strong> para continuar
+          de onde parou.
+        </p>
+
+        <input
+          type="password"
+          className="dash-modal-input"
+          value={password}
+          autoFocus
+          onChange={(e) => setPassword(e.target.value)}
+          onKeyDown={(e) => e.key === "Enter" && handleUnlock()}
+        />
+
+        {error && <p className="dash-modal-error">{error}</p>}
+
+        <div className="dash-modal-footer">
+          <button type="button" className="modal-btn" onClick={onLogout}>
+            Sair
+          </button>
+          <button
+            type="button"
+            className="modal-btn modal-btn-primary"
+            onClick={handleUnlock}
+          >
+            Desbloquear
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
+
+/* ============================================================
+   PASSWORD GENERATOR
+============================================================ */
+interface GeneratedPassword {
+  value: string;
+  entropy_bits: number;
+}
+
+type GeneratorKind = "password" | "passphrase";
+
+interface PasswordGeneratorPanelProps {
+  onUse: (value: string) => void;
+  onClose: () => void;
+}
+
+function PasswordGeneratorPanel({ onUse, onClose }: PasswordGeneratorPanelProps) {
+  const [kind, setKind] = useState<GeneratorKind>("password");
+  const [length, setLength] = useState(20);
+  const [words, setWords] = useState(6);
+  const [lowercase, setLowercase] = useState(true);
+  const [uppercase, setUppercase] = useState(true);
+  const [digits, setDigits] = useState(true);
+  const [symbols, setSymbols] = useState(true);
+  const [excludeAmbiguous, setExcludeAmbiguous] = useState(false);
+
+  const [generated, setGenerated] = useState<GeneratedPassword | null>(null);
+  const [error, setError] = useState("");
+
+  const generate = useCallback(async () => {
+    try {
+      const result =
+        kind === "password"
+          ? await invoke<GeneratedPassword>("generate_password", {
+              policy: {
+                length,
+                lowercase,
+                uppercase,
+                digits,
+                symbols,
+                exclude_ambiguous: excludeAmbiguous,
+              },
+            })
+          : await invoke<GeneratedPassword>("generate_passphrase", {
+              policy: { words },
+            });
+
+      setGenerated(result);
+      setError("");
+    } catch (err) {
+      setGenerated(null);
+      setError(errorMessage(err));
+    }
+  }, [kind, length, words, lowercase, uppercase, digits, symbols, excludeAmbiguous]);
+
+  useEffect(() => {
+    generate();
+  }, [generate]);
+
+  const classOptions: [string, boolean, (v: boolean) => void][] = [
+    ["Minúsculas", lowercase, setLowercase],
+    ["Maiúsculas", uppercase, setUppercase],
+    ["Números", digits, setDigits],
+    ["Símbolos", symbols, setSymbols],
+    ["Evitar ambíguos", excludeAmbiguous, setExcludeAmbiguous],
+  ];
+
+  return (
+    <div className="generator-panel">
+      <div className="generator-kinds">
+        <button
+          type="button"
+          className={`filter-pill ${kind === "password" ? "active" : ""}`}
+          onClick={() => setKind("password")}
+        >
+          Caracteres
+        </button>
+        <button
+          type="button"
+          className={`filter-pill ${kind === "passphrase" ? "active" : ""}`}
+          onClick={() => setKind("passphrase")}
+        >
+          Frase secreta
+        </button>
+      </div>
+
+      {kind === "password" ? (
+        <>
+          <label className="generator-range">
+            Comprimento: {length}
+            <input
+              type="range"
+              min={4}
+              max={64}
+              value={length}
+              onChange={(e) => setLength(Number(e.target.value))}
+            />
+          </label>
+
+          <div className="generator-options">
+            {classOptions.map(([label, checked, setChecked]) => (
+              <label key={label} className="generator-option">
+                <input
+                  type="checkbox"
+                  checked={checked}
+                  onChange={(e) => setChecked(e.target.checked)}
+                />
+                {label}
+              </label>
+            ))}
+          </div>
+        </>
+      ) : (
+        <label className="generator-range">
+          Palavras: {words}
+          <input
+            type="range"
+            min={3}
+            max={12}
+            value={words}
+            onChange={(e) => setWords(Number(e.target.value))}
+          />
+        </label>
+      )}
+
+      {generated && (
+        <div className="generator-preview">
+          <code>{generated.value}</code>
+          <span className="generator-entropy">
+            ~{Math.round(generated.entropy_bits)} bits
+          </span>
+        </div>
+      )}
+
+      {error && <p className="dash-modal-error">{error}</p>}
+
+      <div className="generator-footer">
+        <button type="button" className="btn-secondary" onClick={onClose}>
+          Cancelar
+        </button>
+        <button type="button" className="btn-secondary" onClick={generate}>
+          Gerar novamente
+        </button>
+        <button
+          type="button"
+          className="btn-primary"
+          disabled={!generated}
+          onClick={() => generated && onUse(generated.value)}
+        >
+          Usar
+        </button>
+      </div>
+    </div>
+  );
+}
+
+/* ============================================================
+   TOTP
+============================================================ */
+interface TotpCode {
+  code: string;
+  remaining_seconds: number;
+  period: number;
+}
+
+function TotpDisplay({
+  token,
+  credentialId,
+  onCopy,
+}: {
+  token: string;
+  credentialId: string;
+  onCopy: (value: string) => void;
+}) {
+  const [totp, setTotp] = useState<TotpCode | null>(null);
+  const [error, setError] = useState("");
+
+  useEffect(() => {
+    let cancelled = false;
+
+    async function refresh() {
+      try {
+        const code = await invoke<TotpCode | null>("get_totp_code", {
+          token,
+          id: credentialId,
+        });
+        if (!cancelled) {
+          setTotp(code);
+          setError("");
+        }
+      } catch (err) {
+        if (!cancelled) setError(errorMessage(err));
+      }
+    }
+
+    refresh();
+    const timer = setInterval(refresh, 1000);
+
+    return () => {
+      cancelled = true;
+      clearInterval(timer);
+    };
+  }, [token, credentialId]);
+
+  if (error) return <p className="dash-modal-error">{error}</p>;
+  if (!totp) return null;
+
+  return (
+    <div className="totp-display">
+      <code className="totp-code">{totp.code}</code>
+      <span className="totp-remaining">{totp.remaining_seconds}s</span>
+      <div className="totp-bar">
+        <div
+          className="totp-bar-fill"
+          style={{ width: `${(totp.remaining_seconds / totp.period) * 100}%` }}
+        />
+      </div>
+      <button
+        className="icon-button"
+        onClick={() => onCopy(totp.code)}
+        title="Copiar código"
+      >
+        <svg viewBox="0 0 24 24" className="icon-svg">
+          <rect
+            x="9"
+            y="9"
+            width="11"
+            height="11"
+            rx="2"
+            fill="none"
+            strokeWidth="1.6"
+            stroke="currentColor"
+          />
+          <rect
+            x="4"
+            y="4"
+            width="11"
+            height="11"
+            rx="2"
+            fill="none"
+            strokeWidth="1.6"
+            stroke="currentColor"
+          />
+        </svg>
+      </button>
+    </div>
+  );
+}
+
+/* ============================================================
+   DETAILS COMPONENT
+============================================================ */
+type CopyField = "username" | "password" | "totp";
+
+interface DetailsProps {
+  session: SessionDTO;
+  credential: Credential | null;
+  folders: FolderSummary[];
+  vaults?: VaultSummary[];
+  defaultFolderId?: string | null;
+  mode: DetailsMode;
+  onRefresh: () => void;
+  onClose: () => void;
+  onEditMode: () => void;
+}
+
+function Details({
+  session,
+  credential,
+  folders,
+  vaults = [],
+  defaultFolderId = null,
+  mode,
+  onRefresh,
+  onClose,
+  onEditMode,
+}: DetailsProps) {
+  const isNew = mode === "new";
+  const isEditing = mode === "edit";
+
+  const [loading, setLoading] = useState(!isNew);
+
+  const [name, setName] = useState(credential?.name ?? "");
+  const [username, setUsername] = useState(credential?.username ?? "");
+  const [url, setUrl] = useState(credential?.url ?? "");
+  const [password, setPassword] = useState("");
+  const [notes, setNotes] = useState("");
+  const [totp, setTotp] = useState("");
+  const [loadedTotp, setLoadedTotp] = useState("");
+  const [folderId, setFolderId] = useState<string | null>(
+    credential ? credential.folder_id ?? null : defaultFolderId
+  );
+  const [tagNames, setTagNames] = useState("");
+  const [loadedTags, setLoadedTags] = useState("");
+  const [showPassword, setShowPassword] = useState(false);
+  const [showDeleteModal, setShowDeleteModal] = useState(false);
+  const [showGenerator, setShowGenerator] = useState(false);
+  const [breaches, setBreaches] = useState<number | null>(null);
+  const [copyNotice, setCopyNotice] = useState("");
+  const [targetVault, setTargetVault] = useState("");
+  const [transferError, setTransferError] = useState("");
+
+  const otherVaults = vaults.filter((v) => !v.current);
+
+  /* Senha digitada consta na base local de senhas vazadas? (somente aviso) */
+  useEffect(() => {
+    if ((!isNew && !isEditing) || !password) {
+      setBreaches(null);
+      return;
+    }
+
+    let cancelled = false;
+    const timer = setTimeout(async () => {
+      try {
+        const count = await invoke<number | null>("check_password_breach", {
+          token: session.token,
+          password,
+        });
+        if (!cancelled) setBreaches(count);
+      } catch {
+        if (!cancelled) setBreaches(null);
+      }
+    }, 400);
+
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [password, isNew, isEditing, session]);
+
+  useEffect(() => {
+    if (isNew || !credential) return;
+
+    let cancelled = false;
+    const credentialId = credential.id;
+
+    async function load() {
+      setLoading(true);
+      try {
+        const full = await invoke<{
+          password: string | null;
+          notes: string | null;
+          totp: string | null;
+        }>(
+          "get_credential_full",
+          { token: session.token, id: credentialId }
+        );
+        const credentialTags = await invoke<Tag[]>("get_credential_tags", {
+          token: session.token,
+          id: credentialId,
+        });
+
+        if (!cancelled) {
+          const names = credentialTags.map((t) => t.name).join(", ");
+          setTagNames(names);
+          setLoadedTags(names);
+          setPassword(full.password ?? "");
+          setNotes(full.notes ?? "");
+          setTotp(full.totp ?? "");
+          setLoadedTotp(full.totp ?? "");
+        }
+      } finally {
+        if (!cancelled) setLoading(false);
+      }
+    }
+
+    load();
+
+    return () => {
+      cancelled = true;
+    };
+  }, [credential, session, isNew]);
+
+  const readOnly = !isNew && !isEditing;
+
+  async function handleSave() {
+    const payload = {
+      token: session.token,
+      name,
+      username: username || null,
+      url: url || null,
+      password: password || null,
+      notes: notes || null,
+    };
+
+    const tagList = tagNames
+      .split(",")
+      .map((t) => t.trim())
+      .filter(Boolean);
+
+    if (isNew) {
+      await invoke("create_credential", {
+        ...payload,
+        totp: totp || null,
+        folderId,
+        tags: tagList,
+      });
+    } else if (credential) {
+      await invoke("update_credential", { ...payload, id: credential.id });
+
+      if (folderId !== (credential.folder_id ?? null)) {
+        await invoke("move_credential", {
+          token: session.token,
+          id: credential.id,
+          folderId,
+        });
+      }
+
+      if (tagNames !== loadedTags) {
+        await invoke("set_credential_tags", {
+          token: session.token,
+          id: credential.id,
+          tags: tagList,
+        });
+      }
+
+      if (totp !== loadedTotp) {
+        await invoke("set_credential_totp", {
+          token: session.token,
+          id: credential.id,
+          otpauth: totp || null,
+        });
+      }
+    }
+
+    await onRefresh();
+    onClose();
+  }
+
+  function handleDeleteClick() {
+    if (!credential) return;
+    setShowDeleteModal(true);
+  }
+
+  async function handleConfirmDelete() {
+    if (!credential) return;
+
+    setShowDeleteModal(false);
+    await invoke("delete_credential", {
+      token: session.token,
+      id: credential.id,
+    });
+    await onRefresh();
+    onClose();
+  }
+
+  /* Move (some deste cofre) ou copia a credencial para outro cofre */
+  async function handleTransfer(transferMode: "move" | "copy") {
+    if (!credential || !targetVault) return;
+
+    setTransferError("");
+    try {
+      await invoke("transfer_credentials", {
+        token: session.token,
+        ids: [credential.id],
+        targetVaultId: targetVault,
+        mode: transferMode,
+      });
+    } catch (err) {
+      setTransferError(errorMessage(err));
+      return;
+    }
+
+    await onRefresh();
+    if (transferMode === "move") onClose();
+    else {
+      setCopyNotice("Cópia criada no outro cofre.");
+      setTimeout(() => setCopyNotice(""), 4000);
+    }
+  }
+
+  // O valor é lido e copiado no backend; `fallback` só é usado quando o
+  // sistema não tem área de transferência.
+  async function copy(field: CopyField, fallback: string) {
+    if (!credential || !fallback) return;
+
+    try {
+      const clearAfter = await invoke<number | null>("copy_to_clipboard", {
+        token: session.token,
+        id: credential.id,
+        field,
+      });
+      setCopyNotice(
+        clearAfter
+          ? `Copiado. A área de transferência será limpa em ${clearAfter}s.`
+          : "Copiado."
+      );
+    } catch {
+      // Sem área de transferência no sistema: recorre à do webview, sem limpeza.
+      await navigator.clipboard.writeText(fallback);
+      setCopyNotice("Copiado (sem limpeza automática).");
+    }
+    setTimeout(() => setCopyNotice(""), 4000);
+  }
+
+  return (
+    <>
+      <div className="details-card">
         {loading ? (
           <div className="dash-loading">
             <div className="loader-spinner"></div>
             <p>Carregando…</p>
           </div>
-        ) : (
-          <>
-            {/* HEADER */}
-            <div className="details-header">
-              <div>
-                <h2 className="details-title">
-                  {isNew ? "Nova credencial" : name}
-                </h2>
+        ) : (
+          <>
+            {/* HEADER */}
+            <div className="details-header">
+              <div>
+                <h2 className="details-title">
+                  {isNew ? "Nova credencial" : name}
+                </h2>
+
+                {!isNew && credential?.url && (
+                  <a
+                    href={credential.url}
+                    className="details-url"
+                    onClick={(e) => {
+                      e.preventDefault();
+                      window.open(credential.url!, "_blank");
+                    }}
+                  >
+                    {credential.url}
+                  </a>
+                )}
+              </div>
+
+              <div className="details-actions">
+                {!isNew && !isEditing && (
+                  <>
+                    <button
+                      className="icon-button"
+                      onClick={onEditMode}
+                      title="Editar"
+                    >
+                      <svg viewBox="0 0 24 24" className="icon-svg">
+                        <path
+                          d="M4 20h4l10-10-4-4L4 16v4z"
+                          stroke="currentColor"
+                          strokeWidth="1.6"
+                          fill="none"
+                        />
+                      </svg>
+                    </button>
+
+                    <button
+                      className="icon-button icon-danger"
+                      onClick={handleDeleteClick}
+                      title="Excluir"
+                    >
+                      <svg viewBox="0 0 24 24" className="icon-svg">
+                        <path
+                          d="M4 7h16"
+                          stroke="currentColor"
+                          strokeWidth="1.6"
+                        />
+                        <rect
+                          x="6"
+                          y="7"
+                          width="12"
+                          height="12"
+                          rx="2"
+                          stroke="currentColor"
+                          strokeWidth="1.6"
+                          fill="none"
+                        />
+                      </svg>
+                    </button>
+                  </>
+                )}
+
+                <button
+                  className="icon-button"
+                  onClick={onClose}
+                  title="Fechar"
+                >
+                  <svg viewBox="0 0 24 24" className="icon-svg">
+                    <line
+                      x1="6"
+                      y1="6"
+                      x2="18"
+                      y2="18"
+                      stroke="currentColor"
+                      strokeWidth="1.6"
+                    />
+                    <line
+                      x1="6"
+                      y1="18"
+                      x2="18"
+                      y2="6"
+                      stroke="currentColor"
+                      strokeWidth="1.6"
+                    />
+                  </svg>
+                </button>
+              </div>
+            </div>
+
+            {/* CAMPOS */}
+            <div className="details-fields">
+              {copyNotice && <p className="details-notice">{copyNotice}</p>}
+
+              <div className="details-field-group">
+                <label>Nome</label>
+                <input
+                  value={name}
+                  readOnly={readOnly}
+                  onChange={(e) => setName(e.target.value)}
+                />
+              </div>
+
+              <div className="details-field-group">
+                <label>Usuário</label>
+                <div className="field-inline">
+                  <input
+                    value={username}
+                    readOnly={readOnly}
+                    onChange={(e) => setUsername(e.target.value)}
+                  />
+                  {username && (
+                    <button
+                      className="icon-button"
+                      onClick={() => copy("username", username)}
+                      title="Copiar usuário"
+                    >
+                      <svg viewBox="0 0 24 24" className="icon-svg">
+                        <rect
+                          x="9"
+                          y="9"
+                          width="11"
+                          height="11"
+                          rx="2"
+                          fill="none"
+                          strokeWidth="1.6"
+                          stroke="currentColor"
+                        />
+                        <rect
+                          x="4"
+                          y="4"
+                          width="11"
+                          height="11"
+                          rx="2"
+                          fill="none"
+                          strokeWidth="1.6"
+                          stroke="currentColor"
+                        />
+                      </svg>
+                    </button>
+                  )}
+                </div>
+              </div>
+
+              <div className="details-field-group">
+                <label>URL</label>
+                <input
+                  value={url}
+                  readOnly={readOnly}
+                  onChange={(e) => setUrl(e.target.value)}
+                />
+              </div>
+
+              <div className="details-field-group">
+                <label>Senha</label>
+                <div className="field-inline">
+                  <input
+                    type={showPassword ? "text" : "password"}
+                    value={password}
+                    readOnly={readOnly}
+                    onChange={(e) => setPassword(e.target.value)}
+                  />
+
+                  <button
+                    className="icon-button"
+                    onClick={() => setShowPassword((v) => !v)}
+                    title={showPassword ? "Ocultar senha" : "Mostrar senha"}
+                  >
+                    {showPassword ? (
+                      <svg viewBox="0 0 24 24" className="icon-svg">
+                        <path
+                          d="M3 3l18 18"
+                          stroke="currentColor"
+                          strokeWidth="1.6"
+                        />
+                        <path
+                          d="M5 5c1.7-1.3 4-2 7-2 7 0 11 7 11 7a17.3 17.3 0 0 1-3.1 4.3"
+                          strokeWidth="1.6"
+                          stroke="currentColor"
+                          fill="none"
+                        />
+                      </svg>
+                    ) : (
+                      <svg viewBox="0 0 24 24" className="icon-svg">
+                        <path
+                          d="M1 12s4-7 11-7 11 7 11 7-4 7-11 7S1 12 1 12Z"
+                          strokeWidth="1.6"
+                          stroke="currentColor"
+                          fill="none"
+                        />
+                        <circle
+                          cx="12"
+                          cy="12"
+                          r="3"
+                          strokeWidth="1.6"
+                          stroke="currentColor"
+                          fill="none"
+                        />
+                      </svg>
+                    )}
+                  </button>
+
+                  {!readOnly && (
+                    <button
+                      className="icon-button"
+                      onClick={() => setShowGenerator((v) => !v)}
+                      title="Gerar senha"
+                    >
+                      <svg viewBox="0 0 24 24" className="icon-svg">
+                        <path
+                          d="M20 12a8 8 0 1 1-2.3-5.7"
+                          stroke="currentColor"
+                          strokeWidth="1.6"
+                          fill="none"
+                        />
+                        <path
+                          d="M20 4v5h-5"
+                          stroke="currentColor"
+                          strokeWidth="1.6"
+                          fill="none"
+                        />
+                      </svg>
+                    </button>
+                  )}
+
+                  {password && (
+                    <button
+                      className="icon-button"
+                      onClick={() => copy("password", password)}
+                      title="Copiar senha"
+                    >
+                      <svg viewBox="0 0 24 24" className="icon-svg">
+                        <rect
+                          x="9"
+                          y="9"
+                          width="11"
+                          height="11"
+                          rx="2"
+                          fill="none"
+                          strokeWidth="1.6"
+                          stroke="currentColor"
+                        />
+                        <rect
+                          x="4"
+                          y="4"
+                          width="11"
+                          height="11"
+                          rx="2"
+                          fill="none"
+                          strokeWidth="1.6"
+                          stroke="currentColor"
+                        />
+                      </svg>
+                    </button>
+                  )}
+                </div>
+
+                {!readOnly && breaches !== null && breaches > 0 && (
+                  <p className="details-warning">
+                    Esta senha aparece {breaches.toLocaleString("pt-BR")} vez(es)
+                    em vazamentos conhecidos.
+                  </p>
+                )}
+
+                {showGenerator && !readOnly && (
+                  <PasswordGeneratorPanel
+                    onUse={(value) => {
+                      setPassword(value);
+                      setShowPassword(true);
+                      setShowGenerator(false);
+                    }}
+                    onClose={() => setShowGenerator(false)}
+                  />
+                )}
+              </div>
+
+              {readOnly ? (
+                credential &&
+                loadedTotp && (
+                  <div className="details-field-group">
+                    <label>Código TOTP</label>
+                    <TotpDisplay
+                      token={session.token}
+                      credentialId={credential.id}
+                      onCopy={(code) => copy("totp", code)}
+                    />
+                  </div>
+                )
+              ) : (
+                <div className="details-field-group">
+                  <label>TOTP</label>
+                  <input
+                    value={totp}
+                    placeholder="otpauth://totp/... ou segredo Base32"
+                    onChange={(e) => setTotp(e.target.value)}
+                  />
+                </div>
+              )}
+
+              <div className="details-field-group">
+                <label>Pasta</label>
+                <select
+                  value={folderId ?? ""}
+                  disabled={readOnly}
+                  onChange={(e) => setFolderId(e.target.value || null)}
+                >
+                  <option value="">Sem pasta</option>
+                  {folders.map((f) => (
+                    <option key={f.folder.id} value={f.folder.id}>
+                      {f.path}
+                    </option>
+                  ))}
+                </select>
+              </div>
+
+              <div className="details-field-group">
+                <label>Tags</label>
+                <input
+                  value={tagNames}
+                  readOnly={readOnly}
+                  placeholder={readOnly ? "" : "Separadas por vírgula"}
+                  onChange={(e) => setTagNames(e.target.value)}
+                />
+              </div>
+
+              <div className="details-field-group">
+                <label>Notas</label>
+                <textarea
+                  value={notes}
+                  readOnly={readOnly}
+                  onChange={(e) => setNotes(e.target.value)}
+                />
+              </div>
+
+              {readOnly && credential && (
+                <PasswordHistory
+                  token={session.token}
+                  credentialId={credential.id}
+                  onCopy={copy}
+                  onRestored={async () => {
+                    await onRefresh();
+                    onClose();
+                  }}
+                />
+              )}
+            </div>
+
+            {/* FOOTER */}
+            <div className="details-footer">
+              {isNew ? (
+                <>
+                  <button className="btn-secondary" onClick={onClose}>
+                    Cancelar
+                  </button>
+                  <button className="btn-primary" onClick={handleSave}>
+                    Criar
+                  </button>
+                </>
+              ) : isEditing ? (
+                <>
+                  <button className="btn-secondary" onClick={onClose}>
+                    Cancelar
+                  </button>
+                  <button className="btn-primary" onClick={handleSave}>
+                    Salvar
+                  </button>
+                </>
+              ) : (
+                <>
+                  {otherVaults.length > 0 && (
+                    <div className="details-vault-transfer">
+                      <select
+                        value={targetVault}
+                        onChange={(e) => setTargetVault(e.target.value)}
+                      >
+                        <option value="">Enviar para o cofre…</option>
+                        {otherVaults.map((v) => (
+                          <option key={v.vault.id} value={v.vault.id}>
+                            {v.vault.name}
+                          </option>
+                        ))}
+                      </select>
+                      <button
+                        className="btn-secondary"
+                        disabled={!targetVault}
+                        onClick={() => handleTransfer("move")}
+                      >
+                        Mover
+                      </button>
+                      <button
+                        className="btn-secondary"
+                        disabled={!targetVault}
+                        onClick={() => handleTransfer("copy")}
+                      >
+                        Copiar
+                      </button>
+                    </div>
+                  )}
+                  <button className="btn-secondary" onClick={onClose}>
+                    Fechar
+                  </button>
+                </>
+              )}
+            </div>
+            {transferError && (
+              <p className="dash-modal-error">{transferError}</p>
+            )}
+          </>
+        )}
+      </div>
+
+      {/* MODAL DE EXCLUSÃO */}
+      {showDeleteModal && (
+        <ConfirmDeleteModal
+          credentialName={name || credential?.name}
+          onCancel={() => setShowDeleteModal(false)}
+          onConfirm={handleConfirmDelete}
+        />
+      )}
+    </>
+  );
+}
+
+/* ============================================================
+   HISTÓRICO DE SENHAS
+============================================================ */
+interface HistoryVersion {
+  id: string;
+  password: string | null;
+  notes: string | null;
+  replaced_at: string;
+}
+
+interface PasswordHistoryProps {
+  token: string;
+  credentialId: string;
+  onCopy: (value: string) => void;
+  onRestored: () => Promise<void>;
+}
+
+function PasswordHistory({
+  token,
+  credentialId,
+  onCopy,
+  onRestored,
+}: PasswordHistoryProps) {
+  const [versions, setVersions] = useState<HistoryVersion[] | null>(null);
+  const [error, setError] = useState("");
+
+  async function load() {
+    setError("");
+    try {
+      setVersions(
+        await invoke<HistoryVersion[]>("get_password_history", {
+          token,
+          id: credentialId,
+        })
+      );
+    } catch (err) {
+      setError(errorMessage(err));
+    }
+  }
+
+  async function restore(version: HistoryVersion) {
+    setError("");
+    try {
+      await invoke("restore_password_version", {
+        token,
+        id: credentialId,
+        versionId: version.id,
+      });
+      await onRestored();
+    } catch (err) {
+      setError(errorMessage(err));
+    }
+  }
+
+  return (
+    <div className="details-field-group">
+      <label>Histórico de senhas</label>
+
+      {versions === null ? (
+        <button type="button" className="btn-secondary" onClick={load}>
+          Mostrar versões anteriores
+        </button>
+      ) : versions.length === 0 ? (
+        <p className="history-empty">Nenhuma versão anterior.</p>
+      ) : (
+        <ul className="history-list">
+          {versions.map((v) => (
+            <li key={v.id} className="history-item">
+              <span className="history-date">
+                {new Date(v.replaced_at).toLocaleString()}
+              </span>
+              <span className="history-value">
+                {v.password ? "•".repeat(8) : "senha inalterada"}
+                {v.notes !== null && " · notas"}
+              </span>
+              {v.password && (
+                <button
+                  type="button"
+                  className="filter-pill"
+                  onClick={() => onCopy(v.password!)}
+                >
+                  Copiar
+                </button>
+              )}
+              <button
+                type="button"
+                className="filter-pill"
+                onClick={() => restore(v)}
+              >
+                Restaurar
+              </button>
+            </li>
+          ))}
+        </ul>
+      )}
+
+      {error && <p className="dash-modal-error">{error}</p>}
+    </div>
+  );
+}
+
+/* ============================================================
+   MODAL DE ORGANIZAÇÃO (pastas e tags)
+============================================================ */
+interface OrganizeModalProps {
+  token: string;
+  folders: FolderSummary[];
+  tags: TagSummary[];
+  onChanged: () => Promise<void>;
+  onClose: () => void;
+}
+
+function OrganizeModal({ token, folders, tags, onChanged, onClose }: OrganizeModalProps) {
+  const [newFolder, setNewFolder] = useState("");
+  const [newParent, setNewParent] = useState("");
+  const [error, setError] = useState("");
+  const [busy, setBusy] = useState(false);
+
+  async function run(command: string, args: Record<string, unknown>) {
+    setBusy(true);
+    setError("");
+    try {
+      await invoke(command, { token, ...args });
+      await onChanged();
+      return true;
+    } catch (err) {
+      setError(errorMessage(err));
+      return false;
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  async function handleCreate() {
+    const ok = await run("create_folder", {
+      name: newFolder,
+      parentId: newParent || null,
+    });
+    if (ok) setNewFolder("");
+  }
+
+  /* Destinos válidos para mover uma pasta: fora dela e de suas subpastas */
+  function targetsFor(f: FolderSummary) {
+    const prefix = f.path + "/";
+    return folders.filter(
+      (o) => o.folder.id !== f.folder.id && !o.path.startsWith(prefix)
+    );
+  }
+
+  return (
+    <div className="dash-modal-backdrop">
+      <div className="dash-modal dash-modal-wide">
+        <h3 className="dash-modal-title">Organizar pastas e tags</h3>
+
+        <div className="organize-row">
+          <input
+            className="dash-modal-input"
+            placeholder="Nova pasta"
+            value={newFolder}
+            onChange={(e) => setNewFolder(e.target.value)}
+          />
+          <select
+            className="dash-modal-input"
+            value={newParent}
+            onChange={(e) => setNewParent(e.target.value)}
+          >
+            <option value="">Na raiz</option>
+            {folders.map((f) => (
+              <option key={f.folder.id} value={f.folder.id}>
+                Em {f.path}
+              </option>
+            ))}
+          </select>
+          <button
+            type="button"
+            className="modal-btn modal-btn-primary"
+            disabled={busy || !newFolder.trim()}
+            onClick={handleCreate}
+          >
+            Criar
+          </button>
+        </div>
+
+        <div className="organize-list">
+          {folders.length === 0 && (
+            <p className="dash-modal-text">Nenhuma pasta criada.</p>
+          )}
+          {folders.map((f) => (
+            <div
+              key={f.folder.id}
+              className="organize-item"
+              style={{ paddingLeft: f.depth * 14 }}
+            >
+              <input
+                defaultValue={f.folder.name}
+                disabled={busy}
+                onBlur={(e) => {
+                  const name = e.target.value.trim();
+                  if (name && name !== f.folder.name) {
+                    run("rename_folder", { id: f.folder.id, name });
+                  }
+                }}
+              />
+              <select
+                value={f.folder.parent_id ?? ""}
+                disabled={busy}
+                onChange={(e) =>
+                  run("move_folder", {
+                    id: f.folder.id,
+                    parentId: e.target.value || null,
+                  })
+                }
+              >
+                <option value="">Na raiz</option>
+                {targetsFor(f).map((o) => (
+                  <option key={o.folder.id} value={o.folder.id}>
+                    Em {o.path}
+                  </option>
+                ))}
+              </select>
+              <button
+                type="button"
+                className="modal-btn modal-btn-danger"
+                disabled={busy}
+                title="O conteúdo vai para a pasta superior"
+                onClick={() => run("delete_folder", { id: f.folder.id })}
+              >
+                Remover
+              </button>
+            </div>
+          ))}
+        </div>
+
+        <div className="organize-list">
+          {tags.length === 0 && (
+            <p className="dash-modal-text">Nenhuma tag criada.</p>
+          )}
+          {tags.map((t) => (
+            <div key={t.tag.id} className="organize-item">
+              <input
+                defaultValue={t.tag.name}
+                disabled={busy}
+                onBlur={(e) => {
+                  const name = e.target.value.trim();
+                  if (name && name !== t.tag.name) {
+                    run("rename_tag", { id: t.tag.id, name });
+                  }
+                }}
+              />
+              <span className="dash-nav-count">{t.credentials}</span>
+              <button
+                type="button"
+                className="modal-btn modal-btn-danger"
+                disabled={busy}
+                onClick={() => run("delete_tag", { id: t.tag.id })}
+              >
+                Remover
+              </button>
+            </div>
+          ))}
+        </div>
+
+        {error && <p className="dash-modal-error">{error}</p>}
+
+        <div className="dash-modal-footer">
+          <button type="button" className="modal-btn" onClick={onClose}>
+            Fechar
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
+
+/* ============================================================
+   MODAL DE COFRES
+============================================================ */
+interface VaultsModalProps {
+  token: string;
+  vaults: VaultSummary[];
+  onChanged: () => Promise<void>;
+  onClose: () => void;
+}
+
+function VaultsModal({ token, vaults, onChanged, onClose }: VaultsModalProps) {
+  const [newVault, setNewVault] = useState("");
+  const [confirming, setConfirming] = useState<VaultSummary | null>(null);
+  const [sharing, setSharing] = useState<VaultSummary | null>(null);
+  const [error, setError] = useState("");
+  const [busy, setBusy] = useState(false);
+
+  async function run(command: string, args: Record<string, unknown>) {
+    setBusy(true);
+    setError("");
+    try {
+      await invoke(command, { token, ...args });
+      await onChanged();
+      return true;
+    } catch (err) {
+      setError(errorMessage(err));
+      return false;
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  async function handleCreate() {
+    const ok = await run("create_vault", { name: newVault });
+    if (ok) setNewVault("");
+  }
+
+  async function handleDelete() {
+    if (!confirming) return;
+    await run("delete_vault", { id: confirming.vault.id });
+    setConfirming(null);
+  }
+
+  return (
+    <div className="dash-modal-backdrop">
+      <div className="dash-modal dash-modal-wide">
+        <h3 className="dash-modal-title">Gerenciar cofres</h3>
+
+        <div className="organize-row">
+          <input
+            className="dash-modal-input"
+            placeholder="Novo cofre"
+            value={newVault}
+            onChange={(e) => setNewVault(e.target.value)}
+          />
+          <button
+            type="button"
+            className="modal-btn modal-btn-primary"
+            disabled={busy || !newVault.trim()}
+            onClick={handleCreate}
+          >
+            Criar
+          </button>
+        </div>
+
+        <div className="organize-list">
+          {vaults.map((v) => (
+            <div key={v.vault.id} className="organize-item">
+              <input
+                defaultValue={v.vault.name}
+                disabled={busy || v.role !== "owner"}
+                onBlur={(e) => {
+                  const name = e.target.value.trim();
+                  if (name && name !== v.vault.name) {
+                    run("rename_vault", { id: v.vault.id, name });
+                  }
+                }}
+              />
+              <span className="dash-nav-count">
+                {v.current ? "aberto · " : ""}
+                {v.owner ? `${ROLE_LABELS[v.role]} de ${v.owner} · ` : ""}
+                {v.credentials}
+              </span>
+              <button
+                type="button"
+                className="modal-btn"
+                disabled={busy}
+                title="Membros com acesso ao cofre"
+                onClick={() => setSharing(sharing?.vault.id === v.vault.id ? null : v)}
+              >
+                Membros{v.members > 0 ? ` (${v.members})` : ""}
+              </button>
+              {v.role === "owner" && (
+                <button
+                  type="button"
+                  className="modal-btn modal-btn-danger"
+                  disabled={busy || v.current}
+                  title={
+                    v.current
+                      ? "Alterne para outro cofre antes de excluir este"
+                      : "Exclui o cofre e todas as suas credenciais"
+                  }
+                  onClick={() => setConfirming(v)}
+                >
+                  Excluir
+                </button>
+              )}
+            </div>
+          ))}
+        </div>
+
+        {sharing && (
+          <VaultMembers
+            key={sharing.vault.id}
+            token={token}
+            vault={sharing}
+            onChanged={onChanged}
+          />
+        )}
+
+        {confirming && (
+          <p className="dash-modal-text">
+            Excluir definitivamente o cofre <strong>{confirming.vault.name}</strong>{" "}
+            e suas {confirming.credentials} credenciais?{" "}
+            <button
+              type="button"
+              className="modal-btn modal-btn-danger"
+              disabled={busy}
+              onClick={handleDelete}
+            >
+              Confirmar
+            </button>{" "}
+            <button
+              type="button"
+              className="modal-btn"
+              onClick={() => setConfirming(null)}
+            >
+              Cancelar
+            </button>
+          </p>
+        )}
+
+        {error && <p className="dash-modal-error">{error}</p>}
+
+        <div className="dash-modal-footer">
+          <button type="button" className="modal-btn" onClick={onClose}>
+            Fechar
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
+
+/* ============================================================
+   MEMBROS DE UM COFRE COMPARTILHADO
+============================================================ */
+interface VaultMembersProps {
+  token: string;
+  vault: VaultSummary;
+  onChanged: () => Promise<void>;
+}
+
+function VaultMembers({ token, vault, onChanged }: VaultMembersProps) {
+  const [members, setMembers] = useState<VaultMember[]>([]);
+  const [username, setUsername] = useState("");
+  const [role, setRole] = useState<VaultRole>("viewer");
+  const [revoking, setRevoking] = useState<VaultMember | null>(null);
+  const [error, setError] = useState("");
+  const [busy, setBusy] = useState(false);
+  const isOwner = vault.role === "owner";
+
+  const load = useCallback(async () => {
+    try {
+      setMembers(
+        await invoke<VaultMember[]>("list_vault_members", {
+          token,
+          id: vault.vault.id,
+        }),
+      );
+    } catch (err) {
+      setError(errorMessage(err));
+    }
+  }, [token, vault.vault.id]);
+
+  useEffect(() => {
+    load();
+  }, [load]);
+
+  // usuários cadastrados, sugeridos no convite
+  const [users, setUsers] = useState<string[]>([]);
+
+  useEffect(() => {
+    if (!isOwner) return;
+    invoke<{ username: string }[]>("list_users", { token })
+      .then((list) => setUsers(list.map((u) => u.username)))
+      .catch(() => setUsers([]));
+  }, [token, isOwner]);
+
+  async function run(command: string, args: Record<string, unknown>) {
+    setBusy(true);
+    setError("");
+    try {
+      await invoke(command, { token, id: vault.vault.id, ...args });
+      await load();
+      await onChanged();
+      return true;
+    } catch (err) {
+      setError(errorMessage(err));
+      return false;
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  async function handleInvite() {
+    const ok = await run("invite_vault_member", { username: username.trim(), role });
+    if (ok) setUsername("");
+  }
+
+  async function handleRevoke() {
+    if (!revoking) return;
+    await run("revoke_vault_member", { username: revoking.username });
+    setRevoking(null);
+  }
+
+  return (
+    <div className="vault-members">
+      <h4 className="vault-members-title">Membros de {vault.vault.name}</h4>
+
+      {isOwner && (
+        <div className="organize-row">
+          <input
+            className="dash-modal-input"
+            placeholder="Usuário"
+            value={username}
+            list="vault-invite-users"
+            onChange={(e) => setUsername(e.target.value)}
+          />
+          <datalist id="vault-invite-users">
+            {users
+              .filter((name) => !members.some((m) => m.username === name))
+              .map((name) => (
+                <option key={name} value={name} />
+              ))}
+          </datalist>
+          <select
+            value={role}
+            onChange={(e) => setRole(e.target.value as VaultRole)}
+          >
+            <option value="viewer">Leitor</option>
+            <option value="editor">Editor</option>
+          </select>
+          <button
+            type="button"
+            className="modal-btn modal-btn-primary"
+            disabled={busy || !username.trim()}
+            onClick={handleInvite}
+          >
+            Convidar
+          </button>
+        </div>
+      )}
+
+      <div className="organize-list">
+        {members.map((m) => (
+          <div key={m.user_id} className="organize-item">
+            <span>{m.username}</span>
+            {isOwner && m.role !== "owner" ? (
+              <>
+                <select
+                  value={m.role}
+                  disabled={busy}
+                  onChange={(e) =>
+                    run("set_vault_member_role", {
+                      username: m.username,
+                      role: e.target.value,
+                    })
+                  }
+                >
+                  <option value="viewer">Leitor</option>
+                  <option value="editor">Editor</option>
+                </select>
+                <button
+                  type="button"
+                  className="modal-btn modal-btn-danger"
+                  disabled={busy}
+                  title="Remove o acesso e troca a chave do cofre"
+                  onClick={() => setRevoking(m)}
+                >
+                  Revogar
+                </button>
+              </>
+            ) : (
+              <span className="dash-nav-count">{ROLE_LABELS[m.role]}</span>
+            )}
+          </div>
+        ))}
+      </div>
 
-                {!isNew && credential?.url && (
-                  <a
-                    href={credential.url}
-                    className="details-url"
-                    onClick={(e) => {
-                      e.preventDefault();
-                      window.open(credential.url!, "_blank");
-                    }}
-                  >
-                    {credential.url}
-                  </a>
-                )}
-              </div>
+      {revoking && (
+        <p className="dash-modal-text">
+          Revogar o acesso de <strong>{revoking.username}</strong>? A chave do
+          cofre será trocada e todas as credenciais recifradas.{" "}
+          <button
+            type="button"
+            className="modal-btn modal-btn-danger"
+            disabled={busy}
+            onClick={handleRevoke}
+          >
+            Confirmar
+          </button>{" "}
+          <button type="button" className="modal-btn" onClick={() => setRevoking(null)}>
+            Cancelar
+          </button>
+        </p>
+      )}
 
-              <div className="details-actions">
-                {!isNew && !isEditing && (
-                  <>
-                    <button
-                      className="icon-button"
-                      onClick={onEditMode}
-                      title="Editar"
-                    >
-                      <svg viewBox="0 0 24 24" className="icon-svg">
-                        <path
-                          d="M4 20h4l10-10-4-4L4 16v4z"
-                          stroke="currentColor"
-                          strokeWidth="1.6"
-                          fill="none"
-                        />
-                      </svg>
-                    </button>
+      {error && <p className="dash-modal-error">{error}</p>}
+    </div>
+  );
+}
 
-                    <button
-                      className="icon-button icon-danger"
-                      onClick={handleDeleteClick}
-                      title="Excluir"
-                    >
-                      <svg viewBox="0 0 24 24" className="icon-svg">
-                        <path
-                          d="M4 7h16"
-                          stroke="currentColor"
-                          strokeWidth="1.6"
-                        />
-                        <rect
-                          x="6"
-                          y="7"
-                          width="12"
-                          height="12"
-                          rx="2"
-                          stroke="currentColor"
-                          strokeWidth="1.6"
-                          fill="none"
-                        />
-                      </svg>
-                    </button>
-                  </>
+/* ============================================================
+   MODAL DA LIXEIRA
+============================================================ */
+interface TrashModalProps {
+  token: string;
+  onChanged: () => Promise<void>;
+  onClose: () => void;
+}
+
+function TrashModal({ token, onChanged, onClose }: TrashModalProps) {
+  const [items, setItems] = useState<Credential[]>([]);
+  const [error, setError] = useState("");
+  const [busy, setBusy] = useState(false);
+
+  const load = useCallback(async () => {
+    try {
+      setItems(await invoke<Credential[]>("list_trash", { token }));
+    } catch (err) {
+      setError(errorMessage(err));
+    }
+  }, [token]);
+
+  useEffect(() => {
+    load();
+  }, [load]);
+
+  async function run(command: string, args: Record<string, unknown> = {}) {
+    setBusy(true);
+    setError("");
+    try {
+      await invoke(command, { token, ...args });
+      await Promise.all([load(), onChanged()]);
+    } catch (err) {
+      setError(errorMessage(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  return (
+    <div className="dash-modal-backdrop">
+      <div className="dash-modal dash-modal-wide">
+        <h3 className="dash-modal-title">Lixeira</h3>
+
+        <div className="organize-list">
+          {items.length === 0 && (
+            <p className="dash-modal-text">A lixeira está vazia.</p>
+          )}
+          {items.map((c) => (
+            <div key={c.id} className="organize-item">
+              <span className="trash-name">
+                {c.name}
+                {c.deleted_at && (
+                  <span className="trash-when">
+                    removida {timeAgo(c.deleted_at)}
+                  </span>
                 )}
+              </span>
+              <button
+                type="button"
+                className="modal-btn"
+                disabled={busy}
+                onClick={() => run("restore_credential", { id: c.id })}
+              >
+                Restaurar
+              </button>
+              <button
+                type="button"
+                className="modal-btn modal-btn-danger"
+                disabled={busy}
+                onClick={() => run("purge_credential", { id: c.id })}
+              >
+                Excluir
+              </button>
+            </div>
+          ))}
+        </div>
+
+        {error && <p className="dash-modal-error">{error}</p>}
+
+        <div className="dash-modal-footer">
+          <button
+            type="button"
+            className="modal-btn modal-btn-danger"
+            disabled={busy || items.length === 0}
+            onClick={() => run("empty_trash")}
+          >
+            Esvaziar lixeira
+          </button>
+          <button type="button" className="modal-btn" onClick={onClose}>
+            Fechar
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
+
+/* ============================================================
+   MODAL DO RELATÓRIO DE SEGURANÇA
+============================================================ */
+type StrengthWarning =
+  | "common"
+  | "dictionary"
+  | "repeated"
+  | "sequence"
+  | "keyboard_pattern"
+  | "date"
+  | "too_short"
+  | "single_class";
+
+const STRENGTH_WARNINGS: Record<StrengthWarning, string> = {
+  common: "senha comum",
+  dictionary: "palavras de dicionário",
+  repeated: "caracteres repetidos",
+  sequence: "sequência",
+  keyboard_pattern: "padrão de teclado",
+  date: "ano",
+  too_short: "curta",
+  single_class: "um único tipo de caractere",
+};
+
+interface SecurityReport {
+  total: number;
+  options: { max_age_days: number; min_score: number };
+  weak: {
+    credential: Credential;
+    strength: { score: number; entropy_bits: number; warnings: StrengthWarning[] };
+  }[];
+  reused: { credentials: Credential[] }[];
+  stale: { credential: Credential; age_days: number }[];
+  breach_checked: boolean;
+  breached: { credential: Credential; occurrences: number }[];
+  incomplete: {
+    credential: Credential;
+    missing_username: boolean;
+    missing_url: boolean;
+  }[];
+}
+
+interface SecurityReportModalProps {
+  token: string;
+  onSelect: (cred: Credential) => void;
+  onClose: () => void;
+}
+
+function SecurityReportModal({ token, onSelect, onClose }: SecurityReportModalProps) {
+  const [maxAgeDays, setMaxAgeDays] = useState(365);
+  const [report, setReport] = useState<SecurityReport | null>(null);
+  const [error, setError] = useState("");
+
+  useEffect(() => {
+    let cancelled = false;
+
+    invoke<SecurityReport>("security_report", {
+      token,
+      options: { max_age_days: maxAgeDays },
+    })
+      .then((r) => {
+        if (!cancelled) setReport(r);
+      })
+      .catch((err) => {
+        if (!cancelled) setError(errorMessage(err));
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [token, maxAgeDays]);
+
+  function link(cred: Credential) {
+    return (
+      <button
+        type="button"
+        className="report-link"
+        onClick={() => onSelect(cred)}
+      >
+        {cred.name}
+      </button>
+    );
+  }
 
-                <button
-                  className="icon-button"
-                  onClick={onClose}
-                  title="Fechar"
-                >
-                  <svg viewBox="0 0 24 24" className="icon-svg">
-                    <line
-                      x1="6"
-                      y1="6"
-                      x2="18"
-                      y2="18"
-                      stroke="currentColor"
-                      strokeWidth="1.6"
-                    />
-                    <line
-                      x1="6"
-                      y1="18"
-                      x2="18"
-                      y2="6"
-                      stroke="currentColor"
-                      strokeWidth="1.6"
-                    />
-                  </svg>
-                </button>
-              </div>
-            </div>
+  return (
+    <div className="dash-modal-backdrop">
+      <div className="dash-modal dash-modal-wide">
+        <h3 className="dash-modal-title">Relatório de segurança</h3>
+
+        <label className="dash-modal-text">
+          Senhas antigas a partir de{" "}
+          <input
+            type="number"
+            min={0}
+            className="report-days"
+            value={maxAgeDays}
+            onChange={(e) => setMaxAgeDays(Math.max(0, Number(e.target.value)))}
+          />{" "}
+          dias
+        </label>
+
+        {!report && !error && (
+          <div className="dash-loading">
+            <div className="loader-spinner"></div>
+            <p>Analisando…</p>
+          </div>
+        )}
 
-            {/* CAMPOS */}
-            <div className="details-fields">
-              <div className="details-field-group">
-                <label>Nome</label>
-                <input
-                  value={name}
-                  readOnly={readOnly}
-                  onChange={(e) => setName(e.target.value)}
-                />
+        {report && (
+          <div className="organize-list">
+            <p className="dash-modal-text">
+              {report.total} credencial(is) analisada(s).
+            </p>
+
+            <h4 className="report-section">Senhas fracas ({report.weak.length})</h4>
+            {report.weak.map((w) => (
+              <div key={w.credential.id} className="report-item">
+                {link(w.credential)}
+                <span className="report-detail">
+                  {w.strength.score}/4 ·{" "}
+                  {w.strength.warnings.map((k) => STRENGTH_WARNINGS[k]).join(", ")}
+                </span>
               </div>
-
-              <div className="details-field-group">
-                <label>Usuário</label>
-                <div className="field-inline">
-                  <input
-                    value={username}
-                    readOnly={readOnly}
-                    onChange={(e) => setUsername(e.target.value)}
-                  />
-                  {username && (
-                    <button
-                      className="icon-button"
-                      onClick={() => copy(username)}
-                      title="Copiar usuário"
-                    >
-                      <svg viewBox="0 0 24 24" className="icon-svg">
-                        <rect
-                          x="9"
-                          y="9"
-                          width="11"
-                          height="11"
-                          rx="2"
-                          fill="none"
-                          strokeWidth="1.6"
-                          stroke="currentColor"
-                        />
-                        <rect
-                          x="4"
-                          y="4"
-                          width="11"
-                          height="11"
-                          rx="2"
-                          fill="none"
-                          strokeWidth="1.6"
-                          stroke="currentColor"
-                        />
-                      </svg>
-                    </button>
-                  )}
-                </div>
+            ))}
+
+            {report.breach_checked && (
+              <>
+                <h4 className="report-section">
+                  Senhas vazadas ({report.breached.length})
+                </h4>
+                {report.breached.map((b) => (
+                  <div key={b.credential.id} className="report-item">
+                    {link(b.credential)}
+                    <span className="report-detail">
+                      {b.occurrences.toLocaleString("pt-BR")} ocorrência(s)
+                    </span>
+                  </div>
+                ))}
+              </>
+            )}
+
+            <h4 className="report-section">
+              Senhas repetidas ({report.reused.length})
+            </h4>
+            {report.reused.map((g) => (
+              <div key={g.credentials[0].id} className="report-item">
+                {g.credentials.map((c) => (
+                  <span key={c.id}>{link(c)}</span>
+                ))}
               </div>
-
-              <div className="details-field-group">
-                <label>URL</label>
-                <input
-                  value={url}
-                  readOnly={readOnly}
-                  onChange={(e) => setUrl(e.target.value)}
-                />
+            ))}
+
+            <h4 className="report-section">
+              Senhas antigas ({report.stale.length})
+            </h4>
+            {report.stale.map((s) => (
+              <div key={s.credential.id} className="report-item">
+                {link(s.credential)}
+                <span className="report-detail">há {s.age_days} dias</span>
               </div>
+            ))}
+
+            <h4 className="report-section">
+              Credenciais incompletas ({report.incomplete.length})
+            </h4>
+            {report.incomplete.map((i) => (
+              <div key={i.credential.id} className="report-item">
+                {link(i.credential)}
+                <span className="report-detail">
+                  sem{" "}
+                  {[i.missing_username && "usuário", i.missing_url && "URL"]
+                    .filter(Boolean)
+                    .join(" e ")}
+                </span>
+              </div>
+            ))}
+          </div>
+        )}
 
-              <div className="details-field-group">
-                <label>Senha</label>
-                <div className="field-inline">
-                  <input
-                    type={showPassword ? "text" : "password"}
-                    value={password}
-                    readOnly={readOnly}
-                    onChange={(e) => setPassword(e.target.value)}
-                  />
+        {error && <p className="dash-modal-error">{error}</p>}
 
-                  <button
-                    className="icon-button"
-                    onClick={() => setShowPassword((v) => !v)}
-                    title={showPassword ? "Ocultar senha" : "Mostrar senha"}
-                  >
-                    {showPassword ? (
-                      <svg viewBox="0 0 24 24" className="icon-svg">
-                        <path
-                          d="M3 3l18 18"
-                          stroke="currentColor"
-                          strokeWidth="1.6"
-                        />
-                        <path
-                          d="M5 5c1.7-1.3 4-2 7-2 7 0 11 7 11 7a17.3 17.3 0 0 1-3.1 4.3"
-                          strokeWidth="1.6"
-                          stroke="currentColor"
-                          fill="none"
-                        />
-                      </svg>
-                    ) : (
-                      <svg viewBox="0 0 24 24" className="icon-svg">
-                        <path
-                          d="M1 12s4-7 11-7 11 7 11 7-4 7-11 7S1 12 1 12Z"
-                          strokeWidth="1.6"
-                          stroke="currentColor"
-                          fill="none"
-                        />
-                        <circle
-                          cx="12"
-                          cy="12"
-                          r="3"
-                          strokeWidth="1.6"
-                          stroke="currentColor"
-                          fill="none"
-                        />
-                      </svg>
-                    )}
-                  </button>
+        <div className="dash-modal-footer">
+          <button type="button" className="modal-btn" onClick={onClose}>
+            Fechar
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
 
-                  {password && (
-                    <button
-                      className="icon-button"
-                      onClick={() => copy(password)}
-                      title="Copiar senha"
-                    >
-                      <svg viewBox="0 0 24 24" className="icon-svg">
-                        <rect
-                          x="9"
-                          y="9"
-                          width="11"
-                          height="11"
-                          rx="2"
-                          fill="none"
-                          strokeWidth="1.6"
-                          stroke="currentColor"
-                        />
-                        <rect
-                          x="4"
-                          y="4"
-                          width="11"
-                          height="11"
-                          rx="2"
-                          fill="none"
-                          strokeWidth="1.6"
-                          stroke="currentColor"
-                        />
-                      </svg>
-                    </button>
-                  )}
-                </div>
-              </div>
+type ImportFormat =
+  | "bitwarden"
+  | "keepass_xml"
+  | "one_password1pux"
+  | "one_password_csv"
+  | "browser_csv";
+
+const IMPORT_FORMATS: { value: ImportFormat; label: string }[] = [
+  { value: "bitwarden", label: "Bitwarden (JSON)" },
+  { value: "keepass_xml", label: "KeePass 2 (XML)" },
+  { value: "one_password1pux", label: "1Password (1PUX)" },
+  { value: "one_password_csv", label: "1Password (CSV)" },
+  { value: "browser_csv", label: "Chrome/Firefox (CSV)" },
+];
+
+interface ImportIssue {
+  entry: string;
+  reason: string;
+}
 
-              <div className="details-field-group">
-                <label>Notas</label>
-                <textarea
-                  value={notes}
-                  readOnly={readOnly}
-                  onChange={(e) => setNotes(e.target.value)}
-                />
-              </div>
-            </div>
+interface ImportReport {
+  format: ImportFormat;
+  dry_run: boolean;
+  total: number;
+  imported: number;
+  duplicates: ImportIssue[];
+  skipped: ImportIssue[];
+  warnings: ImportIssue[];
+}
 
-            {/* FOOTER */}
-            <div className="details-footer">
-              {isNew ? (
-                <>
-                  <button className="btn-secondary" onClick={onClose}>
-                    Cancelar
-                  </button>
-                  <button className="btn-primary" onClick={handleSave}>
-                    Criar
-                  </button>
-                </>
-              ) : isEditing ? (
-                <>
-                  <button className="btn-secondary" onClick={onClose}>
-                    Cancelar
-                  </button>
-                  <button className="btn-primary" onClick={handleSave}>
-                    Salvar
-                  </button>
-                </>
-              ) : (
-                <button className="btn-secondary" onClick={onClose}>
-                  Fechar
-                </button>
-              )}
-            </div>
+interface ExternalImportModalProps {
+  token: string;
+  onDone: () => void;
+  onClose: () => void;
+}
+
+function ExternalImportModal({ token, onDone, onClose }: ExternalImportModalProps) {
+  const [path, setPath] = useState("");
+  const [format, setFormat] = useState<ImportFormat | "">("");
+  const [report, setReport] = useState<ImportReport | null>(null);
+  const [error, setError] = useState("");
+  const [busy, setBusy] = useState(false);
+
+  const done = report !== null && !report.dry_run;
+
+  async function run(dryRun: boolean) {
+    setBusy(true);
+    setError("");
+    try {
+      const result = await invoke<ImportReport>("import_external", {
+        token,
+        path,
+        format: format || null,
+        dryRun,
+      });
+      setReport(result);
+      if (!dryRun) onDone();
+    } catch (err) {
+      setReport(null);
+      setError(errorMessage(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  const sections: [string, ImportIssue[]][] = report
+    ? [
+        ["Duplicados", report.duplicates],
+        ["Ignorados", report.skipped],
+        ["Avisos", report.warnings],
+      ]
+    : [];
+
+  return (
+    <div className="dash-modal-backdrop">
+      <div className="dash-modal">
+        <h3 className="dash-modal-title">Importar de outro gerenciador</h3>
+
+        {!done && (
+          <>
+            <p className="dash-modal-text">
+              Simule a importação para conferir o relatório antes de gravar as
+              credenciais no cofre.
+            </p>
+
+            <input
+              className="dash-modal-input"
+              placeholder="Caminho do arquivo"
+              value={path}
+              autoFocus
+              onChange={(e) => {
+                setPath(e.target.value);
+                setReport(null);
+              }}
+            />
+            <select
+              className="dash-modal-input"
+              value={format}
+              onChange={(e) => {
+                setFormat(e.target.value as ImportFormat | "");
+                setReport(null);
+              }}
+            >
+              <option value="">Detectar automaticamente</option>
+              {IMPORT_FORMATS.map((f) => (
+                <option key={f.value} value={f.value}>
+                  {f.label}
+                </option>
+              ))}
+            </select>
           </>
         )}
-      </div>
 
-      {/* MODAL DE EXCLUSÃO */}
-      {showDeleteModal && (
-        <ConfirmDeleteModal
-          credentialName={name || credential?.name}
-          onCancel={() => setShowDeleteModal(false)}
-          onConfirm={handleConfirmDelete}
-        />
-      )}
-    </>
+        {report && (
+          <div className="import-report">
+            <p className="dash-modal-text">
+              {report.total} item(ns) no arquivo;{" "}
+              {report.imported} credencial(is){" "}
+              {report.dry_run ? "seriam importada(s)" : "importada(s)"}.
+            </p>
+            {sections
+              .filter(([, issues]) => issues.length > 0)
+              .map(([title, issues]) => (
+                <div key={title} className="import-report-section">
+                  <span className="import-report-title">
+                    {title} ({issues.length})
+                  </span>
+                  <ul>
+                    {issues.map((issue, i) => (
+                      <li key={i}>
+                        <strong>{issue.entry}</strong>: {issue.reason}
+                      </li>
+                    ))}
+                  </ul>
+                </div>
+              ))}
+          </div>
+        )}
+
+        {error && <p className="dash-modal-error">{error}</p>}
+
+        <div className="dash-modal-footer">
+          <button type="button" className="modal-btn" onClick={onClose}>
+            {done ? "Fechar" : "Cancelar"}
+          </button>
+          {!done && (
+            <button
+              type="button"
+              className="modal-btn"
+              disabled={busy || !path}
+              onClick={() => run(true)}
+            >
+              Simular
+            </button>
+          )}
+          {!done && (
+            <button
+              type="button"
+              className="modal-btn modal-btn-primary"
+              disabled={busy || !report || report.imported === 0}
+              onClick={() => run(false)}
+            >
+              Importar
+            </button>
+          )}
+        </div>
+      </div>
+    </div>
   );
 }